@@ -0,0 +1,327 @@
+use crate::types::analysis::{ApiItem, ApiItemKind};
+
+/// Bloc ouvert par une accolade pendant le parcours d'un fichier
+enum Block {
+    /// Module (racine ou `pub mod` en ligne) : ses éléments `pub` sont relevés,
+    /// préfixés du chemin du module en ligne
+    Items(String),
+    /// `impl` : ses méthodes et constantes `pub` sont relevées comme appartenant au type
+    Impl(String),
+    /// Corps de fonction, de type, module privé ou de test : rien n'y est relevé
+    Opaque,
+}
+
+/// Éléments `pub` d'un fichier Rust (types, traits, fonctions, constantes, statiques,
+/// alias, méthodes des blocs `impl`), sans ceux des modules et éléments `#[cfg(test)]`.
+///
+/// `pub(crate)` et `pub(super)` ne font pas partie de l'API ; les `pub use` non plus.
+pub fn public_items(content: &str) -> Vec<ApiItem> {
+    let code = sanitize(content);
+    if code.trim_start().starts_with("#![cfg(test)]") {
+        return Vec::new();
+    }
+
+    let mut items = Vec::new();
+    let mut blocks = vec![Block::Items(String::new())];
+    let mut statement = String::new();
+    // Parenthèses et crochets ouverts dans l'instruction en cours (`[u8; 4]`)
+    let mut nesting = 0usize;
+
+    for c in code.chars() {
+        match c {
+            '(' | '[' => {
+                nesting += 1;
+                statement.push(c);
+            },
+            ')' | ']' => {
+                nesting = nesting.saturating_sub(1);
+                statement.push(c);
+            },
+            ';' if nesting == 0 => {
+                if let Some(item) = declared_item(&statement, blocks.last(), false) {
+                    items.push(item);
+                }
+                statement.clear();
+            },
+            '{' => {
+                let block = match blocks.last() {
+                    Some(Block::Items(prefix)) if nesting == 0 => inner_block(&statement, prefix),
+                    _ => None,
+                };
+                if nesting == 0 {
+                    if let Some(item) = declared_item(&statement, blocks.last(), true) {
+                        items.push(item);
+                    }
+                }
+                blocks.push(block.unwrap_or(Block::Opaque));
+                statement.clear();
+                nesting = 0;
+            },
+            '}' => {
+                if blocks.len() > 1 {
+                    blocks.pop();
+                }
+                statement.clear();
+                nesting = 0;
+            },
+            _ => statement.push(c),
+        }
+    }
+    items
+}
+
+/// Bloc ouvert par un `pub mod` ou un `impl` ; `None` pour les autres
+fn inner_block(statement: &str, prefix: &str) -> Option<Block> {
+    let (attributes, declaration) = split_attributes(statement);
+    if is_test_only(&attributes) {
+        return None;
+    }
+    let declaration = normalize(declaration);
+    if let Some(name) = declaration.strip_prefix("pub mod ") {
+        return Some(Block::Items(format!("{}{}::", prefix, name.trim())));
+    }
+    let header = declaration.strip_prefix("unsafe ").unwrap_or(&declaration);
+    let header = header.strip_prefix("impl")?;
+    if !header.starts_with([' ', '<']) {
+        return None;
+    }
+    let header = skip_generics(header.trim_start());
+    // `impl Trait for Type` : les méthodes suivent la visibilité du trait
+    let self_type = header.split(" for ").last().unwrap_or(header);
+    let self_type = self_type.split(" where ").next().unwrap_or(self_type).trim();
+    let self_type = self_type.split('<').next().unwrap_or(self_type);
+    let name = self_type.rsplit("::").next().unwrap_or(self_type).trim_start_matches('&').trim();
+    Some(Block::Impl(format!("{}{}", prefix, name)))
+}
+
+/// Élément `pub` déclaré par l'instruction, terminée par `;` ou ouvrant un bloc
+fn declared_item(statement: &str, block: Option<&Block>, opens_block: bool) -> Option<ApiItem> {
+    let owner = match block? {
+        Block::Items(prefix) => Err(prefix.as_str()),
+        Block::Impl(owner) => Ok(owner.clone()),
+        Block::Opaque => return None,
+    };
+    let (attributes, declaration) = split_attributes(statement);
+    if is_test_only(&attributes) {
+        return None;
+    }
+    let declaration = normalize(declaration);
+    declaration.strip_prefix("pub ")?;
+
+    let (kind, name) = kind_and_name(&declaration)?;
+    if owner.is_ok() && !matches!(kind, ApiItemKind::Fn | ApiItemKind::Const | ApiItemKind::Type) {
+        return None;
+    }
+    let signature = match kind {
+        // La valeur n'est pas une signature
+        ApiItemKind::Const | ApiItemKind::Static | ApiItemKind::Type => {
+            declaration.split(" = ").next().unwrap_or(&declaration).to_string()
+        },
+        ApiItemKind::Struct | ApiItemKind::Enum | ApiItemKind::Union | ApiItemKind::Trait if opens_block => {
+            format!("{} {{ .. }}", declaration)
+        },
+        _ => declaration.clone(),
+    };
+    let (name, owner) = match owner {
+        Ok(owner) => (name, Some(owner)),
+        Err(prefix) => (format!("{}{}", prefix, name), None),
+    };
+    Some(ApiItem { kind, name, owner, signature })
+}
+
+/// Nature et nom d'une déclaration commençant par `pub `
+fn kind_and_name(declaration: &str) -> Option<(ApiItemKind, String)> {
+    let mut words = declaration.split_whitespace().skip(1).peekable();
+    while let Some(word) = words.next() {
+        let kind = match word {
+            "fn" => ApiItemKind::Fn,
+            "struct" => ApiItemKind::Struct,
+            "enum" => ApiItemKind::Enum,
+            "union" => ApiItemKind::Union,
+            "trait" => ApiItemKind::Trait,
+            "type" => ApiItemKind::Type,
+            "static" => ApiItemKind::Static,
+            // `pub const fn` est une fonction
+            "const" if !words.peek().is_some_and(|next| ["fn", "async", "unsafe", "extern"].contains(next)) => {
+                ApiItemKind::Const
+            },
+            "const" | "async" | "unsafe" | "extern" | "auto" | "default" => continue,
+            word if word.starts_with('"') => continue,
+            _ => return None,
+        };
+        let name = words.find(|word| *word != "mut")?;
+        let name: String = name.chars().take_while(|c| c.is_alphanumeric() || *c == '_').collect();
+        return (!name.is_empty()).then_some((kind, name));
+    }
+    None
+}
+
+/// Sépare les attributs (`#[...]`, `#![...]`) de la déclaration qui les suit
+fn split_attributes(statement: &str) -> (Vec<&str>, &str) {
+    let mut attributes = Vec::new();
+    let mut rest = statement.trim_start();
+    while rest.starts_with("#[") || rest.starts_with("#![") {
+        let mut depth = 0;
+        let end = rest.char_indices().find_map(|(position, c)| {
+            match c {
+                '[' => depth += 1,
+                ']' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        return Some(position);
+                    }
+                },
+                _ => {},
+            }
+            None
+        });
+        let Some(end) = end else { break };
+        attributes.push(&rest[..=end]);
+        rest = rest[end + 1..].trim_start();
+    }
+    (attributes, rest)
+}
+
+/// Indique si un attribut réserve l'élément aux tests (`#[cfg(test)]`, `#[test]`)
+fn is_test_only(attributes: &[&str]) -> bool {
+    attributes.iter().any(|attribute| {
+        let compact: String = attribute.chars().filter(|c| !c.is_whitespace()).collect();
+        compact == "#[cfg(test)]" || compact == "#[test]" || compact.starts_with("#[cfg(all(test")
+    })
+}
+
+/// Déclaration sur une ligne, espaces superflus retirés
+fn normalize(declaration: &str) -> String {
+    declaration
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace("( ", "(")
+        .replace(", )", ")")
+        .replace(",)", ")")
+        .replace(" >", ">")
+}
+
+/// Saute les paramètres génériques en tête (`<T: Clone> Type<T>` → `Type<T>`)
+fn skip_generics(header: &str) -> &str {
+    if !header.starts_with('<') {
+        return header;
+    }
+    let mut depth = 0;
+    for (position, c) in header.char_indices() {
+        match c {
+            '<' => depth += 1,
+            '>' => {
+                depth -= 1;
+                if depth == 0 {
+                    return header[position + 1..].trim_start();
+                }
+            },
+            _ => {},
+        }
+    }
+    header
+}
+
+/// Retire les commentaires (doc comprise) et neutralise les littéraux dont le contenu
+/// trompe le décompte des blocs : `"{"` devient `".."`, `'}'` devient `'.'`
+fn sanitize(content: &str) -> String {
+    let chars: Vec<char> = content.chars().collect();
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    let mut code = String::with_capacity(content.len());
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        let previous = i.checked_sub(1).map(|j| chars[j]);
+        // `b"..."`, `b'.'`, `r"..."` et `br#"..."#` commencent hors identifiant
+        let prefix_allowed = !previous.is_some_and(is_identifier);
+        match c {
+            '/' if next == Some('/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                continue;
+            },
+            '/' if next == Some('*') => {
+                let mut depth = 0;
+                while i < chars.len() {
+                    match (chars[i], chars.get(i + 1)) {
+                        ('/', Some('*')) => {
+                            depth += 1;
+                            i += 2;
+                        },
+                        ('*', Some('/')) => {
+                            depth -= 1;
+                            i += 2;
+                            if depth == 0 {
+                                break;
+                            }
+                        },
+                        _ => i += 1,
+                    }
+                }
+                code.push(' ');
+                continue;
+            },
+            'r' | 'b' if prefix_allowed => {
+                let start = if c == 'b' && next == Some('r') { i + 2 } else { i + 1 };
+                let is_raw = c == 'r' || start == i + 2;
+                let hashes = chars[start..].iter().take_while(|c| **c == '#').count();
+                if is_raw && chars.get(start + hashes) == Some(&'"') {
+                    let body = start + hashes + 1;
+                    let mut end = body;
+                    while end < chars.len()
+                        && !(chars[end] == '"' && chars[end + 1..].iter().take(hashes).filter(|c| **c == '#').count() == hashes)
+                    {
+                        end += 1;
+                    }
+                    push_literal(&mut code, &chars[i..body], &chars[body..end.min(chars.len())], &chars[end.min(chars.len())..(end + 1 + hashes).min(chars.len())]);
+                    i = end + 1 + hashes;
+                    continue;
+                }
+                if c == 'b' && matches!(next, Some('"') | Some('\'')) {
+                    code.push('b');
+                    i += 1;
+                    continue;
+                }
+                code.push(c);
+            },
+            '"' => {
+                let end = closing_quote(&chars, i + 1, '"');
+                push_literal(&mut code, &chars[i..=i], &chars[i + 1..end], &chars[end.min(chars.len())..(end + 1).min(chars.len())]);
+                i = end + 1;
+                continue;
+            },
+            // `'a'`, `'\n'` sont des caractères ; `'a` une durée de vie
+            '\'' if next == Some('\\') || chars.get(i + 2) == Some(&'\'') => {
+                let end = closing_quote(&chars, i + 1, '\'');
+                push_literal(&mut code, &chars[i..=i], &chars[i + 1..end], &chars[end.min(chars.len())..(end + 1).min(chars.len())]);
+                i = end + 1;
+                continue;
+            },
+            _ => code.push(c),
+        }
+        i += 1;
+    }
+    code
+}
+
+/// Position du guillemet fermant un littéral ouvert avant `start`, échappements compris
+fn closing_quote(chars: &[char], mut start: usize, quote: char) -> usize {
+    while start < chars.len() && chars[start] != quote {
+        start += if chars[start] == '\\' { 2 } else { 1 };
+    }
+    start.min(chars.len())
+}
+
+/// Recopie un littéral, contenu remplacé par `..` s'il contient un délimiteur
+fn push_literal(code: &mut String, open: &[char], body: &[char], close: &[char]) {
+    code.extend(open);
+    if body.iter().any(|c| "{}()[];\n".contains(*c)) {
+        code.push_str(if open.last() == Some(&'\'') { "." } else { ".." });
+    } else {
+        code.extend(body);
+    }
+    code.extend(close);
+}
@@ -0,0 +1,403 @@
+use flate2::read::GzDecoder;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{Cursor, Read};
+use std::path::Path;
+
+use crate::analysis::source::{SourceFile, SourceTree};
+use crate::api::client::DEFAULT_MAX_FILE_SIZE;
+use crate::error::GithubAnalyzerError;
+
+/// Volume décompressé maximal d'une archive par défaut (1 Gio)
+pub const DEFAULT_MAX_ARCHIVE_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Limites appliquées à la décompression d'une archive
+#[derive(Debug, Clone, Copy)]
+pub struct ArchiveLimits {
+    /// Taille au-delà de laquelle le contenu d'un fichier n'est pas extrait
+    pub max_file_size: u64,
+    /// Volume décompressé total au-delà duquel l'archive est refusée
+    pub max_total_size: u64,
+}
+
+impl Default for ArchiveLimits {
+    fn default() -> Self {
+        Self {
+            max_file_size: DEFAULT_MAX_FILE_SIZE as u64,
+            max_total_size: DEFAULT_MAX_ARCHIVE_SIZE,
+        }
+    }
+}
+
+impl ArchiveLimits {
+    /// Ajoute `size` octets au volume décompressé, en erreur au-delà de la limite
+    fn consume(&self, total: &mut u64, size: u64) -> Result<(), GithubAnalyzerError> {
+        *total = total.saturating_add(size);
+        if *total > self.max_total_size {
+            return Err(GithubAnalyzerError::ArchiveError(format!(
+                "Archive expands beyond {} bytes (possible decompression bomb)",
+                self.max_total_size
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Fichier extrait d'une archive
+#[derive(Debug, Clone)]
+pub struct ArchiveEntry {
+    pub path: String,
+    /// Taille du fichier dans l'archive ; le contenu reste vide au-delà de
+    /// `ArchiveLimits::max_file_size`
+    pub size: u64,
+    pub content: Vec<u8>,
+}
+
+impl ArchiveEntry {
+    /// Nom du fichier (dernier segment du chemin)
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+
+    /// Contenu texte, si le fichier est en UTF-8
+    pub fn text(&self) -> Option<String> {
+        String::from_utf8(self.content.clone()).ok()
+    }
+}
+
+/// Contenu d'une archive chargée en mémoire
+#[derive(Debug, Clone)]
+pub struct Archive {
+    pub entries: Vec<ArchiveEntry>,
+    pub sha256: String,
+    /// Chemin du fichier d'archive local, repris dans les URLs des fichiers
+    pub location: Option<String>,
+}
+
+impl Archive {
+    /// Ouvre une archive `.tar.gz`/`.tgz`/`.tar` ou `.zip` selon sa signature
+    pub fn open(path: &Path, limits: ArchiveLimits) -> Result<Self, GithubAnalyzerError> {
+        let bytes = fs::read(path).map_err(|e| {
+            GithubAnalyzerError::ArchiveError(format!("Cannot read {}: {}", path.display(), e))
+        })?;
+        let mut archive = Self::from_bytes(&bytes, limits)?;
+        archive.location = Some(path.display().to_string());
+        Ok(archive)
+    }
+
+    /// Décode une archive à partir de son contenu brut
+    pub fn from_bytes(bytes: &[u8], limits: ArchiveLimits) -> Result<Self, GithubAnalyzerError> {
+        let entries = match bytes {
+            [0x1f, 0x8b, ..] => read_tar(GzDecoder::new(bytes), limits)?,
+            [b'P', b'K', 0x03, 0x04, ..] | [b'P', b'K', 0x05, 0x06, ..] => read_zip(bytes, limits)?,
+            _ if bytes.len() > 262 && &bytes[257..262] == b"ustar" => read_tar(bytes, limits)?,
+            [0x42, 0x5a, 0x68, ..] | [0xfd, b'7', b'z', b'X', b'Z', 0x00, ..] | [0x28, 0xb5, 0x2f, 0xfd, ..] => {
+                return Err(GithubAnalyzerError::ArchiveError(
+                    "Unsupported compression (expected gzip-compressed tar or zip)".to_string(),
+                ));
+            }
+            _ => {
+                return Err(GithubAnalyzerError::ArchiveError(
+                    "Unrecognized archive format (expected .tar.gz or .zip)".to_string(),
+                ));
+            }
+        };
+
+        Ok(Self {
+            entries: strip_single_root(entries),
+            sha256: format!("{:x}", Sha256::digest(bytes)),
+            location: None,
+        })
+    }
+
+    /// Répertoires contenant au moins un fichier de l'archive
+    pub fn directories(&self) -> BTreeSet<String> {
+        let mut directories = BTreeSet::new();
+        for entry in &self.entries {
+            let mut path = entry.path.as_str();
+            while let Some((parent, _)) = path.rsplit_once('/') {
+                directories.insert(parent.to_string());
+                path = parent;
+            }
+        }
+        directories
+    }
+}
+
+impl SourceTree for Archive {
+    fn directories(&self) -> BTreeSet<String> {
+        Archive::directories(self)
+    }
+
+    fn files(&self) -> Vec<SourceFile> {
+        self.entries
+            .iter()
+            .map(|entry| SourceFile { path: entry.path.clone(), size: entry.size })
+            .collect()
+    }
+
+    fn read_bytes(&self, index: usize) -> Option<Vec<u8>> {
+        self.entries.get(index).map(|entry| entry.content.clone())
+    }
+
+    /// `<archive>!/<chemin>` pour une archive locale, `archive://<chemin>` sinon
+    fn url(&self, path: &str) -> String {
+        match &self.location {
+            Some(location) => format!("{}!/{}", location, path),
+            None => format!("archive://{}", path),
+        }
+    }
+}
+
+/// Lit au plus `limit` octets d'une entrée ; `None` si elle en contient davantage
+fn read_limited(reader: impl Read, limit: u64) -> std::io::Result<Option<Vec<u8>>> {
+    let mut content = Vec::new();
+    reader.take(limit.saturating_add(1)).read_to_end(&mut content)?;
+    Ok((content.len() as u64 <= limit).then_some(content))
+}
+
+/// Lit toutes les entrées fichiers d'une archive tar
+///
+/// Le volume total est compté sur les tailles des en-têtes, avant décompression.
+fn read_tar<R: Read>(reader: R, limits: ArchiveLimits) -> Result<Vec<ArchiveEntry>, GithubAnalyzerError> {
+    let corrupt = |e: std::io::Error| GithubAnalyzerError::ArchiveError(format!("Corrupt tar archive: {}", e));
+    let mut archive = tar::Archive::new(reader);
+    let mut entries = Vec::new();
+    let mut total = 0;
+
+    for entry in archive.entries().map_err(corrupt)? {
+        let mut entry = entry.map_err(corrupt)?;
+        if !entry.header().entry_type().is_file() {
+            continue;
+        }
+        let path = entry.path().map_err(corrupt)?.to_string_lossy().replace('\\', "/");
+        let size = entry.size();
+        limits.consume(&mut total, size)?;
+        let content = if size > limits.max_file_size {
+            Vec::new()
+        } else {
+            read_limited(&mut entry, limits.max_file_size).map_err(corrupt)?.unwrap_or_default()
+        };
+        entries.push(ArchiveEntry { path, size, content });
+    }
+
+    Ok(entries)
+}
+
+/// Lit toutes les entrées fichiers d'une archive zip
+///
+/// La taille déclarée d'une entrée zip n'est pas fiable : le volume total compte
+/// les octets réellement décompressés.
+fn read_zip(bytes: &[u8], limits: ArchiveLimits) -> Result<Vec<ArchiveEntry>, GithubAnalyzerError> {
+    let corrupt = |e: zip::result::ZipError| match e {
+        zip::result::ZipError::UnsupportedArchive(msg) => {
+            GithubAnalyzerError::ArchiveError(format!("Unsupported zip compression: {}", msg))
+        }
+        other => GithubAnalyzerError::ArchiveError(format!("Corrupt zip archive: {}", other)),
+    };
+    let mut archive = zip::ZipArchive::new(Cursor::new(bytes)).map_err(corrupt)?;
+    let mut entries = Vec::new();
+    let mut total = 0;
+
+    for index in 0..archive.len() {
+        let mut file = archive.by_index(index).map_err(corrupt)?;
+        if file.is_dir() {
+            continue;
+        }
+        let path = file.name().replace('\\', "/");
+        let declared = file.size();
+        let content = if declared > limits.max_file_size {
+            None
+        } else {
+            read_limited(&mut file, limits.max_file_size)
+                .map_err(|e| GithubAnalyzerError::ArchiveError(format!("Corrupt zip archive: {}", e)))?
+        };
+        let (size, content) = match content {
+            Some(content) => (content.len() as u64, content),
+            // Contenu non extrait : au moins la limite, même si l'entrée déclare moins
+            None => (declared.max(limits.max_file_size + 1), Vec::new()),
+        };
+        limits.consume(&mut total, content.len() as u64)?;
+        entries.push(ArchiveEntry { path, size, content });
+    }
+
+    Ok(entries)
+}
+
+/// Retire le répertoire racine unique ajouté par les archives GitHub (`repo-sha/`)
+fn strip_single_root(mut entries: Vec<ArchiveEntry>) -> Vec<ArchiveEntry> {
+    for entry in entries.iter_mut() {
+        entry.path = entry.path.trim_start_matches("./").to_string();
+    }
+
+    let roots: BTreeSet<&str> = entries
+        .iter()
+        .map(|entry| entry.path.split('/').next().unwrap_or(""))
+        .collect();
+    let all_nested = entries.iter().all(|entry| entry.path.contains('/'));
+
+    if roots.len() == 1 && all_nested {
+        let prefix = format!("{}/", roots.into_iter().next().unwrap_or_default());
+        for entry in entries.iter_mut() {
+            entry.path = entry.path[prefix.len()..].to_string();
+        }
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use flate2::{write::GzEncoder, Compression};
+    use std::io::Write;
+    use zip::write::SimpleFileOptions;
+
+    const FILES: [(&str, &str); 3] = [
+        ("repo-0a1b2c/Cargo.toml", "[package]\nname = \"fixture\"\n"),
+        ("repo-0a1b2c/src/lib.rs", "pub fn answer() -> u32 {\n    42\n}\n"),
+        ("repo-0a1b2c/README.md", "# Fixture\n"),
+    ];
+
+    /// Archive tar.gz des entrées données (chemin, contenu, taille)
+    fn tar_gz(entries: Vec<(&str, Box<dyn Read + '_>, u64)>) -> Vec<u8> {
+        let mut builder = tar::Builder::new(GzEncoder::new(Vec::new(), Compression::fast()));
+        for (path, content, size) in entries {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(size);
+            header.set_mode(0o644);
+            header.set_cksum();
+            builder.append_data(&mut header, path, content).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap()
+    }
+
+    fn fixture_tar_gz() -> Vec<u8> {
+        tar_gz(
+            FILES
+                .iter()
+                .map(|(path, content)| (*path, Box::new(content.as_bytes()) as Box<dyn Read>, content.len() as u64))
+                .collect(),
+        )
+    }
+
+    /// Archive zip des entrées données
+    fn zip(entries: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(Cursor::new(Vec::new()));
+        for (path, content) in entries {
+            writer.start_file(*path, SimpleFileOptions::default()).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    fn fixture_zip() -> Vec<u8> {
+        zip(&FILES.iter().map(|(path, content)| (*path, content.as_bytes())).collect::<Vec<_>>())
+    }
+
+    fn archive_error(result: Result<Archive, GithubAnalyzerError>) -> String {
+        match result {
+            Err(GithubAnalyzerError::ArchiveError(message)) => message,
+            Err(other) => panic!("unexpected error: {:?}", other),
+            Ok(_) => panic!("archive unexpectedly accepted"),
+        }
+    }
+
+    fn assert_fixture(archive: &Archive, bytes: &[u8]) {
+        let mut paths: Vec<&str> = archive.entries.iter().map(|entry| entry.path.as_str()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["Cargo.toml", "README.md", "src/lib.rs"]);
+        let lib = archive.entries.iter().find(|entry| entry.path == "src/lib.rs").unwrap();
+        assert_eq!(lib.text().as_deref(), Some(FILES[1].1));
+        assert_eq!(lib.size, FILES[1].1.len() as u64);
+        assert_eq!(lib.name(), "lib.rs");
+        assert_eq!(archive.sha256, format!("{:x}", Sha256::digest(bytes)));
+        assert_eq!(archive.directories(), BTreeSet::from(["src".to_string()]));
+    }
+
+    #[test]
+    fn tar_gz_fixture_is_read_without_its_root_directory() {
+        let bytes = fixture_tar_gz();
+        assert_fixture(&Archive::from_bytes(&bytes, ArchiveLimits::default()).unwrap(), &bytes);
+    }
+
+    #[test]
+    fn zip_fixture_is_read_without_its_root_directory() {
+        let bytes = fixture_zip();
+        assert_fixture(&Archive::from_bytes(&bytes, ArchiveLimits::default()).unwrap(), &bytes);
+    }
+
+    #[test]
+    fn several_roots_are_kept() {
+        let bytes = zip(&[("a/x.rs", b"x"), ("b/y.rs", b"y")]);
+        let archive = Archive::from_bytes(&bytes, ArchiveLimits::default()).unwrap();
+        let paths: Vec<&str> = archive.entries.iter().map(|entry| entry.path.as_str()).collect();
+        assert_eq!(paths, vec!["a/x.rs", "b/y.rs"]);
+    }
+
+    #[test]
+    fn unsupported_and_corrupt_archives_have_specific_errors() {
+        let bzip2 = b"BZh91AY&SY\x00\x00\x00\x00".to_vec();
+        assert!(archive_error(Archive::from_bytes(&bzip2, ArchiveLimits::default())).starts_with("Unsupported compression"));
+        assert!(archive_error(Archive::from_bytes(b"plain text", ArchiveLimits::default())).starts_with("Unrecognized archive format"));
+
+        let mut truncated = fixture_tar_gz();
+        truncated.truncate(truncated.len() / 2);
+        assert!(archive_error(Archive::from_bytes(&truncated, ArchiveLimits::default())).starts_with("Corrupt tar archive"));
+        let mut truncated = fixture_zip();
+        truncated.truncate(40);
+        assert!(archive_error(Archive::from_bytes(&truncated, ArchiveLimits::default())).starts_with("Corrupt zip archive"));
+    }
+
+    #[test]
+    fn oversized_entries_keep_their_size_but_not_their_content() {
+        let limits = ArchiveLimits { max_file_size: 10, ..Default::default() };
+        let big = [b'a'; 100];
+
+        let archive = Archive::from_bytes(&zip(&[("big.txt", &big), ("small.txt", b"ok")]), limits).unwrap();
+        assert_eq!((archive.entries[0].size, archive.entries[0].content.len()), (100, 0));
+        assert_eq!(archive.entries[1].content, b"ok");
+
+        let bytes = tar_gz(vec![("big.txt", Box::new(&big[..]), 100), ("small.txt", Box::new(&b"ok"[..]), 2)]);
+        let archive = Archive::from_bytes(&bytes, limits).unwrap();
+        let files = archive.files();
+        assert_eq!((files[0].size, archive.entries[0].content.len()), (100, 0));
+        assert_eq!(archive.read_bytes(1).unwrap(), b"ok");
+    }
+
+    #[test]
+    fn decompression_bomb_is_refused() {
+        let limits = ArchiveLimits { max_file_size: 1024 * 1024, max_total_size: 4 * 1024 * 1024 };
+
+        // Tar : un seul fichier de 64 Mio de zéros, quelques dizaines de Kio compressés
+        let zeros = std::io::repeat(0).take(64 * 1024 * 1024);
+        let bytes = tar_gz(vec![("zeros.bin", Box::new(zeros), 64 * 1024 * 1024)]);
+        assert!(bytes.len() < 1024 * 1024);
+        assert!(archive_error(Archive::from_bytes(&bytes, limits)).contains("decompression bomb"));
+
+        // Zip : des fichiers chacun sous la limite, dont la somme la dépasse
+        let block = vec![0u8; 1024 * 1024];
+        let entries: Vec<(String, &[u8])> = (0..8).map(|index| (format!("zeros_{}.bin", index), &block[..])).collect();
+        let bytes = zip(&entries.iter().map(|(path, content)| (path.as_str(), *content)).collect::<Vec<_>>());
+        assert!(archive_error(Archive::from_bytes(&bytes, limits)).contains("decompression bomb"));
+    }
+
+    #[tokio::test]
+    async fn archive_analysis_records_its_provenance() {
+        use crate::analysis::options::AnalysisOptions;
+        use crate::analysis::repository::RepositoryAnalyzer;
+        use crate::types::analysis::SourceKind;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fixture.tar.gz");
+        let bytes = fixture_tar_gz();
+        fs::write(&path, &bytes).unwrap();
+
+        let options = AnalysisOptions { no_cache: true, ..Default::default() };
+        let (summary, _) = RepositoryAnalyzer::with_options(options).analyze_archive(&path).await.unwrap();
+        assert_eq!(summary.provenance.source_kind, SourceKind::Archive);
+        assert_eq!(summary.provenance.archive_sha256, Some(format!("{:x}", Sha256::digest(&bytes))));
+        assert_eq!(summary.provenance.archive_path, Some(path.display().to_string()));
+        assert!(summary.files_analyzed.iter().any(|file| &**file == "src/lib.rs"));
+    }
+}
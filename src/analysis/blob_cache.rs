@@ -0,0 +1,141 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::analysis::boilerplate::stable_hash;
+use crate::analysis::file::ContentSummary;
+use crate::types::analysis::{Configuration, MethodSignature, TypeRelations};
+use crate::types::SCHEMA_VERSION;
+
+/// Taille maximale par défaut du répertoire de cache (512 Mo)
+pub const DEFAULT_CACHE_MAX_SIZE: u64 = 512 * 1024 * 1024;
+
+/// Résultat de `FileAnalyzer::analyze_content` conservé d'une exécution à l'autre
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CachedAnalysis {
+    pub summary: ContentSummary,
+    pub type_relations: Vec<TypeRelations>,
+    pub method_signatures: Vec<MethodSignature>,
+    pub configuration: Configuration,
+}
+
+/// Cache sur disque des contenus de fichiers, indexés par SHA de blob, et de leur analyse.
+///
+/// Un fichier inchangé entre deux exécutions n'est ni téléchargé ni réanalysé.
+#[derive(Debug, Clone)]
+pub struct BlobCache {
+    dir: PathBuf,
+}
+
+impl BlobCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Clé d'analyse : le résultat dépend du contenu, du chemin, du schéma et des réglages
+    pub fn analysis_key(sha: &str, path: &str, settings: &str) -> String {
+        stable_hash(&format!("{}\0{}\0{}\0{}", SCHEMA_VERSION, settings, sha, path))
+    }
+
+    fn content_path(&self, sha: &str) -> Option<PathBuf> {
+        // Un SHA est hexadécimal : tout autre nom est refusé
+        let valid = !sha.is_empty() && sha.chars().all(|c| c.is_ascii_hexdigit());
+        valid.then(|| self.dir.join("blobs").join(format!("{}.txt", sha)))
+    }
+
+    fn analysis_path(&self, key: &str) -> PathBuf {
+        self.dir.join("analysis").join(format!("{}.json", key))
+    }
+
+    /// Contenu en cache pour ce SHA
+    pub fn content(&self, sha: &str) -> Option<String> {
+        let path = self.content_path(sha)?;
+        let content = fs::read_to_string(&path).ok()?;
+        touch(&path);
+        Some(content)
+    }
+
+    /// Enregistre un contenu s'il n'est pas déjà présent (cache au mieux)
+    pub fn store_content(&self, sha: &str, content: &str) {
+        if let Some(path) = self.content_path(sha).filter(|path| !path.exists()) {
+            write_entry(&path, content);
+        }
+    }
+
+    /// Analyse en cache pour cette clé
+    pub fn analysis(&self, key: &str) -> Option<CachedAnalysis> {
+        let path = self.analysis_path(key);
+        let analysis = serde_json::from_str(&fs::read_to_string(&path).ok()?).ok()?;
+        touch(&path);
+        Some(analysis)
+    }
+
+    pub fn store_analysis(&self, key: &str, analysis: &CachedAnalysis) {
+        if let Ok(json) = serde_json::to_string(analysis) {
+            write_entry(&self.analysis_path(key), &json);
+        }
+    }
+
+    /// Supprime les entrées les moins récemment utilisées du répertoire de cache
+    /// (cache HTTP compris) jusqu'à repasser sous `max_size` octets.
+    ///
+    /// Renvoie le nombre d'octets libérés.
+    pub fn evict(&self, max_size: u64) -> u64 {
+        let mut entries = Vec::new();
+        collect_entries(&self.dir, &mut entries);
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        if total <= max_size {
+            return 0;
+        }
+
+        entries.sort_by_key(|(_, _, used)| *used);
+        let mut freed = 0;
+        for (path, size, _) in entries {
+            if total <= max_size {
+                break;
+            }
+            if fs::remove_file(&path).is_ok() {
+                total -= size;
+                freed += size;
+            }
+        }
+        freed
+    }
+}
+
+fn write_entry(path: &Path, content: &str) {
+    if let Some(parent) = path.parent() {
+        let _ = fs::create_dir_all(parent).and_then(|_| fs::write(path, content));
+    }
+}
+
+/// Marque une entrée comme récemment utilisée (date de modification)
+fn touch(path: &Path) {
+    let _ = fs::File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+}
+
+/// Fichiers du cache avec leur taille et leur dernière utilisation
+fn collect_entries(dir: &Path, entries: &mut Vec<(PathBuf, u64, SystemTime)>) {
+    let Ok(read_dir) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in read_dir.flatten() {
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        if metadata.is_dir() {
+            collect_entries(&entry.path(), entries);
+        } else {
+            let used = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+            entries.push((entry.path(), metadata.len(), used));
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use std::collections::HashMap;
+
+use crate::types::analysis::BoilerplateHeader;
+
+/// Part minimale des fichiers partageant un en-tête pour le considérer comme boilerplate
+const BOILERPLATE_RATIO: f64 = 0.2;
+/// Nombre minimal de lignes d'un bloc de commentaires d'en-tête
+const MIN_HEADER_LINES: usize = 3;
+/// Nombre de lignes conservées dans la section "File start" des résumés
+const FILE_START_LINES: usize = 5;
+
+/// Hash FNV-1a 64 bits, stable d'une version à l'autre
+pub fn stable_hash(text: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in text.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("{:016x}", hash)
+}
+
+/// Extrait le bloc de commentaires en tête de fichier (licence, copyright, ...)
+///
+/// Retourne le texte du bloc et son nombre de lignes.
+pub fn leading_comment_block(content: &str) -> Option<(String, usize)> {
+    let mut block = Vec::new();
+    let mut in_block_comment = false;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        let is_comment = in_block_comment
+            || trimmed.starts_with("//")
+            || trimmed.starts_with("/*")
+            || (trimmed.starts_with('#') && !trimmed.starts_with("#[") && !trimmed.starts_with("#!["))
+            || trimmed.starts_with("--");
+
+        if trimmed.starts_with("/*") {
+            in_block_comment = true;
+        }
+        if in_block_comment && trimmed.contains("*/") {
+            in_block_comment = false;
+        }
+
+        // Les doc-comments de module font partie du code documenté, pas de l'en-tête
+        if trimmed.starts_with("//!") || trimmed.starts_with("///") {
+            break;
+        }
+        let blank_inside_block = trimmed.is_empty() && !block.is_empty();
+        if !is_comment && !blank_inside_block {
+            break;
+        }
+        block.push(line);
+    }
+
+    while block.last().is_some_and(|line| line.trim().is_empty()) {
+        block.pop();
+    }
+
+    let comment_lines = block.iter().filter(|line| !line.trim().is_empty()).count();
+    if comment_lines < MIN_HEADER_LINES {
+        return None;
+    }
+    Some((block.join("\n"), block.len()))
+}
+
+/// Section "File start" d'un résumé, construite à partir des lignes données
+pub fn file_start(lines: &[&str]) -> String {
+    if lines.is_empty() {
+        return String::new();
+    }
+    let mut start = String::from("File start:\n");
+    for line in lines.iter().take(FILE_START_LINES) {
+        start.push_str(&format!("{}\n", line));
+    }
+    start
+}
+
+/// En-tête observé dans un fichier, en attente de classification
+#[derive(Debug, Clone)]
+struct ObservedHeader {
+    hash: String,
+    line_count: usize,
+    original_start: String,
+    stripped_start: String,
+}
+
+/// Détecte les en-têtes de licence copiés dans de nombreux fichiers
+#[derive(Debug, Default)]
+pub struct HeaderDetector {
+    files_scanned: usize,
+    observed: HashMap<String, ObservedHeader>,
+    /// Texte de chaque en-tête et fichier dont il a été relevé
+    texts: HashMap<String, (String, String)>,
+}
+
+impl HeaderDetector {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enregistre l'en-tête éventuel d'un fichier source
+    pub fn observe(&mut self, path: &str, content: &str) {
+        self.files_scanned += 1;
+
+        let Some((block, line_count)) = leading_comment_block(content) else {
+            return;
+        };
+        let hash = stable_hash(&block);
+        let lines: Vec<&str> = content.lines().collect();
+        let remaining: Vec<&str> = lines
+            .iter()
+            .skip(line_count)
+            .skip_while(|line| line.trim().is_empty())
+            .copied()
+            .collect();
+
+        self.observed.insert(path.to_string(), ObservedHeader {
+            hash: hash.clone(),
+            line_count,
+            original_start: file_start(&lines),
+            stripped_start: file_start(&remaining),
+        });
+        self.texts.entry(hash).or_insert((block, path.to_string()));
+    }
+
+    /// Classe les en-têtes fréquents comme boilerplate
+    pub fn boilerplate(&self) -> Vec<BoilerplateHeader> {
+        let mut counts: HashMap<&str, usize> = HashMap::new();
+        for header in self.observed.values() {
+            *counts.entry(header.hash.as_str()).or_insert(0) += 1;
+        }
+
+        let threshold = (self.files_scanned as f64 * BOILERPLATE_RATIO).max(1.0);
+        let mut headers: Vec<BoilerplateHeader> = counts
+            .into_iter()
+            .filter(|(_, count)| *count >= 2 && *count as f64 > threshold)
+            .map(|(hash, count)| {
+                let line_count = self.observed
+                    .values()
+                    .find(|header| header.hash == hash)
+                    .map(|header| header.line_count)
+                    .unwrap_or_default();
+                let (text, source) = self.texts.get(hash).cloned().unwrap_or_default();
+                BoilerplateHeader {
+                    hash: hash.to_string(),
+                    line_count: line_count as i32,
+                    affected_files: count as i32,
+                    text,
+                    source,
+                }
+            })
+            .collect();
+        headers.sort_by(|a, b| b.affected_files.cmp(&a.affected_files).then(a.hash.cmp(&b.hash)));
+        headers
+    }
+
+    /// Retire l'en-tête boilerplate de la section "File start" d'un résumé
+    pub fn strip_from_summary(&self, path: &str, summary: &mut String, boilerplate: &[BoilerplateHeader]) {
+        let Some(header) = self.observed.get(path) else {
+            return;
+        };
+        if !boilerplate.iter().any(|b| b.hash == header.hash) {
+            return;
+        }
+        if let Some(rest) = summary.strip_prefix(&header.original_start) {
+            *summary = format!("{}{}", header.stripped_start, rest);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::options::AnalysisOptions;
+    use crate::analysis::repository::RepositoryAnalyzer;
+
+    const APACHE_HEADER: &str = "\
+// Copyright 2024 The Fixture Authors
+//
+// Licensed under the Apache License, Version 2.0 (the \"License\");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an \"AS IS\" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.";
+
+    fn with_header(body: &str) -> String {
+        format!("{}\n\n{}", APACHE_HEADER, body)
+    }
+
+    #[test]
+    fn leading_block_stops_at_code_and_module_docs() {
+        let content = with_header("//! Module docs\nuse std::fmt;\n");
+        let (block, line_count) = leading_comment_block(&content).unwrap();
+        assert_eq!(block, APACHE_HEADER);
+        assert_eq!(line_count, 13);
+
+        assert_eq!(leading_comment_block("// one\n// two\nfn main() {}\n"), None);
+        assert_eq!(leading_comment_block("//! Crate docs\n//! more\n//! lines\n"), None);
+    }
+
+    #[test]
+    fn shared_apache_header_is_classified_as_boilerplate() {
+        let mut detector = HeaderDetector::new();
+        for index in 0..4 {
+            detector.observe(&format!("src/file_{}.rs", index), &with_header(&format!("pub fn f{}() {{}}\n", index)));
+        }
+        detector.observe("src/main.rs", "fn main() {}\n");
+
+        let boilerplate = detector.boilerplate();
+        assert_eq!(boilerplate.len(), 1);
+        let header = &boilerplate[0];
+        assert_eq!(header.hash, stable_hash(APACHE_HEADER));
+        assert_eq!(header.affected_files, 4);
+        assert_eq!(header.line_count, 13);
+        assert_eq!(header.text, APACHE_HEADER);
+        assert!(header.source.starts_with("src/file_"));
+    }
+
+    #[test]
+    fn header_in_few_files_is_not_boilerplate() {
+        let mut detector = HeaderDetector::new();
+        detector.observe("src/a.rs", &with_header("pub fn a() {}\n"));
+        detector.observe("src/b.rs", &with_header("pub fn b() {}\n"));
+        for index in 0..10 {
+            detector.observe(&format!("src/plain_{}.rs", index), "pub fn plain() {}\n");
+        }
+        // 2 fichiers sur 12 : sous le seuil de 20 %
+        assert!(detector.boilerplate().is_empty());
+    }
+
+    #[test]
+    fn stripped_summary_starts_after_the_header() {
+        let mut detector = HeaderDetector::new();
+        let content = with_header("use std::fmt;\n\npub struct Point;\n");
+        detector.observe("src/a.rs", &content);
+        detector.observe("src/b.rs", &content);
+        let boilerplate = detector.boilerplate();
+
+        let lines: Vec<&str> = content.lines().collect();
+        let mut summary = format!("{}Types: Point\n", file_start(&lines));
+        detector.strip_from_summary("src/a.rs", &mut summary, &boilerplate);
+        assert_eq!(summary, "File start:\nuse std::fmt;\n\npub struct Point;\nTypes: Point\n");
+
+        // Un fichier sans en-tête boilerplate garde son résumé
+        let mut untouched = String::from("File start:\nfn main() {}\n");
+        detector.strip_from_summary("src/main.rs", &mut untouched, &boilerplate);
+        assert_eq!(untouched, "File start:\nfn main() {}\n");
+    }
+
+    #[tokio::test]
+    async fn analysis_records_header_and_cleans_file_summaries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"fixture\"\nversion = \"0.1.0\"\n").unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), with_header("pub mod a;\npub mod b;\n")).unwrap();
+        std::fs::write(dir.path().join("src/a.rs"), with_header("pub fn a() {}\n")).unwrap();
+        std::fs::write(dir.path().join("src/b.rs"), with_header("pub fn b() {}\n")).unwrap();
+
+        let options = AnalysisOptions { no_cache: true, ..Default::default() };
+        let (summary, _) = RepositoryAnalyzer::with_options(options).analyze_local(dir.path()).await.unwrap();
+
+        assert_eq!(summary.boilerplate_headers.len(), 1);
+        assert_eq!(summary.boilerplate_headers[0].affected_files, 3);
+        assert_eq!(summary.boilerplate_headers[0].hash, stable_hash(APACHE_HEADER));
+        for file_summary in summary.file_summaries.iter().filter(|file| file.path.ends_with(".rs")) {
+            assert!(!file_summary.summary.contains("Apache License"), "{}", file_summary.path);
+            assert!(file_summary.summary.starts_with("File start:\npub "), "{}", file_summary.summary);
+        }
+    }
+}
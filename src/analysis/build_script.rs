@@ -0,0 +1,62 @@
+use regex::Regex;
+
+use crate::analysis::masking::mask_code;
+use crate::types::analysis::BuildScript;
+
+/// Générateurs de code et outils de compilation native reconnus dans un script de
+/// build, par chemin de crate (`prost_build::compile_protos`), avec leur nom cargo
+const GENERATORS: &[(&str, &str)] = &[
+    ("bindgen", "bindgen"),
+    ("prost_build", "prost-build"),
+    ("tonic_build", "tonic-build"),
+    ("cxx_build", "cxx-build"),
+    ("cbindgen", "cbindgen"),
+    ("lalrpop", "lalrpop"),
+    ("cc", "cc"),
+    ("cmake", "cmake"),
+    ("pkg_config", "pkg-config"),
+];
+
+/// Le fichier est le script de build du dépôt (`build.rs` à la racine)
+pub fn is_build_script(path: &str) -> bool {
+    path == "build.rs"
+}
+
+/// Résume un script de build : directives `cargo:` émises par `println!`, variables
+/// d'environnement lues et générateurs invoqués
+pub fn analyze_build_script(path: &str, content: &str) -> BuildScript {
+    let directive = Regex::new(r#"println!\s*\(\s*"cargo::?([\w-]+)=([^"]*)""#).unwrap();
+    let variable = Regex::new(r#"\b(?:env::var(?:_os)?|env!|option_env!)\s*\(\s*"([^"]+)""#).unwrap();
+    let generator = Regex::new(r"\b(\w+)::").unwrap();
+
+    let mut script = BuildScript { path: path.to_string(), ..Default::default() };
+    for captures in directive.captures_iter(content) {
+        let (key, value) = (&captures[1], captures[2].trim());
+        let (values, value) = match key {
+            "rerun-if-changed" | "rerun-if-env-changed" => (&mut script.rerun_if_changed, value.to_string()),
+            "rustc-link-lib" => (&mut script.link_libs, value.to_string()),
+            "rustc-cfg" => (&mut script.cfgs, value.to_string()),
+            // Déclaration des `cfg` attendus, sans effet sur la compilation
+            "rustc-check-cfg" => continue,
+            _ => (&mut script.other_directives, format!("{}={}", key, value)),
+        };
+        if !values.contains(&value) {
+            values.push(value);
+        }
+    }
+    for captures in variable.captures_iter(content) {
+        if !script.env_vars.iter().any(|name| *name == captures[1]) {
+            script.env_vars.push(captures[1].to_string());
+        }
+    }
+    // Les chemins sont cherchés hors commentaires et chaînes
+    for captures in generator.captures_iter(&mask_code(content)) {
+        let found = GENERATORS.iter().find(|(module, _)| *module == &captures[1]);
+        if let Some((_, name)) = found {
+            if !script.generators.iter().any(|generator| generator == name) {
+                script.generators.push(name.to_string());
+            }
+        }
+    }
+    script
+}
@@ -0,0 +1,175 @@
+use toml::{Table, Value};
+
+use crate::analysis::targets::TargetKind;
+use crate::types::analysis::{CrateMetadata, Dependency, DependencyKind};
+
+/// Sections de dépendances d'un manifeste
+const SECTIONS: [(&str, DependencyKind); 3] = [
+    ("dependencies", DependencyKind::Normal),
+    ("dev-dependencies", DependencyKind::Dev),
+    ("build-dependencies", DependencyKind::Build),
+];
+
+/// Dépendances d'un `Cargo.toml` : sections `[dependencies]`, `[dev-dependencies]` et
+/// `[build-dependencies]`, y compris celles propres à une cible
+/// (`[target.'cfg(unix)'.dependencies]`).
+///
+/// `None` si le manifeste n'est pas du TOML valide.
+pub fn cargo_dependencies(content: &str) -> Option<Vec<Dependency>> {
+    let manifest: Table = content.parse().ok()?;
+    let targets = manifest
+        .get("target")
+        .and_then(Value::as_table)
+        .into_iter()
+        .flat_map(|targets| targets.values())
+        .filter_map(Value::as_table);
+
+    let mut dependencies = Vec::new();
+    for table in std::iter::once(&manifest).chain(targets) {
+        for (section, kind) in SECTIONS {
+            // `dev_dependencies` est l'orthographe historique, encore acceptée par Cargo
+            let entries = table
+                .get(section)
+                .or_else(|| table.get(&section.replace('-', "_")))
+                .and_then(Value::as_table);
+            for (name, spec) in entries.into_iter().flatten() {
+                dependencies.push(dependency(name, spec, kind));
+            }
+        }
+    }
+    Some(dependencies)
+}
+
+/// Nom du paquet déclaré par un `Cargo.toml` (`[package] name`) ; `None` pour un
+/// manifeste d'espace de travail ou invalide
+pub fn package_name(content: &str) -> Option<String> {
+    let manifest: Table = content.parse().ok()?;
+    manifest.get("package")?.get("name")?.as_str().map(str::to_string)
+}
+
+/// Membres d'un espace de travail (`[workspace] members`, motifs glob compris) et
+/// répertoires exclus (`exclude`) ; `None` hors manifeste d'espace de travail
+pub fn workspace_members(content: &str) -> Option<(Vec<String>, Vec<String>)> {
+    let manifest: Table = content.parse().ok()?;
+    let workspace = manifest.get("workspace")?;
+    let paths = |key: &str| -> Vec<String> {
+        workspace
+            .get(key)
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(|path| path.trim_start_matches("./").trim_end_matches('/').to_string())
+            .collect()
+    };
+    Some((paths("members"), paths("exclude")))
+}
+
+/// Métadonnées du paquet d'un `Cargo.toml` ; les valeurs héritées de l'espace de
+/// travail (`edition.workspace = true`) sont lues dans le `[workspace.package]` du
+/// manifeste racine `workspace_manifest`. `None` sans section `[package]`.
+pub fn package_metadata(content: &str, workspace_manifest: Option<&str>) -> Option<CrateMetadata> {
+    let manifest: Table = content.parse().ok()?;
+    let package = manifest.get("package")?.as_table()?;
+    let workspace_manifest: Option<Table> = workspace_manifest.and_then(|content| content.parse().ok());
+    let inherited = workspace_manifest
+        .as_ref()
+        .and_then(|root| root.get("workspace")?.get("package")?.as_table());
+    let field = |key: &str| -> Option<String> {
+        let value = package.get(key)?;
+        let value = match value.get("workspace").and_then(Value::as_bool) {
+            Some(true) => inherited?.get(key)?,
+            _ => value,
+        };
+        // `rust-version` et `edition` sont parfois écrits sans guillemets (`1.70`)
+        match value {
+            Value::String(value) => Some(value.clone()),
+            Value::Float(_) | Value::Integer(_) => Some(value.to_string()),
+            _ => None,
+        }
+    };
+    Some(CrateMetadata {
+        name: field("name").unwrap_or_default(),
+        version: field("version"),
+        edition: field("edition"),
+        rust_version: field("rust-version"),
+        description: field("description"),
+        lib: manifest.contains_key("lib"),
+        bin: manifest.get("bin").and_then(Value::as_array).is_some_and(|bins| !bins.is_empty()),
+    })
+}
+
+/// Cibles déclarées avec un chemin explicite par `[[bin]]`, `[[example]]` et `[[bench]]`,
+/// chemins relatifs au répertoire du manifeste
+pub fn declared_targets(content: &str) -> Vec<(TargetKind, String)> {
+    let Ok(manifest) = content.parse::<Table>() else { return Vec::new() };
+    [TargetKind::Bin, TargetKind::Example, TargetKind::Bench]
+        .into_iter()
+        .flat_map(|kind| {
+            manifest
+                .get(kind.manifest_key())
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(|target| target.get("path")?.as_str())
+                .map(move |path| (kind, path.trim_start_matches("./").to_string()))
+        })
+        .collect()
+}
+
+/// Nom du paquet d'une crate de macros procédurales (`[lib] proc-macro = true`)
+pub fn proc_macro_package(content: &str) -> Option<String> {
+    let manifest: Table = content.parse().ok()?;
+    let lib = manifest.get("lib")?;
+    let proc_macro = lib.get("proc-macro").or_else(|| lib.get("proc_macro"))?.as_bool()?;
+    if !proc_macro {
+        return None;
+    }
+    manifest.get("package")?.get("name")?.as_str().map(str::to_string)
+}
+
+/// Fonctionnalités déclarées par un `Cargo.toml` : clés de `[features]`, et dépendances
+/// optionnelles, qui en définissent une implicitement tant qu'aucune fonctionnalité ne
+/// les active par `dep:nom`
+pub fn declared_features(content: &str) -> Vec<String> {
+    let Ok(manifest) = content.parse::<Table>() else { return Vec::new() };
+    let features = manifest.get("features").and_then(Value::as_table);
+    let mut declared: Vec<String> = features.into_iter().flat_map(|features| features.keys().cloned()).collect();
+    let explicit: Vec<&str> = features
+        .into_iter()
+        .flat_map(|features| features.values())
+        .filter_map(Value::as_array)
+        .flatten()
+        .filter_map(Value::as_str)
+        .filter_map(|enabled| enabled.strip_prefix("dep:"))
+        .collect();
+    for dependency in cargo_dependencies(content).into_iter().flatten().filter(|dependency| dependency.optional) {
+        if !explicit.contains(&dependency.name.as_str()) && !declared.contains(&dependency.name) {
+            declared.push(dependency.name);
+        }
+    }
+    declared
+}
+
+/// Dépendance déclarée par `name = "1.0"` ou `name = { version = "1.0", .. }`
+fn dependency(name: &str, spec: &Value, kind: DependencyKind) -> Dependency {
+    let field = |key: &str| spec.as_table().and_then(|detail| detail.get(key));
+    Dependency {
+        name: name.to_string(),
+        version_req: spec
+            .as_str()
+            .or_else(|| field("version").and_then(Value::as_str))
+            .map(str::to_string),
+        kind,
+        optional: field("optional").and_then(Value::as_bool).unwrap_or(false),
+        features: field("features")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        defined_in: String::new(),
+        unused: false,
+    }
+}
@@ -0,0 +1,199 @@
+use regex::Regex;
+
+use crate::types::analysis::CiPipeline;
+
+/// Fournisseur d'intégration continue d'un fichier, d'après son chemin
+pub fn ci_provider(path: &str) -> Option<&'static str> {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let yaml = name.ends_with(".yml") || name.ends_with(".yaml");
+    match name {
+        ".gitlab-ci.yml" => Some("GitLab CI"),
+        "Jenkinsfile" => Some("Jenkins"),
+        "azure-pipelines.yml" | "azure-pipelines.yaml" => Some("Azure Pipelines"),
+        ".travis.yml" => Some("Travis CI"),
+        _ if yaml && path.starts_with(".github/workflows/") => Some("GitHub Actions"),
+        _ if yaml && path.starts_with(".circleci/") => Some("CircleCI"),
+        _ => None,
+    }
+}
+
+/// Clés de premier niveau de `.gitlab-ci.yml` qui ne sont pas des jobs
+const GITLAB_RESERVED_KEYS: &[&str] = &[
+    "stages", "variables", "image", "services", "before_script", "after_script", "cache",
+    "include", "default", "workflow", "types",
+];
+
+/// Ligne significative d'un fichier YAML (sans commentaire) et son indentation
+struct YamlLine<'a> {
+    indent: usize,
+    text: &'a str,
+}
+
+/// Lecture minimale d'un fichier YAML de CI : seules les clés de premier niveau et
+/// leurs enfants directs sont interprétés, ce qui suffit aux workflows usuels
+struct YamlOutline<'a> {
+    lines: Vec<YamlLine<'a>>,
+}
+
+impl<'a> YamlOutline<'a> {
+    fn parse(content: &'a str) -> Self {
+        let lines = content
+            .lines()
+            .filter_map(|line| {
+                let text = match line.find(" #") {
+                    Some(comment) => &line[..comment],
+                    None => line,
+                }
+                .trim_end();
+                let trimmed = text.trim_start();
+                if trimmed.is_empty() || trimmed.starts_with('#') || trimmed == "---" {
+                    return None;
+                }
+                Some(YamlLine { indent: text.len() - trimmed.len(), text: trimmed })
+            })
+            .collect();
+        Self { lines }
+    }
+
+    /// Clés de premier niveau, dans l'ordre du fichier
+    fn top_level_keys(&self) -> Vec<&'a str> {
+        self.lines
+            .iter()
+            .filter(|line| line.indent == 0)
+            .filter_map(|line| mapping_key(line.text))
+            .collect()
+    }
+
+    /// Valeur en ligne d'une clé de premier niveau (`name: CI`)
+    fn value(&self, key: &str) -> Option<&'a str> {
+        self.lines
+            .iter()
+            .filter(|line| line.indent == 0)
+            .find(|line| mapping_key(line.text) == Some(key))
+            .and_then(|line| line.text.split_once(':'))
+            .map(|(_, value)| unquote(value.trim()))
+            .filter(|value| !value.is_empty())
+    }
+
+    /// Enfants directs d'une clé de premier niveau : clés d'un mapping ou éléments
+    /// d'une liste
+    fn children(&self, key: &str) -> Vec<&'a str> {
+        let Some(start) = self.lines
+            .iter()
+            .position(|line| line.indent == 0 && mapping_key(line.text) == Some(key))
+        else {
+            return Vec::new();
+        };
+        let block: Vec<&YamlLine> = self.lines[start + 1..]
+            .iter()
+            .take_while(|line| line.indent > 0)
+            .collect();
+        let Some(indent) = block.iter().map(|line| line.indent).min() else {
+            return Vec::new();
+        };
+        block
+            .iter()
+            .filter(|line| line.indent == indent)
+            .filter_map(|line| match line.text.strip_prefix("- ") {
+                Some(item) => Some(mapping_key(item).unwrap_or(unquote(item.trim()))),
+                None => mapping_key(line.text),
+            })
+            .collect()
+    }
+
+    /// Valeur en ligne d'une clé de premier niveau, ou ses enfants directs ; une
+    /// liste en ligne (`[push, pull_request]`) est dépliée
+    fn list(&self, key: &str) -> Vec<String> {
+        match self.value(key) {
+            Some(value) => value
+                .trim_start_matches('[')
+                .trim_end_matches(']')
+                .split(',')
+                .map(|item| unquote(item.trim()).to_string())
+                .filter(|item| !item.is_empty())
+                .collect(),
+            None => self.children(key).into_iter().map(str::to_string).collect(),
+        }
+    }
+}
+
+/// Clé d'une ligne `key:` ou `key: value`, guillemets retirés
+fn mapping_key(text: &str) -> Option<&str> {
+    let (key, _) = text.split_once(':')?;
+    let key = unquote(key.trim());
+    (!key.is_empty() && !key.contains(' ') && !key.starts_with('-')).then_some(key)
+}
+
+fn unquote(value: &str) -> &str {
+    value.trim_matches(|c| c == '"' || c == '\'')
+}
+
+/// Extrait le nom, les déclencheurs et les jobs d'un fichier de CI ; `None` si le
+/// chemin n'est pas celui d'une configuration de CI reconnue
+pub fn analyze_ci(path: &str, content: &str) -> Option<CiPipeline> {
+    let provider = ci_provider(path)?;
+    let file_stem = path
+        .rsplit('/')
+        .next()
+        .unwrap_or(path)
+        .trim_end_matches(".yml")
+        .trim_end_matches(".yaml")
+        .to_string();
+
+    let (name, triggers, jobs) = match provider {
+        "GitHub Actions" => {
+            let outline = YamlOutline::parse(content);
+            // `on` est parfois écrit entre guillemets pour éviter sa lecture comme booléen
+            let triggers = ["on", "true"]
+                .iter()
+                .map(|key| outline.list(key))
+                .find(|triggers| !triggers.is_empty())
+                .unwrap_or_default();
+            let name = outline.value("name").map(str::to_string).unwrap_or(file_stem);
+            (name, triggers, outline.list("jobs"))
+        },
+        "GitLab CI" => {
+            let outline = YamlOutline::parse(content);
+            let jobs = outline
+                .top_level_keys()
+                .into_iter()
+                .filter(|key| !key.starts_with('.') && !GITLAB_RESERVED_KEYS.contains(key))
+                .map(str::to_string)
+                .collect();
+            (provider.to_string(), Vec::new(), jobs)
+        },
+        "Azure Pipelines" => {
+            let outline = YamlOutline::parse(content);
+            let triggers = [("trigger", "push"), ("pr", "pull_request"), ("schedules", "schedule")]
+                .iter()
+                .filter(|(key, _)| outline.top_level_keys().contains(key))
+                .map(|(_, event)| event.to_string())
+                .collect();
+            let jobs = outline.lines
+                .iter()
+                .filter_map(|line| line.text.strip_prefix("- job:"))
+                .map(|job| unquote(job.trim()).to_string())
+                .collect();
+            let name = outline.value("name").map(str::to_string).unwrap_or_else(|| provider.to_string());
+            (name, triggers, jobs)
+        },
+        "CircleCI" => {
+            let outline = YamlOutline::parse(content);
+            (provider.to_string(), Vec::new(), outline.list("jobs"))
+        },
+        "Jenkins" => {
+            let stage = Regex::new(r#"stage\s*\(\s*['"]([^'"]+)['"]"#).unwrap();
+            let jobs = stage.captures_iter(content).map(|captures| captures[1].to_string()).collect();
+            (provider.to_string(), Vec::new(), jobs)
+        },
+        _ => (provider.to_string(), Vec::new(), Vec::new()),
+    };
+
+    Some(CiPipeline {
+        path: path.to_string(),
+        provider: provider.to_string(),
+        name,
+        triggers,
+        jobs,
+    })
+}
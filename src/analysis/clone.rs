@@ -0,0 +1,284 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Output;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::process::Command;
+
+use crate::error::GithubAnalyzerError;
+use crate::types::locator::RepoLocator;
+
+/// Clone superficiel (`--depth 1`) d'un dépôt dans un répertoire temporaire,
+/// supprimé à la destruction sauf avec `keep`
+#[derive(Debug)]
+pub struct GitClone {
+    dir: PathBuf,
+    keep: bool,
+    /// Commit extrait (HEAD du clone)
+    pub head_sha: String,
+    /// Référence extraite : celle demandée, sinon la branche par défaut du dépôt
+    pub branch: String,
+}
+
+impl GitClone {
+    /// Clone `repo_url` à la référence donnée (branche, tag ou SHA de commit).
+    ///
+    /// Une URL SSH (`git@host:owner/repo.git`, `ssh://...`) passe par l'agent SSH ;
+    /// sinon le clone se fait en HTTPS, avec le token s'il y en a un.
+    pub async fn shallow(
+        repo_url: &str,
+        locator: &RepoLocator,
+        git_ref: Option<&str>,
+        token: Option<&str>,
+        keep: bool,
+    ) -> Result<Self, GithubAnalyzerError> {
+        let ssh = repo_url.starts_with("git@") || repo_url.starts_with("ssh://");
+        let url = if ssh {
+            repo_url.to_string()
+        } else {
+            format!("https://{}/{}/{}.git", locator.host, locator.owner, locator.repo)
+        };
+        let token = token.filter(|_| !ssh);
+
+        let mut clone = Self {
+            dir: temp_dir(locator),
+            keep,
+            head_sha: String::new(),
+            branch: String::new(),
+        };
+        let dir = clone.dir.to_string_lossy().into_owned();
+
+        checkout(&url, &clone.dir, git_ref, token).await?;
+
+        clone.head_sha = git(&["-C", &dir, "rev-parse", "HEAD"], None).await?;
+        clone.branch = match git_ref {
+            Some(git_ref) => git_ref.to_string(),
+            None => git(&["-C", &dir, "rev-parse", "--abbrev-ref", "HEAD"], None).await?,
+        };
+        Ok(clone)
+    }
+
+    /// Répertoire de travail du clone
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Indique si le clone est conservé après l'analyse
+    pub fn kept(&self) -> bool {
+        self.keep
+    }
+}
+
+impl Drop for GitClone {
+    fn drop(&mut self) {
+        if !self.keep {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+}
+
+/// Répertoire temporaire propre à ce clone
+fn temp_dir(locator: &RepoLocator) -> PathBuf {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos())
+        .unwrap_or(0);
+    std::env::temp_dir().join(format!(
+        "repo_analyzer-{}_{}-{}-{}",
+        locator.owner, locator.repo, std::process::id(), nanos
+    ))
+}
+
+/// Extrait `url` dans `dir` à la référence donnée, ou à la branche par défaut.
+///
+/// Un SHA complet est récupéré seul. Toute autre référence est d'abord cherchée
+/// parmi les branches et tags ; à défaut, si elle a la forme d'un SHA abrégé,
+/// elle est résolue sur l'historique du dépôt (cloné sans le contenu des fichiers).
+async fn checkout(url: &str, dir: &Path, git_ref: Option<&str>, token: Option<&str>) -> Result<(), GithubAnalyzerError> {
+    let dir = dir.to_string_lossy();
+    let dir = dir.as_ref();
+    match git_ref {
+        // `clone --branch` n'accepte pas de SHA : récupération du commit seul
+        Some(sha) if is_full_commit_sha(sha) => {
+            git(&["init", "-q", dir], token).await?;
+            git(&["-C", dir, "fetch", "-q", "--depth", "1", url, sha], token).await?;
+            git(&["-C", dir, "checkout", "-q", "--detach", "FETCH_HEAD"], None).await?;
+        }
+        Some(git_ref) => {
+            match git(&["clone", "-q", "--depth", "1", "--branch", git_ref, url, dir], token).await {
+                Err(GithubAnalyzerError::NotFound(_)) if is_abbreviated_sha(git_ref) => {
+                    // Les serveurs refusent un SHA abrégé : résolution sur l'historique local
+                    let _ = fs::remove_dir_all(dir);
+                    git(&["clone", "-q", "--filter=blob:none", "--no-checkout", url, dir], token).await?;
+                    let commit = format!("{}^{{commit}}", git_ref);
+                    let sha = git(&["-C", dir, "rev-parse", "--verify", "--quiet", &commit], None)
+                        .await
+                        .map_err(|_| GithubAnalyzerError::NotFound(format!("No branch, tag or commit named {}", git_ref)))?;
+                    git(&["-C", dir, "checkout", "-q", "--detach", &sha], token).await?;
+                }
+                result => {
+                    result?;
+                }
+            }
+        }
+        None => {
+            git(&["clone", "-q", "--depth", "1", url, dir], token).await?;
+        }
+    }
+    Ok(())
+}
+
+/// SHA de commit complet (40 caractères hexadécimaux), récupérable directement
+fn is_full_commit_sha(git_ref: &str) -> bool {
+    git_ref.len() == 40 && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Référence qui peut désigner un SHA abrégé, si aucune branche ni tag ne porte ce nom
+fn is_abbreviated_sha(git_ref: &str) -> bool {
+    (7..40).contains(&git_ref.len()) && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Exécute git et renvoie sa sortie standard.
+///
+/// Le token est transmis par l'environnement (`GIT_CONFIG_*`) pour ne pas
+/// apparaître dans la ligne de commande ; git n'invite jamais à saisir d'identifiants.
+async fn git(args: &[&str], token: Option<&str>) -> Result<String, GithubAnalyzerError> {
+    let mut command = Command::new("git");
+    command.args(args).env("GIT_TERMINAL_PROMPT", "0");
+    if let Some(token) = token {
+        let credentials = base64::encode(format!("x-access-token:{}", token));
+        command
+            .env("GIT_CONFIG_COUNT", "1")
+            .env("GIT_CONFIG_KEY_0", "http.extraHeader")
+            .env("GIT_CONFIG_VALUE_0", format!("Authorization: Basic {}", credentials));
+    }
+
+    let output = command.output().await.map_err(|e| {
+        GithubAnalyzerError::NetworkError(format!("Cannot run git (is it installed?): {}", e))
+    })?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(git_error(args, &output))
+    }
+}
+
+/// Convertit l'échec d'une commande git en erreur explicite
+fn git_error(args: &[&str], output: &Output) -> GithubAnalyzerError {
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    let subcommand = match args {
+        ["-C", _, subcommand, ..] | [subcommand, ..] => subcommand,
+        [] => "",
+    };
+    let message = format!("git {} failed: {}", subcommand, stderr);
+    let lower = stderr.to_lowercase();
+    if lower.contains("authentication failed")
+        || lower.contains("permission denied")
+        || lower.contains("could not read username")
+    {
+        GithubAnalyzerError::AuthError(message)
+    } else if lower.contains("not found")
+        || lower.contains("couldn't find remote ref")
+        || lower.contains("does not appear to be a git repository")
+    {
+        GithubAnalyzerError::NotFound(message)
+    } else {
+        GithubAnalyzerError::NetworkError(message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command as StdCommand;
+
+    fn run(dir: &Path, args: &[&str]) -> String {
+        let output = StdCommand::new("git")
+            .args(["-c", "user.name=Test", "-c", "user.email=test@example.com", "-c", "init.defaultBranch=main"])
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?}: {}", args, String::from_utf8_lossy(&output.stderr));
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    /// Dépôt source à deux commits : `main` sur le second, la branche `cafe1234`
+    /// (nom hexadécimal) et le tag `v1` sur le premier. Renvoie l'URL et les deux SHA.
+    fn source_repository(root: &Path) -> (String, String, String) {
+        let source = root.join("source");
+        fs::create_dir(&source).unwrap();
+        run(&source, &["init", "-q"]);
+        run(&source, &["config", "uploadpack.allowAnySHA1InWant", "true"]);
+        fs::write(source.join("lib.rs"), "pub fn first() {}\n").unwrap();
+        run(&source, &["add", "."]);
+        run(&source, &["commit", "-q", "-m", "first"]);
+        let first = run(&source, &["rev-parse", "HEAD"]);
+        run(&source, &["branch", "cafe1234"]);
+        run(&source, &["tag", "v1"]);
+        fs::write(source.join("lib.rs"), "pub fn second() {}\n").unwrap();
+        run(&source, &["commit", "-q", "-am", "second"]);
+        let second = run(&source, &["rev-parse", "HEAD"]);
+        (format!("file://{}", source.display()), first, second)
+    }
+
+    async fn checked_out(url: &str, root: &Path, name: &str, git_ref: Option<&str>) -> Result<(String, String), GithubAnalyzerError> {
+        let dir = root.join(name);
+        checkout(url, &dir, git_ref, None).await?;
+        Ok((run(&dir, &["rev-parse", "HEAD"]), fs::read_to_string(dir.join("lib.rs")).unwrap()))
+    }
+
+    #[test]
+    fn only_forty_hex_characters_are_a_full_sha() {
+        assert!(is_full_commit_sha(&"a1".repeat(20)));
+        assert!(!is_full_commit_sha(&"a1".repeat(19)));
+        assert!(!is_full_commit_sha(&"g1".repeat(20)));
+        assert!(is_abbreviated_sha("deadbee"));
+        assert!(is_abbreviated_sha(&"a".repeat(39)));
+        assert!(!is_abbreviated_sha("dead"));
+        assert!(!is_abbreviated_sha("feature"));
+        assert!(!is_abbreviated_sha(&"a".repeat(40)));
+    }
+
+    #[tokio::test]
+    async fn branches_tags_and_full_shas_are_checked_out() {
+        let root = tempfile::tempdir().unwrap();
+        let (url, first, second) = source_repository(root.path());
+
+        assert_eq!(checked_out(&url, root.path(), "default", None).await.unwrap().0, second);
+        assert_eq!(checked_out(&url, root.path(), "tag", Some("v1")).await.unwrap().0, first);
+        let (head, content) = checked_out(&url, root.path(), "full", Some(&first)).await.unwrap();
+        assert_eq!((head, content.as_str()), (first, "pub fn first() {}\n"));
+    }
+
+    #[tokio::test]
+    async fn hexadecimal_branch_names_win_over_abbreviated_shas() {
+        let root = tempfile::tempdir().unwrap();
+        let (url, first, _) = source_repository(root.path());
+        assert_eq!(checked_out(&url, root.path(), "branch", Some("cafe1234")).await.unwrap().0, first);
+    }
+
+    #[tokio::test]
+    async fn abbreviated_shas_are_resolved_when_no_ref_matches() {
+        let root = tempfile::tempdir().unwrap();
+        let (url, first, second) = source_repository(root.path());
+
+        let (head, content) = checked_out(&url, root.path(), "short", Some(&first[..10])).await.unwrap();
+        assert_eq!((head, content.as_str()), (first, "pub fn first() {}\n"));
+        assert_eq!(checked_out(&url, root.path(), "short_head", Some(&second[..7])).await.unwrap().0, second);
+    }
+
+    #[tokio::test]
+    async fn unknown_refs_are_not_found() {
+        let root = tempfile::tempdir().unwrap();
+        let (url, _, _) = source_repository(root.path());
+
+        let missing_branch = checked_out(&url, root.path(), "missing", Some("no-such-branch")).await.unwrap_err();
+        assert!(matches!(missing_branch, GithubAnalyzerError::NotFound(_)), "{:?}", missing_branch);
+        let missing_sha = checked_out(&url, root.path(), "missing_sha", Some("0000000")).await.unwrap_err();
+        assert!(
+            matches!(&missing_sha, GithubAnalyzerError::NotFound(message) if message == "No branch, tag or commit named 0000000"),
+            "{:?}",
+            missing_sha
+        );
+    }
+}
@@ -0,0 +1,248 @@
+use std::collections::{HashMap, VecDeque};
+
+use regex::Regex;
+
+use crate::analysis::masking::mask_code;
+use crate::types::analysis::MethodSignature;
+
+/// Mots-clés après lesquels `&&`, `||` et `?` ouvrent une expression au lieu de
+/// prolonger la précédente (`return &&x`, `move || ..`)
+const KEYWORDS: [&str; 8] = ["return", "in", "move", "mut", "else", "match", "if", "while"];
+
+/// Corps de fonction délimité par ses accolades (positions dans le code masqué)
+struct Body {
+    open: usize,
+    close: usize,
+}
+
+/// Renseigne la complexité cyclomatique approchée et le nombre de lignes du corps de
+/// chaque signature, dans l'ordre du fichier : les signatures de même nom reçoivent
+/// les corps de même nom dans l'ordre où ils apparaissent (une déclaration sans corps
+/// garde une complexité nulle).
+pub fn measure_functions(content: &str, signatures: &mut [MethodSignature]) {
+    let mut measured: HashMap<String, VecDeque<Option<(u32, usize)>>> = HashMap::new();
+    for (name, metrics) in function_metrics(content) {
+        measured.entry(name).or_default().push_back(metrics);
+    }
+    for signature in signatures.iter_mut() {
+        if let Some((complexity, body_lines)) = measured.get_mut(&*signature.name).and_then(VecDeque::pop_front).flatten() {
+            signature.complexity = complexity;
+            signature.body_lines = body_lines;
+        }
+    }
+}
+
+/// Fonctions du fichier dans l'ordre, avec la complexité et le nombre de lignes de
+/// leur corps (`None` pour une déclaration sans corps).
+///
+/// La complexité vaut 1 plus un point par `if` (gardes de `match` comprises), `while`,
+/// `for`, `&&`, `||`, `?` et bras de `match` au-delà du premier. Les fermetures comptent
+/// pour la fonction qui les contient ; une fonction imbriquée compte pour elle-même.
+fn function_metrics(content: &str) -> Vec<(String, Option<(u32, usize)>)> {
+    let code = mask_code(content);
+    let declaration = Regex::new(r"\bfn\s+(\w+)").unwrap();
+    let branch = Regex::new(r"\b(?:if|while|for|match)\b|=>|&&|\|\||\?").unwrap();
+
+    let mut functions = Vec::new();
+    let mut bodies = Vec::new();
+    for captures in declaration.captures_iter(&code) {
+        let body = captures.get(0).and_then(|found| body(&code, found.end()));
+        functions.push((captures[1].to_string(), bodies.len(), body.is_some()));
+        bodies.extend(body);
+    }
+
+    // Chaque point de décision revient au corps le plus intérieur qui le contient
+    let mut scores = vec![1i64; bodies.len()];
+    for found in branch.find_iter(&code) {
+        let innermost = bodies
+            .iter()
+            .enumerate()
+            .filter(|(_, body)| body.open < found.start() && found.start() < body.close)
+            .max_by_key(|(_, body)| body.open);
+        let Some((index, _)) = innermost else { continue };
+        scores[index] += weight(&code, found.start(), found.as_str());
+    }
+
+    functions
+        .into_iter()
+        .map(|(name, index, has_body)| {
+            let metrics = has_body.then(|| {
+                let body = &bodies[index];
+                let lines = code[body.open..=body.close].matches('\n').count() + 1;
+                (scores[index].max(1) as u32, lines)
+            });
+            (name, metrics)
+        })
+        .collect()
+}
+
+/// Corps qui suit la signature commençant à `start` : première accolade hors
+/// parenthèses et crochets, `None` si un `;` termine la déclaration avant
+fn body(code: &str, start: usize) -> Option<Body> {
+    let mut nesting = 0usize;
+    let mut open = None;
+    for (offset, c) in code[start..].char_indices() {
+        match c {
+            '(' | '[' => nesting += 1,
+            ')' | ']' => nesting = nesting.saturating_sub(1),
+            ';' if nesting == 0 => return None,
+            '{' if nesting == 0 => {
+                open = Some(start + offset);
+                break;
+            },
+            _ => {},
+        }
+    }
+    let open = open?;
+    let mut depth = 0usize;
+    for (offset, c) in code[open..].char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(Body { open, close: open + offset });
+                }
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Points ajoutés par un jeton ; `&&`, `||` et `?` ne comptent qu'après une
+/// expression (`&&x` est une double référence, `|| x` une fermeture, `?Sized` une borne)
+fn weight(code: &str, position: usize, token: &str) -> i64 {
+    match token {
+        "if" | "while" | "=>" => 1,
+        // `match` à n bras : n - 1 points, les bras étant comptés par `=>`
+        "match" => -1,
+        // `for<'a>` est une borne d'ordre supérieur
+        "for" => i64::from(!code[position + 3..].trim_start().starts_with('<')),
+        _ => i64::from(follows_expression(&code[..position])),
+    }
+}
+
+/// Le texte se termine par une expression : identifiant (hors mot-clé), littéral ou
+/// parenthèse, crochet ou accolade fermants
+fn follows_expression(before: &str) -> bool {
+    let before = before.trim_end();
+    let Some(last) = before.chars().next_back() else { return false };
+    if last.is_alphanumeric() || last == '_' {
+        let word_start = before.rfind(|c: char| !(c.is_alphanumeric() || c == '_')).map_or(0, |i| i + 1);
+        return !KEYWORDS.contains(&&before[word_start..]);
+    }
+    matches!(last, ')' | ']' | '}' | '?' | '"' | '\'')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Complexité de chaque fonction, dans l'ordre du fichier
+    fn complexities(content: &str) -> Vec<(String, Option<u32>)> {
+        function_metrics(content)
+            .into_iter()
+            .map(|(name, metrics)| (name, metrics.map(|(complexity, _)| complexity)))
+            .collect()
+    }
+
+    fn only(content: &str) -> u32 {
+        match complexities(content).as_slice() {
+            [(_, Some(complexity))] => *complexity,
+            other => panic!("expected a single body: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn straight_line_functions_score_one_and_count_their_lines() {
+        let content = "fn add(a: u32, b: u32) -> u32 {\n    let sum = a + b;\n    sum\n}\n";
+        assert_eq!(function_metrics(content), vec![("add".to_string(), Some((1, 4)))]);
+        // Une déclaration de trait n'a pas de corps
+        assert_eq!(function_metrics("trait T {\n    fn run(&self) -> Result<(), E>;\n}\n"), vec![("run".to_string(), None)]);
+    }
+
+    #[test]
+    fn conditions_loops_and_operators_add_one_each() {
+        let content = "fn f(x: i32) -> Result<i32, E> {
+    if x > 0 && x < 10 {
+        return Ok(1);
+    } else if x == 0 || x == -1 {
+        return Ok(0);
+    }
+    while ready() {}
+    for item in items() {
+        step(item)?;
+    }
+    Ok(parse(x)?)
+}";
+        // 1 + if, &&, else if, ||, while, for, ?, ?
+        assert_eq!(only(content), 9);
+    }
+
+    #[test]
+    fn match_arms_and_guards_count_beyond_the_first_arm() {
+        let content = "fn kind(n: i32) -> &'static str {
+    match n {
+        0 => \"zero\",
+        x if x < 0 => \"negative\",
+        x if x % 2 == 0 && x > 100 => \"big even\",
+        _ => \"other\",
+    }
+}";
+        // 1 + 3 bras supplémentaires + 2 gardes + `&&`
+        assert_eq!(only(content), 7);
+        assert_eq!(only("fn one(n: i32) -> i32 {\n    match n {\n        _ => 0,\n    }\n}"), 1);
+    }
+
+    #[test]
+    fn closures_count_for_their_function_without_their_pipes() {
+        let content = "fn filter(items: &[i32]) -> Vec<i32> {
+    let spawn = move || run();
+    let callback = || {};
+    items
+        .iter()
+        .filter(|x| **x > 0 && **x < 10)
+        .map(|x| if *x > 5 { x * 2 } else { *x })
+        .collect()
+}";
+        // 1 + `&&` + `if` dans les fermetures ; `move ||` et `= ||` n'ajoutent rien
+        assert_eq!(only(content), 3);
+    }
+
+    #[test]
+    fn references_bounds_and_masked_text_are_not_branches() {
+        let content = "fn g<T: ?Sized>(value: &T) -> bool {
+    let double = &&value;
+    let callback: Box<dyn for<'a> Fn(&'a str)> = Box::new(|_| {});
+    // if while for && || ?
+    let text = \"if a && b || c?\";
+    true
+}";
+        assert_eq!(only(content), 1);
+    }
+
+    #[test]
+    fn nested_functions_score_separately() {
+        let content = "fn outer(x: bool) -> u32 {
+    fn inner(y: bool) -> u32 {
+        if y { 1 } else { 0 }
+    }
+    if x && inner(x) > 0 { 2 } else { 3 }
+}";
+        assert_eq!(
+            complexities(content),
+            vec![("outer".to_string(), Some(3)), ("inner".to_string(), Some(2))]
+        );
+    }
+
+    #[test]
+    fn measurements_follow_signatures_of_the_same_name_in_order() {
+        let content = "impl A { fn new() -> Self { if x { A } else { A } } }\nimpl B { fn new() -> Self { B } }\n";
+        let signature = serde_json::json!({ "name": "new", "params": [], "return_type": "Self", "visibility": "private" });
+        let mut signatures: Vec<MethodSignature> = vec![serde_json::from_value(signature).unwrap(); 2];
+        measure_functions(content, &mut signatures);
+        assert_eq!(signatures.iter().map(|signature| signature.complexity).collect::<Vec<_>>(), vec![2, 1]);
+        assert!(signatures.iter().all(|signature| signature.body_lines == 1));
+    }
+}
@@ -0,0 +1,374 @@
+use regex::Regex;
+use std::collections::BTreeMap;
+
+use crate::types::analysis::ConfigKey;
+
+/// Marqueurs identifiant un fichier modèle de configuration
+const TEMPLATE_MARKERS: [&str; 4] = ["example", "sample", "template", "dist"];
+
+/// Format d'un fichier modèle de configuration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateFormat {
+    Dotenv,
+    Yaml,
+    Toml,
+}
+
+/// Clé déclarée dans un modèle de configuration
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TemplateEntry {
+    pub key: String,
+    pub default: Option<String>,
+    pub comment: Option<String>,
+}
+
+/// Détecte un modèle de configuration (`.env.example`, `config.sample.yaml`,
+/// `settings.toml.example`, ...) et renvoie son format
+pub fn template_format(filename: &str) -> Option<TemplateFormat> {
+    let name = filename.rsplit('/').next().unwrap_or(filename).to_lowercase();
+    let parts: Vec<&str> = name.split('.').collect();
+    if !parts.iter().any(|part| TEMPLATE_MARKERS.contains(part)) {
+        return None;
+    }
+
+    if name.starts_with(".env") || parts.contains(&"env") {
+        Some(TemplateFormat::Dotenv)
+    } else if parts.contains(&"yaml") || parts.contains(&"yml") {
+        Some(TemplateFormat::Yaml)
+    } else if parts.contains(&"toml") {
+        Some(TemplateFormat::Toml)
+    } else {
+        None
+    }
+}
+
+pub fn is_config_template(filename: &str) -> bool {
+    template_format(filename).is_some()
+}
+
+/// Extrait les clés d'un modèle de configuration.
+///
+/// Les lignes de commentaire qui précèdent une clé en sont la documentation.
+/// Les clés YAML et TOML imbriquées sont aplaties avec des points (`database.url`).
+pub fn parse_template(format: TemplateFormat, content: &str) -> Vec<TemplateEntry> {
+    let mut entries = Vec::new();
+    let mut comment: Vec<String> = Vec::new();
+    // Pile (indentation, clé) pour les sections YAML, section courante en TOML
+    let mut yaml_parents: Vec<(usize, String)> = Vec::new();
+    let mut toml_section = String::new();
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            comment.clear();
+            continue;
+        }
+        if let Some(text) = trimmed.strip_prefix('#') {
+            let text = text.trim();
+            if !text.is_empty() {
+                comment.push(text.to_string());
+            }
+            continue;
+        }
+
+        let parsed = match format {
+            TemplateFormat::Dotenv => {
+                let assignment = trimmed.strip_prefix("export ").unwrap_or(trimmed).trim_start();
+                assignment
+                    .split_once('=')
+                    .map(|(key, value)| (key.trim().to_string(), value))
+            }
+            TemplateFormat::Toml => {
+                if trimmed.starts_with('[') {
+                    toml_section = trimmed.trim_matches(|c| c == '[' || c == ']').trim().to_string();
+                    comment.clear();
+                    continue;
+                }
+                trimmed.split_once('=').map(|(key, value)| {
+                    let key = key.trim().trim_matches('"');
+                    let key = if toml_section.is_empty() {
+                        key.to_string()
+                    } else {
+                        format!("{}.{}", toml_section, key)
+                    };
+                    (key, value)
+                })
+            }
+            TemplateFormat::Yaml => {
+                if trimmed.starts_with('-') || trimmed == "---" {
+                    continue;
+                }
+                let indent = line.len() - line.trim_start().len();
+                while yaml_parents.last().is_some_and(|(parent_indent, _)| *parent_indent >= indent) {
+                    yaml_parents.pop();
+                }
+                let Some((key, value)) = trimmed.split_once(':') else {
+                    continue;
+                };
+                let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+                let path: Vec<&str> = yaml_parents
+                    .iter()
+                    .map(|(_, parent)| parent.as_str())
+                    .chain(std::iter::once(key))
+                    .collect();
+                let full_key = path.join(".");
+                if strip_inline_comment(value).is_empty() {
+                    // Section : ses enfants portent la clé en préfixe
+                    yaml_parents.push((indent, key.to_string()));
+                    comment.clear();
+                    continue;
+                }
+                Some((full_key, value))
+            }
+        };
+
+        let Some((key, value)) = parsed else {
+            comment.clear();
+            continue;
+        };
+        if key.is_empty() {
+            continue;
+        }
+
+        let value = unquote(strip_inline_comment(value));
+        entries.push(TemplateEntry {
+            key,
+            default: Some(value).filter(|value| !value.is_empty()),
+            comment: Some(comment.join(" ")).filter(|comment| !comment.is_empty()),
+        });
+        comment.clear();
+    }
+
+    entries
+}
+
+/// Retire un commentaire de fin de ligne (` # ...`) hors guillemets
+fn strip_inline_comment(value: &str) -> &str {
+    let value = value.trim();
+    if value.starts_with('"') || value.starts_with('\'') {
+        return value;
+    }
+    match value.find(" #") {
+        Some(position) => value[..position].trim_end(),
+        None => value,
+    }
+}
+
+/// Retire les guillemets entourant une valeur
+fn unquote(value: &str) -> String {
+    let value = value.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = value.strip_prefix(quote) {
+            if let Some(end) = inner.find(quote) {
+                return inner[..end].to_string();
+            }
+        }
+    }
+    value.to_string()
+}
+
+/// Collecte les clés documentées dans les modèles et celles lues par le code
+pub struct ConfigCollector {
+    env_patterns: Vec<Regex>,
+    templates: BTreeMap<String, (TemplateEntry, String)>,
+    used: BTreeMap<String, Vec<String>>,
+}
+
+impl Default for ConfigCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConfigCollector {
+    pub fn new() -> Self {
+        let env_patterns = [
+            // Rust
+            r#"env::var(?:_os)?\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#,
+            r#"(?:option_)?env!\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#,
+            // JavaScript / TypeScript
+            r#"process\.env\.([A-Za-z_][A-Za-z0-9_]*)"#,
+            r#"process\.env\[\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#,
+            // Python
+            r#"os\.environ(?:\.get)?[\[(]\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#,
+            r#"os\.getenv\(\s*['"]([A-Za-z_][A-Za-z0-9_]*)['"]"#,
+            // Go
+            r#"os\.(?:Getenv|LookupEnv)\(\s*"([A-Za-z_][A-Za-z0-9_]*)""#,
+        ]
+        .iter()
+        .map(|pattern| Regex::new(pattern).unwrap())
+        .collect();
+
+        Self {
+            env_patterns,
+            templates: BTreeMap::new(),
+            used: BTreeMap::new(),
+        }
+    }
+
+    /// Enregistre les clés d'un modèle de configuration
+    pub fn observe_template(&mut self, path: &str, content: &str) {
+        let Some(format) = template_format(path) else {
+            return;
+        };
+        for entry in parse_template(format, content) {
+            self.templates
+                .entry(entry.key.clone())
+                .or_insert_with(|| (entry, path.to_string()));
+        }
+    }
+
+    /// Relève les variables d'environnement lues par un fichier source
+    pub fn observe_source(&mut self, path: &str, content: &str) {
+        for pattern in &self.env_patterns {
+            for captures in pattern.captures_iter(content) {
+                let files = self.used.entry(captures[1].to_string()).or_default();
+                if !files.iter().any(|file| file == path) {
+                    files.push(path.to_string());
+                }
+            }
+        }
+    }
+
+    /// Matrice de configuration unifiée, triée par clé
+    pub fn matrix(&self) -> Vec<ConfigKey> {
+        let mut keys: Vec<&String> = self.templates.keys().chain(self.used.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        keys.into_iter()
+            .map(|key| {
+                let template = self.templates.get(key);
+                let used_in = self.used.get(key).cloned().unwrap_or_default();
+                ConfigKey {
+                    key: key.clone(),
+                    required_by_code: !used_in.is_empty(),
+                    documented_in_template: template.is_some(),
+                    default: template.and_then(|(entry, _)| entry.default.clone()),
+                    comment: template.and_then(|(entry, _)| entry.comment.clone()),
+                    template: template.map(|(_, path)| path.clone()),
+                    used_in,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(key: &str, default: Option<&str>, comment: Option<&str>) -> TemplateEntry {
+        TemplateEntry { key: key.to_string(), default: default.map(str::to_string), comment: comment.map(str::to_string) }
+    }
+
+    #[test]
+    fn template_files_are_recognised_by_name() {
+        assert_eq!(template_format(".env.example"), Some(TemplateFormat::Dotenv));
+        assert_eq!(template_format("deploy/config.sample.yml"), Some(TemplateFormat::Yaml));
+        assert_eq!(template_format("settings.toml.dist"), Some(TemplateFormat::Toml));
+        assert_eq!(template_format(".env"), None);
+        assert_eq!(template_format("example.json"), None);
+    }
+
+    #[test]
+    fn dotenv_templates_keep_exports_quotes_and_comments() {
+        let content = r#"# Jeton de l'API GitHub
+# (lecture seule suffit)
+export GITHUB_TOKEN=
+DATABASE_URL="postgres://localhost/app # pas un commentaire"
+LOG_LEVEL=info # debug, info, warn
+
+# Ancienne option
+  export   PORT = '8080'
+"#;
+        assert_eq!(
+            parse_template(TemplateFormat::Dotenv, content),
+            vec![
+                entry("GITHUB_TOKEN", None, Some("Jeton de l'API GitHub (lecture seule suffit)")),
+                entry("DATABASE_URL", Some("postgres://localhost/app # pas un commentaire"), None),
+                entry("LOG_LEVEL", Some("info"), None),
+                entry("PORT", Some("8080"), Some("Ancienne option")),
+            ]
+        );
+    }
+
+    #[test]
+    fn yaml_sections_prefix_their_keys_and_lists_are_skipped() {
+        let content = "---
+# Connexion
+database:
+  # Adresse du serveur
+  host: localhost
+  pool:
+    size: 5 # connexions
+  replicas:
+    - db1
+    - db2
+server:
+  port: \"8080\"
+debug: false
+";
+        assert_eq!(
+            parse_template(TemplateFormat::Yaml, content),
+            vec![
+                entry("database.host", Some("localhost"), Some("Adresse du serveur")),
+                entry("database.pool.size", Some("5"), None),
+                entry("server.port", Some("8080"), None),
+                entry("debug", Some("false"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn toml_sections_prefix_their_keys() {
+        let content = "title = \"demo\"
+
+[database]
+# Adresse du serveur
+url = \"postgres://localhost\"
+\"max-connections\" = 10
+
+[ server ]
+port = 8080
+";
+        assert_eq!(
+            parse_template(TemplateFormat::Toml, content),
+            vec![
+                entry("title", Some("demo"), None),
+                entry("database.url", Some("postgres://localhost"), Some("Adresse du serveur")),
+                entry("database.max-connections", Some("10"), None),
+                entry("server.port", Some("8080"), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn matrix_flags_undocumented_and_unread_keys() {
+        let mut collector = ConfigCollector::new();
+        collector.observe_template(".env.example", "# Jeton\nGITHUB_TOKEN=\nLEGACY_MODE=off\n");
+        collector.observe_source("src/main.rs", r#"let token = std::env::var("GITHUB_TOKEN"); let _ = env::var("CACHE_DIR");"#);
+        collector.observe_source("src/cache.rs", r#"std::env::var_os("CACHE_DIR")"#);
+        collector.observe_source("web/app.js", "const port = process.env.PORT;");
+
+        let matrix = collector.matrix();
+        let row = |key: &str| matrix.iter().find(|row| row.key == key).unwrap();
+        assert_eq!(matrix.iter().map(|row| row.key.as_str()).collect::<Vec<_>>(), ["CACHE_DIR", "GITHUB_TOKEN", "LEGACY_MODE", "PORT"]);
+
+        // Lue par le code sans être documentée
+        let cache = row("CACHE_DIR");
+        assert!(cache.required_by_code && !cache.documented_in_template);
+        assert_eq!(cache.used_in, ["src/main.rs", "src/cache.rs"]);
+        assert_eq!(cache.template, None);
+
+        // Documentée mais jamais lue
+        let legacy = row("LEGACY_MODE");
+        assert!(legacy.documented_in_template && !legacy.required_by_code);
+        assert_eq!(legacy.default.as_deref(), Some("off"));
+        assert_eq!(legacy.template.as_deref(), Some(".env.example"));
+
+        let token = row("GITHUB_TOKEN");
+        assert!(token.documented_in_template && token.required_by_code);
+        assert_eq!(token.comment.as_deref(), Some("Jeton"));
+    }
+}
@@ -0,0 +1,114 @@
+use std::collections::{BTreeSet, HashSet};
+
+use regex::Regex;
+
+use crate::analysis::file::{generic_params, where_clause};
+use crate::analysis::masking::mask_code;
+use crate::types::analysis::{Conversion, ConversionKind, TypeRelations};
+use crate::types::SharedStr;
+
+/// Conversions déclarées par les blocs `impl From<A> for B`, `impl TryFrom<A> for B` et
+/// `impl Into<B> for A` des fichiers Rust, ainsi que les `From` générés par l'attribut
+/// `#[from]` de `thiserror` sur les variantes d'une énumération.
+///
+/// Toutes sont relevées fichier par fichier ; seules celles qui touchent un type du
+/// projet sont retenues en fin d'analyse, les types pouvant être déclarés ailleurs.
+#[derive(Debug)]
+pub struct ConversionCollector {
+    found: BTreeSet<(String, String, ConversionKind)>,
+    header: Regex,
+    conversion: Regex,
+    enum_start: Regex,
+    from_attribute: Regex,
+}
+
+impl Default for ConversionCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ConversionCollector {
+    pub fn new() -> Self {
+        Self {
+            found: BTreeSet::new(),
+            header: Regex::new(r"(?m)^\s*(?:unsafe\s+)?impl\b").unwrap(),
+            conversion: Regex::new(r"^(?:\w+::)*(From|TryFrom|Into)\s*<(.+)>\s+for\s+(.+)$").unwrap(),
+            enum_start: Regex::new(r"\benum\s+([A-Z]\w*)").unwrap(),
+            from_attribute: Regex::new(r"#\[from\]\s*([^,)}]+)").unwrap(),
+        }
+    }
+
+    /// Relève les conversions d'un fichier Rust ; l'en-tête d'un bloc `impl` peut
+    /// s'étendre sur plusieurs lignes
+    pub fn observe(&mut self, content: &str) {
+        let code = mask_code(content);
+        for start in self.header.find_iter(&code) {
+            let header = code[start.end()..].split(['{', ';']).next().unwrap_or_default();
+            let header = header.split_whitespace().collect::<Vec<_>>().join(" ");
+            let Some((_, header)) = generic_params(&header) else { continue };
+            let (header, _) = where_clause(header);
+            let Some(captures) = self.conversion.captures(header.trim()) else { continue };
+
+            let (argument, implementor) = (captures[2].trim().to_string(), captures[3].trim().to_string());
+            let conversion = match &captures[1] {
+                "From" => (argument, implementor, ConversionKind::From),
+                "TryFrom" => (argument, implementor, ConversionKind::TryFrom),
+                _ => (implementor, argument, ConversionKind::Into),
+            };
+            self.found.insert(conversion);
+        }
+
+        // `#[from]` : le champ marqué se convertit en l'énumération qui le précède
+        for captures in self.from_attribute.captures_iter(&code) {
+            let position = captures.get(0).map_or(0, |found| found.start());
+            let Some(owner) = self.enum_start.captures_iter(&code[..position]).last() else { continue };
+            // Variante structure : `#[from] source: io::Error`
+            let field = match captures[1].split_once(':') {
+                Some((name, field_type)) if !field_type.starts_with(':') && !name.contains('<') => field_type,
+                _ => &captures[1],
+            };
+            let source = field.split_whitespace().collect::<Vec<_>>().join(" ");
+            self.found.insert((source, owner[1].to_string(), ConversionKind::From));
+        }
+    }
+
+    /// Conversions dont la source ou la cible est un type du projet
+    pub fn conversions(&self, relations: &[TypeRelations]) -> Vec<Conversion> {
+        let declared: HashSet<&str> = relations.iter().map(|relation| &*relation.type_name).collect();
+        self.found
+            .iter()
+            .filter_map(|(from, to, kind)| {
+                let (source, target) = (declared.contains(base_name(from)), declared.contains(base_name(to)));
+                (source || target).then(|| Conversion {
+                    from: from.clone(),
+                    to: to.clone(),
+                    kind: *kind,
+                    external: !(source && target),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Ajoute au graphe des types les conversions entre types du projet : le type obtenu
+/// dépend du type converti, ce qui rend visibles les chaînes de conversion d'erreurs
+pub fn link_conversions(conversions: &[Conversion], relations: &mut [TypeRelations]) {
+    for conversion in conversions.iter().filter(|conversion| !conversion.external) {
+        let (from, to) = (base_name(&conversion.from), base_name(&conversion.to));
+        if from == to {
+            continue;
+        }
+        let Some(relation) = relations.iter_mut().find(|relation| &*relation.type_name == to) else { continue };
+        if !relation.depends_on.iter().any(|name| &**name == from) {
+            relation.depends_on.push(SharedStr::from(from));
+        }
+    }
+}
+
+/// Nom d'un type sans référence, chemin ni paramètres (`&'a mut io::Error<T>` → `Error`)
+fn base_name(text: &str) -> &str {
+    let text = text.split('<').next().unwrap_or(text);
+    let text = text.rsplit("::").next().unwrap_or(text);
+    text.rsplit(|c: char| c.is_whitespace() || c == '&').next().unwrap_or(text).trim()
+}
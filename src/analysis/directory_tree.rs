@@ -0,0 +1,84 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Répertoire de l'arborescence rendue : sous-répertoires puis fichiers, triés par nom
+#[derive(Debug, Default)]
+struct Node {
+    directories: BTreeMap<String, Node>,
+    files: BTreeSet<String>,
+    /// Crate membre de l'espace de travail dont le répertoire est la racine
+    crate_name: Option<String>,
+}
+
+impl Node {
+    /// Nombre de fichiers du répertoire et de ses sous-répertoires
+    fn file_count(&self) -> usize {
+        self.files.len() + self.directories.values().map(Node::file_count).sum::<usize>()
+    }
+
+    fn render(&self, prefix: &str, output: &mut String) {
+        let entries = self.directories.len() + self.files.len();
+        let mut position = 0;
+        for (name, directory) in &self.directories {
+            position += 1;
+            let last = position == entries;
+            output.push_str(&format!(
+                "{}{}{}/ ({}){}\n",
+                prefix,
+                if last { "└── " } else { "├── " },
+                name,
+                file_count_label(directory.file_count()),
+                crate_label(directory.crate_name.as_deref())
+            ));
+            directory.render(&format!("{}{}", prefix, if last { "    " } else { "│   " }), output);
+        }
+        for name in &self.files {
+            position += 1;
+            let last = position == entries;
+            output.push_str(&format!("{}{}{}\n", prefix, if last { "└── " } else { "├── " }, name));
+        }
+    }
+}
+
+/// Arborescence des chemins analysés, dans le style de `tree` (`├──` / `└──`) ;
+/// chaque répertoire indique son nombre de fichiers, et les racines des crates membres
+/// (`crate_roots`, répertoire → nom) sont marquées. Vide s'il n'y a aucun chemin.
+pub fn render_directory_tree<'a>(
+    paths: impl IntoIterator<Item = &'a str>,
+    crate_roots: &BTreeMap<String, String>,
+) -> String {
+    let mut root = Node::default();
+    for path in paths {
+        let mut components: Vec<&str> = path.split('/').filter(|component| !component.is_empty()).collect();
+        let Some(file) = components.pop() else {
+            continue;
+        };
+        let mut node = &mut root;
+        for component in components {
+            node = node.directories.entry(component.to_string()).or_default();
+        }
+        node.files.insert(file.to_string());
+    }
+    for (directory, name) in crate_roots {
+        let mut node = &mut root;
+        for component in directory.split('/').filter(|component| !component.is_empty()) {
+            node = node.directories.entry(component.to_string()).or_default();
+        }
+        node.crate_name = Some(name.clone());
+    }
+
+    let total = root.file_count();
+    if total == 0 {
+        return String::new();
+    }
+    let mut output = format!(". ({}){}\n", file_count_label(total), crate_label(root.crate_name.as_deref()));
+    root.render("", &mut output);
+    output
+}
+
+fn crate_label(crate_name: Option<&str>) -> String {
+    crate_name.map(|name| format!(" [crate {}]", name)).unwrap_or_default()
+}
+
+fn file_count_label(count: usize) -> String {
+    format!("{} file{}", count, if count == 1 { "" } else { "s" })
+}
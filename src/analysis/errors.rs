@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::types::analysis::{
+    Conversion, ErrorBoundary, ErrorTaxonomy, ErrorType, FileSummary, ImportKind, MethodSignature, TypeKind,
+    TypeRelations,
+};
+use crate::types::Visibility;
+
+/// Crates d'erreurs dynamiques, nom sous lequel elles sont regroupées et types
+/// qu'elles exportent pour les signatures
+const DYNAMIC_ERRORS: [(&str, &str, &[&str]); 3] = [
+    ("anyhow", "anyhow", &["Result", "Error"]),
+    ("eyre", "eyre", &["Result", "Report"]),
+    ("color_eyre", "eyre", &["Result", "Report"]),
+];
+
+/// Traits reportés dans `ErrorType::traits`, dans cet ordre
+const ERROR_TRAITS: [&str; 6] = ["Debug", "Clone", "PartialEq", "Display", "Error", "Serialize"];
+
+/// Types d'erreur du projet et usage d'`anyhow` / `eyre` dans les types de retour.
+///
+/// Un type est une erreur s'il implémente ou dérive un trait `Error` ; sans `Display`
+/// implémenté à la main, `Display` et `Error` sont réputés dérivés (`thiserror`). Les
+/// conversions sont celles de `conversions`, déjà restreintes aux types du projet.
+pub fn error_taxonomy(
+    relations: &[TypeRelations],
+    conversions: &[Conversion],
+    signatures: &[MethodSignature],
+    files: &[FileSummary],
+) -> ErrorTaxonomy {
+    let mut types: Vec<ErrorType> = relations
+        .iter()
+        .filter(|relation| {
+            relation.kind != TypeKind::Trait && relation.implemented_traits.iter().any(|name| base_name(name) == "Error")
+        })
+        .map(|relation| {
+            let implemented: Vec<&str> = relation.implemented_traits.iter().map(|name| base_name(name)).collect();
+            let derived = !implemented.contains(&"Display")
+                || relation.implemented_traits.iter().any(|name| &**name == "thiserror::Error");
+            let traits = ERROR_TRAITS
+                .iter()
+                .filter(|name| implemented.contains(name) || (derived && matches!(**name, "Display" | "Error")))
+                .map(|name| name.to_string())
+                .collect();
+            let converts_from = conversions
+                .iter()
+                .filter(|conversion| base_name(&conversion.to) == &*relation.type_name)
+                .map(|conversion| conversion.from.clone())
+                .collect();
+            ErrorType {
+                name: relation.type_name.to_string(),
+                kind: relation.kind,
+                defined_in: relation.defined_in.to_string(),
+                variants: relation.variants.clone(),
+                traits,
+                derived,
+                converts_from,
+            }
+        })
+        .collect();
+    types.sort_by(|a, b| a.name.cmp(&b.name));
+
+    ErrorTaxonomy { types, boundaries: error_boundaries(signatures, files) }
+}
+
+/// Fonctions dont le type de retour désigne `anyhow` ou `eyre`, par un chemin
+/// (`anyhow::Result<T>`) ou par un nom importé dans le fichier (`use anyhow::Result;`)
+fn error_boundaries(signatures: &[MethodSignature], files: &[FileSummary]) -> Vec<ErrorBoundary> {
+    // Noms locaux importés d'une crate d'erreurs, par fichier
+    let mut imported: HashMap<&str, HashMap<&str, &str>> = HashMap::new();
+    for file in files {
+        for import in file.imports.iter().filter(|import| import.kind == ImportKind::External) {
+            let Some((_, group, names)) = DYNAMIC_ERRORS.iter().find(|(name, _, _)| *name == import.crate_name()) else {
+                continue;
+            };
+            let names_in_file = imported.entry(&file.path).or_default();
+            if import.glob && import.path == import.crate_name() {
+                names_in_file.extend(names.iter().map(|name| (*name, *group)));
+            } else if let Some(name) = import.path.rsplit("::").next().filter(|name| names.contains(name)) {
+                names_in_file.insert(import.alias.as_deref().unwrap_or(name), group);
+            }
+        }
+    }
+
+    let path = Regex::new(r"[A-Za-z_]\w*(?:::[A-Za-z_]\w*)*").unwrap();
+    let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+    for signature in signatures {
+        let names_in_file = imported.get(&*signature.defined_in);
+        let group = path.find_iter(&signature.return_type).find_map(|found| {
+            let found = found.as_str();
+            match found.split_once("::") {
+                Some((root, _)) => {
+                    DYNAMIC_ERRORS.iter().find(|(name, _, _)| *name == root).map(|(_, group, _)| *group)
+                },
+                None => names_in_file.and_then(|names| names.get(found).copied()),
+            }
+        });
+        if let Some(group) = group {
+            let count = counts.entry(group).or_default();
+            count.0 += 1;
+            if signature.visibility == Visibility::Public {
+                count.1 += 1;
+            }
+        }
+    }
+
+    ["anyhow", "eyre"]
+        .iter()
+        .filter_map(|group| {
+            let (functions, public_functions) = counts.get(group).copied()?;
+            Some(ErrorBoundary { crate_name: group.to_string(), functions, public_functions })
+        })
+        .collect()
+}
+
+/// Nom d'un type ou trait sans chemin ni paramètres (`std::error::Error` → `Error`)
+fn base_name(text: &str) -> &str {
+    let text = text.split('<').next().unwrap_or(text);
+    text.rsplit("::").next().unwrap_or(text).trim()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::conversions::ConversionCollector;
+    use crate::analysis::file::{categorize_path, merge_type_relations, FileAnalyzer};
+    use crate::analysis::imports::scan_imports;
+
+    /// Taxonomie d'un petit projet, calculée comme en fin d'analyse
+    fn taxonomy(files: &[(&str, &str)]) -> ErrorTaxonomy {
+        let analyzer = FileAnalyzer::new();
+        let mut conversions = ConversionCollector::new();
+        let (mut relations, mut signatures, mut summaries) = (Vec::new(), Vec::new(), Vec::new());
+        for (path, content) in files {
+            let (mut file_relations, mut file_signatures, _) = analyzer.analyze_with_patterns(content);
+            for relation in file_relations.iter_mut() {
+                relation.defined_in = (*path).into();
+            }
+            for signature in file_signatures.iter_mut() {
+                signature.defined_in = (*path).into();
+            }
+            relations.extend(file_relations);
+            signatures.extend(file_signatures);
+            conversions.observe(content);
+
+            let mut summary: FileSummary = serde_json::from_value(serde_json::json!({
+                "path": path,
+                "size": content.len(),
+                "summary": "",
+                "category": categorize_path(path),
+                "url": "",
+            }))
+            .unwrap();
+            summary.imports = scan_imports(content).imports;
+            summaries.push(summary);
+        }
+        let relations = merge_type_relations(relations);
+        let conversions = conversions.conversions(&relations);
+        error_taxonomy(&relations, &conversions, &signatures, &summaries)
+    }
+
+    /// (crate, fonctions, fonctions publiques) de chaque frontière
+    fn boundaries(taxonomy: &ErrorTaxonomy) -> Vec<(&str, usize, usize)> {
+        taxonomy
+            .boundaries
+            .iter()
+            .map(|boundary| (boundary.crate_name.as_str(), boundary.functions, boundary.public_functions))
+            .collect()
+    }
+
+    #[test]
+    fn thiserror_enums_derive_display_and_error() {
+        let content = r#"use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum FetchError {
+    #[error("request failed: {0}")]
+    Http(String),
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("timed out after {seconds}s")]
+    Timeout { seconds: u64 },
+}
+"#;
+        let taxonomy = taxonomy(&[("src/fetch.rs", content)]);
+        let [error] = taxonomy.types.as_slice() else { panic!("{:?}", taxonomy.types) };
+        assert_eq!(error.name, "FetchError");
+        assert_eq!(error.kind, TypeKind::Enum);
+        assert!(error.derived);
+        assert_eq!(error.traits, ["Debug", "Display", "Error"]);
+        assert_eq!(error.variants.iter().map(|variant| variant.render()).collect::<Vec<_>>(), [
+            "Http(String)",
+            "Io(std::io::Error)",
+            "Timeout { seconds: u64 }",
+        ]);
+        assert_eq!(error.converts_from, ["std::io::Error"]);
+    }
+
+    #[test]
+    fn manual_implementations_are_not_derived() {
+        let content = "#[derive(Debug, Clone, PartialEq)]
+pub enum StoreError {
+    Missing(String),
+    Parse(ParseIntError),
+}
+
+impl fmt::Display for StoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { write!(f, \"store\") }
+}
+
+impl std::error::Error for StoreError {}
+
+impl From<ParseIntError> for StoreError {
+    fn from(error: ParseIntError) -> Self { StoreError::Parse(error) }
+}
+
+impl From<io::Error> for StoreError {
+    fn from(error: io::Error) -> Self { StoreError::Missing(error.to_string()) }
+}
+
+/// Pas une erreur : aucun trait `Error`
+pub struct Report;
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { Ok(()) }
+}
+";
+        let taxonomy = taxonomy(&[("src/store.rs", content)]);
+        let [error] = taxonomy.types.as_slice() else { panic!("{:?}", taxonomy.types) };
+        assert_eq!(error.name, "StoreError");
+        assert!(!error.derived);
+        assert_eq!(error.traits, ["Debug", "Clone", "PartialEq", "Display", "Error"]);
+        assert_eq!(error.converts_from, ["ParseIntError", "io::Error"]);
+        assert!(taxonomy.boundaries.is_empty());
+    }
+
+    #[test]
+    fn imported_anyhow_result_marks_boundaries() {
+        let content = "use anyhow::Result;
+
+pub fn load(path: &str) -> Result<String> { todo!() }
+fn parse(text: &str) -> Result<u32> { todo!() }
+pub fn direct() -> anyhow::Result<()> { Ok(()) }
+pub fn plain() -> std::io::Result<()> { Ok(()) }
+";
+        // Sans import, `Result` reste celui de la bibliothèque standard
+        let other = "pub fn local() -> Result<(), String> { Ok(()) }\n";
+        let taxonomy = taxonomy(&[("src/load.rs", content), ("src/other.rs", other)]);
+        assert!(taxonomy.types.is_empty());
+        assert_eq!(boundaries(&taxonomy), [("anyhow", 3, 2)]);
+    }
+
+    #[test]
+    fn aliased_and_glob_imports_are_followed() {
+        let aliased = "use anyhow::{Context, Result as AnyResult};
+
+pub fn run() -> AnyResult<()> { Ok(()) }
+pub fn keep() -> Result<(), String> { Ok(()) }
+";
+        let glob = "use anyhow::*;\n\nfn start() -> Result<()> { Ok(()) }\n";
+        let taxonomy = taxonomy(&[("src/run.rs", aliased), ("src/start.rs", glob)]);
+        assert_eq!(boundaries(&taxonomy), [("anyhow", 2, 1)]);
+    }
+
+    #[test]
+    fn color_eyre_counts_as_eyre() {
+        let content = "use color_eyre::eyre::Report;
+use color_eyre::Result;
+
+pub fn main() -> Result<()> { Ok(()) }
+pub fn check() -> Result<(), Report> { Ok(()) }
+fn wrapped() -> color_eyre::Result<()> { Ok(()) }
+pub fn direct() -> eyre::Result<()> { Ok(()) }
+";
+        assert_eq!(boundaries(&taxonomy(&[("src/main.rs", content)])), [("eyre", 4, 3)]);
+    }
+
+    #[test]
+    fn this_crate_error_type_is_listed_with_its_variants() {
+        let taxonomy = taxonomy(&[("src/error.rs", include_str!("../error.rs"))]);
+        let [error] = taxonomy.types.as_slice() else { panic!("{:?}", taxonomy.types) };
+        assert_eq!(error.name, "GithubAnalyzerError");
+        assert_eq!(error.kind, TypeKind::Enum);
+        assert_eq!(error.defined_in, "src/error.rs");
+        assert!(!error.derived);
+        assert_eq!(error.traits, ["Debug", "Display", "Error"]);
+        let variants: Vec<&str> = error.variants.iter().map(|variant| variant.name.as_str()).collect();
+        for expected in ["NetworkError", "ParseError", "RateLimitError", "Forbidden", "AuthError", "NotFound"] {
+            assert!(variants.contains(&expected), "{} not in {:?}", expected, variants);
+        }
+    }
+}
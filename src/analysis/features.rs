@@ -0,0 +1,143 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use regex::Regex;
+
+use crate::analysis::file::{braces, generic_params};
+use crate::analysis::masking::mask_code;
+use crate::analysis::modules::module_path;
+
+/// Éléments soumis à une fonctionnalité cargo : l'élément qui suit un attribut
+/// `#[cfg(..feature = "x"..)]` (module, fonction, type, bloc `impl`, constante), et le
+/// fichier entier sous `#![cfg(feature = "x")]`.
+///
+/// Un `mod x;` conditionnel soumet aussi tout le fichier du module à la fonctionnalité.
+#[derive(Debug)]
+pub struct FeatureGates {
+    /// Éléments par fonctionnalité, sous la forme `fn crate::a::Type::name`
+    gated: BTreeMap<String, BTreeSet<String>>,
+    feature: Regex,
+    item: Regex,
+}
+
+impl Default for FeatureGates {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FeatureGates {
+    pub fn new() -> Self {
+        Self {
+            gated: BTreeMap::new(),
+            feature: Regex::new(r#"\bfeature\s*=\s*"([^"]+)""#).unwrap(),
+            item: Regex::new(
+                r#"^(?:pub(?:\([^)]*\))?\s+)?(?:(?:const|async|unsafe|default|extern(?:\s+"[^"]*")?)\s+)*(mod|fn|struct|enum|union|trait|type|const|static|impl|macro_rules!)\b\s*(\w*)"#,
+            )
+            .unwrap(),
+        }
+    }
+
+    /// Relève les éléments conditionnés d'un fichier Rust. Les attributs sont lus dans
+    /// le texte d'origine (les noms de fonctionnalités sont des chaînes), la structure
+    /// dans le code masqué, ligne à ligne.
+    pub fn observe(&mut self, path: &str, content: &str) {
+        let module = module_path(path).unwrap_or_else(|| path.to_string());
+        let code = mask_code(content);
+
+        // Attribut `cfg` en cours (texte d'origine) et crochets restés ouverts
+        let mut attribute = String::new();
+        let mut attribute_depth = 0i32;
+        // Fonctionnalités des `cfg` qui précèdent l'élément en cours
+        let mut features: Vec<String> = Vec::new();
+        // Types des blocs `impl` et traits ouverts, avec la profondeur de leur bloc
+        let mut owners: Vec<(usize, String)> = Vec::new();
+        let mut pending_owner: Option<String> = None;
+        let mut depth = 0usize;
+
+        for (masked, original) in code.lines().zip(content.lines()) {
+            let line = masked.trim();
+            if attribute_depth > 0 || line.starts_with("#[") || line.starts_with("#![") {
+                attribute.push_str(original);
+                attribute.push('\n');
+                attribute_depth += line.matches('[').count() as i32 - line.matches(']').count() as i32;
+                if attribute_depth <= 0 {
+                    let text = attribute.trim_start();
+                    let found: Vec<String> =
+                        self.feature.captures_iter(text).map(|captures| captures[1].to_string()).collect();
+                    if text.starts_with("#![cfg(") {
+                        for feature in found {
+                            self.gate(feature, format!("mod {}", module));
+                        }
+                    } else if text.starts_with("#[cfg(") {
+                        features.extend(found);
+                    }
+                    attribute.clear();
+                }
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(captures) = self.item.captures(line) {
+                let owner = owners.last().map(|(_, owner)| format!("{}::", owner)).unwrap_or_default();
+                let item = match &captures[1] {
+                    "impl" => {
+                        let header = line.split_once("impl").map_or("", |(_, header)| header);
+                        let header = generic_params(header.trim_start()).map_or(header, |(_, header)| header);
+                        let header = header.split(['{', ';']).next().unwrap_or_default();
+                        let header = header.split(" where").next().unwrap_or_default().trim();
+                        pending_owner = Some(self_type(header).to_string());
+                        Some(format!("impl {} ({})", header, module))
+                    },
+                    "trait" => {
+                        pending_owner = Some(captures[2].to_string());
+                        Some(format!("trait {}::{}", module, &captures[2]))
+                    },
+                    "macro_rules!" => Some(format!("macro {}", &captures[2])),
+                    kind if !captures[2].is_empty() => Some(format!("{} {}::{}{}", kind, module, owner, &captures[2])),
+                    _ => None,
+                };
+                if let Some(item) = item {
+                    for feature in features.drain(..) {
+                        self.gate(feature, item.clone());
+                    }
+                }
+            }
+            features.clear();
+
+            for brace in braces(line) {
+                if brace == '{' {
+                    depth += 1;
+                    if let Some(owner) = pending_owner.take() {
+                        owners.push((depth, owner));
+                    }
+                } else {
+                    if owners.last().is_some_and(|(opened, _)| *opened == depth) {
+                        owners.pop();
+                    }
+                    depth = depth.saturating_sub(1);
+                }
+            }
+            if line.ends_with(';') {
+                pending_owner = None;
+            }
+        }
+    }
+
+    /// Éléments conditionnés, par fonctionnalité puis par nom
+    pub fn feature_map(&self) -> BTreeMap<String, Vec<String>> {
+        self.gated.iter().map(|(feature, items)| (feature.clone(), items.iter().cloned().collect())).collect()
+    }
+
+    fn gate(&mut self, feature: String, item: String) {
+        self.gated.entry(feature).or_default().insert(item);
+    }
+}
+
+/// Type d'un en-tête `impl` sans ses paramètres (`fmt::Display for Foo<T>` → `Foo`)
+fn self_type(header: &str) -> &str {
+    let implemented = header.rsplit(" for ").next().unwrap_or(header);
+    let implemented = implemented.split('<').next().unwrap_or(implemented).trim();
+    implemented.rsplit("::").next().unwrap_or(implemented)
+}
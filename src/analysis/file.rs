@@ -1,8 +1,20 @@
 use regex::Regex;
-use std::collections::{HashMap, HashSet};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use crate::analysis::ci::ci_provider;
+use crate::analysis::complexity::measure_functions;
+use crate::analysis::config::is_config_template;
+use crate::analysis::graph::DirectedGraph;
+use crate::analysis::imports::{scan_imports, ImportScan};
+use crate::analysis::line_count::count_lines;
+use crate::analysis::masking::mask_code;
+use crate::analysis::targets::{target_directory, TargetKind};
+use crate::analysis::panics::scan_panics;
+use crate::analysis::test_count::{count_tests, is_integration_test};
+use crate::analysis::unsafe_code::{scan_unsafe, UnsafeScan};
 use crate::types::{
-    analysis::{TypeRelations, MethodSignature, Configuration},
-    FileCategory, Visibility
+    analysis::{TypeRelations, TypeKind, MethodSignature, Configuration, ConstantDefinition, EnumVariant, KeyType, LineCounts, PanicCounts, StructField, VariantShape},
+    FileCategory, SharedStr, Visibility
 };
 
 /// Catégorisation des fichiers selon leur type
@@ -13,14 +25,22 @@ pub fn categorize_file(filename: &str) -> FileCategory {
         .unwrap_or("");
     
     match extension {
+        // Fichiers binaires
+        ext if is_binary_extension(ext) => FileCategory::Binary,
+
         // Fichiers source
         "rs" | "go" | "js" | "py" | "java" | "cpp" | "c" => 
             FileCategory::Source(extension.to_string()),
+
+        // Langages analysés heuristiquement
+        "ts" | "rb" | "kt" | "swift" | "scala" | "cs" | "php" | "ex" | "exs" | "erl"
+        | "hs" | "zig" | "ml" | "mli" | "clj" | "lua" | "dart" | "nim" | "jl" | "fs" => 
+            FileCategory::Source(extension.to_string()),
         
         // Autres types de fichiers
         _ => match filename {
             // Fichiers de configuration
-            f if is_config_file(f) => FileCategory::Configuration,
+            f if is_config_file(f) || is_config_template(f) => FileCategory::Configuration,
             
             // Documentation
             f if is_documentation_file(f) => FileCategory::Documentation,
@@ -34,6 +54,132 @@ pub fn categorize_file(filename: &str) -> FileCategory {
     }
 }
 
+/// Catégorisation d'un fichier d'après son chemin complet : les configurations de CI,
+/// les exemples et les bancs d'essai cargo se reconnaissent à leur répertoire
+/// (`.github/workflows/`, `examples/`, `benches/`), le reste à leur nom
+pub fn categorize_path(path: &str) -> FileCategory {
+    if ci_provider(path).is_some() {
+        return FileCategory::CiConfig;
+    }
+    match target_directory(path) {
+        Some(TargetKind::Example) => return FileCategory::Example,
+        Some(TargetKind::Bench) => return FileCategory::Benchmark,
+        _ => {},
+    }
+    categorize_file(path.rsplit('/').next().unwrap_or(path))
+}
+
+/// Nom d'affichage du langage d'une extension source (`rs` → `Rust`)
+pub fn language_name(extension: &str) -> &str {
+    match extension {
+        "rs" => "Rust",
+        "go" => "Go",
+        "js" => "JavaScript",
+        "ts" => "TypeScript",
+        "py" => "Python",
+        "java" => "Java",
+        "cpp" => "C++",
+        "c" => "C",
+        "rb" => "Ruby",
+        "kt" => "Kotlin",
+        "swift" => "Swift",
+        "scala" => "Scala",
+        "cs" => "C#",
+        "php" => "PHP",
+        "ex" | "exs" => "Elixir",
+        "erl" => "Erlang",
+        "hs" => "Haskell",
+        "zig" => "Zig",
+        "ml" | "mli" => "OCaml",
+        "clj" => "Clojure",
+        "lua" => "Lua",
+        "dart" => "Dart",
+        "nim" => "Nim",
+        "jl" => "Julia",
+        "fs" => "F#",
+        other => other,
+    }
+}
+
+/// Indique si la ligne `index` est précédée d'un doc-comment (attributs ignorés)
+fn is_documented(lines: &[&str], index: usize) -> bool {
+    lines[..index]
+        .iter()
+        .rev()
+        .map(|line| line.trim())
+        .find(|line| !line.starts_with("#["))
+        .is_some_and(|line| line.starts_with("///"))
+}
+
+/// Doc-comments `///` indexés par la ligne de l'élément qu'ils documentent
+/// (attributs intercalés ignorés, même sur plusieurs lignes), une ligne de texte
+/// par ligne de commentaire.
+///
+/// Une ligne vide ou un commentaire ordinaire entre le doc-comment et l'élément
+/// le détache.
+fn doc_comments(content: &str) -> HashMap<usize, String> {
+    let mut docs = HashMap::new();
+    let mut run: Vec<&str> = Vec::new();
+    // Crochets encore ouverts de l'attribut en cours
+    let mut attribute_depth = 0i32;
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if attribute_depth > 0 || line.starts_with("#[") {
+            attribute_depth += line.matches('[').count() as i32 - line.matches(']').count() as i32;
+            continue;
+        }
+        // `////` est un commentaire ordinaire
+        if let Some(text) = line.strip_prefix("///").filter(|text| !text.starts_with('/')) {
+            run.push(text.strip_prefix(' ').unwrap_or(text));
+            continue;
+        }
+        let doc = run.join("\n");
+        if !line.is_empty() && !line.starts_with("//") && !doc.trim().is_empty() {
+            docs.insert(index, doc.trim().to_string());
+        }
+        run.clear();
+    }
+    docs
+}
+
+/// Doc-comment réduit à `limit` caractères, coupé à la fin d'un mot et suivi de `…` ;
+/// `None` si `limit` est nul
+pub fn truncate_doc(doc: &str, limit: usize) -> Option<String> {
+    if limit == 0 {
+        return None;
+    }
+    let Some((cut, _)) = doc.char_indices().nth(limit) else {
+        return Some(doc.to_string());
+    };
+    let kept = &doc[..cut];
+    let kept = kept.rfind(char::is_whitespace).map_or(kept, |space| &kept[..space]);
+    Some(format!("{}…", kept.trim_end()))
+}
+
+/// Octets examinés pour reconnaître un contenu binaire
+const BINARY_SNIFF_LENGTH: usize = 8 * 1024;
+
+fn is_binary_extension(extension: &str) -> bool {
+    const BINARY_EXTENSIONS: [&str; 44] = [
+        // Images
+        "png", "jpg", "jpeg", "gif", "bmp", "ico", "icns", "webp", "tif", "tiff", "psd",
+        // Polices
+        "woff", "woff2", "ttf", "otf", "eot",
+        // Code compilé et bibliothèques
+        "wasm", "so", "dll", "dylib", "exe", "a", "lib", "o", "obj", "class", "jar", "pyc", "rlib",
+        // Documents, archives et médias
+        "pdf", "zip", "gz", "tgz", "bz2", "xz", "7z", "rar", "tar", "mp3", "mp4", "wav", "ogg",
+        // Bases de données
+        "sqlite", "db",
+    ];
+    BINARY_EXTENSIONS.contains(&extension.to_ascii_lowercase().as_str())
+}
+
+/// Indique si un contenu est binaire : octet NUL dans ses premiers 8 Ko
+pub fn is_binary_content(bytes: &[u8]) -> bool {
+    bytes.iter().take(BINARY_SNIFF_LENGTH).any(|byte| *byte == 0)
+}
+
 fn is_config_file(filename: &str) -> bool {
     const CONFIG_FILES: [&str; 5] = [
         "Cargo.toml",
@@ -48,6 +194,7 @@ fn is_config_file(filename: &str) -> bool {
 fn is_documentation_file(filename: &str) -> bool {
     filename.ends_with(".md") 
         || filename.starts_with("LICENSE")
+        || filename.starts_with("COPYING")
         || filename.starts_with("CONTRIBUTING")
         || filename.starts_with("README")
         || filename.starts_with("CHANGELOG")
@@ -65,20 +212,13 @@ fn is_test_file(filename: &str) -> bool {
 /// Structure contenant les motifs d'analyse de code
 #[derive(Debug)]
 pub struct CodePatterns {
-    type_pattern: Regex,
     impl_pattern: Regex,
-    use_pattern: Regex,
+    trait_pattern: Regex,
     method_pattern: Regex,
     const_pattern: Regex,
     feature_pattern: Regex,
     attribute_pattern: Regex,
-    field_type_pattern: Regex,
-    return_type_pattern: Regex,
-    generic_type_pattern: Regex,
     trait_impl_pattern: Regex,
-    derive_pattern: Regex,
-    type_reference_pattern: Regex,
-    method_signature_pattern: Regex,
 }
 
 impl Default for CodePatterns {
@@ -90,36 +230,72 @@ impl Default for CodePatterns {
 impl CodePatterns {
     pub fn new() -> Self {
         Self {
-            type_pattern: Regex::new(r"^pub (?:struct|enum|type) (\w+)").unwrap(),
             impl_pattern: Regex::new(r"^impl(?:<[^>]+>)? (?:([^<\s]+)(?:<[^>]+>)? for )?([^<\s]+)").unwrap(),
-            use_pattern: Regex::new(r"use .+::(\w+)").unwrap(),
+            trait_pattern: Regex::new(r"^(?:pub(?:\([^)]*\))?\s+)?(?:unsafe\s+)?trait\s+(\w+)").unwrap(),
 
-            derive_pattern: Regex::new(r"#\[derive\((.*?)\)\]").unwrap(),
+            // En-tête `impl` privé de ses paramètres génériques et de sa clause `where`
             trait_impl_pattern: Regex::new(
-                r"impl(?:\s*<[^>]*>)?\s+([A-Z][a-zA-Z0-9_]*(?:<[^>]+>)?)\s+for\s+([A-Z][a-zA-Z0-9_]*(?:<[^>]+>)?)"
-            ).unwrap(),
-            method_signature_pattern: Regex::new(
-                r"fn\s+\w+\s*(?:<[^>]*>)?\s*\(((?:[^()]*|\([^()]*\))*)\)(?:\s*->\s*([^{;]+))?"
-            ).unwrap(),
-            type_reference_pattern: Regex::new(
-                r"[A-Z][a-zA-Z0-9_]*(?:<[^>]+>)?"
+                r"^((?:\w+::)*\w+)\s*(?:<.*>)?\s+for\s+&?(?:'\w+\s+)?(?:mut\s+)?(?:dyn\s+)?((?:\w+::)*\w+)"
             ).unwrap(),
 
+            // Début d'une signature ; paramètres et retour sont lus par `parse_signature`
             method_pattern: Regex::new(
-                r"(?P<vis>pub(?:\([^)]+\))?)?\s*fn\s+(?P<name>\w+)\s*<?\s*(?P<params>[^>]*?)>\s*\((?P<args>[^)]*)\)(?:\s*->\s*(?P<ret>[^{]+))?"
+                r#"(?P<vis>pub(?:\([^)]+\))?)?\s*(?P<qualifiers>(?:(?:const|async|unsafe|extern\s+"[^"]*")\s+)*)fn\s+(?P<name>\w+)"#
             ).unwrap(),
             const_pattern: Regex::new(r"(?:pub\s+)?const\s+([A-Z_][A-Z0-9_]*)\s*:\s*([^=]+)\s*=\s*([^;]+);").unwrap(),
             feature_pattern: Regex::new(r#"#\[cfg\(feature\s*=\s*"([^"]+)"\)\]"#).unwrap(),
             attribute_pattern: Regex::new(r"#\[([^\]]+)\]").unwrap(),
-            field_type_pattern: Regex::new(r":\s*(?:&)?([A-Z][a-zA-Z0-9_]*(?:<[^>]+>)?)").unwrap(),
-            return_type_pattern: Regex::new(r"->\s*(?:Result<)?(?:&)?([A-Z][a-zA-Z0-9_]*(?:<[^>]+>)?)").unwrap(),
-            generic_type_pattern: Regex::new(r"<[^>]*?([A-Z][a-zA-Z0-9_]*)[^>]*>").unwrap(),
         }
     }
 }
 
+/// Nombre d'entrées par motif conservées par défaut dans un résumé
+pub const DEFAULT_SUMMARY_ENTRIES: usize = 10;
+
+/// Nombre de types retenus par défaut dans `key_types`
+pub const DEFAULT_KEY_TYPES: usize = 10;
+
+/// Caractères conservés par défaut dans le doc-comment d'un type ou d'une fonction
+pub const DEFAULT_DOC_LENGTH: usize = 400;
+
+/// Motifs du résumé texte : (expression, préfixe affiché, clé de comptage)
+const SUMMARY_PATTERNS: [(&str, &str, &str); 9] = [
+    (r"^///\s*(.*)$", "Documentation: ", "documentation"),
+    (r"^//!\s*(.*)$", "Module documentation: ", "module_documentation"),
+    (r"^pub fn (\w+)", "Public method: ", "public_method"),
+    (r"^fn (\w+)", "Private method: ", "private_method"),
+    (r"^pub struct (\w+)", "Public struct: ", "public_struct"),
+    (r"^pub enum (\w+)", "Public enum: ", "public_enum"),
+    (r"^pub trait (\w+)", "Public trait: ", "public_trait"),
+    (r"^impl\s+(\w+)", "Implementation: ", "implementation"),
+    (r"^\[.*\]", "Section: ", "section"),
+];
+
+/// Résumé texte d'un fichier et nombre total de correspondances par motif
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ContentSummary {
+    pub text: String,
+    pub pattern_counts: BTreeMap<String, usize>,
+    #[serde(default)]
+    pub loc: LineCounts,
+    /// Fonctions de test (fichiers Rust)
+    #[serde(default)]
+    pub tests: usize,
+    /// Code `unsafe` (fichiers Rust)
+    #[serde(default)]
+    pub unsafe_code: UnsafeScan,
+    /// Points de panique hors code de test (fichiers Rust hors tests d'intégration)
+    #[serde(default)]
+    pub panic_points: PanicCounts,
+    /// Imports et crates désignées par un chemin (fichiers Rust)
+    #[serde(default)]
+    pub imports: ImportScan,
+}
+
 pub struct FileAnalyzer {
     patterns: CodePatterns,
+    summary_patterns: Vec<(Regex, &'static str, &'static str)>,
+    summary_limit: usize,
 }
 
 impl Default for FileAnalyzer {
@@ -129,9 +305,22 @@ impl Default for FileAnalyzer {
 }
 
 impl FileAnalyzer {
+    /// Analyse des fichiers Rust : arbre syntaxique (`syn`) ou motifs
+    pub const BACKEND: &'static str = if cfg!(feature = "syn-backend") { "syn" } else { "regex" };
+
     pub fn new() -> Self {
+        Self::with_summary_limit(DEFAULT_SUMMARY_ENTRIES)
+    }
+
+    /// Analyseur dont les résumés gardent au plus `limit` entrées par motif
+    pub fn with_summary_limit(limit: usize) -> Self {
         Self {
             patterns: CodePatterns::new(),
+            summary_patterns: SUMMARY_PATTERNS
+                .iter()
+                .map(|(pattern, prefix, key)| (Regex::new(pattern).unwrap(), *prefix, *key))
+                .collect(),
+            summary_limit: limit,
         }
     }
 
@@ -140,13 +329,42 @@ impl FileAnalyzer {
         &self,
         content: &str,
         file_path: &str,
-    ) -> (String, Vec<TypeRelations>, Vec<MethodSignature>, Configuration) {
+    ) -> (ContentSummary, Vec<TypeRelations>, Vec<MethodSignature>, Configuration) {
         println!("\n📁 Analyzing file: {}", file_path);
 
-        let summary = self.generate_summary(content);
+        let mut summary = self.generate_summary(content);
         println!("📝 Generated file summary");
 
-        let type_relations = self.analyze_type_relations(content);
+        let name = file_path.rsplit('/').next().unwrap_or(file_path);
+        let extension = name.rsplit_once('.').map_or(name, |(_, extension)| extension);
+        summary.loc = count_lines(content, extension);
+        println!(
+            "📏 {} lines ({} code, {} comments, {} blank)",
+            summary.loc.total, summary.loc.code, summary.loc.comments, summary.loc.blank
+        );
+
+        if extension == "rs" {
+            summary.tests = count_tests(content);
+            summary.unsafe_code = scan_unsafe(content);
+            if !is_integration_test(file_path) {
+                summary.panic_points = scan_panics(content);
+            }
+            summary.imports = scan_imports(content);
+        }
+
+        // L'arbre syntaxique, quand il est disponible, remplace les motifs
+        #[cfg(feature = "syn-backend")]
+        let precise = file_path.ends_with(".rs").then(|| crate::analysis::syntax::analyze(content)).flatten();
+        #[cfg(not(feature = "syn-backend"))]
+        let precise: Option<(Vec<TypeRelations>, Vec<MethodSignature>, Configuration)> = None;
+        let (type_relations, mut method_signatures, configuration) = match precise {
+            Some((mut type_relations, method_signatures, configuration)) => {
+                println!("🌳 Parsed syntax tree");
+                close_type_relations(&mut type_relations);
+                (type_relations, method_signatures, configuration)
+            },
+            None => self.analyze_with_patterns(content),
+        };
         println!("🔄 Analyzed type relations: {} types found", type_relations.len());
 
         for relation in &type_relations {
@@ -162,10 +380,11 @@ impl FileAnalyzer {
             }
         }
 
-        let method_signatures = self.analyze_method_signatures(content);
+        if extension == "rs" {
+            measure_functions(content, &mut method_signatures);
+        }
         println!("🔍 Found {} method signatures", method_signatures.len());
 
-        let configuration = self.analyze_configuration(content);
         println!("⚙️ Configuration analysis complete");
         println!("  ↪ {} constants", configuration.constants.len());
         println!("  ↪ {} feature flags", configuration.feature_flags.len());
@@ -174,43 +393,64 @@ impl FileAnalyzer {
         (summary, type_relations, method_signatures, configuration)
     }
 
-    /// Génère un résumé du contenu du fichier
-    fn generate_summary(&self, content: &str) -> String {
-        let mut summary = String::new();
+    /// Relations de types, signatures et configuration relevées par les motifs
+    /// (analyse sans arbre syntaxique)
+    pub(crate) fn analyze_with_patterns(
+        &self,
+        content: &str,
+    ) -> (Vec<TypeRelations>, Vec<MethodSignature>, Configuration) {
+        // Les motifs ne doivent voir ni les commentaires ni le texte des chaînes ;
+        // les doc-comments, relevés avant, sont rattachés par numéro de ligne
+        let code = mask_code(content);
+        let docs = doc_comments(content);
+        (
+            self.analyze_type_relations(&code, &docs),
+            self.analyze_method_signatures(&code, &docs),
+            self.analyze_configuration(content),
+        )
+    }
+
+    /// Génère un résumé du contenu du fichier.
+    ///
+    /// Chaque motif garde au plus `summary_limit` entrées, les éléments
+    /// documentés d'abord ; le nombre total de correspondances est conservé
+    /// dans `pattern_counts`.
+    fn generate_summary(&self, content: &str) -> ContentSummary {
+        let mut summary = ContentSummary::default();
         let lines: Vec<&str> = content.lines().collect();
 
         if !lines.is_empty() {
-            summary.push_str("File start:\n");
+            summary.text.push_str("File start:\n");
             for line in lines.iter().take(5) {
-                summary.push_str(&format!("{}\n", line));
+                summary.text.push_str(&format!("{}\n", line));
             }
         }
 
-        let patterns = [
-            (r"^///\s*(.*)$", "Documentation: "),
-            (r"^//!\s*(.*)$", "Module documentation: "),
-            (r"^pub fn (\w+)", "Public method: "),
-            (r"^fn (\w+)", "Private method: "),
-            (r"^pub struct (\w+)", "Public struct: "),
-            (r"^pub enum (\w+)", "Public enum: "),
-            (r"^pub trait (\w+)", "Public trait: "),
-            (r"^impl\s+(\w+)", "Implementation: "),
-            (r"^\[.*\]", "Section: "),
-        ];
+        for (re, prefix, key) in &self.summary_patterns {
+            let matches: Vec<usize> = (0..lines.len()).filter(|&i| re.is_match(lines[i])).collect();
+            if matches.is_empty() {
+                continue;
+            }
+            summary.pattern_counts.insert(key.to_string(), matches.len());
 
-        for (pattern, prefix) in patterns {
-            let re = Regex::new(pattern).unwrap();
-            for line in &lines {
-                if re.is_match(line) {
-                    summary.push_str(&format!("{}{}\n", prefix, line.trim()));
-                }
+            let mut selected = matches.clone();
+            if selected.len() > self.summary_limit {
+                selected.sort_by_key(|&i| !is_documented(&lines, i));
+                selected.truncate(self.summary_limit);
+                selected.sort_unstable();
+            }
+            for &i in &selected {
+                summary.text.push_str(&format!("{}{}\n", prefix, lines[i].trim()));
+            }
+            if matches.len() > selected.len() {
+                summary.text.push_str(&format!("{}… and {} more\n", prefix, matches.len() - selected.len()));
             }
         }
 
         summary
     }
 
-    fn analyze_type_relations(&self, content: &str) -> Vec<TypeRelations> {
+    fn analyze_type_relations(&self, content: &str, docs: &HashMap<usize, String>) -> Vec<TypeRelations> {
         println!("\n🔎 Starting type relations analysis");
 
         let mut relations = Vec::new();
@@ -221,47 +461,123 @@ impl FileAnalyzer {
         let mut processed_types = HashSet::new();
 
         let mut project_types = HashSet::new();
-        let type_decl = Regex::new(r"^(?:pub\s+)?(?:struct|enum|type)\s+([A-Z][a-zA-Z0-9_]*)").unwrap();
+        let mut kinds: HashMap<String, TypeKind> = HashMap::new();
+        let mut generics: HashMap<String, Vec<String>> = HashMap::new();
+        let mut type_docs: HashMap<String, String> = HashMap::new();
+        let type_decl = Regex::new(
+            r"^(?:pub(?:\([^)]*\))?\s+)?(struct|enum|type|trait)\s+([A-Z][a-zA-Z0-9_]*)"
+        ).unwrap();
+        let derive_pattern = Regex::new(r"\bderive\s*\(").unwrap();
 
-        // Première passe : collecter tous les types déclarés
-        for line in content.lines() {
-            let line = line.trim();
-            if let Some(captures) = type_decl.captures(line) {
-                let type_name = captures[1].to_string();
-                println!("  Found type declaration: {}", type_name);
+        // Première passe : collecter tous les types et traits déclarés
+        for (index, line) in content.lines().enumerate() {
+            if let Some(captures) = type_declaration(&type_decl, line) {
+                let type_name = captures[2].to_string();
+                println!("  Found {} declaration: {}", &captures[1], type_name);
+                let kind = match &captures[1] {
+                    "enum" => TypeKind::Enum,
+                    "trait" => TypeKind::Trait,
+                    "type" => TypeKind::Alias,
+                    _ => TypeKind::Struct,
+                };
+                let after_name = &line.trim()[captures.get(0).map_or(0, |name| name.end())..];
+                if let Some((params, _)) = generic_params(after_name).filter(|(params, _)| !params.is_empty()) {
+                    generics.insert(type_name.clone(), params);
+                }
+                if let Some(doc) = docs.get(&index) {
+                    type_docs.entry(type_name.clone()).or_insert_with(|| doc.clone());
+                }
+                kinds.insert(type_name.clone(), kind);
                 project_types.insert(type_name);
             }
         }
 
         println!("  Discovered types: {:?}", project_types);
 
+        // Variantes des énumérations, lues dans le corps entre accolades
+        let enum_start = Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?enum\s+([A-Z]\w*)[^{;]*\{").unwrap();
+        let mut variants: HashMap<String, Vec<EnumVariant>> = HashMap::new();
+        for captures in enum_start.captures_iter(content) {
+            let body = &content[captures.get(0).map_or(0, |start| start.end())..];
+            if let Some(close) = closing(body, '{', '}') {
+                variants.entry(captures[1].to_string()).or_insert_with(|| enum_variants(&body[..close]));
+            }
+        }
+
+        // Cibles des alias (`type Result<T> = std::result::Result<T, Error>;`), hors types
+        // associés indentés
+        let alias_start = Regex::new(r"(?m)^(?:pub(?:\([^)]*\))?\s+)?type\s+([A-Z]\w*)").unwrap();
+        let mut aliases: HashMap<String, String> = HashMap::new();
+        for captures in alias_start.captures_iter(content) {
+            let after_name = &content[captures.get(0).map_or(0, |start| start.end())..];
+            let target = generic_params(after_name.trim_start())
+                .and_then(|(_, rest)| rest.trim_start().strip_prefix('='))
+                .and_then(|target| target.split_once(';'))
+                .map(|(target, _)| target.split_whitespace().collect::<Vec<_>>().join(" "));
+            if let Some(target) = target.filter(|target| !target.is_empty()) {
+                aliases.entry(captures[1].to_string()).or_insert(target);
+            }
+        }
+
+        // Champs des structures, entre accolades ou entre parenthèses (structure tuple)
+        let struct_start = Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?struct\s+([A-Z]\w*)").unwrap();
+        let mut fields: HashMap<String, Vec<StructField>> = HashMap::new();
+        for captures in struct_start.captures_iter(content) {
+            let after_name = &content[captures.get(0).map_or(0, |start| start.end())..];
+            if let Some(declared) = struct_fields(after_name) {
+                fields.entry(captures[1].to_string()).or_insert(declared);
+            }
+        }
+
         // Deuxième passe : analyser les relations
         let lines: Vec<&str> = content.lines().collect();
+        // Attributs précédant l'élément en cours, et crochets restés ouverts
+        let mut attributes = String::new();
+        let mut attribute_depth = 0i32;
         let mut i = 0;
         while i < lines.len() {
             let line = lines[i].trim();
 
-            // Analyse des dérivations (#[derive(...)])
-            if line.starts_with("#[derive") {
-                println!("  📍 Found derive: {}", line);
-                if let Some(next_line) = lines.get(i + 1) {
-                    if let Some(captures) = type_decl.captures(next_line.trim()) {
-                        let type_name = captures[1].to_string();
-                        let derive_pattern = Regex::new(r"#\[derive\((.*?)\)\]").unwrap();
-                        if let Some(derive_captures) = derive_pattern.captures(line) {
-                            let traits = derive_captures[1]
-                                .split(',')
-                                .map(|s| s.trim().to_string())
-                                .collect::<Vec<_>>();
-                            traits_map.insert(type_name, traits);
-                        }
+            // Dérivations (`#[derive(..)]`, `#[cfg_attr(.., derive(..))]`) : les attributs
+            // peuvent s'empiler et s'étendre sur plusieurs lignes avant la déclaration
+            if attribute_depth > 0 || line.starts_with("#[") {
+                attributes.push_str(line);
+                attributes.push('\n');
+                attribute_depth += line.matches('[').count() as i32 - line.matches(']').count() as i32;
+            } else if let Some(captures) = type_declaration(&type_decl, lines[i]) {
+                let derived = derived_traits(&derive_pattern, &attributes);
+                if !derived.is_empty() {
+                    println!("  📍 Found derive: {}", derived.join(", "));
+                    traits_map.entry(captures[2].to_string()).or_default().extend(derived);
+                }
+                attributes.clear();
+            } else if !line.is_empty() {
+                attributes.clear();
+            }
+
+            // Implémentations de traits (`impl<T> fmt::Display for Type<T>`), l'en-tête
+            // pouvant s'étendre jusqu'au `{` des lignes suivantes
+            if line.starts_with("impl") || line.starts_with("unsafe impl") {
+                let mut header = String::new();
+                for next in &lines[i..] {
+                    header.push_str(next.trim());
+                    header.push(' ');
+                    if next.contains('{') || next.contains(';') {
+                        break;
+                    }
+                }
+                let header = header.split(['{', ';']).next().unwrap_or_default();
+                if let Some((implemented, type_name)) = self.trait_impl(header) {
+                    let traits = traits_map.entry(type_name).or_default();
+                    if !traits.contains(&implemented) {
+                        traits.push(implemented);
                     }
                 }
             }
 
             // Analyse des déclarations de types
-            if let Some(captures) = type_decl.captures(line) {
-                let type_name = captures[1].to_string();
+            if let Some(captures) = type_declaration(&type_decl, lines[i]) {
+                let type_name = captures[2].to_string();
 
                 // Ne traiter que si c'est un nouveau type
                 if !processed_types.contains(&type_name) {
@@ -315,13 +631,56 @@ impl FileAnalyzer {
             );
         }
 
+        for relation in relations.iter_mut() {
+            relation.kind = kinds.get(&*relation.type_name).copied().unwrap_or_default();
+            relation.generics = generics.remove(&*relation.type_name).unwrap_or_default();
+            relation.doc = type_docs.remove(&*relation.type_name);
+            relation.variants = variants.remove(&*relation.type_name).unwrap_or_default();
+            relation.fields = fields.remove(&*relation.type_name).unwrap_or_default();
+            relation.alias_of = aliases.remove(&*relation.type_name);
+
+            // Les types des champs, ceux portés par les variantes et la cible d'un alias
+            // sont des dépendances
+            let carried: Vec<SharedStr> = relation
+                .variants
+                .iter()
+                .flat_map(|variant| &variant.fields)
+                .chain(relation.fields.iter().map(|field| &field.field_type))
+                .chain(&relation.alias_of)
+                .flat_map(|field| field.split(|c: char| !(c.is_alphanumeric() || c == '_')))
+                .filter(|word| word.starts_with(|c: char| c.is_ascii_uppercase()) && *word != &*relation.type_name)
+                .map(SharedStr::from)
+                .collect();
+            for name in carried {
+                let edges = if project_types.contains(&*name) { &mut relation.depends_on } else { &mut relation.references };
+                if !edges.contains(&name) {
+                    edges.push(name);
+                }
+            }
+        }
+
         println!("🔄 Building transitive relations");
-        self.build_type_relations(&mut relations);
+        close_type_relations(&mut relations);
         println!("✅ Type analysis complete: {} relations found", relations.len());
 
         relations
     }
 
+    /// Trait et type d'un en-tête `impl<..> Trait<..> for Type<..> where ..`, réduits au
+    /// dernier segment de leur chemin ; `None` pour un bloc `impl Type` inhérent
+    fn trait_impl(&self, header: &str) -> Option<(String, String)> {
+        let header = header.trim();
+        let rest = header.strip_prefix("unsafe ").unwrap_or(header).trim_start().strip_prefix("impl")?;
+        if !rest.starts_with(|c: char| c == '<' || c.is_whitespace()) {
+            return None;
+        }
+        let (_, rest) = generic_params(rest.trim_start())?;
+        let (rest, _) = where_clause(rest);
+        let captures = self.patterns.trait_impl_pattern.captures(rest.trim())?;
+        let last_segment = |path: &str| path.rsplit("::").next().unwrap_or(path).to_string();
+        Some((last_segment(&captures[1]), last_segment(&captures[2])))
+    }
+
     /// Analyse les dépendances dans une ligne de code
     fn analyze_dependencies(
         &self,
@@ -342,18 +701,24 @@ impl FileAnalyzer {
             r"impl(?:\s*<[^>]*>)?\s+([A-Z][a-zA-Z0-9_]*)\s+for",
             r"<[^>]*?([A-Z][a-zA-Z0-9_]*)[^>]*>",
             r"(?:Vec|Option|Box)<([A-Z][a-zA-Z0-9_]*)>",
+            // Bornes suivantes d'un paramètre générique (`T: Clone + Handler`)
+            r"\+\s*\??([A-Z][a-zA-Z0-9_]*)",
         ] {
             let re = Regex::new(pattern).unwrap();
             for captures in re.captures_iter(line) {
                 let type_name = captures[1].to_string();
-                if project_types.contains(&type_name) && type_name != current_type {
+                if type_name == current_type {
+                    continue;
+                }
+                // Les types déclarés ailleurs sont rattachés au graphe du projet en fin d'analyse
+                if project_types.contains(&type_name) {
                     println!("    Found dependency: {} -> {}", current_type, type_name);
-                    dependencies.insert(type_name.clone());
                     usage_map
-                        .entry(type_name)
+                        .entry(type_name.clone())
                         .or_default()
                         .insert(current_type.to_string());
                 }
+                dependencies.insert(type_name);
             }
         }
     }
@@ -368,151 +733,179 @@ impl FileAnalyzer {
         usage_map: &HashMap<String, HashSet<String>>,
         project_types: &HashSet<String>,
     ) {
-        let mut implemented_traits = traits_map
+        let implemented_traits = traits_map
             .get(type_name)
-            .cloned()
+            .map(|traits| traits.iter().map(|t| SharedStr::from(t.as_str())).collect())
             .unwrap_or_default();
 
         let mut used_by = vec![];
         if let Some(users) = usage_map.get(type_name) {
-            used_by.extend(users.iter().cloned());
+            used_by.extend(users.iter().map(|u| SharedStr::from(u.as_str())));
         }
 
+        // Un trait du projet implémenté par le type est une dépendance de celui-ci
+        let implemented_project_traits = traits_map
+            .get(type_name)
+            .into_iter()
+            .flatten()
+            .filter(|name| name.as_str() != type_name && !dependencies.contains(*name));
         let depends_on = dependencies
             .iter()
+            .chain(implemented_project_traits)
             .filter(|dep| project_types.contains(*dep))
-            .cloned()
+            .map(|dep| SharedStr::from(dep.as_str()))
+            .collect();
+        let mut references: Vec<SharedStr> = dependencies
+            .iter()
+            .filter(|dep| !project_types.contains(*dep))
+            .map(|dep| SharedStr::from(dep.as_str()))
             .collect();
+        references.sort();
 
         relations.push(TypeRelations {
-            type_name: type_name.to_string(),
+            type_name: SharedStr::from(type_name),
+            kind: TypeKind::default(),
             implemented_traits,
             used_by,
             depends_on,
+            defined_in: SharedStr::from(""),
+            module: SharedStr::from(""),
+            references,
+            implementors: Vec::new(),
+            generics: Vec::new(),
+            doc: None,
+            macros: Vec::new(),
+            variants: Vec::new(),
+            fields: Vec::new(),
+            alias_of: None,
         });
     }
 
-    /// Construit les relations transitives entre types
-    fn build_type_relations(&self, relations: &mut [TypeRelations]) {
-        let mut deps_graph: HashMap<String, HashSet<String>> = HashMap::new();
-        let mut users_graph: HashMap<String, HashSet<String>> = HashMap::new();
-
-        // Construction des graphes initiaux
-        for relation in relations.iter() {
-            deps_graph
-                .entry(relation.type_name.clone())
-                .or_default()
-                .extend(relation.depends_on.iter().cloned());
-
-            for dep in &relation.depends_on {
-                users_graph
-                    .entry(dep.clone())
-                    .or_default()
-                    .insert(relation.type_name.clone());
-            }
-        }
-
-        // Calcul des fermetures transitives
-        let mut changed = true;
-        while changed {
-            changed = false;
-
-            for relation in relations.iter() {
-                // Dépendances transitives
-                let current_deps = deps_graph
-                    .get(&relation.type_name)
-                    .cloned()
-                    .unwrap_or_default();
-
-                let mut new_deps = current_deps.clone();
-                for dep in &current_deps {
-                    if let Some(indirect_deps) = deps_graph.get(dep) {
-                        for indirect_dep in indirect_deps {
-                            if new_deps.insert(indirect_dep.clone()) {
-                                changed = true;
-                            }
-                        }
-                    }
-                }
+    /// Analyse les signatures des méthodes.
+    ///
+    /// Une signature coupée par rustfmt s'étend sur plusieurs lignes : elles sont
+    /// jointes jusqu'au `{` ou au `;` qui suit la fermeture des parenthèses. Les
+    /// accolades sont suivies pour rattacher chaque méthode au bloc `impl` ou au
+    /// trait qui la contient.
+    fn analyze_method_signatures(&self, content: &str, docs: &HashMap<usize, String>) -> Vec<MethodSignature> {
+        let mut signatures = Vec::new();
+        // Signature en cours, contexte et ligne de début
+        let mut statement: Option<(String, Option<ImplContext>, usize)> = None;
+        // Contexte de chaque accolade ouverte ; `None` pour un corps de fonction ou autre
+        let mut blocks: Vec<Option<ImplContext>> = Vec::new();
+        let mut pending: Option<ImplContext> = None;
 
-                if changed {
-                    deps_graph.insert(relation.type_name.clone(), new_deps);
-                }
+        for (index, line) in content.lines().enumerate() {
+            let line = line.split("//").next().unwrap_or("").trim();
+            let context = blocks.last().cloned().flatten();
+            match statement.as_mut() {
+                // Les attributs de paramètre (`#[cfg(..)]`, `#[allow(..)]`) sont retirés,
+                // qu'ils soient seuls sur leur ligne ou devant le paramètre
+                Some((statement, _, _)) => {
+                    let line = without_attributes(line).unwrap_or_default();
+                    if !line.is_empty() {
+                        statement.push(' ');
+                        statement.push_str(line);
+                    }
+                },
+                None if self.patterns.method_pattern.is_match(line) => {
+                    // `impl Trait for Type { fn name(..) { .. } }` sur une seule ligne
+                    let opened = self.impl_context(line).filter(|_| line.contains('{'));
+                    let context = if opened.is_some() { opened } else { context };
+                    statement = Some((line.to_string(), context, index));
+                },
+                None => {},
+            }
 
-                // Utilisateurs transitifs
-                let current_users = users_graph
-                    .get(&relation.type_name)
-                    .cloned()
-                    .unwrap_or_default();
-
-                let mut new_users = current_users.clone();
-                for user in &current_users {
-                    if let Some(indirect_users) = users_graph.get(user) {
-                        for indirect_user in indirect_users {
-                            if new_users.insert(indirect_user.clone()) {
-                                changed = true;
-                            }
-                        }
+            if let Some(end) = statement.as_ref().and_then(|(text, _, _)| signature_end(text)) {
+                let (text, context, start) = statement.take().unwrap_or_default();
+                if let Some(mut signature) = self.parse_signature(&text[..end]) {
+                    if let Some(context) = context {
+                        signature.owner = Some(SharedStr::from(context.owner));
+                        signature.trait_impl = context.trait_impl.map(SharedStr::from);
                     }
+                    signature.doc = docs.get(&start).cloned();
+                    signatures.push(signature);
                 }
+            }
 
-                if changed {
-                    users_graph.insert(relation.type_name.clone(), new_users);
+            // Le contexte d'un `impl` ou d'un trait s'attache à sa première accolade
+            if let Some(opened) = self.impl_context(line) {
+                pending = Some(opened);
+            }
+            for brace in braces(line) {
+                if brace == '{' {
+                    blocks.push(pending.take());
+                } else {
+                    blocks.pop();
                 }
             }
         }
 
-        // Mise à jour des relations avec les dépendances transitives
-        for relation in relations.iter_mut() {
-            if let Some(deps) = deps_graph.get(&relation.type_name) {
-                relation.depends_on = deps.iter().cloned().collect();
-                relation.depends_on.sort();
-            }
+        signatures
+    }
 
-            if let Some(users) = users_graph.get(&relation.type_name) {
-                relation.used_by = users.iter().cloned().collect();
-                relation.used_by.sort();
-            }
+    /// Contexte ouvert par une ligne `impl Type`, `impl Trait for Type` ou `trait Name`
+    fn impl_context(&self, line: &str) -> Option<ImplContext> {
+        let last_segment = |path: &str| path.rsplit("::").next().unwrap_or(path).to_string();
+        if let Some(captures) = self.patterns.impl_pattern.captures(line) {
+            return Some(ImplContext {
+                owner: last_segment(captures[2].trim_end_matches('{')),
+                trait_impl: captures.get(1).map(|implemented| last_segment(implemented.as_str())),
+            });
         }
+        let captures = self.patterns.trait_pattern.captures(line)?;
+        Some(ImplContext { owner: captures[1].to_string(), trait_impl: None })
     }
 
-    /// Analyse les signatures des méthodes
-    fn analyze_method_signatures(&self, content: &str) -> Vec<MethodSignature> {
-        let mut signatures = Vec::new();
-        
-        for line in content.lines() {
-            if let Some(captures) = self.patterns.method_pattern.captures(line) {
-                let visibility = match captures.name("vis").map(|m| m.as_str()) {
-                    Some("pub") => Visibility::Public,
-                    Some("pub(crate)") => Visibility::PublicCrate,
-                    _ => Visibility::Private,
-                };
-                
-                let name = captures.name("name")
-                    .map(|m| m.as_str().to_string())
-                    .unwrap_or_default();
-
-                let params = captures.name("args")
-                    .map(|args| args.as_str()
-                        .split(',')
-                        .filter_map(|p| Some(p.trim().to_string()))
-                        .collect())
-                    .unwrap_or_default();
-                    
-                let return_type = captures.name("ret")
-                    .map(|r| r.as_str().trim().to_string())
-                    .unwrap_or_else(|| "()".to_string());
-                    
-                signatures.push(MethodSignature {
-                    name,
-                    params,
-                    return_type,
-                    visibility,
-                });
-            }
-        }
-        
-        signatures
+    /// Lit une signature complète (sans corps) : visibilité, nom, paramètres, retour
+    fn parse_signature(&self, text: &str) -> Option<MethodSignature> {
+        let captures = self.patterns.method_pattern.captures(text)?;
+        let visibility = match captures.name("vis").map(|m| m.as_str()) {
+            Some("pub") => Visibility::Public,
+            // `pub(crate)`, `pub(super)`, `pub(in path)`, comme `syn::Visibility::Restricted`
+            Some(_) => Visibility::PublicCrate,
+            None => Visibility::Private,
+        };
+        let name = captures.name("name")?.as_str().to_string();
+        let qualifiers: Vec<&str> = captures.name("qualifiers").map_or("", |m| m.as_str()).split_whitespace().collect();
+
+        let (generics, rest) = generic_params(text[captures.get(0)?.end()..].trim_start())?;
+        let args = rest.trim_start().strip_prefix('(')?;
+        let close = closing(args, '(', ')')?;
+        let (args, after) = (&args[..close], &args[close + 1..]);
+        let (after, where_bounds) = where_clause(after);
+
+        let params = split_top_level(args)
+            .into_iter()
+            .map(str::trim)
+            .filter(|param| !param.is_empty())
+            .map(SharedStr::from)
+            .collect();
+        let return_type = after
+            .trim()
+            .strip_prefix("->")
+            .map(str::trim)
+            .unwrap_or("()");
+
+        Some(MethodSignature {
+            name: SharedStr::from(name),
+            params,
+            return_type: SharedStr::from(return_type),
+            visibility,
+            defined_in: SharedStr::from(""),
+            module: SharedStr::from(""),
+            owner: None,
+            trait_impl: None,
+            is_async: qualifiers.contains(&"async"),
+            is_unsafe: qualifiers.contains(&"unsafe"),
+            is_const: qualifiers.contains(&"const"),
+            generics,
+            where_bounds,
+            doc: None,
+            complexity: 0,
+            body_lines: 0,
+        })
     }
 
     /// Analyse la configuration (constantes, features, attributs)
@@ -526,11 +919,12 @@ impl FileAnalyzer {
         for line in content.lines() {
             // Analyse des constantes
             if let Some(captures) = self.patterns.const_pattern.captures(line) {
-                config.constants.push((
-                    captures[1].to_string(),
-                    captures[2].trim().to_string(),
-                    captures[3].trim().to_string(),
-                ));
+                config.constants.push(ConstantDefinition {
+                    name: captures[1].to_string(),
+                    const_type: captures[2].trim().to_string(),
+                    value: captures[3].trim().to_string(),
+                    defined_in: String::new(),
+                });
             }
             
             // Analyse des features
@@ -549,4 +943,885 @@ impl FileAnalyzer {
         
         config
     }
-}
\ No newline at end of file
+}
+
+/// Déclaration de type d'une ligne ; un `type` indenté est un type associé
+/// (`type Err = String;` d'un `impl`) et non un alias
+fn type_declaration<'a>(pattern: &Regex, line: &'a str) -> Option<regex::Captures<'a>> {
+    pattern
+        .captures(line.trim())
+        .filter(|captures| &captures[1] != "type" || !line.starts_with(char::is_whitespace))
+}
+
+/// Traits listés par les `derive(..)` d'attributs consécutifs, y compris dans
+/// `cfg_attr(predicate, derive(..))`
+fn derived_traits(derive: &Regex, attributes: &str) -> Vec<String> {
+    let mut traits = Vec::new();
+    for found in derive.find_iter(attributes) {
+        let list = &attributes[found.end()..];
+        let mut depth = 1;
+        let end = list
+            .char_indices()
+            .find(|&(_, c)| {
+                match c {
+                    '(' => depth += 1,
+                    ')' => depth -= 1,
+                    _ => {},
+                }
+                depth == 0
+            })
+            .map_or(list.len(), |(position, _)| position);
+        traits.extend(list[..end].split(',').map(str::trim).filter(|name| !name.is_empty()).map(str::to_string));
+    }
+    traits
+}
+
+/// Bloc `impl` ou trait contenant des méthodes
+#[derive(Debug, Clone, Default)]
+struct ImplContext {
+    owner: String,
+    trait_impl: Option<String>,
+}
+
+/// Accolades d'une ligne, hors chaînes et caractères (`"{}"`, `'{'`)
+pub(crate) fn braces(line: &str) -> Vec<char> {
+    let mut braces = Vec::new();
+    let mut chars = line.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if in_string => {
+                chars.next();
+            },
+            '"' => in_string = !in_string,
+            '\'' if !in_string => {
+                // `'{'` est un caractère ; `'a` une durée de vie
+                let mut lookahead = chars.clone();
+                if let (Some(_), Some('\'')) = (lookahead.next(), lookahead.next()) {
+                    chars.nth(1);
+                }
+            },
+            '{' | '}' if !in_string => braces.push(c),
+            _ => {},
+        }
+    }
+    braces
+}
+
+/// Position du `{` ou du `;` qui termine une signature, une fois ses parenthèses
+/// refermées ; `None` tant qu'elle se poursuit sur les lignes suivantes
+fn signature_end(text: &str) -> Option<usize> {
+    let (mut depth, mut opened) = (0usize, false);
+    for (position, c) in text.char_indices() {
+        match c {
+            '(' => {
+                depth += 1;
+                opened = true;
+            },
+            '[' => depth += 1,
+            ')' | ']' => depth = depth.saturating_sub(1),
+            '{' | ';' if opened && depth == 0 => return Some(position),
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Paramètres génériques en tête de `text` (`<T: Clone, 'a>(..)`) et texte qui les
+/// suit ; aucun paramètre si `text` ne commence pas par `<`, `None` s'il n'est pas fermé
+pub(crate) fn generic_params(text: &str) -> Option<(Vec<String>, &str)> {
+    let Some(inner) = text.strip_prefix('<') else { return Some((Vec::new(), text)) };
+    let close = closing(inner, '<', '>')?;
+    let params = split_top_level(&inner[..close])
+        .into_iter()
+        .map(str::trim)
+        .filter(|param| !param.is_empty())
+        .map(str::to_string)
+        .collect();
+    Some((params, &inner[close + 1..]))
+}
+
+/// Texte précédant la clause `where` et prédicats de celle-ci (`T: DeserializeOwned`)
+pub(crate) fn where_clause(text: &str) -> (&str, Vec<String>) {
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    let keyword = text.match_indices("where").map(|(position, _)| position).find(|&position| {
+        !text[..position].ends_with(is_identifier) && !text[position + 5..].starts_with(is_identifier)
+    });
+    let Some(position) = keyword else { return (text, Vec::new()) };
+    let predicates = split_top_level(&text[position + 5..])
+        .into_iter()
+        .map(str::trim)
+        .filter(|predicate| !predicate.is_empty())
+        .map(str::to_string)
+        .collect();
+    (&text[..position], predicates)
+}
+
+/// Position du délimiteur fermant celui qui précède `text` (les `>` de `->` ne
+/// ferment rien)
+fn closing(text: &str, open: char, close: char) -> Option<usize> {
+    let mut depth = 1;
+    let mut previous = ' ';
+    for (position, c) in text.char_indices() {
+        if c == open {
+            depth += 1;
+        } else if c == close && !(c == '>' && previous == '-') {
+            depth -= 1;
+            if depth == 0 {
+                return Some(position);
+            }
+        }
+        previous = c;
+    }
+    None
+}
+
+/// Variantes du corps d'une énumération (texte entre ses accolades), sans leurs
+/// attributs ni leur discriminant
+fn enum_variants(body: &str) -> Vec<EnumVariant> {
+    // Les chevrons ne comptent pas : hors des charges, ils n'apparaissent que dans les
+    // discriminants (`A = 1 << 2`)
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0i32, 0);
+    for (position, c) in body.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&body[start..position]);
+                start = position + 1;
+            },
+            _ => {},
+        }
+    }
+    parts.push(&body[start..]);
+
+    parts
+        .into_iter()
+        .filter_map(|part| {
+            let part = without_attributes(part)?;
+            let end = part.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(part.len());
+            let (name, rest) = part.split_at(end);
+            if name.is_empty() {
+                return None;
+            }
+            let rest = rest.trim_start();
+            let (shape, payload) = if let Some(inner) = rest.strip_prefix('(') {
+                (VariantShape::Tuple, &inner[..closing(inner, '(', ')')?])
+            } else if let Some(inner) = rest.strip_prefix('{') {
+                (VariantShape::Struct, &inner[..closing(inner, '{', '}')?])
+            } else {
+                (VariantShape::Unit, "")
+            };
+            let fields = split_top_level(payload)
+                .into_iter()
+                .filter_map(without_attributes)
+                .map(|field| field.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|field| !field.is_empty())
+                .collect();
+            Some(EnumVariant { name: name.to_string(), shape, fields })
+        })
+        .collect()
+}
+
+/// Champs d'une structure d'après le texte qui suit son nom (`<T>(pub T);`,
+/// `where T: Clone { .. }`) ; aucun pour une structure unitaire
+fn struct_fields(after_name: &str) -> Option<Vec<StructField>> {
+    let (_, rest) = generic_params(after_name.trim_start())?;
+    let rest = rest.trim_start();
+    let normalize = |text: &str| text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if let Some(inner) = rest.strip_prefix('(') {
+        let body = &inner[..closing(inner, '(', ')')?];
+        let fields = split_top_level(body)
+            .into_iter()
+            .filter_map(without_attributes)
+            .filter(|field| !field.is_empty())
+            .enumerate()
+            .map(|(position, field)| {
+                let (visibility, field_type) = field_visibility(field);
+                StructField { name: position.to_string(), field_type: normalize(field_type), visibility }
+            })
+            .collect();
+        return Some(fields);
+    }
+
+    let open = rest.find(['{', ';'])?;
+    let Some(inner) = rest[open..].strip_prefix('{') else { return Some(Vec::new()) };
+    let body = &inner[..closing(inner, '{', '}')?];
+    let fields = split_top_level(body)
+        .into_iter()
+        .filter_map(without_attributes)
+        .filter_map(|field| {
+            let (visibility, field) = field_visibility(field);
+            let (name, field_type) = field.split_once(':')?;
+            Some(StructField { name: name.trim().to_string(), field_type: normalize(field_type), visibility })
+        })
+        .collect();
+    Some(fields)
+}
+
+/// Visibilité en tête d'un champ (`pub`, `pub(crate)`) et reste du champ
+fn field_visibility(field: &str) -> (Visibility, &str) {
+    let field = field.trim();
+    let Some(rest) = field.strip_prefix("pub") else { return (Visibility::Private, field) };
+    if let Some(restricted) = rest.trim_start().strip_prefix('(') {
+        let scope = closing(restricted, '(', ')').filter(|&close| {
+            let scope = restricted[..close].trim();
+            matches!(scope, "crate" | "self" | "super") || scope.starts_with("in ")
+        });
+        // Sans portée, `pub (u8, u8)` est un champ public de type tuple
+        return match scope {
+            Some(close) => (Visibility::PublicCrate, restricted[close + 1..].trim_start()),
+            None => (Visibility::Public, rest.trim_start()),
+        };
+    }
+    if rest.starts_with(char::is_whitespace) {
+        return (Visibility::Public, rest.trim_start());
+    }
+    (Visibility::Private, field)
+}
+
+/// Élément privé de ses attributs de tête (`#[default]`, `#[serde(..)]`) ; `None` si
+/// un attribut n'est pas fermé
+fn without_attributes(item: &str) -> Option<&str> {
+    let mut item = item.trim();
+    while let Some(attribute) = item.strip_prefix("#[") {
+        item = attribute[closing(attribute, '[', ']')? + 1..].trim_start();
+    }
+    Some(item)
+}
+
+/// Paramètres séparés par les virgules de premier niveau (`HashMap<K, V>` reste entier)
+fn split_top_level(args: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let (mut depth, mut start, mut previous) = (0i32, 0, ' ');
+    for (position, c) in args.char_indices() {
+        match c {
+            '(' | '[' | '<' => depth += 1,
+            ')' | ']' => depth -= 1,
+            '>' if previous != '-' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&args[start..position]);
+                start = position + 1;
+            },
+            _ => {},
+        }
+        previous = c;
+    }
+    parts.push(&args[start..]);
+    parts
+}
+
+/// Remplace les relations directes par leurs fermetures transitives.
+///
+/// Les types pris dans un cycle dépendent les uns des autres (et d'eux-mêmes).
+pub fn close_type_relations(relations: &mut [TypeRelations]) {
+    let mut nodes: HashMap<SharedStr, usize> = HashMap::new();
+    let mut names: Vec<SharedStr> = Vec::new();
+    let mut node_of = |name: &SharedStr| -> usize {
+        *nodes.entry(name.clone()).or_insert_with(|| {
+            names.push(name.clone());
+            names.len() - 1
+        })
+    };
+
+    let mut edges = Vec::new();
+    for relation in relations.iter() {
+        let from = node_of(&relation.type_name);
+        for dep in &relation.depends_on {
+            edges.push((from, node_of(dep)));
+        }
+    }
+
+    let mut graph = DirectedGraph::new(names.len());
+    for (from, to) in edges {
+        graph.add_edge(from, to);
+    }
+    let depends_on = graph.transitive_closure();
+    let used_by = graph.reversed().transitive_closure();
+
+    let to_names = |indices: &[usize]| -> Vec<SharedStr> {
+        let mut result: Vec<SharedStr> = indices.iter().map(|&i| names[i].clone()).collect();
+        result.sort();
+        result
+    };
+    for relation in relations.iter_mut() {
+        let node = nodes[&relation.type_name];
+        relation.depends_on = to_names(&depends_on[node]);
+        if !used_by[node].is_empty() {
+            relation.used_by = to_names(&used_by[node]);
+        }
+    }
+}
+
+/// Graphe des types à l'échelle du projet : fusionne les relations relevées fichier par
+/// fichier en une entrée par type, puis recalcule les `depends_on` directs en
+/// confrontant les références de chaque type à l'ensemble des types déclarés.
+/// `close_type_relations` en calcule ensuite la fermeture et les `used_by`.
+///
+/// Un nom déclaré dans plusieurs fichiers garde le premier `defined_in`.
+pub fn merge_type_relations(relations: Vec<TypeRelations>) -> Vec<TypeRelations> {
+    let mut merged: BTreeMap<SharedStr, TypeRelations> = BTreeMap::new();
+    for relation in relations {
+        match merged.get_mut(&relation.type_name) {
+            Some(existing) => {
+                existing.implemented_traits.extend(relation.implemented_traits);
+                existing.references.extend(relation.references);
+                existing.depends_on.extend(relation.depends_on);
+                if existing.variants.is_empty() {
+                    existing.variants = relation.variants;
+                }
+                if existing.alias_of.is_none() {
+                    existing.alias_of = relation.alias_of;
+                }
+            },
+            None => {
+                merged.insert(relation.type_name.clone(), relation);
+            },
+        }
+    }
+
+    let declared: HashSet<SharedStr> = merged.keys().cloned().collect();
+    let mut relations: Vec<TypeRelations> = merged.into_values().collect();
+    for relation in relations.iter_mut() {
+        let mut direct: Vec<SharedStr> = std::mem::take(&mut relation.references)
+            .into_iter()
+            .chain(std::mem::take(&mut relation.depends_on))
+            .filter(|name| declared.contains(name) && *name != relation.type_name)
+            .collect();
+        direct.sort();
+        direct.dedup();
+        relation.depends_on = direct;
+        relation.used_by.clear();
+
+        let mut traits = Vec::new();
+        for name in relation.implemented_traits.drain(..) {
+            if !traits.contains(&name) {
+                traits.push(name);
+            }
+        }
+        relation.implemented_traits = traits;
+    }
+
+    // Les traits du projet listent les types qui les implémentent
+    let mut implementors: HashMap<SharedStr, Vec<SharedStr>> = HashMap::new();
+    for relation in &relations {
+        for implemented in &relation.implemented_traits {
+            implementors.entry(implemented.clone()).or_default().push(relation.type_name.clone());
+        }
+    }
+    for relation in relations.iter_mut().filter(|relation| relation.kind == TypeKind::Trait) {
+        relation.implementors = implementors.remove(&relation.type_name).unwrap_or_default();
+    }
+    relations
+}
+
+/// Types les plus centraux : nombre de types qui les utilisent directement, plus nombre
+/// de fichiers autres que celui qui les déclare où un type ou une signature les
+/// mentionne. `relations` sont les relations directes de `merge_type_relations`.
+///
+/// Un type utilisé d'un module à l'autre passe ainsi devant un type aussi utilisé mais
+/// confiné à son fichier. Les types jamais utilisés sont écartés.
+pub fn rank_key_types(relations: &[TypeRelations], signatures: &[MethodSignature], limit: usize) -> Vec<KeyType> {
+    let defined_in: HashMap<&str, &str> = relations
+        .iter()
+        .map(|relation| (&*relation.type_name, &*relation.defined_in))
+        .collect();
+
+    let mut users: HashMap<&str, usize> = HashMap::new();
+    let mut files: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for relation in relations {
+        for dependency in &relation.depends_on {
+            *users.entry(dependency).or_default() += 1;
+            files.entry(dependency).or_default().insert(&relation.defined_in);
+        }
+    }
+    for signature in signatures {
+        let mentioned = signature.params.iter().map(|param| &**param).chain([&*signature.return_type]);
+        for name in mentioned.flat_map(|text| text.split(|c: char| !(c.is_alphanumeric() || c == '_'))) {
+            if defined_in.contains_key(name) {
+                files.entry(name).or_default().insert(&signature.defined_in);
+            }
+        }
+    }
+
+    let mut ranked: Vec<KeyType> = relations
+        .iter()
+        .map(|relation| {
+            let used_by = users.get(&*relation.type_name).copied().unwrap_or(0);
+            let referencing_files = files
+                .get(&*relation.type_name)
+                .map_or(0, |files| files.iter().filter(|file| **file != &*relation.defined_in).count());
+            KeyType {
+                name: relation.type_name.to_string(),
+                score: used_by + referencing_files,
+                used_by,
+                referencing_files,
+                defined_in: relation.defined_in.to_string(),
+            }
+        })
+        .filter(|key_type| key_type.score > 0)
+        .collect();
+    ranked.sort_by(|a, b| b.score.cmp(&a.score).then_with(|| a.name.cmp(&b.name)));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// Groupes de types qui dépendent mutuellement les uns des autres (composantes
+/// fortement connexes de plus d'un type)
+pub fn type_cycles(relations: &[TypeRelations]) -> Vec<Vec<String>> {
+    let index: HashMap<&SharedStr, usize> = relations
+        .iter()
+        .enumerate()
+        .map(|(i, relation)| (&relation.type_name, i))
+        .collect();
+    let mut graph = DirectedGraph::new(relations.len());
+    for (from, relation) in relations.iter().enumerate() {
+        for dependency in &relation.depends_on {
+            if let Some(&to) = index.get(dependency).filter(|to| **to != from) {
+                graph.add_edge(from, to);
+            }
+        }
+    }
+    graph
+        .cycles()
+        .into_iter()
+        .map(|members| members.into_iter().map(|member| relations[member].type_name.to_string()).collect())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Fichier de 10 000 lignes : 2 000 fonctions publiques, dont une sur quatre
+    /// documentée, et autant de fonctions privées
+    fn pattern_heavy_file() -> String {
+        let mut content = String::from("//! Fonctions générées\n");
+        for index in 0..2_000 {
+            if index % 4 == 0 {
+                content.push_str(&format!("/// Documente f{}\n", index));
+            }
+            content.push_str(&format!("pub fn f{}() -> usize {{ {} }}\n", index, index));
+            content.push_str(&format!("fn g{}() {{}}\n", index));
+            content.push_str("\n\n");
+        }
+        content
+    }
+
+    #[test]
+    fn small_summary_is_rendered_in_full() {
+        let content = "//! Outils\n/// Ajoute un\npub fn increment(x: u32) -> u32 { x + 1 }\nfn helper() {}\npub struct Counter;\nimpl Counter {}\n";
+        let summary = FileAnalyzer::new().generate_summary(content);
+        assert_eq!(
+            summary.text,
+            "File start:\n\
+             //! Outils\n\
+             /// Ajoute un\n\
+             pub fn increment(x: u32) -> u32 { x + 1 }\n\
+             fn helper() {}\n\
+             pub struct Counter;\n\
+             Documentation: /// Ajoute un\n\
+             Module documentation: //! Outils\n\
+             Public method: pub fn increment(x: u32) -> u32 { x + 1 }\n\
+             Private method: fn helper() {}\n\
+             Public struct: pub struct Counter;\n\
+             Implementation: impl Counter {}\n"
+        );
+    }
+
+    #[test]
+    fn each_pattern_is_capped_with_a_trailer() {
+        let content = pattern_heavy_file();
+        let summary = FileAnalyzer::with_summary_limit(3).generate_summary(&content);
+
+        let public: Vec<&str> = summary.text.lines().filter(|line| line.starts_with("Public method: ")).collect();
+        assert_eq!(
+            public,
+            vec![
+                "Public method: pub fn f0() -> usize { 0 }",
+                "Public method: pub fn f4() -> usize { 4 }",
+                "Public method: pub fn f8() -> usize { 8 }",
+                "Public method: … and 1997 more",
+            ]
+        );
+        let private: Vec<&str> = summary.text.lines().filter(|line| line.starts_with("Private method: ")).collect();
+        assert_eq!(private, vec!["Private method: fn g0() {}", "Private method: fn g1() {}", "Private method: fn g2() {}", "Private method: … and 1997 more"]);
+    }
+
+    #[test]
+    fn truncation_keeps_documented_items_first() {
+        let content = "pub fn a() {}\npub fn b() {}\n/// Documentée\n#[inline]\npub fn c() {}\n";
+        let summary = FileAnalyzer::with_summary_limit(1).generate_summary(content);
+        assert!(summary.text.contains("Public method: pub fn c() {}\nPublic method: … and 2 more\n"), "{}", summary.text);
+    }
+
+    /// Signatures relevées par les motifs, sous une forme compacte
+    fn signatures(content: &str) -> Vec<String> {
+        let (_, signatures, _) = FileAnalyzer::new().analyze_with_patterns(content);
+        signatures
+            .iter()
+            .map(|signature| {
+                let mut shape = format!(
+                    "{:?} {}{}({}) -> {}",
+                    signature.visibility,
+                    if signature.is_async { "async " } else { "" },
+                    signature.name,
+                    signature.params.join(", "),
+                    signature.return_type
+                );
+                if !signature.generics.is_empty() {
+                    shape.push_str(&format!(" <{}>", signature.generics.join(", ")));
+                }
+                if !signature.where_bounds.is_empty() {
+                    shape.push_str(&format!(" where {}", signature.where_bounds.join(", ")));
+                }
+                if let Some(doc) = &signature.doc {
+                    shape.push_str(&format!(" /// {}", doc));
+                }
+                shape
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rustfmt_wrapped_parameters_are_joined() {
+        let content = "impl RepositoryAnalyzer {
+    pub async fn process_file(
+        &self,
+        content: &GithubContent,
+        project_summary: &mut ProjectSummary,
+    ) -> Result<(), GithubAnalyzerError> {
+        Ok(())
+    }
+
+    fn with_long_return(
+        &mut self,
+    ) -> HashMap<String, Vec<(usize, Option<String>)>> {
+        HashMap::new()
+    }
+}
+";
+        assert_eq!(
+            signatures(content),
+            vec![
+                "Public async process_file(&self, content: &GithubContent, project_summary: &mut ProjectSummary) -> Result<(), GithubAnalyzerError>",
+                "Private with_long_return(&mut self) -> HashMap<String, Vec<(usize, Option<String>)>>",
+            ]
+        );
+    }
+
+    #[test]
+    fn where_clauses_on_their_own_lines_are_kept() {
+        let content = "pub fn collect<I>(items: I) -> Vec<String>
+where
+    I: IntoIterator<Item = String>,
+    I::IntoIter: Clone,
+{
+    items.into_iter().collect()
+}
+
+pub trait Provider {
+    fn get_repo_tree<'a>(
+        &'a self,
+        branch: &'a str,
+    ) -> BoxFuture<'a, Result<GithubTree, GithubAnalyzerError>>
+    where
+        Self: Sync;
+}
+";
+        assert_eq!(
+            signatures(content),
+            vec![
+                "Public collect(items: I) -> Vec<String> <I> where I: IntoIterator<Item = String>, I::IntoIter: Clone",
+                "Private get_repo_tree(&'a self, branch: &'a str) -> BoxFuture<'a, Result<GithubTree, GithubAnalyzerError>> <'a> where Self: Sync",
+            ]
+        );
+    }
+
+    #[test]
+    fn attributes_between_doc_and_signature_keep_the_doc() {
+        let content = r#"/// Traite un fichier
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(skip(self, content), level = "debug")
+)]
+pub fn process(
+    #[allow(unused)] flag: bool,
+    content: &str,
+) {}
+
+/// Détachée par une ligne vide
+
+pub fn undocumented() {}
+"#;
+        assert_eq!(
+            signatures(content),
+            vec!["Public process(flag: bool, content: &str) -> () /// Traite un fichier", "Public undocumented() -> ()"]
+        );
+    }
+
+    #[test]
+    fn types_named_only_in_comments_and_strings_are_not_dependencies() {
+        let content = r##"/// Construit un `ProjectSummary` à partir d'un `GithubTree`
+pub struct Report {
+    // Anciennement un HashMap<String, Settings>
+    entries: Vec<Entry>,
+    /* Voir aussi : Cache, Limiter */
+    label: String,
+}
+
+impl Report {
+    /// Renvoie un Summary
+    pub fn render(&self) -> String {
+        println!("HashMap {} Config", self.label);
+        let pattern = r#"impl Display for Phantom"#;
+        format!("Vec<Ghost>: {}", pattern)
+    }
+}
+
+pub struct Entry {
+    value: Option<Value>,
+}
+"##;
+        let (relations, signatures, _) = FileAnalyzer::new().analyze_with_patterns(content);
+        let report = relations.iter().find(|relation| &*relation.type_name == "Report").unwrap();
+        assert_eq!(report.depends_on, vec![SharedStr::from("Entry")]);
+        assert_eq!(report.references, vec![SharedStr::from("String"), SharedStr::from("Vec")]);
+        assert!(report.implemented_traits.is_empty());
+        let entry = relations.iter().find(|relation| &*relation.type_name == "Entry").unwrap();
+        assert_eq!(entry.references, vec![SharedStr::from("Option"), SharedStr::from("Value")]);
+        // La doc reste lue sur le texte d'origine
+        assert_eq!(signatures[0].doc.as_deref(), Some("Renvoie un Summary"));
+    }
+
+    /// Traits implémentés de chaque type, dans l'ordre du fichier
+    fn implemented_traits(content: &str) -> Vec<(String, Vec<String>)> {
+        let (relations, _, _) = FileAnalyzer::new().analyze_with_patterns(content);
+        relations
+            .iter()
+            .map(|relation| {
+                let traits = relation.implemented_traits.iter().map(|name| name.to_string()).collect();
+                (relation.type_name.to_string(), traits)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn stacked_derives_are_merged_across_other_attributes() {
+        let content = r#"#[derive(Debug, Clone)]
+#[serde(rename_all = "camelCase")]
+/// Doc entre les attributs
+#[derive(Serialize, Deserialize)]
+pub struct Stacked {
+    value: u32,
+}
+
+#[inline]
+fn helper() {}
+
+pub struct Plain;
+"#;
+        assert_eq!(
+            implemented_traits(content),
+            vec![
+                ("Stacked".to_string(), vec!["Debug".to_string(), "Clone".to_string(), "Serialize".to_string(), "Deserialize".to_string()]),
+                ("Plain".to_string(), vec![]),
+            ]
+        );
+    }
+
+    #[test]
+    fn derives_spanning_several_lines_are_complete() {
+        let content = "#[derive(
+    Debug,
+    Clone,
+    PartialEq, Eq,
+)]
+pub enum MultiLine {
+    A,
+}
+";
+        assert_eq!(
+            implemented_traits(content),
+            vec![("MultiLine".to_string(), vec!["Debug".to_string(), "Clone".to_string(), "PartialEq".to_string(), "Eq".to_string()])]
+        );
+    }
+
+    #[test]
+    fn conditional_derives_are_recorded() {
+        let content = r#"#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(test, derive(Default), allow(dead_code))]
+#[derive(Copy)]
+pub struct Conditional(u8);
+"#;
+        assert_eq!(
+            implemented_traits(content),
+            vec![("Conditional".to_string(), vec!["Serialize".to_string(), "Deserialize".to_string(), "Default".to_string(), "Copy".to_string()])]
+        );
+    }
+
+    #[test]
+    fn manual_trait_implementations_are_recorded() {
+        let content = "pub trait Visitor {
+    fn visit(&self);
+}
+
+pub struct Node<T> {
+    value: T,
+}
+
+impl<T: Clone> fmt::Display for Node<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { Ok(()) }
+}
+
+impl<T> Visitor
+    for Node<T>
+where
+    T: Send,
+{
+    fn visit(&self) {}
+}
+
+impl<'a> From<&'a str> for Node<String> {
+    fn from(value: &'a str) -> Self { Node { value: value.to_string() } }
+}
+
+impl Iterator for Node<u32> {
+    type Item = u32;
+    fn next(&mut self) -> Option<u32> { None }
+}
+
+impl Drop for Node<u8> {
+    fn drop(&mut self) {}
+}
+
+impl<T> Node<T> {
+    fn inherent(&self) {}
+}
+";
+        let traits = |names: &[&str]| names.iter().map(|name| name.to_string()).collect::<Vec<_>>();
+        assert_eq!(
+            implemented_traits(content),
+            vec![
+                ("Visitor".to_string(), vec![]),
+                ("Node".to_string(), traits(&["Display", "Visitor", "From", "Iterator", "Drop"])),
+            ]
+        );
+        // Seul le trait du projet devient une dépendance
+        let (relations, _, _) = FileAnalyzer::new().analyze_with_patterns(content);
+        assert_eq!(relations[1].depends_on, vec![SharedStr::from("Visitor")]);
+    }
+
+    #[test]
+    fn error_type_of_this_crate_implements_display_and_error() {
+        let (relations, _, _) = FileAnalyzer::new().analyze_with_patterns(include_str!("../error.rs"));
+        let error = relations.iter().find(|relation| &*relation.type_name == "GithubAnalyzerError").unwrap();
+        for expected in ["Debug", "Display", "Error"] {
+            assert!(error.implemented_traits.iter().any(|name| &**name == expected), "{:?}", error.implemented_traits);
+        }
+    }
+
+    /// Signatures courantes et forme attendue de leur analyse, communes aux deux
+    /// analyseurs (motifs et `syn`)
+    const SIGNATURE_CASES: &[(&str, &str)] = &[
+        ("fn main() {}", "private main() -> ()"),
+        ("pub fn new() -> Self { todo!() }", "public new() -> Self"),
+        ("impl FileAnalyzer { pub fn new() -> Self { todo!() } }", "public new() -> Self [FileAnalyzer]"),
+        ("impl Cache { fn len(&self) -> usize { 0 } }", "private len(&self) -> usize [Cache]"),
+        ("impl Cache { pub fn clear(&mut self) {} }", "public clear(&mut self) -> () [Cache]"),
+        ("impl Cache { pub fn into_inner(self) -> Vec<u8> { self.0 } }", "public into_inner(self) -> Vec<u8> [Cache]"),
+        ("impl Cache { fn take(mut self) -> Self { self } }", "private take(mut self) -> Self [Cache]"),
+        ("pub(crate) fn helper(x: u32) -> u32 { x }", "public_crate helper(x: u32) -> u32"),
+        ("pub(super) fn parent_only() {}", "public_crate parent_only() -> ()"),
+        ("pub(in crate::analysis) fn scoped() {}", "public_crate scoped() -> ()"),
+        ("pub async fn fetch(url: &str) -> Result<String, Error> { todo!() }", "public async fetch(url: &str) -> Result<String, Error>"),
+        ("pub const fn max_depth() -> usize { 8 }", "public const max_depth() -> usize"),
+        ("pub unsafe fn raw(ptr: *const u8) -> u8 { *ptr }", "public unsafe raw(ptr: *const u8) -> u8"),
+        ("pub const unsafe fn both() {}", "public const unsafe both() -> ()"),
+        ("pub fn first<T>(items: Vec<T>) -> Option<T> { None }", "public first<T>(items: Vec<T>) -> Option<T>"),
+        ("pub fn pair<K: Ord, V>(key: K, value: V) -> (K, V) { (key, value) }", "public pair<K: Ord, V>(key: K, value: V) -> (K, V)"),
+        ("pub fn longest<'a>(a: &'a str, b: &'a str) -> &'a str { a }", "public longest<'a>(a: &'a str, b: &'a str) -> &'a str"),
+        ("fn sized<T: ?Sized>(value: &T) {}", "private sized<T: ?Sized>(value: &T) -> ()"),
+        ("pub fn sum<I>(items: I) -> u64 where I: Iterator<Item = u64> { 0 }", "public sum<I>(items: I) -> u64 where I: Iterator<Item = u64>"),
+        ("pub fn apply<F>(f: F) where F: Fn(u32) -> u32, F: Send {}", "public apply<F>(f: F) -> () where F: Fn(u32) -> u32, F: Send"),
+        ("pub fn lookup(map: &HashMap<String, Vec<u32>>, key: &str) -> Option<&Vec<u32>> { None }", "public lookup(map: &HashMap<String, Vec<u32>>, key: &str) -> Option<&Vec<u32>>"),
+        ("pub fn callback(f: impl Fn(&str) -> bool) -> bool { true }", "public callback(f: impl Fn(&str) -> bool) -> bool"),
+        ("pub fn boxed() -> Box<dyn Fn(u32) -> u32> { todo!() }", "public boxed() -> Box<dyn Fn(u32) -> u32>"),
+        ("pub fn iter(&self) -> impl Iterator<Item = &u32> + '_ { todo!() }", "public iter(&self) -> impl Iterator<Item = &u32> + '_"),
+        ("pub fn split((a, b): (u32, u32)) -> u32 { a }", "public split((a, b): (u32, u32)) -> u32"),
+        ("pub fn bytes(buffer: &mut [u8; 4]) {}", "public bytes(buffer: &mut [u8; 4]) -> ()"),
+        ("pub fn never() -> ! { loop {} }", "public never() -> !"),
+        ("pub extern \"C\" fn exported(x: i32) -> i32 { x }", "public exported(x: i32) -> i32"),
+        ("pub trait Store { fn get(&self, key: &str) -> Option<String>; }", "private get(&self, key: &str) -> Option<String> [Store]"),
+        ("pub trait Store { async fn load(&mut self) -> Result<(), Error> { Ok(()) } }", "private async load(&mut self) -> Result<(), Error> [Store]"),
+        ("impl fmt::Display for Node { fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { Ok(()) } }", "private fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result [Node: Display]"),
+        ("impl<T: Clone> From<T> for Wrapper<T> { fn from(value: T) -> Self { Wrapper(value) } }", "private from(value: T) -> Self [Wrapper: From]"),
+    ];
+
+    /// Forme compacte d'une signature, comparable entre les deux analyseurs
+    fn signature_shape(signature: &MethodSignature) -> String {
+        let visibility = serde_json::to_value(signature.visibility).unwrap();
+        let mut shape = visibility.as_str().unwrap_or_default().to_string();
+        for (qualifier, present) in [("async", signature.is_async), ("const", signature.is_const), ("unsafe", signature.is_unsafe)] {
+            if present {
+                shape.push(' ');
+                shape.push_str(qualifier);
+            }
+        }
+        shape.push_str(&format!(" {}", signature.name));
+        if !signature.generics.is_empty() {
+            shape.push_str(&format!("<{}>", signature.generics.join(", ")));
+        }
+        shape.push_str(&format!("({}) -> {}", signature.params.join(", "), signature.return_type));
+        if !signature.where_bounds.is_empty() {
+            shape.push_str(&format!(" where {}", signature.where_bounds.join(", ")));
+        }
+        match (&signature.owner, &signature.trait_impl) {
+            (Some(owner), Some(trait_impl)) => shape.push_str(&format!(" [{}: {}]", owner, trait_impl)),
+            (Some(owner), None) => shape.push_str(&format!(" [{}]", owner)),
+            _ => {},
+        }
+        shape
+    }
+
+    /// Compare chaque cas à la signature unique produite par `analyze`
+    fn check_signature_cases(analyze: impl Fn(&str) -> Vec<MethodSignature>) {
+        let failures: Vec<String> = SIGNATURE_CASES
+            .iter()
+            .filter_map(|(source, expected)| {
+                let shapes: Vec<String> = analyze(source).iter().map(signature_shape).collect();
+                (shapes != [*expected]).then(|| format!("{}\n  expected {}\n  got      {:?}", source, expected, shapes))
+            })
+            .collect();
+        assert!(failures.is_empty(), "{} of {} cases differ:\n{}", failures.len(), SIGNATURE_CASES.len(), failures.join("\n"));
+    }
+
+    #[test]
+    fn pattern_backend_parses_common_signatures() {
+        check_signature_cases(|source| FileAnalyzer::new().analyze_with_patterns(source).1);
+    }
+
+    #[cfg(feature = "syn-backend")]
+    #[test]
+    fn syn_backend_parses_common_signatures() {
+        check_signature_cases(|source| crate::analysis::syntax::analyze(source).expect("case parses").1);
+    }
+
+    #[test]
+    fn counts_stay_complete_while_the_text_shrinks_tenfold() {
+        let content = pattern_heavy_file();
+        let capped = FileAnalyzer::new().generate_summary(&content);
+        let full = FileAnalyzer::with_summary_limit(usize::MAX).generate_summary(&content);
+
+        assert!(!full.text.contains("more\n"));
+        assert!(capped.text.len() * 10 <= full.text.len(), "{} vs {} bytes", capped.text.len(), full.text.len());
+        assert_eq!(capped.pattern_counts, full.pattern_counts);
+        assert_eq!(capped.pattern_counts["public_method"], 2_000);
+        assert_eq!(capped.pattern_counts["private_method"], 2_000);
+        assert_eq!(capped.pattern_counts["documentation"], 500);
+        assert_eq!(capped.pattern_counts["module_documentation"], 1);
+    }
+}
@@ -0,0 +1,275 @@
+/// Graphe orienté indexé, utilisé pour les fermetures transitives entre types
+#[derive(Debug, Clone, Default)]
+pub struct DirectedGraph {
+    edges: Vec<Vec<usize>>,
+}
+
+impl DirectedGraph {
+    pub fn new(node_count: usize) -> Self {
+        Self {
+            edges: vec![Vec::new(); node_count],
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    pub fn add_edge(&mut self, from: usize, to: usize) {
+        self.edges[from].push(to);
+    }
+
+    /// Graphe des arêtes inversées
+    pub fn reversed(&self) -> Self {
+        let mut reversed = Self::new(self.node_count());
+        for (from, targets) in self.edges.iter().enumerate() {
+            for &to in targets {
+                reversed.add_edge(to, from);
+            }
+        }
+        reversed
+    }
+
+    /// Calcule, pour chaque nœud, l'ensemble trié des nœuds atteignables.
+    ///
+    /// Un nœud n'apparaît dans son propre ensemble que s'il fait partie d'un
+    /// cycle (boucle comprise) : les nœuds d'un même cycle s'atteignent tous
+    /// mutuellement. Le calcul passe par les composantes fortement connexes
+    /// (Tarjan, itératif) puis propage des bitsets dans l'ordre topologique
+    /// inverse, ce qui le rend linéaire en nombre d'arêtes fois n/64.
+    pub fn transitive_closure(&self) -> Vec<Vec<usize>> {
+        let (components, members) = self.strongly_connected_components();
+        let words = self.node_count().div_ceil(64);
+        let mut reach: Vec<Vec<u64>> = Vec::with_capacity(members.len());
+
+        // Tarjan émet les composantes puits en premier : leurs successeurs sont déjà calculés
+        for (component, nodes) in members.iter().enumerate() {
+            let mut bits = vec![0u64; words];
+            let cyclic = nodes.len() > 1 || self.edges[nodes[0]].contains(&nodes[0]);
+            if cyclic {
+                for &node in nodes {
+                    bits[node / 64] |= 1 << (node % 64);
+                }
+            }
+            for &node in nodes {
+                for &target in &self.edges[node] {
+                    let target_component = components[target];
+                    if target_component == component {
+                        continue;
+                    }
+                    bits[target / 64] |= 1 << (target % 64);
+                    for (word, other) in bits.iter_mut().zip(&reach[target_component]) {
+                        *word |= other;
+                    }
+                }
+            }
+            reach.push(bits);
+        }
+
+        (0..self.node_count())
+            .map(|node| set_bits(&reach[components[node]]))
+            .collect()
+    }
+
+    /// Cycles du graphe : composantes fortement connexes de plus d'un nœud, membres
+    /// triés, dans l'ordre de leur plus petit nœud
+    pub fn cycles(&self) -> Vec<Vec<usize>> {
+        let (_, members) = self.strongly_connected_components();
+        let mut cycles: Vec<Vec<usize>> = members
+            .into_iter()
+            .filter(|nodes| nodes.len() > 1)
+            .map(|mut nodes| {
+                nodes.sort_unstable();
+                nodes
+            })
+            .collect();
+        cycles.sort();
+        cycles
+    }
+
+    /// Composantes fortement connexes : (composante de chaque nœud, membres par composante)
+    fn strongly_connected_components(&self) -> (Vec<usize>, Vec<Vec<usize>>) {
+        const UNVISITED: usize = usize::MAX;
+        let count = self.node_count();
+        let mut index = vec![UNVISITED; count];
+        let mut lowlink = vec![0; count];
+        let mut on_stack = vec![false; count];
+        let mut components = vec![UNVISITED; count];
+        let mut members: Vec<Vec<usize>> = Vec::new();
+        let mut stack = Vec::new();
+        let mut next_index = 0;
+
+        for root in 0..count {
+            if index[root] != UNVISITED {
+                continue;
+            }
+            // Pile d'appels explicite : (nœud, prochaine arête à visiter)
+            let mut calls = vec![(root, 0)];
+            index[root] = next_index;
+            lowlink[root] = next_index;
+            next_index += 1;
+            stack.push(root);
+            on_stack[root] = true;
+
+            while let Some(&mut (node, ref mut edge)) = calls.last_mut() {
+                if let Some(&target) = self.edges[node].get(*edge) {
+                    *edge += 1;
+                    if index[target] == UNVISITED {
+                        index[target] = next_index;
+                        lowlink[target] = next_index;
+                        next_index += 1;
+                        stack.push(target);
+                        on_stack[target] = true;
+                        calls.push((target, 0));
+                    } else if on_stack[target] {
+                        lowlink[node] = lowlink[node].min(index[target]);
+                    }
+                    continue;
+                }
+
+                calls.pop();
+                if let Some(&(parent, _)) = calls.last() {
+                    lowlink[parent] = lowlink[parent].min(lowlink[node]);
+                }
+                if lowlink[node] == index[node] {
+                    let component = members.len();
+                    let mut nodes = Vec::new();
+                    while let Some(member) = stack.pop() {
+                        on_stack[member] = false;
+                        components[member] = component;
+                        nodes.push(member);
+                        if member == node {
+                            break;
+                        }
+                    }
+                    members.push(nodes);
+                }
+            }
+        }
+
+        (components, members)
+    }
+}
+
+/// Indices des bits à 1, dans l'ordre croissant
+fn set_bits(bits: &[u64]) -> Vec<usize> {
+    let mut result = Vec::new();
+    for (position, &word) in bits.iter().enumerate() {
+        let mut word = word;
+        while word != 0 {
+            result.push(position * 64 + word.trailing_zeros() as usize);
+            word &= word - 1;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeSet;
+    use std::time::{Duration, Instant};
+
+    fn graph(node_count: usize, edges: &[(usize, usize)]) -> DirectedGraph {
+        let mut graph = DirectedGraph::new(node_count);
+        for &(from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    /// Fermeture de référence : parcours en largeur depuis chaque nœud
+    fn naive_closure(graph: &DirectedGraph) -> Vec<Vec<usize>> {
+        (0..graph.node_count())
+            .map(|start| {
+                let mut reached = BTreeSet::new();
+                let mut queue: Vec<usize> = graph.edges[start].clone();
+                while let Some(node) = queue.pop() {
+                    if reached.insert(node) {
+                        queue.extend(&graph.edges[node]);
+                    }
+                }
+                reached.into_iter().collect()
+            })
+            .collect()
+    }
+
+    /// Graphe pseudo-aléatoire reproductible : `edges_per_node` arêtes par nœud, vers
+    /// l'avant seulement si `acyclic`
+    fn synthetic(node_count: usize, edges_per_node: usize, acyclic: bool) -> DirectedGraph {
+        let mut state: u64 = 0x2545f4914f6cdd1d;
+        let mut next = || {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (state >> 33) as usize
+        };
+        let mut graph = DirectedGraph::new(node_count);
+        for from in 0..node_count {
+            for _ in 0..edges_per_node {
+                let to = next() % node_count;
+                if !acyclic || to > from {
+                    graph.add_edge(from, to);
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn chain_reaches_every_later_node() {
+        let closure = graph(4, &[(0, 1), (1, 2), (2, 3)]).transitive_closure();
+        assert_eq!(closure, vec![vec![1, 2, 3], vec![2, 3], vec![3], vec![]]);
+    }
+
+    #[test]
+    fn nodes_in_a_cycle_reach_each_other_and_themselves() {
+        // 0 → 1 → 2 → 0, puis 2 → 3 ; 4 boucle sur lui-même
+        let graph = graph(5, &[(0, 1), (1, 2), (2, 0), (2, 3), (4, 4)]);
+        let closure = graph.transitive_closure();
+        for (node, reached) in closure.iter().enumerate().take(3) {
+            assert_eq!(reached, &vec![0, 1, 2, 3], "node {}", node);
+        }
+        assert_eq!(closure[3], Vec::<usize>::new());
+        assert_eq!(closure[4], vec![4]);
+        assert_eq!(graph.cycles(), vec![vec![0, 1, 2]]);
+    }
+
+    #[test]
+    fn reversed_closure_lists_predecessors() {
+        let graph = graph(3, &[(0, 1), (1, 2)]);
+        assert_eq!(graph.reversed().transitive_closure(), vec![vec![], vec![0], vec![0, 1]]);
+    }
+
+    #[test]
+    fn closure_matches_a_naive_search() {
+        for acyclic in [true, false] {
+            let graph = synthetic(300, 3, acyclic);
+            assert_eq!(graph.transitive_closure(), naive_closure(&graph), "acyclic: {}", acyclic);
+        }
+        assert!(synthetic(300, 3, true).cycles().is_empty());
+    }
+
+    #[test]
+    fn two_thousand_nodes_with_cycles_close_quickly() {
+        let graph = synthetic(2_000, 4, false);
+        assert!(!graph.cycles().is_empty());
+
+        let started = Instant::now();
+        let closure = graph.transitive_closure();
+        let reversed = graph.reversed().transitive_closure();
+        let elapsed = started.elapsed();
+
+        assert_eq!(closure.len(), 2_000);
+        assert_eq!(reversed.len(), 2_000);
+        assert!(elapsed < Duration::from_millis(500), "closure took {:?}", elapsed);
+        // Les deux fermetures décrivent les mêmes paires
+        let pairs: usize = closure.iter().map(Vec::len).sum();
+        assert_eq!(pairs, reversed.iter().map(Vec::len).sum::<usize>());
+    }
+
+    #[test]
+    fn deep_chain_does_not_overflow_the_stack() {
+        let edges: Vec<(usize, usize)> = (0..99_999).map(|node| (node, node + 1)).collect();
+        let graph = graph(100_000, &edges);
+        assert!(graph.cycles().is_empty());
+    }
+}
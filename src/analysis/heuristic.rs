@@ -0,0 +1,283 @@
+use regex::Regex;
+
+use crate::types::analysis::{HeuristicAnalysis, HeuristicDefinition};
+
+/// Langages disposant d'un analyseur dédié
+const DEDICATED_LANGUAGES: [&str; 1] = ["rs"];
+
+/// Indique si un analyseur dédié existe pour cette extension
+pub fn has_language_analyzer(extension: &str) -> bool {
+    DEDICATED_LANGUAGES.contains(&extension)
+}
+
+/// Analyseur de dernier recours pour les langages non supportés
+///
+/// Tous les résultats sont approximatifs et marqués `heuristic: true`.
+pub struct HeuristicAnalyzer {
+    definition_pattern: Regex,
+    signature_pattern: Regex,
+    import_pattern: Regex,
+}
+
+impl Default for HeuristicAnalyzer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HeuristicAnalyzer {
+    pub fn new() -> Self {
+        Self {
+            definition_pattern: Regex::new(
+                r"^\s*(?:(?:pub|export|public|private|protected|static|async|inline|extern)\s+)*(def|defp|defmacro|defmodule|defstruct|fn|func|function|fun|let|val|class|struct|enum|union|type|data|newtype|module|interface|trait|protocol)\s+([A-Za-z_][\w.']*)"
+            ).unwrap(),
+            signature_pattern: Regex::new(r"^([a-z_][\w']*)\s*::").unwrap(),
+            import_pattern: Regex::new(
+                r#"^\s*(?:import|use|require|alias|include|open|from\s+\S+\s+import)\b|@import\("#
+            ).unwrap(),
+        }
+    }
+
+    /// Analyse approximative d'un fichier source
+    pub fn analyze(&self, content: &str, language: &str) -> HeuristicAnalysis {
+        let mut analysis = HeuristicAnalysis {
+            heuristic: true,
+            language: language.to_string(),
+            definitions: Vec::new(),
+            imports: Vec::new(),
+            code_lines: 0,
+            comment_lines: 0,
+            blank_lines: 0,
+            max_nesting: 0,
+        };
+
+        let mut in_block_comment = false;
+        let mut indent_unit = 0usize;
+        let mut seen_signatures = Vec::new();
+
+        for (index, raw_line) in content.lines().enumerate() {
+            let line = raw_line.trim();
+
+            if line.is_empty() {
+                analysis.blank_lines += 1;
+                continue;
+            }
+
+            if in_block_comment {
+                analysis.comment_lines += 1;
+                if ["*/", "-}", "*)"].iter().any(|end| line.contains(end)) {
+                    in_block_comment = false;
+                }
+                continue;
+            }
+            if ["/*", "{-", "(*"].iter().any(|start| line.starts_with(start)) {
+                analysis.comment_lines += 1;
+                in_block_comment = !["*/", "-}", "*)"].iter().any(|end| line.ends_with(end));
+                continue;
+            }
+            if ["//", "#", "--", ";"].iter().any(|marker| line.starts_with(marker))
+                && !line.starts_with("#[")
+                && !line.starts_with("#include")
+                || is_percent_comment(line)
+            {
+                analysis.comment_lines += 1;
+                continue;
+            }
+
+            analysis.code_lines += 1;
+
+            // Comptage des blocs par indentation
+            let indent = raw_line
+                .chars()
+                .take_while(|c| c.is_whitespace())
+                .map(|c| if c == '\t' { 4 } else { 1 })
+                .sum::<usize>();
+            if indent > 0 && (indent_unit == 0 || indent < indent_unit) {
+                indent_unit = indent;
+            }
+            if let Some(depth) = indent.checked_div(indent_unit) {
+                analysis.max_nesting = analysis.max_nesting.max(depth as i32);
+            }
+
+            if self.import_pattern.is_match(raw_line) {
+                analysis.imports.push(line.to_string());
+            }
+
+            let line_number = index as i32 + 1;
+            if let Some(captures) = self.definition_pattern.captures(raw_line) {
+                analysis.definitions.push(HeuristicDefinition {
+                    name: captures[2].to_string(),
+                    kind: definition_kind(&captures[1]).to_string(),
+                    line: line_number,
+                });
+            } else if let Some(captures) = self.signature_pattern.captures(raw_line) {
+                let name = captures[1].to_string();
+                if !seen_signatures.contains(&name) {
+                    seen_signatures.push(name.clone());
+                    analysis.definitions.push(HeuristicDefinition {
+                        name,
+                        kind: "function".to_string(),
+                        line: line_number,
+                    });
+                }
+            }
+        }
+
+        analysis
+    }
+
+    /// Résumé textuel ajouté au résumé du fichier
+    pub fn summarize(&self, analysis: &HeuristicAnalysis) -> String {
+        let mut summary = format!(
+            "Heuristic analysis ({}): {} code, {} comment, {} blank lines\n",
+            analysis.language, analysis.code_lines, analysis.comment_lines, analysis.blank_lines
+        );
+        for definition in &analysis.definitions {
+            summary.push_str(&format!(
+                "Probable {} (heuristic): {} (line {})\n",
+                definition.kind, definition.name, definition.line
+            ));
+        }
+        for import in &analysis.imports {
+            summary.push_str(&format!("Probable import (heuristic): {}\n", import));
+        }
+        summary
+    }
+}
+
+/// Commentaire `%` (Erlang, Prolog, LaTeX), à distinguer des littéraux Elixir
+/// `%{...}` et `%Struct{...}`
+fn is_percent_comment(line: &str) -> bool {
+    line.strip_prefix('%')
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['%', ' ', '\t']))
+}
+
+fn definition_kind(keyword: &str) -> &'static str {
+    match keyword {
+        "class" | "struct" | "enum" | "union" | "type" | "data" | "newtype"
+        | "interface" | "trait" | "protocol" | "defstruct" => "type",
+        "module" | "defmodule" => "module",
+        _ => "function",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ELIXIR: &str = r#"# Gestion des comptes
+defmodule Bank.Account do
+  alias Bank.Ledger
+  import Ecto.Query
+
+  defstruct [:id, :balance]
+
+  @doc "Crée un compte"
+  def open(id) do
+    %__MODULE__{id: id, balance: 0}
+  end
+
+  def deposit(account, amount) when amount > 0 do
+    if amount > 1000 do
+      Ledger.flag(account)
+    end
+    %{account | balance: account.balance + amount}
+  end
+
+  defp audit(account), do: Ledger.log(account)
+end
+"#;
+
+    const HASKELL: &str = r#"{- Arbre binaire
+   de recherche -}
+module Data.Tree (Tree(..), insert, member) where
+
+import qualified Data.List as List
+import Data.Maybe (fromMaybe)
+
+data Tree a = Leaf | Node (Tree a) a (Tree a)
+
+newtype Size = Size Int
+
+-- Insère une valeur
+insert :: Ord a => a -> Tree a -> Tree a
+insert x Leaf = Node Leaf x Leaf
+insert x node@(Node l v r)
+  | x < v = Node (insert x l) v r
+  | otherwise = node
+
+member :: Ord a => a -> Tree a -> Bool
+member _ Leaf = False
+member x (Node l v r) = x == v || member x l || member x r
+"#;
+
+    fn definition<'a>(analysis: &'a HeuristicAnalysis, name: &str) -> &'a HeuristicDefinition {
+        analysis
+            .definitions
+            .iter()
+            .find(|definition| definition.name == name)
+            .unwrap_or_else(|| panic!("{} not found in {:?}", name, analysis.definitions))
+    }
+
+    #[test]
+    fn dedicated_languages_skip_the_heuristic_analyzer() {
+        assert!(has_language_analyzer("rs"));
+        assert!(!has_language_analyzer("ex"));
+        assert!(!has_language_analyzer("hs"));
+    }
+
+    #[test]
+    fn elixir_fixture_yields_flagged_definitions_and_imports() {
+        let analysis = HeuristicAnalyzer::new().analyze(ELIXIR, "ex");
+
+        assert!(analysis.heuristic);
+        assert_eq!(analysis.language, "ex");
+        assert_eq!(definition(&analysis, "Bank.Account").kind, "module");
+        assert_eq!(definition(&analysis, "Bank.Account").line, 2);
+        for name in ["open", "deposit", "audit"] {
+            assert_eq!(definition(&analysis, name).kind, "function");
+        }
+        assert_eq!(analysis.imports, vec!["alias Bank.Ledger", "import Ecto.Query"]);
+        assert_eq!(analysis.comment_lines, 1);
+        assert_eq!(analysis.blank_lines, 4);
+        assert_eq!(analysis.code_lines, 16);
+        assert_eq!(analysis.max_nesting, 3);
+    }
+
+    #[test]
+    fn haskell_fixture_yields_flagged_definitions_and_imports() {
+        let analysis = HeuristicAnalyzer::new().analyze(HASKELL, "hs");
+
+        assert!(analysis.heuristic);
+        assert_eq!(definition(&analysis, "Data.Tree").kind, "module");
+        assert_eq!(definition(&analysis, "Tree").kind, "type");
+        assert_eq!(definition(&analysis, "Size").kind, "type");
+        // Une seule définition par signature de type, à la ligne de la signature
+        assert_eq!(definition(&analysis, "insert").line, 13);
+        assert_eq!(analysis.definitions.iter().filter(|definition| definition.name == "insert").count(), 1);
+        assert_eq!(definition(&analysis, "member").kind, "function");
+        assert_eq!(analysis.imports, vec!["import qualified Data.List as List", "import Data.Maybe (fromMaybe)"]);
+        // Commentaire de bloc sur deux lignes et commentaire de ligne
+        assert_eq!(analysis.comment_lines, 3);
+    }
+
+    #[test]
+    fn percent_starts_a_comment_but_not_an_elixir_literal() {
+        assert!(is_percent_comment("% Erlang comment"));
+        assert!(is_percent_comment("%% section"));
+        assert!(is_percent_comment("%"));
+        assert!(!is_percent_comment("%{account | balance: 0}"));
+        assert!(!is_percent_comment("%__MODULE__{id: id}"));
+    }
+
+    #[test]
+    fn summary_marks_every_result_as_heuristic() {
+        let analyzer = HeuristicAnalyzer::new();
+        let summary = analyzer.summarize(&analyzer.analyze(HASKELL, "hs"));
+
+        assert!(summary.starts_with("Heuristic analysis (hs): "));
+        assert!(summary.contains("Probable type (heuristic): Tree (line 8)\n"));
+        assert!(summary.contains("Probable import (heuristic): import Data.Maybe (fromMaybe)\n"));
+        assert!(summary.lines().skip(1).all(|line| line.contains("(heuristic)")));
+    }
+}
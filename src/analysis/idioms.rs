@@ -0,0 +1,243 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use regex::Regex;
+
+use crate::analysis::file::braces;
+use crate::types::analysis::{ImportantPattern, MethodSignature, TypeKind, TypeRelations};
+
+/// Idiomes Rust reconnus dans le projet : constructeurs (builder, `Default` + `new()`),
+/// newtypes, typestate, types d'erreur et objets trait servant de greffons.
+///
+/// Les formes des types (champs, structures unitaires, paramètres des blocs `impl`)
+/// sont relevées fichier par fichier ; les relations de types et les signatures
+/// complètent la détection en fin d'analyse.
+#[derive(Debug)]
+pub struct IdiomCollector {
+    /// Structures tuple à un champ et type enveloppé
+    newtypes: BTreeMap<String, String>,
+    /// Structures sans champ (`struct Open;`)
+    unit_structs: BTreeSet<String>,
+    /// Blocs `impl Type<Marker>` : type et paramètre
+    generic_impls: BTreeSet<(String, String)>,
+    /// Champs `Box<dyn Trait>` (ou `Arc`, `Rc`) : trait et structure qui le détient
+    trait_objects: BTreeSet<(String, String)>,
+    newtype: Regex,
+    unit_struct: Regex,
+    struct_start: Regex,
+    generic_impl: Regex,
+    trait_object: Regex,
+}
+
+impl Default for IdiomCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IdiomCollector {
+    pub fn new() -> Self {
+        let visibility = r"^(?:pub(?:\([^)]*\))?\s+)?";
+        Self {
+            newtypes: BTreeMap::new(),
+            unit_structs: BTreeSet::new(),
+            generic_impls: BTreeSet::new(),
+            trait_objects: BTreeSet::new(),
+            newtype: Regex::new(&format!(
+                r"{}struct\s+([A-Z]\w*)(?:<[^>]*>)?\s*\(\s*(?:pub(?:\([^)]*\))?\s+)?([^()]+?)\s*\)\s*;",
+                visibility
+            ))
+            .unwrap(),
+            unit_struct: Regex::new(&format!(r"{}struct\s+([A-Z]\w*)\s*;", visibility)).unwrap(),
+            struct_start: Regex::new(&format!(r"{}struct\s+([A-Z]\w*)[^;(]*\{{", visibility)).unwrap(),
+            generic_impl: Regex::new(r"^impl(?:<[^>]*>)?\s+([A-Z]\w*)<([A-Z]\w*)>\s*(?:\{|where\b|$)").unwrap(),
+            trait_object: Regex::new(r"\b(?:Box|Arc|Rc)<\s*dyn\s+(?:[a-z_]\w*::)*([A-Z]\w*)").unwrap(),
+        }
+    }
+
+    /// Relève les formes de types d'un fichier Rust
+    pub fn observe(&mut self, content: &str) {
+        // Structure dont les champs sont en cours de lecture, et profondeur de son bloc
+        let mut current: Option<(String, usize)> = None;
+        let mut depth = 0usize;
+
+        for line in content.lines() {
+            let line = line.split("//").next().unwrap_or("").trim();
+
+            if let Some(captures) = self.newtype.captures(line) {
+                if !has_top_level_comma(&captures[2]) {
+                    self.newtypes.insert(captures[1].to_string(), captures[2].to_string());
+                }
+            } else if let Some(captures) = self.unit_struct.captures(line) {
+                self.unit_structs.insert(captures[1].to_string());
+            } else if let Some(captures) = self.generic_impl.captures(line) {
+                self.generic_impls.insert((captures[1].to_string(), captures[2].to_string()));
+            } else if let Some(captures) = self.struct_start.captures(line) {
+                current = Some((captures[1].to_string(), depth + 1));
+            }
+
+            if let Some((holder, _)) = &current {
+                for captures in self.trait_object.captures_iter(line) {
+                    self.trait_objects.insert((captures[1].to_string(), holder.clone()));
+                }
+            }
+
+            for brace in braces(line) {
+                if brace == '{' {
+                    depth += 1;
+                } else {
+                    depth = depth.saturating_sub(1);
+                }
+            }
+            if current.as_ref().is_some_and(|(_, opened)| depth < *opened) {
+                current = None;
+            }
+        }
+    }
+
+    /// Idiomes du projet, un par forme reconnue, dans un ordre fixe
+    pub fn patterns(&self, relations: &[TypeRelations], signatures: &[MethodSignature]) -> Vec<ImportantPattern> {
+        let declared: HashMap<&str, &TypeRelations> =
+            relations.iter().map(|relation| (&*relation.type_name, relation)).collect();
+        // Le type `owner` a une méthode `name` satisfaisant `accept`
+        let has_method = |owner: &str, name: &str, accept: &dyn Fn(&MethodSignature) -> bool| {
+            signatures
+                .iter()
+                .any(|signature| signature.owner.as_deref() == Some(owner) && &*signature.name == name && accept(signature))
+        };
+        let implements = |relation: &TypeRelations, trait_name: &str| {
+            relation.kind != TypeKind::Trait && relation.implemented_traits.iter().any(|name| &**name == trait_name)
+        };
+
+        let mut patterns = Vec::new();
+
+        // Builder : `FooBuilder` dont `build()` rend un `Foo`
+        let builders: Vec<(&str, &str)> = relations
+            .iter()
+            .filter_map(|relation| {
+                let target = relation.type_name.strip_suffix("Builder").filter(|target| !target.is_empty())?;
+                has_method(&relation.type_name, "build", &|signature| mentions(&signature.return_type, target))
+                    .then_some((&*relation.type_name, target))
+            })
+            .collect();
+        push_pattern(
+            &mut patterns,
+            "Builder",
+            "builders whose `build()` returns the configured type",
+            builders.iter().map(|(builder, target)| (format!("`{}` → `{}`", builder, target), vec![*builder, *target])),
+        );
+
+        // `Default` et `new()` sans argument
+        let constructors = relations.iter().filter(|relation| {
+            implements(relation, "Default") && has_method(&relation.type_name, "new", &|signature| signature.params.is_empty())
+        });
+        push_pattern(
+            &mut patterns,
+            "Default + new()",
+            "types constructible both through `Default` and an argument-less `new()`",
+            constructors.map(|relation| (format!("`{}`", relation.type_name), vec![&*relation.type_name])),
+        );
+
+        // Newtypes : structures tuple à un seul champ
+        push_pattern(
+            &mut patterns,
+            "Newtype",
+            "single-field tuple structs wrapping another type",
+            self.newtypes
+                .iter()
+                .filter(|(name, _)| declared.contains_key(name.as_str()))
+                .map(|(name, wrapped)| (format!("`{}({})`", name, wrapped), vec![name.as_str()])),
+        );
+
+        // Typestate : un type générique dont les blocs `impl` dépendent d'un marqueur unitaire
+        let mut states: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (base, marker) in &self.generic_impls {
+            if self.unit_structs.contains(marker) {
+                states.entry(base.as_str()).or_default().push(marker.as_str());
+            }
+        }
+        push_pattern(
+            &mut patterns,
+            "Typestate",
+            "generic types whose methods depend on a zero-sized state marker",
+            states.iter().filter(|(_, markers)| markers.len() > 1).map(|(base, markers)| {
+                let listed: Vec<String> = markers.iter().map(|marker| format!("`{}`", marker)).collect();
+                let types = std::iter::once(*base).chain(markers.iter().copied()).collect();
+                (format!("`{}` ({})", base, listed.join(", ")), types)
+            }),
+        );
+
+        // Types d'erreur : `impl std::error::Error for ..`
+        push_pattern(
+            &mut patterns,
+            "Error type",
+            "types implementing `std::error::Error`",
+            relations
+                .iter()
+                .filter(|relation| implements(relation, "Error"))
+                .map(|relation| (format!("`{}`", relation.type_name), vec![&*relation.type_name])),
+        );
+
+        // Greffons : champs `Box<dyn Trait>` sur un trait du projet
+        let mut plugins: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for (trait_name, holder) in &self.trait_objects {
+            if declared.get(trait_name.as_str()).is_some_and(|relation| relation.kind == TypeKind::Trait) {
+                plugins.entry(trait_name.as_str()).or_default().push(holder.as_str());
+            }
+        }
+        push_pattern(
+            &mut patterns,
+            "Trait-object plugin",
+            "project traits held as `Box<dyn Trait>` fields, so implementations are swappable",
+            plugins.iter().map(|(trait_name, holders)| {
+                let listed: Vec<String> = holders.iter().map(|holder| format!("`{}`", holder)).collect();
+                let types = std::iter::once(*trait_name).chain(holders.iter().copied()).collect();
+                (format!("`dyn {}` held by {}", trait_name, listed.join(", ")), types)
+            }),
+        );
+
+        patterns
+    }
+}
+
+/// Ajoute un idiome s'il a au moins une occurrence : description suivie des
+/// occurrences, types impliqués sans doublon
+fn push_pattern<'a>(
+    patterns: &mut Vec<ImportantPattern>,
+    name: &str,
+    summary: &str,
+    occurrences: impl Iterator<Item = (String, Vec<&'a str>)>,
+) {
+    let mut described = Vec::new();
+    let mut types = BTreeSet::new();
+    for (occurrence, involved) in occurrences {
+        described.push(occurrence);
+        types.extend(involved.into_iter().map(str::to_string));
+    }
+    if described.is_empty() {
+        return;
+    }
+    patterns.push(ImportantPattern {
+        name: name.to_string(),
+        description: format!("{}: {}", summary, described.join(", ")),
+        types: types.into_iter().collect(),
+    });
+}
+
+/// Indique si un type (`Result<Foo, Error>`) mentionne `name` comme identifiant entier
+fn mentions(text: &str, name: &str) -> bool {
+    text.split(|c: char| !(c.is_alphanumeric() || c == '_')).any(|word| word == name || word == "Self")
+}
+
+/// Virgule hors chevrons (`(u8, u8)` a deux champs, `HashMap<K, V>` un seul)
+fn has_top_level_comma(fields: &str) -> bool {
+    let mut depth = 0i32;
+    fields.chars().any(|c| {
+        match c {
+            '<' | '[' => depth += 1,
+            '>' | ']' => depth -= 1,
+            ',' => return depth == 0,
+            _ => {},
+        }
+        false
+    })
+}
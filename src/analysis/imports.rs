@@ -0,0 +1,276 @@
+use std::collections::BTreeSet;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::masking::mask_code;
+use crate::types::analysis::{Import, ImportKind};
+
+/// Crates livrées avec le compilateur, jamais déclarées dans un `Cargo.toml`
+const STANDARD_CRATES: [&str; 5] = ["std", "core", "alloc", "proc_macro", "test"];
+
+/// Imports d'un fichier Rust et crates qu'il désigne par un chemin qualifié
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ImportScan {
+    pub imports: Vec<Import>,
+    /// Premiers segments des chemins qualifiés hors `use` (`serde_json::to_string`,
+    /// `#[tokio::main]`) : une crate peut servir sans jamais être importée
+    pub path_roots: BTreeSet<String>,
+}
+
+/// Crates désignées par les fichiers Rust du projet, et paquets que le projet déclare
+/// lui-même (un binaire importe la bibliothèque de son paquet comme une crate tierce)
+#[derive(Debug, Default)]
+pub struct CrateUsage {
+    referenced: BTreeSet<String>,
+    packages: BTreeSet<String>,
+}
+
+impl CrateUsage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Relève les crates tierces importées ou désignées par un chemin dans un fichier
+    pub fn observe(&mut self, scan: &ImportScan) {
+        let imported = scan.imports.iter().filter(|import| import.kind == ImportKind::External);
+        self.referenced.extend(imported.map(|import| import.crate_name().to_string()));
+        self.referenced.extend(scan.path_roots.iter().cloned());
+    }
+
+    /// Relève le paquet déclaré par un `Cargo.toml` (`[package] name`)
+    pub fn observe_package(&mut self, name: &str) {
+        self.packages.insert(crate_ident(name));
+    }
+
+    /// Crate d'un paquet du projet
+    pub fn is_local(&self, name: &str) -> bool {
+        self.packages.contains(&crate_ident(name))
+    }
+
+    /// La dépendance est importée ou désignée par un chemin quelque part
+    pub fn is_referenced(&self, dependency: &str) -> bool {
+        self.referenced.contains(&crate_ident(dependency))
+    }
+}
+
+/// Nom d'une crate dans le code (`async-recursion` s'écrit `async_recursion`)
+fn crate_ident(name: &str) -> String {
+    name.replace('-', "_")
+}
+
+/// Relève les `use` (groupés, renommés, globaux) et `extern crate` d'un fichier Rust,
+/// hors commentaires et chaînes, et classe chaque import : module du crate, bibliothèque
+/// standard ou crate externe.
+///
+/// Un premier segment qui nomme un module déclaré dans le fichier (`mod x;`) ou un type
+/// (`use Kind::*`) est interne.
+pub fn scan_imports(content: &str) -> ImportScan {
+    let code = mask_code(content);
+    let statement = Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?use\s+([^;]+);").unwrap();
+    let extern_crate = Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?extern\s+crate\s+(\w+)(?:\s+as\s+(\w+))?\s*;").unwrap();
+    let module = Regex::new(r"(?m)^\s*(?:pub(?:\([^)]*\))?\s+)?mod\s+(\w+)").unwrap();
+    let path_root = Regex::new(r"(?:^|[^\w:])([a-z_][a-z0-9_]*)::").unwrap();
+
+    let local_modules: BTreeSet<&str> =
+        module.captures_iter(&code).filter_map(|captures| captures.get(1)).map(|name| name.as_str()).collect();
+    let mut scan = ImportScan::default();
+
+    for captures in extern_crate.captures_iter(&code) {
+        let name = &captures[1];
+        scan.imports.push(Import {
+            path: name.to_string(),
+            alias: captures.get(2).map(|alias| alias.as_str().to_string()),
+            glob: false,
+            kind: import_kind(name, &local_modules),
+        });
+    }
+
+    for captures in statement.captures_iter(&code) {
+        let tree = captures[1].split_whitespace().collect::<Vec<_>>().join(" ");
+        for (segments, alias) in expand_use_tree(&tree) {
+            let glob = segments.last().is_some_and(|last| last == "*");
+            let segments = &segments[..segments.len() - usize::from(glob)];
+            let Some(first) = segments.first() else { continue };
+            scan.imports.push(Import {
+                path: segments.join("::"),
+                alias,
+                glob,
+                kind: import_kind(first, &local_modules),
+            });
+        }
+    }
+
+    // Les `use` sont retirés pour ne garder que les chemins écrits dans le code
+    let code = statement.replace_all(&code, "");
+    scan.path_roots = path_root
+        .captures_iter(&code)
+        .map(|captures| captures[1].to_string())
+        .filter(|root| !matches!(root.as_str(), "crate" | "self" | "super") && !local_modules.contains(root.as_str()))
+        .collect();
+    scan
+}
+
+/// Chemins désignés par un arbre `use`, chacun avec son renommage éventuel
+/// (`a::{b as c, d::{self, *}}` → `a::b` renommé `c`, `a::d`, `a::d::*`).
+///
+/// Le `::` initial des chemins absolus (`::serde::Serialize`) est retiré.
+pub(crate) fn expand_use_tree(tree: &str) -> Vec<(Vec<String>, Option<String>)> {
+    let tree = tree.trim().trim_start_matches("::");
+    let (Some(open), Some(close)) = (tree.find('{'), tree.rfind('}')) else {
+        let (path, alias) = match tree.split_once(" as ") {
+            Some((path, alias)) => (path, Some(alias.trim().to_string())),
+            None => (tree, None),
+        };
+        let segments = path.split("::").map(|segment| segment.trim().to_string()).collect();
+        return vec![(segments, alias)];
+    };
+    let prefix: Vec<String> = tree[..open]
+        .trim()
+        .trim_end_matches("::")
+        .split("::")
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| segment.trim().to_string())
+        .collect();
+
+    let inner = &tree[open + 1..close];
+    let mut parts = Vec::new();
+    let (mut depth, mut start) = (0, 0);
+    for (position, c) in inner.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(&inner[start..position]);
+                start = position + 1;
+            },
+            _ => {},
+        }
+    }
+    parts.push(&inner[start..]);
+
+    parts
+        .into_iter()
+        .filter(|part| !part.trim().is_empty())
+        .flat_map(expand_use_tree)
+        .map(|(path, alias)| {
+            // `self` dans des accolades désigne le préfixe lui-même
+            let path: Vec<String> = path.into_iter().filter(|segment| segment != "self").collect();
+            (prefix.iter().cloned().chain(path).collect(), alias)
+        })
+        .collect()
+}
+
+/// Nature d'un import d'après son premier segment
+fn import_kind(first: &str, local_modules: &BTreeSet<&str>) -> ImportKind {
+    if matches!(first, "crate" | "self" | "super")
+        || local_modules.contains(first)
+        || first.starts_with(|c: char| c.is_ascii_uppercase())
+    {
+        ImportKind::Internal
+    } else if STANDARD_CRATES.contains(&first) {
+        ImportKind::Std
+    } else {
+        ImportKind::External
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Imports d'un fichier sous la forme (chemin, renommage, global, nature)
+    fn imports(content: &str) -> Vec<(String, Option<String>, bool, ImportKind)> {
+        scan_imports(content)
+            .imports
+            .into_iter()
+            .map(|import| (import.path, import.alias, import.glob, import.kind))
+            .collect()
+    }
+
+    fn plain(path: &str, kind: ImportKind) -> (String, Option<String>, bool, ImportKind) {
+        (path.to_string(), None, false, kind)
+    }
+
+    #[test]
+    fn nested_groups_expand_with_self() {
+        let found = imports("use std::{fmt, io::{self, Read, Write as _}};\nuse serde::{\n    de::{self, Visitor},\n    Serialize,\n};\n");
+        assert_eq!(found, [
+            plain("std::fmt", ImportKind::Std),
+            plain("std::io", ImportKind::Std),
+            plain("std::io::Read", ImportKind::Std),
+            ("std::io::Write".to_string(), Some("_".to_string()), false, ImportKind::Std),
+            plain("serde::de", ImportKind::External),
+            plain("serde::de::Visitor", ImportKind::External),
+            plain("serde::Serialize", ImportKind::External),
+        ]);
+    }
+
+    #[test]
+    fn aliases_globs_and_re_exports() {
+        let found = imports(
+            "pub use crate::types::analysis::Import as ParsedImport;\n\
+            pub(crate) use super::masking::*;\n\
+            use anyhow::{Context as _, Result as AnyResult};\n\
+            use tokio::prelude::*;\n\
+            extern crate alloc as heap;\n",
+        );
+        assert_eq!(found, [
+            ("alloc".to_string(), Some("heap".to_string()), false, ImportKind::Std),
+            ("crate::types::analysis::Import".to_string(), Some("ParsedImport".to_string()), false, ImportKind::Internal),
+            ("super::masking".to_string(), None, true, ImportKind::Internal),
+            ("anyhow::Context".to_string(), Some("_".to_string()), false, ImportKind::External),
+            ("anyhow::Result".to_string(), Some("AnyResult".to_string()), false, ImportKind::External),
+            ("tokio::prelude".to_string(), None, true, ImportKind::External),
+        ]);
+    }
+
+    #[test]
+    fn internal_paths_are_classified_by_their_first_segment() {
+        let found = imports(
+            "mod parser;\n\
+            use crate::error::GithubAnalyzerError;\n\
+            use self::parser::Parser;\n\
+            use super::super::types::Kind;\n\
+            use parser::Token;\n\
+            use Kind::*;\n\
+            use ::regex::Regex;\n\
+            use core::mem;\n",
+        );
+        assert_eq!(found, [
+            plain("crate::error::GithubAnalyzerError", ImportKind::Internal),
+            plain("self::parser::Parser", ImportKind::Internal),
+            plain("super::super::types::Kind", ImportKind::Internal),
+            plain("parser::Token", ImportKind::Internal),
+            ("Kind".to_string(), None, true, ImportKind::Internal),
+            plain("regex::Regex", ImportKind::External),
+            plain("core::mem", ImportKind::Std),
+        ]);
+        let scan = scan_imports("use crate::a::B;\nuse regex::Regex;\n");
+        assert_eq!(scan.imports[1].crate_name(), "regex");
+    }
+
+    #[test]
+    fn comments_and_strings_are_not_imports() {
+        let found = imports("// use hidden::Thing;\n/* use other::Thing; */\nconst DOC: &str = \"use fake::Thing;\";\nuse real::Thing;\n");
+        assert_eq!(found, [plain("real::Thing", ImportKind::External)]);
+    }
+
+    #[test]
+    fn path_roots_exclude_imports_and_local_modules() {
+        let scan = scan_imports(
+            "mod helpers;\nuse serde::Serialize;\n\n#[tokio::main]\nasync fn main() {\n    \
+            let json = serde_json::to_string(&1).unwrap();\n    helpers::run(crate::VERSION, self::x(), super::y());\n}\n",
+        );
+        let roots: Vec<&str> = scan.path_roots.iter().map(String::as_str).collect();
+        assert_eq!(roots, ["serde_json", "tokio"]);
+
+        let mut usage = CrateUsage::new();
+        usage.observe(&scan);
+        usage.observe_package("my-tool");
+        assert!(usage.is_referenced("serde"));
+        assert!(usage.is_referenced("serde-json"));
+        assert!(!usage.is_referenced("anyhow"));
+        assert!(usage.is_local("my_tool"));
+    }
+}
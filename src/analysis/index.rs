@@ -0,0 +1,178 @@
+use regex::Regex;
+use std::path::Path;
+
+use crate::error::GithubAnalyzerError;
+use crate::types::analysis::{ProjectSummary, TypeLocation};
+
+/// Repère les déclarations de types d'un fichier Rust avec leur emplacement
+pub struct TypeLocator {
+    declaration: Regex,
+}
+
+impl Default for TypeLocator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TypeLocator {
+    pub fn new() -> Self {
+        Self {
+            declaration: Regex::new(
+                r"^\s*(?:pub(?:\([^)]+\))?\s+)?(struct|enum|trait|type|union)\s+([A-Za-z_][A-Za-z0-9_]*)"
+            ).unwrap(),
+        }
+    }
+
+    /// Liste les types déclarés dans `content` sous la forme (nom, emplacement)
+    pub fn locate(&self, content: &str, file_path: &str) -> Vec<(String, TypeLocation)> {
+        let module_path = module_path_for(file_path);
+
+        content
+            .lines()
+            .enumerate()
+            .filter_map(|(index, line)| {
+                let captures = self.declaration.captures(line)?;
+                // Les types associés (`type Err = ...;` dans un impl) ne sont pas des définitions
+                if &captures[1] == "type" && line.starts_with(char::is_whitespace) && !line.trim_start().starts_with("pub") {
+                    return None;
+                }
+                Some((
+                    captures[2].to_string(),
+                    TypeLocation {
+                        file: file_path.to_string(),
+                        line: index as i32 + 1,
+                        module_path: module_path.clone(),
+                        kind: captures[1].to_string(),
+                    },
+                ))
+            })
+            .collect()
+    }
+}
+
+/// Déduit le chemin de module Rust à partir du chemin du fichier
+///
+/// `src/analysis/file.rs` → `crate::analysis::file`, `src/lib.rs` → `crate`.
+pub fn module_path_for(file_path: &str) -> String {
+    let relative = file_path
+        .split_once("src/")
+        .map(|(_, rest)| rest)
+        .unwrap_or(file_path)
+        .trim_end_matches(".rs");
+
+    let mut segments: Vec<&str> = relative.split('/').filter(|s| !s.is_empty()).collect();
+    if matches!(segments.last(), Some(&"mod") | Some(&"lib") | Some(&"main")) {
+        segments.pop();
+    }
+
+    std::iter::once("crate")
+        .chain(segments)
+        .collect::<Vec<_>>()
+        .join("::")
+}
+
+/// Accès en lecture aux résultats d'une analyse
+pub struct AnalysisIndex {
+    summary: ProjectSummary,
+}
+
+impl AnalysisIndex {
+    pub fn new(summary: ProjectSummary) -> Self {
+        Self { summary }
+    }
+
+    /// Charge un fichier `analysis.json` produit par l'export
+    pub fn load(path: &Path) -> Result<Self, GithubAnalyzerError> {
+        Ok(Self::new(crate::schema::load(path)?))
+    }
+
+    pub fn summary(&self) -> &ProjectSummary {
+        &self.summary
+    }
+
+    /// Retourne tous les emplacements où un type de ce nom est défini
+    ///
+    /// Accepte un nom court (`Config`) ou qualifié (`crate::config::Config`).
+    pub fn find_type(&self, name: &str) -> Vec<&TypeLocation> {
+        let (module, short_name) = match name.rsplit_once("::") {
+            Some((module, short)) => (Some(module), short),
+            None => (None, name),
+        };
+
+        self.summary
+            .type_index
+            .get(short_name)
+            .map(|locations| {
+                locations
+                    .iter()
+                    .filter(|location| module.is_none_or(|m| location.module_path == m))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::repository::RepositoryAnalyzer;
+
+    /// Index construit comme pendant l'analyse, à partir de fichiers (chemin, contenu)
+    fn index(files: &[(&str, &str)]) -> AnalysisIndex {
+        let locator = TypeLocator::new();
+        let mut summary = RepositoryAnalyzer::empty_summary("https://github.com/owner/repo", "main");
+        for (path, content) in files {
+            for (type_name, location) in locator.locate(content, path) {
+                summary.type_index.entry(type_name).or_default().push(location);
+            }
+        }
+        AnalysisIndex::new(summary)
+    }
+
+    fn found(index: &AnalysisIndex, name: &str) -> Vec<(String, i32, String)> {
+        index
+            .find_type(name)
+            .into_iter()
+            .map(|location| (location.file.clone(), location.line, location.kind.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn find_type_lists_every_definition_of_a_duplicate_name() {
+        let index = index(&[
+            ("src/config.rs", "use std::fmt;\n\npub struct Config {\n    pub verbose: bool,\n}\n"),
+            ("src/export/config.rs", "/// Export settings\npub(crate) enum Config {\n    Json,\n}\n"),
+            ("src/lib.rs", "pub mod config;\n"),
+        ]);
+
+        assert_eq!(found(&index, "Config"), [
+            ("src/config.rs".to_string(), 3, "struct".to_string()),
+            ("src/export/config.rs".to_string(), 2, "enum".to_string()),
+        ]);
+        assert_eq!(found(&index, "crate::export::config::Config"), [
+            ("src/export/config.rs".to_string(), 2, "enum".to_string()),
+        ]);
+        assert!(found(&index, "crate::other::Config").is_empty());
+        assert!(found(&index, "Missing").is_empty());
+    }
+
+    #[test]
+    fn associated_types_are_not_definitions() {
+        let index = index(&[
+            ("src/parse.rs", "pub struct Version(u32);\n\n\
+                impl std::str::FromStr for Version {\n    type Err = ParseError;\n\n    \
+                fn from_str(s: &str) -> Result<Self, Self::Err> {\n        todo!()\n    }\n}\n\n\
+                pub trait Source {\n    type Item;\n    type Error: std::error::Error;\n}\n\n\
+                pub type Err = String;\n\n\
+                mod inner {\n    pub type Item = u8;\n}\n"),
+        ]);
+
+        assert_eq!(found(&index, "Version"), [("src/parse.rs".to_string(), 1, "struct".to_string())]);
+        assert_eq!(found(&index, "Source"), [("src/parse.rs".to_string(), 11, "trait".to_string())]);
+        // Seul l'alias de premier niveau est une définition de `Err`
+        assert_eq!(found(&index, "Err"), [("src/parse.rs".to_string(), 16, "type".to_string())]);
+        assert_eq!(found(&index, "Item"), [("src/parse.rs".to_string(), 19, "type".to_string())]);
+        assert!(found(&index, "Error").is_empty());
+    }
+}
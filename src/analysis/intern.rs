@@ -0,0 +1,74 @@
+use std::collections::HashSet;
+
+use crate::types::{
+    analysis::{MethodSignature, TypeRelations},
+    SharedStr,
+};
+
+/// Table d'internement des chaînes répétées (noms de types, types de retour, chemins)
+///
+/// Chaque chaîne distincte n'est allouée qu'une seule fois pour tout le projet.
+#[derive(Debug, Default)]
+pub struct StringInterner {
+    strings: HashSet<SharedStr>,
+    /// Chaque chaîne garde sa propre allocation (mesure de référence)
+    disabled: bool,
+}
+
+impl StringInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Table qui n'interne rien, pour comparer l'occupation mémoire
+    #[cfg(test)]
+    pub(crate) fn disabled() -> Self {
+        Self { disabled: true, ..Self::default() }
+    }
+
+    /// Retourne l'instance partagée correspondant à `value`
+    pub fn intern(&mut self, value: &str) -> SharedStr {
+        if self.disabled {
+            return SharedStr::from(value);
+        }
+        if let Some(existing) = self.strings.get(value) {
+            return existing.clone();
+        }
+        let shared = SharedStr::from(value);
+        self.strings.insert(shared.clone());
+        shared
+    }
+
+    /// Remplace une chaîne par son instance partagée
+    pub fn share(&mut self, value: &mut SharedStr) {
+        if self.disabled {
+            return;
+        }
+        *value = self.intern(value);
+    }
+
+    /// Partage toutes les chaînes d'une relation de types
+    pub fn share_relation(&mut self, relation: &mut TypeRelations) {
+        self.share(&mut relation.type_name);
+        for name in relation.implemented_traits.iter_mut()
+            .chain(relation.used_by.iter_mut())
+            .chain(relation.depends_on.iter_mut())
+            .chain(relation.references.iter_mut())
+            .chain(relation.implementors.iter_mut())
+        {
+            self.share(name);
+        }
+    }
+
+    /// Partage le nom, les paramètres, les types et le type de retour d'une signature
+    pub fn share_signature(&mut self, signature: &mut MethodSignature) {
+        self.share(&mut signature.name);
+        for name in signature.params.iter_mut()
+            .chain(signature.owner.iter_mut())
+            .chain(signature.trait_impl.iter_mut())
+        {
+            self.share(name);
+        }
+        self.share(&mut signature.return_type);
+    }
+}
@@ -0,0 +1,56 @@
+use regex::Regex;
+
+/// Caractères lus en tête d'un fichier de licence pour l'identifier
+const HEADER_LENGTH: usize = 2000;
+
+/// Empreintes des licences courantes : identifiant SPDX et extraits (en minuscules,
+/// espaces normalisés) qui doivent tous figurer dans l'en-tête. Les variantes les plus
+/// spécifiques passent en premier : le texte de la LGPL cite aussi la GPL.
+const FINGERPRINTS: &[(&str, &[&str])] = &[
+    ("AGPL-3.0", &["gnu affero general public license", "version 3"]),
+    ("LGPL-3.0", &["gnu lesser general public license", "version 3"]),
+    ("LGPL-2.1", &["gnu lesser general public license", "version 2.1"]),
+    ("GPL-3.0", &["gnu general public license", "version 3"]),
+    ("GPL-2.0", &["gnu general public license", "version 2"]),
+    ("Apache-2.0", &["apache license", "version 2.0"]),
+    ("MPL-2.0", &["mozilla public license", "2.0"]),
+    ("BSD-3-Clause", &["redistribution and use in source and binary forms", "neither the name"]),
+    ("BSD-2-Clause", &["redistribution and use in source and binary forms"]),
+    ("MIT", &["permission is hereby granted, free of charge"]),
+    ("ISC", &["permission to use, copy, modify, and/or distribute this software for any purpose"]),
+    ("Unlicense", &["this is free and unencumbered software released into the public domain"]),
+];
+
+/// Indique si le fichier est un texte de licence (`LICENSE`, `LICENSE-MIT`, `COPYING`, ...)
+pub fn is_license_file(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path).to_ascii_uppercase();
+    name.starts_with("LICENSE") || name.starts_with("LICENCE") || name.starts_with("COPYING")
+}
+
+/// Identifiant SPDX d'un texte de licence, d'après son en-tête
+pub fn detect_license_text(content: &str) -> Option<&'static str> {
+    let header: String = content.chars().take(HEADER_LENGTH).collect::<String>().to_lowercase();
+    let header = header.split_whitespace().collect::<Vec<_>>().join(" ");
+    FINGERPRINTS
+        .iter()
+        .find(|(_, excerpts)| excerpts.iter().all(|excerpt| header.contains(excerpt)))
+        .map(|(spdx, _)| *spdx)
+}
+
+/// Licence déclarée par un manifeste (`license` de `Cargo.toml` ou `package.json`)
+pub fn manifest_license(filename: &str, content: &str) -> Option<String> {
+    let license = match filename {
+        "Cargo.toml" => Regex::new(r#"(?m)^\s*license\s*=\s*"([^"]+)""#)
+            .unwrap()
+            .captures(content)
+            .map(|captures| captures[1].to_string()),
+        "package.json" => serde_json::from_str::<serde_json::Value>(content)
+            .ok()?
+            .get("license")?
+            .as_str()
+            .map(str::to_string),
+        _ => None,
+    }?;
+    let license = license.trim().to_string();
+    (!license.is_empty()).then_some(license)
+}
@@ -0,0 +1,274 @@
+use crate::types::analysis::LineCounts;
+
+/// Syntaxe des commentaires d'un langage
+struct CommentSyntax {
+    line: &'static [&'static str],
+    block: Option<(&'static str, &'static str)>,
+    /// Les commentaires de bloc s'imbriquent (`/* /* */ */` en Rust)
+    nested: bool,
+    /// Délimiteurs de chaînes ; hors de cette liste, `'` ouvre un caractère isolé
+    quotes: &'static [char],
+}
+
+const NO_COMMENTS: CommentSyntax = CommentSyntax { line: &[], block: None, nested: false, quotes: &[] };
+const DOUBLE: &[char] = &['"'];
+const BOTH: &[char] = &['"', '\''];
+
+/// Syntaxe des commentaires selon l'extension (ou le nom) du fichier
+fn comment_syntax(extension: &str) -> CommentSyntax {
+    let c_like = |nested, quotes| CommentSyntax { line: &["//"], block: Some(("/*", "*/")), nested, quotes };
+    let hash = |quotes| CommentSyntax { line: &["#"], block: None, nested: false, quotes };
+    match extension {
+        "rs" | "swift" | "kt" | "scala" | "dart" => c_like(true, DOUBLE),
+        "c" | "h" | "cpp" | "cc" | "hpp" | "java" | "cs" | "go" | "groovy" | "gradle" | "proto" => c_like(false, DOUBLE),
+        "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "css" | "scss" | "less" => c_like(false, BOTH),
+        "php" => CommentSyntax { line: &["//", "#"], ..c_like(false, BOTH) },
+        "zig" => CommentSyntax { block: None, ..c_like(false, DOUBLE) },
+        "py" | "rb" | "sh" | "bash" | "zsh" | "pl" | "r" | "nim" | "ex" | "exs" | "tf" | "cmake" => hash(BOTH),
+        // Apostrophes libres dans les valeurs non citées
+        "yaml" | "yml" | "toml" | "Dockerfile" | "Makefile" => hash(DOUBLE),
+        "jl" => CommentSyntax { line: &["#"], block: Some(("#=", "=#")), nested: true, quotes: DOUBLE },
+        "sql" => CommentSyntax { line: &["--"], block: Some(("/*", "*/")), nested: false, quotes: BOTH },
+        "lua" => CommentSyntax { line: &["--"], block: Some(("--[[", "]]")), nested: false, quotes: BOTH },
+        "hs" => CommentSyntax { line: &["--"], block: Some(("{-", "-}")), nested: true, quotes: DOUBLE },
+        "ml" | "mli" | "fs" => CommentSyntax { line: &[], block: Some(("(*", "*)")), nested: true, quotes: DOUBLE },
+        "erl" => CommentSyntax { line: &["%"], quotes: DOUBLE, ..NO_COMMENTS },
+        "clj" => CommentSyntax { line: &[";"], quotes: DOUBLE, ..NO_COMMENTS },
+        "html" | "xml" | "md" | "svg" | "vue" => CommentSyntax { block: Some(("<!--", "-->")), ..NO_COMMENTS },
+        _ => NO_COMMENTS,
+    }
+}
+
+/// Position dans le texte pendant le décompte
+enum State {
+    Code,
+    /// Commentaire de bloc, avec sa profondeur d'imbrication
+    Block(usize),
+    /// Chaîne délimitée par ce guillemet
+    Str(char),
+    /// Chaîne brute Rust fermée par `"` suivi de ce nombre de `#`
+    RawStr(usize),
+}
+
+/// Compte les lignes d'un fichier : vides, de commentaire seul, et de code (toute
+/// ligne portant au moins un caractère hors commentaire).
+///
+/// Les chaînes sont suivies pour qu'un `/*` ou un `//` entre guillemets n'ouvre pas
+/// de commentaire, et qu'un guillemet dans un commentaire n'ouvre pas de chaîne.
+pub fn count_lines(content: &str, extension: &str) -> LineCounts {
+    let syntax = comment_syntax(extension);
+    let mut counts = LineCounts::default();
+    let mut state = State::Code;
+
+    for line in content.lines() {
+        counts.total += 1;
+        let starts_in_string = matches!(state, State::Str(_) | State::RawStr(_));
+        if line.trim().is_empty() && !starts_in_string {
+            counts.blank += 1;
+            continue;
+        }
+
+        // Une ligne qui poursuit une chaîne multiligne est du code
+        let (mut has_code, mut has_comment) = (starts_in_string, false);
+        let mut rest = line;
+        while let Some(c) = rest.chars().next() {
+            match state {
+                State::Block(depth) => {
+                    has_comment = true;
+                    let (open, close) = syntax.block.unwrap_or_default();
+                    if let Some(after) = rest.strip_prefix(close) {
+                        state = if depth > 1 { State::Block(depth - 1) } else { State::Code };
+                        rest = after;
+                        continue;
+                    }
+                    if let Some(after) = rest.strip_prefix(open).filter(|_| syntax.nested) {
+                        state = State::Block(depth + 1);
+                        rest = after;
+                        continue;
+                    }
+                },
+                State::Str(quote) => {
+                    has_code = true;
+                    if c == '\\' {
+                        rest = skip_chars(rest, 2);
+                        continue;
+                    }
+                    if c == quote {
+                        state = State::Code;
+                    }
+                },
+                State::RawStr(hashes) => {
+                    has_code = true;
+                    if c == '"' && rest[1..].chars().take(hashes).filter(|c| *c == '#').count() == hashes {
+                        state = State::Code;
+                        rest = skip_chars(rest, 1 + hashes);
+                        continue;
+                    }
+                },
+                State::Code => {
+                    if c.is_whitespace() {
+                        rest = skip_chars(rest, 1);
+                        continue;
+                    }
+                    // Le bloc est testé avant la ligne : `--[[` commence par `--` en Lua
+                    if let Some(after) = syntax.block.and_then(|(open, _)| rest.strip_prefix(open)) {
+                        has_comment = true;
+                        state = State::Block(1);
+                        rest = after;
+                        continue;
+                    }
+                    if syntax.line.iter().any(|marker| rest.starts_with(marker)) {
+                        has_comment = true;
+                        break;
+                    }
+                    has_code = true;
+                    if let Some(hashes) = raw_string_start(rest, extension) {
+                        state = State::RawStr(hashes);
+                        rest = skip_chars(rest, rest.find('"').unwrap_or(0) + 1);
+                        continue;
+                    }
+                    if syntax.quotes.contains(&c) {
+                        state = State::Str(c);
+                    } else if c == '\'' && !syntax.quotes.is_empty() {
+                        // Caractère isolé (`'"'`, `'\''`) : sauté pour ne pas ouvrir de chaîne
+                        rest = skip_char_literal(rest);
+                    }
+                },
+            }
+            rest = skip_chars(rest, 1);
+        }
+
+        if has_code {
+            counts.code += 1;
+        } else if has_comment {
+            counts.comments += 1;
+        } else {
+            counts.blank += 1;
+        }
+    }
+    counts
+}
+
+/// Nombre de `#` d'une chaîne brute Rust (`r"`, `r#"`, `br#"`) commençant ici
+fn raw_string_start(rest: &str, extension: &str) -> Option<usize> {
+    if extension != "rs" {
+        return None;
+    }
+    let after = rest.strip_prefix("br").or_else(|| rest.strip_prefix('r'))?;
+    let hashes = after.chars().take_while(|c| *c == '#').count();
+    after[hashes..].starts_with('"').then_some(hashes)
+}
+
+/// Saute un littéral caractère (`'x'`, `'\n'`) jusqu'à son dernier guillemet ; une
+/// durée de vie (`'a`) n'est sautée que de son apostrophe
+fn skip_char_literal(rest: &str) -> &str {
+    let mut chars = rest.char_indices().skip(1);
+    match (chars.next(), chars.next()) {
+        (Some((_, '\\')), _) => match rest[2..].find('\'') {
+            Some(end) => &rest[end + 2..],
+            None => rest,
+        },
+        (Some(_), Some((end, '\''))) => &rest[end..],
+        _ => rest,
+    }
+}
+
+/// Reste de la ligne après `count` caractères
+fn skip_chars(rest: &str, count: usize) -> &str {
+    match rest.char_indices().nth(count) {
+        Some((position, _)) => &rest[position..],
+        None => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(content: &str, extension: &str) -> (usize, usize, usize, usize) {
+        let counts = count_lines(content, extension);
+        assert_eq!(counts.total, counts.code + counts.comments + counts.blank);
+        (counts.total, counts.code, counts.comments, counts.blank)
+    }
+
+    #[test]
+    fn plain_rust_file_splits_code_comments_and_blanks() {
+        let content = "//! Module\n\nuse std::fmt;\n\n/// Doc\npub fn f() {} // fin\n    \n";
+        // total, code, commentaires, vides
+        assert_eq!(counts(content, "rs"), (7, 2, 2, 3));
+    }
+
+    #[test]
+    fn block_comment_quotes_do_not_open_strings() {
+        let content = r#"/* un commentaire avec un "guillemet
+   ouvert sur plusieurs lignes
+*/
+let x = 1;
+"#;
+        assert_eq!(counts(content, "rs"), (4, 1, 3, 0));
+    }
+
+    #[test]
+    fn comment_markers_inside_strings_are_code() {
+        let content = r#"let glob = "src/**/*.rs";
+let url = "https://example.com";
+let text = "début /* pas un commentaire
+suite de la chaîne */ toujours dans la chaîne
+
+fin";
+let after = 2; /* vrai commentaire */
+"#;
+        // La ligne vide au milieu de la chaîne multiligne compte comme du code
+        assert_eq!(counts(content, "rs"), (7, 7, 0, 0));
+    }
+
+    #[test]
+    fn block_comment_spanning_a_string_literal_ends_at_its_first_delimiter() {
+        let content = r#"/* exemple : let s = "*/";
+let s = "fin";
+"#;
+        // Le commentaire se ferme dans la chaîne citée ; le reste (`";`) est du code
+        assert_eq!(counts(content, "c"), (2, 2, 0, 0));
+    }
+
+    #[test]
+    fn rust_block_comments_nest() {
+        let content = "/* externe\n/* interne */\ntoujours commenté\n*/\nfn main() {}\n";
+        assert_eq!(counts(content, "rs"), (5, 1, 4, 0));
+        // En C, le premier `*/` ferme le commentaire
+        assert_eq!(counts(content, "c"), (5, 3, 2, 0));
+    }
+
+    #[test]
+    fn raw_strings_and_char_literals_do_not_confuse_the_scan() {
+        let content = r####"let raw = r#"quote " and /* not a comment"#;
+let nested = r##"fin "# pas encore
+"##;
+let quote = '"';
+let escaped = '\'';
+fn borrow<'a>(s: &'a str) -> &'a str { s } // commentaire
+// seul commentaire
+"####;
+        assert_eq!(counts(content, "rs"), (7, 6, 1, 0));
+    }
+
+    #[test]
+    fn hash_languages_ignore_hashes_inside_strings() {
+        let content = "# commentaire\ncolor = \"#fff\"  # inline\nname = 'a # b'\n\n";
+        assert_eq!(counts(content, "py"), (4, 2, 1, 1));
+        assert_eq!(counts("key = \"v\"\n# section\nit's = 1\n", "toml"), (3, 2, 1, 0));
+    }
+
+    #[test]
+    fn language_specific_block_delimiters() {
+        assert_eq!(counts("--[[ bloc\nlua ]]\nlocal x = 1 -- fin\n", "lua"), (3, 1, 2, 0));
+        assert_eq!(counts("{- bloc {- imbriqué -}\n-}\nmain = print 1\n", "hs"), (3, 1, 2, 0));
+        assert_eq!(counts("<!-- note\n-->\n<p>texte</p>\n", "html"), (3, 1, 2, 0));
+        assert_eq!(counts("(* bloc *)\nlet x = 1\n", "ml"), (2, 1, 1, 0));
+    }
+
+    #[test]
+    fn unknown_extensions_count_every_non_blank_line_as_code() {
+        assert_eq!(counts("// pas un commentaire\n# non plus\n\ntexte\n", "txt"), (4, 3, 0, 1));
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashMap;
+
+use crate::types::locator::{Provider, RepoLocator};
+
+/// Mode Git d'une entrée de lien symbolique dans la Git Trees API
+pub const SYMLINK_MODE: &str = "120000";
+
+/// Chemins et URLs des sous-modules déclarés dans un `.gitmodules`
+pub fn parse_gitmodules(content: &str) -> HashMap<String, String> {
+    let mut submodules = HashMap::new();
+    let mut path = None;
+    let mut url = None;
+    for line in content.lines().map(str::trim) {
+        if line.starts_with('[') {
+            if let (Some(path), Some(url)) = (path.take(), url.take()) {
+                submodules.insert(path, url);
+            }
+            continue;
+        }
+        match line.split_once('=').map(|(key, value)| (key.trim(), value.trim())) {
+            Some(("path", value)) => path = Some(value.trim_end_matches('/').to_string()),
+            Some(("url", value)) => url = Some(value.to_string()),
+            _ => {},
+        }
+    }
+    if let (Some(path), Some(url)) = (path, url) {
+        submodules.insert(path, url);
+    }
+    submodules
+}
+
+/// URL d'un sous-module hébergé sur le même hôte GitHub que le dépôt parent ;
+/// les URLs relatives (`../lib.git`) partent du dépôt parent
+pub fn github_submodule_url(parent: &RepoLocator, url: &str) -> Option<String> {
+    let locator = if url.starts_with("../") || url.starts_with("./") {
+        let mut segments = vec![parent.owner.as_str(), parent.repo.as_str()];
+        for component in url.split('/') {
+            match component {
+                "." | "" => {},
+                ".." => {
+                    segments.pop()?;
+                },
+                component => segments.push(component),
+            }
+        }
+        format!("https://{}/{}", parent.host, segments.join("/")).parse::<RepoLocator>().ok()?
+    } else {
+        url.parse::<RepoLocator>().ok()?
+    };
+    (locator.provider == Provider::Github && locator.host == parent.host)
+        .then(|| format!("https://{}/{}", locator.host, locator.project_path()))
+}
+
+/// Chemin, depuis la racine du dépôt, de la cible d'un lien symbolique ; `None` si
+/// elle est absolue ou sort du dépôt
+pub fn resolve_symlink(link: &str, target: &str) -> Option<String> {
+    let target = target.trim();
+    if target.is_empty() || target.starts_with('/') {
+        return None;
+    }
+    let mut components: Vec<&str> = link.split('/').collect();
+    components.pop();
+    for component in target.split('/') {
+        match component {
+            "." | "" => {},
+            ".." => {
+                components.pop()?;
+            },
+            component => components.push(component),
+        }
+    }
+    (!components.is_empty()).then(|| components.join("/"))
+}
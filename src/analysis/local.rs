@@ -0,0 +1,117 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::analysis::source::{SourceFile, SourceTree};
+use crate::error::GithubAnalyzerError;
+
+/// Répertoires ignorés par défaut : historique git, artefacts de build, dépendances
+pub const DEFAULT_SKIPPED_DIRS: &[&str] = &[".git", "target", "node_modules"];
+
+/// Répertoire local analysé sans accès réseau
+#[derive(Debug, Clone)]
+pub struct LocalTree {
+    root: PathBuf,
+    files: Vec<SourceFile>,
+    /// Base des URLs de fichiers, `file://<racine>` par défaut
+    url_base: Option<String>,
+}
+
+impl LocalTree {
+    /// Parcourt `root` en ignorant `DEFAULT_SKIPPED_DIRS` ; les liens symboliques
+    /// ne sont pas suivis
+    pub fn open(root: &Path) -> Result<Self, GithubAnalyzerError> {
+        let root = root.canonicalize().map_err(|e| {
+            GithubAnalyzerError::NotFound(format!("Cannot open {}: {}", root.display(), e))
+        })?;
+        if !root.is_dir() {
+            return Err(GithubAnalyzerError::NotFound(format!("{} is not a directory", root.display())));
+        }
+
+        let walker = WalkDir::new(&root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| {
+                entry.depth() == 0
+                    || !entry.file_type().is_dir()
+                    || !DEFAULT_SKIPPED_DIRS.iter().any(|skipped| entry.file_name() == *skipped)
+            });
+
+        let mut files = Vec::new();
+        for entry in walker {
+            let entry = match entry {
+                Ok(entry) => entry,
+                Err(e) => {
+                    println!("Warning: Skipping unreadable entry: {}", e);
+                    continue;
+                }
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let Ok(relative) = entry.path().strip_prefix(&root) else {
+                continue;
+            };
+            let path = relative.to_string_lossy().replace('\\', "/");
+            let size = entry.metadata().map(|metadata| metadata.len()).unwrap_or(0);
+            files.push(SourceFile { path, size });
+        }
+
+        Ok(Self { root, files, url_base: None })
+    }
+
+    /// Remplace la base des URLs de fichiers (dépôt distant dont la racine est une copie)
+    pub fn with_url_base(mut self, url_base: impl Into<String>) -> Self {
+        self.url_base = Some(url_base.into());
+        self
+    }
+
+    /// Chemin absolu de la racine analysée
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Nom du répertoire racine, utilisé comme libellé du projet
+    pub fn label(&self) -> String {
+        self.root
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "local".to_string())
+    }
+
+    /// URL `file://` de la racine
+    pub fn root_url(&self) -> String {
+        format!("file://{}", self.root.to_string_lossy().replace('\\', "/"))
+    }
+}
+
+impl SourceTree for LocalTree {
+    fn directories(&self) -> BTreeSet<String> {
+        let mut directories = BTreeSet::new();
+        for file in &self.files {
+            let mut path = file.path.as_str();
+            while let Some((parent, _)) = path.rsplit_once('/') {
+                directories.insert(parent.to_string());
+                path = parent;
+            }
+        }
+        directories
+    }
+
+    fn files(&self) -> Vec<SourceFile> {
+        self.files.clone()
+    }
+
+    fn read_bytes(&self, index: usize) -> Option<Vec<u8>> {
+        let file = self.files.get(index)?;
+        fs::read(self.root.join(&file.path)).ok()
+    }
+
+    fn url(&self, path: &str) -> String {
+        match &self.url_base {
+            Some(url_base) => format!("{}/{}", url_base, path),
+            None => format!("{}/{}", self.root_url(), path),
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::collections::BTreeMap;
+
+use regex::Regex;
+
+use crate::analysis::masking::mask_code;
+use crate::types::analysis::{MacroDefinition, MacroKind, TypeRelations};
+
+/// Macros définies par le projet (`macro_rules!`, fonctions `#[proc_macro]`,
+/// `#[proc_macro_derive]`, `#[proc_macro_attribute]`) et leurs usages dans les fichiers
+/// Rust : invocations `name!(..)`, dérivations et attributs appliqués aux types.
+///
+/// Les usages sont relevés pour tous les noms ; seuls ceux des macros du projet sont
+/// retenus en fin d'analyse, une macro pouvant être définie après son premier usage.
+#[derive(Debug)]
+pub struct MacroCollector {
+    definitions: Vec<MacroDefinition>,
+    /// Invocations `name!` par nom
+    invocations: BTreeMap<String, usize>,
+    /// Dérivations et attributs appliqués, par nom : type annoté, `None` pour un autre élément
+    applied: BTreeMap<String, Vec<Option<String>>>,
+    invocation: Regex,
+    macro_rules: Regex,
+    attribute: Regex,
+    function: Regex,
+    type_declaration: Regex,
+}
+
+impl Default for MacroCollector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MacroCollector {
+    pub fn new() -> Self {
+        let visibility = r"^(?:pub(?:\([^)]*\))?\s+)?";
+        Self {
+            definitions: Vec::new(),
+            invocations: BTreeMap::new(),
+            applied: BTreeMap::new(),
+            invocation: Regex::new(r"\b([A-Za-z_]\w*)!\s*[(\[{]").unwrap(),
+            macro_rules: Regex::new(r"^macro_rules!\s*(\w+)").unwrap(),
+            attribute: Regex::new(r"^#\[\s*((?:\w+::)*(\w+))\s*(?:\((.*)\))?").unwrap(),
+            function: Regex::new(&format!(r#"{}(?:(?:const|async|unsafe|extern(?:\s+"[^"]*")?)\s+)*fn\s+(\w+)"#, visibility))
+                .unwrap(),
+            type_declaration: Regex::new(&format!(r"{}(?:struct|enum|union)\s+(\w+)", visibility)).unwrap(),
+        }
+    }
+
+    /// Relève les définitions et les usages de macros d'un fichier Rust
+    pub fn observe(&mut self, path: &str, content: &str) {
+        let code = mask_code(content);
+        // Attributs précédant l'élément en cours
+        let mut attributes: Vec<&str> = Vec::new();
+
+        for (index, line) in code.lines().enumerate() {
+            let line = line.trim();
+            for captures in self.invocation.captures_iter(line) {
+                *self.invocations.entry(captures[1].to_string()).or_default() += 1;
+            }
+            if line.starts_with("#[") {
+                attributes.push(line);
+                continue;
+            }
+            if line.is_empty() {
+                continue;
+            }
+            let attributes = std::mem::take(&mut attributes);
+
+            if let Some(captures) = self.macro_rules.captures(line) {
+                let exported = attributes.iter().any(|attribute| self.attribute_name(attribute) == Some("macro_export"));
+                self.define(&captures[1], MacroKind::Declarative, exported, path, index);
+            } else if let Some(captures) = self.function.captures(line) {
+                for attribute in &attributes {
+                    let Some(parsed) = self.attribute.captures(attribute) else { continue };
+                    match &parsed[2] {
+                        "proc_macro" => self.define(&captures[1], MacroKind::FunctionLike, true, path, index),
+                        "proc_macro_attribute" => self.define(&captures[1], MacroKind::Attribute, true, path, index),
+                        // `#[proc_macro_derive(Name, attributes(helper))]` : le nom dérivé est le premier argument
+                        "proc_macro_derive" => {
+                            let derived = parsed.get(3).and_then(|args| args.as_str().split(',').next()).unwrap_or("").trim();
+                            if !derived.is_empty() {
+                                self.define(derived, MacroKind::Derive, true, path, index);
+                            }
+                        },
+                        _ => {},
+                    }
+                }
+            }
+
+            let target = self.type_declaration.captures(line).map(|captures| captures[1].to_string());
+            for attribute in &attributes {
+                let Some(parsed) = self.attribute.captures(attribute) else { continue };
+                if &parsed[2] == "derive" {
+                    let derived = parsed.get(3).map_or("", |args| args.as_str());
+                    for name in derived.split(',').map(|name| name.trim().rsplit("::").next().unwrap_or("")) {
+                        if !name.is_empty() {
+                            self.applied.entry(name.to_string()).or_default().push(target.clone());
+                        }
+                    }
+                } else {
+                    self.applied.entry(parsed[2].to_string()).or_default().push(target.clone());
+                }
+            }
+        }
+    }
+
+    /// Macros du projet avec leur nombre d'invocations, triées par nom
+    pub fn definitions(&self) -> Vec<MacroDefinition> {
+        let mut definitions: Vec<MacroDefinition> = self
+            .definitions
+            .iter()
+            .map(|definition| {
+                let invocations = match definition.kind {
+                    MacroKind::Declarative | MacroKind::FunctionLike => {
+                        self.invocations.get(&definition.name).copied().unwrap_or(0)
+                    },
+                    MacroKind::Derive | MacroKind::Attribute => self.applied.get(&definition.name).map_or(0, Vec::len),
+                };
+                MacroDefinition { invocations, ..definition.clone() }
+            })
+            .collect();
+        definitions.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.defined_in.cmp(&b.defined_in)));
+        definitions
+    }
+
+    /// Note sur chaque type les dérivations et attributs du projet qui lui sont appliqués
+    pub fn annotate(&self, relations: &mut [TypeRelations]) {
+        for definition in self.definitions.iter().filter(|definition| {
+            matches!(definition.kind, MacroKind::Derive | MacroKind::Attribute)
+        }) {
+            let targets = self.applied.get(&definition.name).into_iter().flatten().flatten();
+            for target in targets {
+                let relation = relations.iter_mut().find(|relation| &*relation.type_name == target);
+                if let Some(relation) = relation.filter(|relation| !relation.macros.contains(&definition.name)) {
+                    relation.macros.push(definition.name.clone());
+                }
+            }
+        }
+    }
+
+    fn define(&mut self, name: &str, kind: MacroKind, exported: bool, path: &str, index: usize) {
+        self.definitions.push(MacroDefinition {
+            name: name.to_string(),
+            kind,
+            exported,
+            defined_in: path.to_string(),
+            line: index + 1,
+            invocations: 0,
+        });
+    }
+
+    /// Nom d'un attribut, sans son chemin (`#[serde::rename(..)]` → `rename`)
+    fn attribute_name<'a>(&self, attribute: &'a str) -> Option<&'a str> {
+        self.attribute.captures(attribute).and_then(|captures| captures.get(2)).map(|name| name.as_str())
+    }
+}
@@ -0,0 +1,193 @@
+/// Contenu dont les commentaires (`//`, `///`, `/* */` imbriqués) et le texte des
+/// littéraux (chaînes, chaînes brutes, caractères) sont remplacés par des espaces.
+///
+/// Les délimiteurs des littéraux et les retours à la ligne sont conservés : les
+/// numéros de ligne et les colonnes restent ceux du fichier d'origine, et les motifs
+/// appliqués ligne à ligne ne voient plus les types cités en commentaire ou dans un
+/// `println!`.
+pub fn mask_code(content: &str) -> String {
+    scan(content).0
+}
+
+/// Commentaires d'un contenu Rust, délimiteurs compris (`// ..`, `/* .. */`), avec la
+/// ligne (à partir de 1) où chacun commence
+pub fn comments(content: &str) -> Vec<(usize, String)> {
+    let (_, comments) = scan(content);
+    let mut line = 1;
+    let mut position = 0;
+    let mut chars = content.chars();
+    comments
+        .into_iter()
+        .map(|(start, text)| {
+            line += chars.by_ref().take(start - position).filter(|c| *c == '\n').count();
+            position = start;
+            (line, text)
+        })
+        .collect()
+}
+
+/// Code masqué et commentaires rencontrés (position du premier caractère, texte)
+fn scan(content: &str) -> (String, Vec<(usize, String)>) {
+    let chars: Vec<char> = content.chars().collect();
+    let blank = |c: char| if c == '\n' { '\n' } else { ' ' };
+    let is_identifier = |c: char| c.is_alphanumeric() || c == '_';
+    let mut code = String::with_capacity(content.len());
+    let mut comments = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let next = chars.get(i + 1).copied();
+        match c {
+            '/' if next == Some('/') => {
+                let start = i;
+                while i < chars.len() && chars[i] != '\n' {
+                    code.push(' ');
+                    i += 1;
+                }
+                comments.push((start, chars[start..i].iter().collect()));
+            },
+            '/' if next == Some('*') => {
+                let start = i;
+                let mut depth = 0;
+                while i < chars.len() {
+                    match (chars[i], chars.get(i + 1)) {
+                        ('/', Some('*')) => depth += 1,
+                        ('*', Some('/')) => depth -= 1,
+                        (c, _) => {
+                            code.push(blank(c));
+                            i += 1;
+                            continue;
+                        },
+                    }
+                    code.push_str("  ");
+                    i += 2;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                comments.push((start, chars[start..i.min(chars.len())].iter().collect()));
+            },
+            '"' => {
+                code.push('"');
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    if chars[i] == '\\' {
+                        code.push(' ');
+                        i += 1;
+                    }
+                    if let Some(&c) = chars.get(i) {
+                        code.push(blank(c));
+                        i += 1;
+                    }
+                }
+                if i < chars.len() {
+                    code.push('"');
+                    i += 1;
+                }
+            },
+            // Chaîne brute `r#".."#` : le contenu s'arrête au guillemet suivi des mêmes `#`
+            'r' if !i.checked_sub(1).is_some_and(|j| is_identifier(chars[j])) => {
+                let hashes = chars[i + 1..].iter().take_while(|c| **c == '#').count();
+                if chars.get(i + 1 + hashes) != Some(&'"') {
+                    code.push(c);
+                    i += 1;
+                    continue;
+                }
+                code.extend(&chars[i..i + 2 + hashes]);
+                i += 2 + hashes;
+                while i < chars.len() {
+                    let closes = chars[i] == '"'
+                        && chars[i + 1..].iter().take(hashes).filter(|c| **c == '#').count() == hashes;
+                    if closes {
+                        code.extend(&chars[i..i + 1 + hashes]);
+                        i += 1 + hashes;
+                        break;
+                    }
+                    code.push(blank(chars[i]));
+                    i += 1;
+                }
+            },
+            // `'"'`, `'\''` sont des caractères ; `'a` une durée de vie
+            '\'' if next == Some('\\') || chars.get(i + 2) == Some(&'\'') => {
+                code.push('\'');
+                i += 1;
+                while i < chars.len() && chars[i] != '\'' {
+                    if chars[i] == '\\' {
+                        code.push(' ');
+                        i += 1;
+                    }
+                    if let Some(&c) = chars.get(i) {
+                        code.push(blank(c));
+                        i += 1;
+                    }
+                }
+                if i < chars.len() {
+                    code.push('\'');
+                    i += 1;
+                }
+            },
+            _ => {
+                code.push(c);
+                i += 1;
+            },
+        }
+    }
+    (code, comments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masking_keeps_the_layout_of_every_line() {
+        let content = "let a = \"HashMap<K, V>\"; // ProjectSummary\n/* Cache\n   Limiter */ let b = 'x';\n";
+        let masked = mask_code(content);
+        assert_eq!(masked.lines().count(), content.lines().count());
+        for (masked, original) in masked.lines().zip(content.lines()) {
+            assert_eq!(masked.chars().count(), original.chars().count());
+        }
+        assert_eq!(masked.lines().next(), Some("let a = \"             \";                  "));
+        assert!(!masked.contains("Cache") && !masked.contains("Limiter"));
+        assert!(masked.contains("let b = ' ';"));
+    }
+
+    #[test]
+    fn macro_strings_escapes_and_raw_strings_are_blanked() {
+        let content = r##"println!("{} HashMap \" Config", x);
+let raw = r#"impl Display for "Phantom""#;
+let path = "C:\\Users\\";
+"##;
+        let masked = mask_code(content);
+        for phantom in ["HashMap", "Config", "Display", "Phantom", "Users"] {
+            assert!(!masked.contains(phantom), "{}: {}", phantom, masked);
+        }
+        assert!(masked.contains("println!(\""));
+        assert!(masked.contains("\", x);"));
+        assert!(masked.contains("let raw = r#\""));
+        assert!(masked.contains("let path = \""));
+    }
+
+    #[test]
+    fn lifetimes_and_nested_comments_are_not_mistaken_for_literals() {
+        let content = "fn f<'a>(s: &'a str) -> &'a str { s }\n/* a /* b */ Hidden */ Visible\n";
+        let masked = mask_code(content);
+        assert_eq!(masked.lines().next(), content.lines().next());
+        assert!(!masked.contains("Hidden"));
+        assert!(masked.contains("Visible"));
+    }
+
+    #[test]
+    fn comments_are_reported_with_their_starting_line() {
+        let content = "/// Doc\nfn f() {} // fin\nlet s = \"// pas un commentaire\";\n/* bloc\n sur deux lignes */\n";
+        assert_eq!(
+            comments(content),
+            vec![
+                (1, "/// Doc".to_string()),
+                (2, "// fin".to_string()),
+                (4, "/* bloc\n sur deux lignes */".to_string()),
+            ]
+        );
+    }
+}
@@ -1,2 +1,45 @@
+pub mod api_surface;
+#[cfg(feature = "archive")]
+pub mod archive;
+pub mod blob_cache;
+pub mod boilerplate;
+pub mod build_script;
+pub mod cargo_manifest;
+pub mod ci;
+#[cfg(feature = "git")]
+pub mod clone;
+pub mod complexity;
+pub mod config;
+pub mod conversions;
+pub mod directory_tree;
+pub mod errors;
+pub mod features;
 pub mod file;
-pub mod repository;
\ No newline at end of file
+pub mod graph;
+pub mod heuristic;
+pub mod idioms;
+pub mod imports;
+pub mod index;
+pub mod intern;
+pub mod license;
+pub mod line_count;
+pub mod links;
+pub mod local;
+pub mod macros;
+pub mod masking;
+pub mod modules;
+pub mod options;
+pub mod panics;
+pub mod path_filter;
+pub mod readme;
+pub mod release;
+pub mod repository;
+pub mod source;
+#[cfg(feature = "syn-backend")]
+pub mod syntax;
+pub mod targets;
+pub mod test_count;
+pub mod todos;
+pub mod unsafe_code;
+pub mod usage;
+pub mod workspace;
@@ -0,0 +1,242 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use regex::Regex;
+
+use crate::analysis::graph::DirectedGraph;
+use crate::analysis::imports::expand_use_tree;
+use crate::analysis::masking::mask_code;
+use crate::types::analysis::ModuleNode;
+
+/// Module Rust : répertoire de la crate (vide à la racine du dépôt) et chemin dans
+/// la crate (`["analysis", "file"]`)
+type ModuleId = (String, Vec<String>);
+
+/// Graphe des modules de chaque crate, déduit des `use crate::`, `use super::` et
+/// `use self::` de ses fichiers.
+///
+/// La racine de la crate (`lib.rs`, `main.rs`) est écartée : elle déclare tous les
+/// modules et réexporte souvent leurs types, ce qui ferait de chaque import un cycle.
+#[derive(Debug, Default)]
+pub struct ModuleGraph {
+    imports: BTreeMap<ModuleId, BTreeSet<Vec<String>>>,
+}
+
+impl ModuleGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Relève les imports internes d'un fichier Rust placé sous un répertoire `src/`
+    pub fn observe(&mut self, path: &str, content: &str) {
+        let Some((crate_dir, module)) = module_of(path) else {
+            return;
+        };
+        let mut imports = BTreeSet::new();
+        for statement in use_statements(content) {
+            for (import, _) in expand_use_tree(&statement) {
+                if let Some(absolute) = absolute_import(&module, &import) {
+                    imports.insert(absolute);
+                }
+            }
+        }
+        self.imports.entry((crate_dir, module)).or_default().extend(imports);
+    }
+
+    /// Groupes de modules qui s'importent mutuellement (composantes fortement
+    /// connexes de plus d'un module), nommés `crate::a::b`
+    pub fn cycles(&self) -> Vec<Vec<String>> {
+        let modules: Vec<&ModuleId> = self.imports.keys().filter(|(_, module)| !module.is_empty()).collect();
+        let index: BTreeMap<&ModuleId, usize> = modules.iter().enumerate().map(|(i, module)| (*module, i)).collect();
+
+        let mut graph = DirectedGraph::new(modules.len());
+        for (from, module) in modules.iter().enumerate() {
+            let (crate_dir, _) = module;
+            let mut targets = BTreeSet::new();
+            for import in &self.imports[*module] {
+                // Le module importé est le plus long préfixe connu du chemin
+                let target = (1..=import.len())
+                    .rev()
+                    .find_map(|len| index.get(&(crate_dir.clone(), import[..len].to_vec())));
+                if let Some(&target) = target.filter(|target| **target != from) {
+                    targets.insert(target);
+                }
+            }
+            for target in targets {
+                graph.add_edge(from, target);
+            }
+        }
+
+        graph
+            .cycles()
+            .into_iter()
+            .map(|members| members.into_iter().map(|member| module_name(modules[member])).collect())
+            .collect()
+    }
+}
+
+/// Arbre des modules de chaque crate : déclarations `mod x;` de chaque fichier,
+/// résolues vers `x.rs` ou `x/mod.rs` selon l'emplacement du fichier déclarant.
+///
+/// Les modules en ligne (`mod tests { .. }`) ne sont pas relevés.
+#[derive(Debug)]
+pub struct ModuleTree {
+    /// Modules déclarés par chaque fichier, dans l'ordre : nom et visibilité `pub`
+    declarations: BTreeMap<String, Vec<(String, bool)>>,
+    declaration: Regex,
+}
+
+impl Default for ModuleTree {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModuleTree {
+    pub fn new() -> Self {
+        Self {
+            declarations: BTreeMap::new(),
+            declaration: Regex::new(r"(?m)^\s*(pub(?:\([^)]*\))?\s+)?mod\s+(\w+)\s*;").unwrap(),
+        }
+    }
+
+    /// Relève les déclarations de modules d'un fichier Rust placé sous un répertoire `src/`
+    pub fn observe(&mut self, path: &str, content: &str) {
+        if module_of(path).is_none() {
+            return;
+        }
+        let declared = self
+            .declaration
+            .captures_iter(&mask_code(content))
+            .map(|captures| (captures[2].to_string(), captures.get(1).is_some_and(|vis| vis.as_str().trim() == "pub")))
+            .collect();
+        self.declarations.insert(path.to_string(), declared);
+    }
+
+    /// Un arbre par racine de crate analysée (`lib.rs`, `main.rs`)
+    pub fn tree(&self) -> Vec<ModuleNode> {
+        self.declarations
+            .keys()
+            .filter_map(|file| {
+                let root = module_of(file).filter(|(_, module)| module.is_empty())?;
+                let path = module_name(&root);
+                Some(self.node(path.clone(), path, Some(file), true))
+            })
+            .collect()
+    }
+
+    /// Nœud d'un module et, s'il a un fichier, de ses sous-modules déclarés
+    fn node(&self, name: String, path: String, file: Option<&str>, is_public: bool) -> ModuleNode {
+        let declared = file.and_then(|file| self.declarations.get(file)).map_or(&[][..], Vec::as_slice);
+        let children = declared
+            .iter()
+            .map(|(child, public)| {
+                let file = file.unwrap_or_default();
+                let found = module_files(file, child).into_iter().find(|candidate| self.declarations.contains_key(candidate));
+                self.node(child.clone(), format!("{}::{}", path, child), found.as_deref(), *public)
+            })
+            .collect();
+        ModuleNode { name, path, file: file.map(str::to_string), is_public, children }
+    }
+}
+
+/// Fichiers possibles du module `name` déclaré dans `parent` : à côté d'une racine de
+/// crate ou d'un `mod.rs`, dans le répertoire homonyme de `parent` sinon
+/// (`src/analysis.rs` → `src/analysis/name.rs`)
+fn module_files(parent: &str, name: &str) -> [String; 2] {
+    let (dir, file) = parent.rsplit_once('/').unwrap_or(("", parent));
+    let owns_directory = file == "mod.rs" || module_of(parent).is_some_and(|(_, module)| module.is_empty());
+    let dir = if owns_directory {
+        dir.to_string()
+    } else {
+        format!("{}/{}", dir, file.trim_end_matches(".rs"))
+    };
+    [format!("{}/{}.rs", dir, name), format!("{}/{}/mod.rs", dir, name)]
+}
+
+/// Module d'un fichier `.rs` placé sous un répertoire `src/` (`crate::analysis::file`,
+/// `crate` pour la racine)
+pub fn module_path(path: &str) -> Option<String> {
+    module_of(path).map(|module| module_name(&module))
+}
+
+/// Nom affiché d'un module : `crate::a::b`, préfixé du répertoire de la crate hors racine
+fn module_name((crate_dir, module): &ModuleId) -> String {
+    let name = if module.is_empty() {
+        "crate".to_string()
+    } else {
+        format!("crate::{}", module.join("::"))
+    };
+    if crate_dir.is_empty() {
+        name
+    } else {
+        format!("{}/{}", crate_dir, name)
+    }
+}
+
+/// Crate et module d'un fichier `.rs` : `src/analysis/file.rs` → `("", [analysis, file])`,
+/// `crates/core/src/api/mod.rs` → `("crates/core", [api])`
+fn module_of(path: &str) -> Option<ModuleId> {
+    let source = path.strip_suffix(".rs")?;
+    let (crate_dir, module) = if let Some(module) = source.strip_prefix("src/") {
+        ("", module)
+    } else {
+        let (crate_dir, module) = source.rsplit_once("/src/")?;
+        (crate_dir, module)
+    };
+    let mut segments: Vec<String> = module.split('/').map(str::to_string).collect();
+    if segments.last().is_some_and(|last| last == "mod") {
+        segments.pop();
+    }
+    if segments.len() == 1 && (segments[0] == "lib" || segments[0] == "main") {
+        segments.clear();
+    }
+    Some((crate_dir.to_string(), segments))
+}
+
+/// Arbres des instructions `use` internes (`crate::...`, `super::...`, `self::...`),
+/// éventuellement sur plusieurs lignes
+fn use_statements(content: &str) -> Vec<String> {
+    let mut statements = Vec::new();
+    let mut current: Option<String> = None;
+    for line in content.lines() {
+        let line = line.split("//").next().unwrap_or("").trim();
+        match current.as_mut() {
+            Some(statement) => statement.push_str(line),
+            None => {
+                let rest = ["pub(crate) ", "pub(super) ", "pub "]
+                    .iter()
+                    .find_map(|visibility| line.strip_prefix(visibility))
+                    .unwrap_or(line);
+                match rest.strip_prefix("use ") {
+                    Some(tree) if ["crate::", "super::", "self::"].iter().any(|root| tree.starts_with(root)) => {
+                        current = Some(tree.to_string());
+                    },
+                    _ => continue,
+                }
+            },
+        }
+        if let Some(statement) = current.take_if(|statement| statement.contains(';')) {
+            statements.push(statement.split(';').next().unwrap_or("").to_string());
+        }
+    }
+    statements
+}
+
+/// Chemin d'un import depuis la racine de la crate ; `None` s'il est externe
+fn absolute_import(module: &[String], import: &[String]) -> Option<Vec<String>> {
+    let (first, rest) = import.split_first()?;
+    let mut path = match first.as_str() {
+        "crate" => return Some(rest.to_vec()),
+        "self" => module.to_vec(),
+        "super" => module[..module.len().checked_sub(1)?].to_vec(),
+        _ => return None,
+    };
+    for segment in rest {
+        if segment == "super" {
+            path.pop()?;
+        } else {
+            path.push(segment.clone());
+        }
+    }
+    Some(path)
+}
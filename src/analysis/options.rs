@@ -0,0 +1,240 @@
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::analysis::blob_cache::{BlobCache, DEFAULT_CACHE_MAX_SIZE};
+use crate::analysis::file::{DEFAULT_DOC_LENGTH, DEFAULT_KEY_TYPES, DEFAULT_SUMMARY_ENTRIES};
+use crate::analysis::path_filter::GlobFilter;
+use crate::api::cache::HttpCache;
+use crate::api::client::{
+    http_client, GithubClient, DEFAULT_CONNECT_TIMEOUT_SECS, DEFAULT_MAX_FILE_SIZE, DEFAULT_REQUEST_TIMEOUT_SECS,
+    DEFAULT_USER_AGENT,
+};
+use crate::api::gitea::GiteaClient;
+use crate::api::gitlab::GitlabClient;
+use crate::api::provider::RepositoryProvider;
+use crate::api::scheduler::{DEFAULT_BYTE_BUDGET, DEFAULT_MAX_IN_FLIGHT};
+use crate::types::locator::{Provider, RepoLocator};
+
+/// Conduite à tenir quand le quota d'API ne couvre visiblement pas l'exécution
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BudgetPolicy {
+    /// Affiche un avertissement et poursuit
+    #[default]
+    Warn,
+    /// Interrompt l'analyse avant le premier téléchargement
+    Abort,
+}
+
+impl FromStr for BudgetPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "warn" => Ok(BudgetPolicy::Warn),
+            "abort" => Ok(BudgetPolicy::Abort),
+            other => Err(format!("Invalid budget policy: {} (expected warn or abort)", other)),
+        }
+    }
+}
+
+/// Options contrôlant le déroulement de l'analyse d'un dépôt
+#[derive(Debug, Clone)]
+pub struct AnalysisOptions {
+    /// Désactive les appels API optionnels (liste des tags, ...)
+    pub offline: bool,
+    /// Somme maximale des tailles déclarées des fichiers en cours de téléchargement
+    pub fetch_byte_budget: u64,
+    /// Nombre maximal de téléchargements simultanés
+    pub max_in_flight: usize,
+    /// Branche, tag ou commit à analyser (sinon la branche par défaut du dépôt)
+    pub git_ref: Option<String>,
+    /// Sous-répertoire à analyser (sinon celui de l'URL, sinon tout le dépôt)
+    pub subpath: Option<String>,
+    /// Télécharge le dépôt en une archive tarball au lieu d'un appel API par fichier
+    pub tarball: bool,
+    /// Analyse un clone git superficiel au lieu de passer par l'API
+    pub clone: bool,
+    /// Conserve le répertoire du clone après l'analyse
+    pub keep_clone: bool,
+    /// Désactive les caches sur disque (HTTP par ETag et contenus par SHA)
+    pub no_cache: bool,
+    /// Analyse aussi les répertoires vendorisés et les chemins du `.gitignore`
+    pub no_default_ignores: bool,
+    /// Analyse aussi les sous-modules hébergés sur le même hôte GitHub
+    pub recurse_submodules: bool,
+    /// Motifs glob des fichiers à analyser (tous si vide)
+    pub include: Vec<String>,
+    /// Motifs glob des fichiers à écarter, prioritaires sur `include`
+    pub exclude: Vec<String>,
+    /// Emplacement du cache HTTP (défaut : `~/.cache/repo_analyzer`)
+    pub cache_dir: Option<PathBuf>,
+    /// Taille maximale du répertoire de cache ; les entrées les plus anciennes sont supprimées
+    pub cache_max_size: u64,
+    /// Entrées conservées par motif dans le résumé texte de chaque fichier
+    pub summary_entries: usize,
+    /// Types retenus dans `key_types`
+    pub key_types: usize,
+    /// Caractères conservés par doc-comment de type ou de fonction (0 : aucun)
+    pub doc_length: usize,
+    /// Base de l'API GitHub (GitHub Enterprise : `https://ghe.example.com/api/v3`)
+    /// ou GitLab (`https://gitlab.example.com/api/v4`)
+    pub api_base: Option<String>,
+    /// Forge imposée (`--provider`) ; sinon déduite de l'hôte de chaque dépôt
+    pub provider: Option<Provider>,
+    /// Token personnel passé par `--token`, prioritaire sur l'environnement
+    pub token: Option<String>,
+    /// Taille au-delà de laquelle un fichier est ignoré (octets)
+    pub max_file_size: usize,
+    /// Réaction à un quota d'API insuffisant pour l'exécution estimée
+    pub budget_policy: BudgetPolicy,
+    /// Délai maximal d'établissement d'une connexion (secondes)
+    pub connect_timeout_secs: u64,
+    /// Délai maximal d'une requête complète (secondes)
+    pub timeout_secs: u64,
+    /// Proxy HTTP(S) pour toutes les requêtes
+    pub proxy: Option<String>,
+    /// User-agent personnalisé (sinon `GitHub-Repository-Analyzer`)
+    pub user_agent: Option<String>,
+}
+
+impl Default for AnalysisOptions {
+    fn default() -> Self {
+        Self {
+            offline: false,
+            fetch_byte_budget: DEFAULT_BYTE_BUDGET,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+            git_ref: None,
+            subpath: None,
+            tarball: false,
+            clone: false,
+            keep_clone: false,
+            no_cache: false,
+            no_default_ignores: false,
+            recurse_submodules: false,
+            include: Vec::new(),
+            exclude: Vec::new(),
+            cache_dir: None,
+            cache_max_size: DEFAULT_CACHE_MAX_SIZE,
+            summary_entries: DEFAULT_SUMMARY_ENTRIES,
+            key_types: DEFAULT_KEY_TYPES,
+            doc_length: DEFAULT_DOC_LENGTH,
+            api_base: None,
+            provider: None,
+            token: None,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            budget_policy: BudgetPolicy::Warn,
+            connect_timeout_secs: DEFAULT_CONNECT_TIMEOUT_SECS,
+            timeout_secs: DEFAULT_REQUEST_TIMEOUT_SECS,
+            proxy: None,
+            user_agent: None,
+        }
+    }
+}
+
+impl AnalysisOptions {
+    /// Filtres `--include` / `--exclude` compilés
+    pub fn glob_filter(&self) -> Result<GlobFilter, String> {
+        GlobFilter::new(&self.include, &self.exclude)
+    }
+
+    /// Cache HTTP à utiliser pour ces options (aucun avec `no_cache`)
+    pub fn http_cache(&self) -> Option<HttpCache> {
+        if self.no_cache {
+            return None;
+        }
+        self.cache_dir.clone().or_else(HttpCache::default_dir).map(HttpCache::new)
+    }
+
+    /// Cache des contenus et analyses par SHA, dans le même répertoire que le cache HTTP
+    pub fn blob_cache(&self) -> Option<BlobCache> {
+        self.http_cache().map(|cache| BlobCache::new(cache.dir()))
+    }
+
+    /// Client GitHub configuré selon ces options (cache, token, réseau, base de l'API,
+    /// taille maximale)
+    pub fn github_client(&self) -> GithubClient {
+        self.github_client_for_host(None)
+    }
+
+    /// Client GitHub dont le token de secours (CLI `gh`) est celui de `host`,
+    /// l'hôte du dépôt analysé
+    pub fn github_client_for_host(&self, host: Option<&str>) -> GithubClient {
+        let mut builder = GithubClient::builder()
+            .cache(self.http_cache())
+            .token(self.token.clone())
+            .connect_timeout(Duration::from_secs(self.connect_timeout_secs))
+            .timeout(Duration::from_secs(self.timeout_secs))
+            .max_file_size(self.max_file_size);
+        if let Some(api_base) = &self.api_base {
+            builder = builder.api_base(api_base.as_str());
+        }
+        if let Some(proxy) = &self.proxy {
+            builder = builder.proxy(proxy.as_str());
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent.as_str());
+        }
+        if let Some(host) = host {
+            builder = builder.host(host);
+        }
+        builder.build()
+    }
+
+    /// Client GitLab configuré selon ces options ; le token `--token` est prioritaire
+    /// sur `GITLAB_TOKEN`
+    pub fn gitlab_client(&self) -> GitlabClient {
+        let client = GitlabClient::new(self.http_client(), self.token.clone()).with_max_file_size(self.max_file_size);
+        match &self.api_base {
+            Some(api_base) => client.with_api_base(api_base.as_str()),
+            None => client,
+        }
+    }
+
+    /// Client Gitea configuré selon ces options ; le token `--token` est prioritaire
+    /// sur `GITEA_TOKEN`
+    pub fn gitea_client(&self) -> GiteaClient {
+        let client = GiteaClient::new(self.http_client(), self.token.clone()).with_max_file_size(self.max_file_size);
+        match &self.api_base {
+            Some(api_base) => client.with_api_base(api_base.as_str()),
+            None => client,
+        }
+    }
+
+    /// Client HTTP partagé par les forges autres que GitHub
+    fn http_client(&self) -> Result<reqwest::Client, String> {
+        http_client(
+            Duration::from_secs(self.connect_timeout_secs),
+            Duration::from_secs(self.timeout_secs),
+            self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT),
+            self.proxy.as_deref(),
+        )
+    }
+
+    /// Forge d'un dépôt : celle imposée par les options, sinon celle de son hôte
+    pub fn provider_for(&self, repo_url: &str) -> Provider {
+        self.provider
+            .or_else(|| RepoLocator::parse_as(repo_url, None).ok().map(|locator| locator.provider))
+            .unwrap_or_default()
+    }
+
+    /// Client de la forge hébergeant `repo_url`
+    pub fn repository_provider(&self, repo_url: &str) -> Box<dyn RepositoryProvider> {
+        match self.provider_for(repo_url) {
+            Provider::Github => {
+                let locator = RepoLocator::parse_as(repo_url, self.provider).ok();
+                Box::new(self.github_client_for_host(locator.as_ref().map(|locator| locator.host.as_str())))
+            },
+            provider => self.provider_client(provider),
+        }
+    }
+
+    /// Client d'une forge donnée
+    pub fn provider_client(&self, provider: Provider) -> Box<dyn RepositoryProvider> {
+        match provider {
+            Provider::Github => Box::new(self.github_client()),
+            Provider::Gitlab => Box::new(self.gitlab_client()),
+            Provider::Gitea => Box::new(self.gitea_client()),
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use regex::Regex;
+
+use crate::analysis::file::braces;
+use crate::analysis::masking::mask_code;
+use crate::types::analysis::PanicCounts;
+
+/// Compte les points de panique d'un fichier Rust : `.unwrap()`, `.expect(..)`,
+/// `panic!`, `unreachable!` et `todo!`, hors commentaires et chaînes, et hors code de
+/// test (fichier `#![cfg(test)]`, modules, fonctions et blocs `impl` marqués
+/// `#[cfg(test)]` ou `#[test]`).
+pub fn scan_panics(content: &str) -> PanicCounts {
+    let code = mask_code(content);
+    let call = Regex::new(r"\.\s*(unwrap)\s*\(\s*\)|\.\s*(expect)\s*\(|\b(panic|unreachable|todo)!\s*[(\[{]").unwrap();
+
+    let mut counts = PanicCounts::default();
+    for (line, in_test) in code.lines().zip(test_code_lines(&code)) {
+        if in_test {
+            continue;
+        }
+        for captures in call.captures_iter(line) {
+            let name = (1..=3).find_map(|group| captures.get(group)).map_or("", |found| found.as_str());
+            counts.record(name);
+        }
+    }
+    counts
+}
+
+/// Indique, pour chaque ligne d'un code masqué par `mask_code`, si elle relève du code
+/// de test : fichier `#![cfg(test)]`, éléments marqués `#[cfg(test)]` ou `#[test]`.
+/// Les lignes d'attribut seules ne sont jamais du code de test.
+pub(crate) fn test_code_lines(code: &str) -> Vec<bool> {
+    let mut lines = Vec::new();
+    // Chaque accolade ouverte indique si elle ouvre du code de test
+    let mut blocks: Vec<bool> = Vec::new();
+    let mut opens_test_block = false;
+
+    for line in code.lines() {
+        let rest = line.trim();
+        if rest.strip_prefix("#![").is_some_and(is_test_attribute) {
+            return vec![true; code.lines().count()];
+        }
+        if let Some(attribute) = rest.strip_prefix("#[") {
+            opens_test_block |= is_test_attribute(attribute);
+            // Ligne d'attribut seule ; sinon l'élément suit (`#[test] fn a() {`)
+            if rest.ends_with(']') {
+                lines.push(false);
+                continue;
+            }
+        }
+
+        // Un bloc de test peut s'ouvrir sur la ligne même (`fn a() { x.unwrap() }`)
+        let in_test = blocks.last().copied().unwrap_or(false) || (opens_test_block && rest.contains('{'));
+        lines.push(in_test);
+
+        for brace in braces(rest) {
+            if brace == '{' {
+                let opens_test = std::mem::take(&mut opens_test_block);
+                blocks.push(in_test || opens_test);
+            } else {
+                blocks.pop();
+            }
+        }
+        // `mod tests;` : module déclaré dans un autre fichier
+        if rest.ends_with(';') {
+            opens_test_block = false;
+        }
+    }
+    lines
+}
+
+/// Attribut (sans `#[` / `#![`) réservant l'élément aux tests : `cfg(test)`,
+/// éventuellement combiné (`cfg(all(test, ..))`), ou `test`
+fn is_test_attribute(attribute: &str) -> bool {
+    let compact: String = attribute.chars().filter(|c| !c.is_whitespace()).collect();
+    compact.starts_with("cfg(test)") || compact.starts_with("cfg(all(test") || compact.starts_with("test]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (unwrap, expect, panic, unreachable, todo)
+    fn counts(content: &str) -> (usize, usize, usize, usize, usize) {
+        let counts = scan_panics(content);
+        (counts.unwrap, counts.expect, counts.panic, counts.unreachable, counts.todo)
+    }
+
+    #[test]
+    fn each_kind_of_call_is_counted() {
+        let content = r#"fn run(x: Option<u32>) -> u32 {
+    let a = x.unwrap();
+    let b = x
+        .expect("present");
+    let c = x . unwrap ( );
+    let d = x.unwrap_or(0);
+    if a > b { panic!("too big") }
+    match c { 0 => unreachable!(), _ => todo!{} }
+}
+"#;
+        assert_eq!(counts(content), (2, 1, 1, 1, 1));
+    }
+
+    #[test]
+    fn comments_and_strings_are_ignored() {
+        let content = r##"/// Appelle `.unwrap()` ou `panic!("..")`
+fn f() {
+    // x.unwrap();
+    /* todo!() */
+    let help = "call .expect( or unreachable!()";
+    let raw = r#"panic!("no")"#;
+    println!("{}", help);
+}
+"##;
+        assert_eq!(counts(content), (0, 0, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_modules_and_functions_are_excluded() {
+        let content = r#"pub fn kept() { a.unwrap(); }
+
+#[cfg(test)]
+mod tests {
+    fn helper() { b.unwrap(); }
+
+    #[test]
+    fn case() {
+        c.expect("value");
+        if bad { panic!() }
+    }
+}
+
+#[test]
+fn top_level() { d.unwrap() }
+
+#[cfg(all(test, feature = "slow"))]
+impl Fixture {
+    fn build() { todo!() }
+}
+
+pub fn after() { e.unwrap(); }
+"#;
+        assert_eq!(counts(content), (2, 0, 0, 0, 0));
+    }
+
+    #[test]
+    fn external_test_modules_do_not_hide_following_code() {
+        let content = "#[cfg(test)]\nmod tests;\n\npub fn kept() { x.unwrap(); }\n";
+        assert_eq!(counts(content), (1, 0, 0, 0, 0));
+        // Fichier entièrement réservé aux tests
+        assert_eq!(counts("#![cfg(test)]\n\nfn f() { x.unwrap(); }\n"), (0, 0, 0, 0, 0));
+    }
+
+    #[tokio::test]
+    async fn integration_tests_have_no_panic_points() {
+        let content = "fn f() { x.unwrap(); }\n";
+        let analyzer = crate::analysis::file::FileAnalyzer::new();
+        for (path, expected) in [("src/lib.rs", 1), ("tests/cli.rs", 0), ("crates/core/tests/it.rs", 0)] {
+            let (summary, _, _, _) = analyzer.analyze_content(content, path).await;
+            assert_eq!(summary.panic_points.total(), expected, "{}", path);
+        }
+    }
+}
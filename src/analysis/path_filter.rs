@@ -0,0 +1,273 @@
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+
+/// Répertoires de dépendances vendorisées et d'artefacts de build ignorés par défaut
+pub const DEFAULT_IGNORED_DIRS: &[&str] = &["node_modules", "vendor", "target", "dist", "third_party"];
+
+/// Motifs glob `--include` / `--exclude` ; une exclusion l'emporte sur une inclusion
+///
+/// `*` ne traverse pas les `/`, contrairement à `**`. Les motifs portent sur le chemin
+/// complet relatif à la racine du dépôt.
+#[derive(Debug, Clone, Default)]
+pub struct GlobFilter {
+    includes: Option<GlobSet>,
+    excludes: Option<GlobSet>,
+    /// Préfixes des exclusions de la forme `<motif>/**`, qui couvrent un répertoire entier
+    excluded_dirs: Option<GlobSet>,
+}
+
+impl GlobFilter {
+    pub fn new(includes: &[String], excludes: &[String]) -> Result<Self, String> {
+        let dir_prefixes: Vec<String> = excludes
+            .iter()
+            .filter_map(|pattern| pattern.strip_suffix("/**"))
+            .map(str::to_string)
+            .collect();
+        Ok(Self {
+            includes: glob_set(includes)?,
+            excludes: glob_set(excludes)?,
+            excluded_dirs: glob_set(&dir_prefixes)?,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.includes.is_none() && self.excludes.is_none()
+    }
+
+    /// Indique si le fichier `path` est retenu
+    pub fn accepts(&self, path: &str) -> bool {
+        let path = path.trim_start_matches('/');
+        !self.excludes.as_ref().is_some_and(|excludes| excludes.is_match(path))
+            && self.includes.as_ref().is_none_or(|includes| includes.is_match(path))
+    }
+
+    /// Indique si tout le contenu du répertoire `path` est exclu
+    pub fn excludes_dir(&self, path: &str) -> bool {
+        self.excluded_dirs
+            .as_ref()
+            .is_some_and(|excluded| excluded.is_match(path.trim_start_matches('/')))
+    }
+}
+
+/// Compile une liste de motifs (aucun ensemble si la liste est vide)
+fn glob_set(patterns: &[String]) -> Result<Option<GlobSet>, String> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        // Les chemins testés sont relatifs à la racine : un `/` initial l'y ancre déjà
+        let glob = GlobBuilder::new(pattern.trim_start_matches('/'))
+            .literal_separator(true)
+            .build()
+            .map_err(|e| format!("Invalid glob pattern {}: {}", pattern, e))?;
+        builder.add(glob);
+    }
+    builder.build().map(Some).map_err(|e| e.to_string())
+}
+
+/// Chemins exclus de l'analyse : répertoires par défaut et motifs du `.gitignore`
+/// racine du dépôt, puis filtres `--include` / `--exclude`
+///
+/// Les chemins testés sont relatifs à la racine du dépôt, pas au sous-répertoire analysé.
+#[derive(Debug, Clone)]
+pub struct PathFilter {
+    default_dirs: bool,
+    gitignore: Gitignore,
+    globs: GlobFilter,
+}
+
+impl Default for PathFilter {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+impl PathFilter {
+    /// Filtre sans motifs gitignore ; `default_dirs` active `DEFAULT_IGNORED_DIRS`
+    pub fn new(default_dirs: bool) -> Self {
+        Self { default_dirs, gitignore: Gitignore::empty(), globs: GlobFilter::default() }
+    }
+
+    /// Ajoute les filtres `--include` / `--exclude`
+    pub fn with_globs(mut self, globs: GlobFilter) -> Self {
+        self.globs = globs;
+        self
+    }
+
+    /// Indique si le fichier `path` est écarté par les filtres `--include` / `--exclude`
+    pub fn filters_out(&self, path: &str) -> bool {
+        !self.globs.accepts(path)
+    }
+
+    /// Indique si le répertoire `path` est entièrement écarté par un filtre `--exclude`
+    pub fn filters_out_dir(&self, path: &str) -> bool {
+        self.globs.excludes_dir(path)
+    }
+
+    /// Filtre qui n'exclut rien (`--no-default-ignores`)
+    pub fn disabled() -> Self {
+        Self::new(false)
+    }
+
+    /// Ajoute les motifs d'un fichier `.gitignore` ; les lignes invalides sont ignorées
+    pub fn with_gitignore(mut self, content: &str) -> Self {
+        let mut builder = GitignoreBuilder::new("");
+        for line in content.lines() {
+            if let Err(e) = builder.add_line(None, line) {
+                println!("Warning: Ignoring invalid .gitignore pattern: {}", e);
+            }
+        }
+        match builder.build() {
+            Ok(gitignore) => self.gitignore = gitignore,
+            Err(e) => println!("Warning: Could not read the repository .gitignore: {}", e),
+        }
+        self
+    }
+
+    /// Indique si `path` (ou l'un de ses répertoires parents) est exclu
+    pub fn skips(&self, path: &str, is_dir: bool) -> bool {
+        let path = path.trim_start_matches('/');
+        if self.default_dirs {
+            let mut components: Vec<&str> = path.split('/').collect();
+            if !is_dir {
+                components.pop();
+            }
+            if components.iter().any(|component| DEFAULT_IGNORED_DIRS.contains(component)) {
+                return true;
+            }
+        }
+        !self.gitignore.is_empty() && self.gitignore.matched_path_or_any_parents(path, is_dir).is_ignore()
+    }
+
+    /// Indique si `path` est un répertoire exclu dont le parent ne l'est pas, c'est-à-dire
+    /// le point où le parcours s'arrête (compté dans les statistiques)
+    pub fn skips_from(&self, path: &str) -> bool {
+        self.skips(path, true)
+            && path.rsplit_once('/').is_none_or(|(parent, _)| !self.skips(parent, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn globs(includes: &[&str], excludes: &[&str]) -> GlobFilter {
+        let owned = |patterns: &[&str]| patterns.iter().map(|pattern| pattern.to_string()).collect::<Vec<_>>();
+        GlobFilter::new(&owned(includes), &owned(excludes)).unwrap()
+    }
+
+    #[test]
+    fn excludes_win_over_includes_on_the_full_path() {
+        let filter = globs(&["src/**/*.rs"], &["**/generated/**"]);
+
+        assert!(filter.accepts("src/main.rs"));
+        assert!(filter.accepts("src/api/client.rs"));
+        assert!(!filter.accepts("src/generated/bindings.rs"));
+        assert!(!filter.accepts("src/api/generated/schema.rs"));
+        assert!(!filter.accepts("generated/src/lib.rs"));
+        // Le motif porte sur le chemin complet, pas sur un suffixe
+        assert!(!filter.accepts("crates/core/src/lib.rs"));
+        assert!(!filter.accepts("src/README.md"));
+        assert!(!filter.is_empty());
+        assert!(GlobFilter::default().is_empty());
+        assert!(GlobFilter::default().accepts("anything/at/all.txt"));
+    }
+
+    #[test]
+    fn single_star_does_not_cross_separators() {
+        let filter = globs(&["src/*.rs"], &[]);
+        assert!(filter.accepts("src/lib.rs"));
+        assert!(!filter.accepts("src/api/client.rs"));
+
+        let filter = globs(&["src/**/*.rs"], &[]);
+        assert!(filter.accepts("src/lib.rs"));
+        assert!(filter.accepts("src/api/client.rs"));
+
+        let filter = globs(&["*.toml"], &[]);
+        assert!(filter.accepts("Cargo.toml"));
+        assert!(!filter.accepts("crates/core/Cargo.toml"));
+    }
+
+    #[test]
+    fn leading_slash_is_relative_to_the_root() {
+        let filter = globs(&["/src/**"], &["/src/bin/*"]);
+        assert!(filter.accepts("src/lib.rs"));
+        assert!(filter.accepts("/src/lib.rs"));
+        assert!(!filter.accepts("/src/bin/tool.rs"));
+        assert!(!filter.accepts("tests/src/lib.rs"));
+    }
+
+    #[test]
+    fn directory_exclusions_prune_whole_directories() {
+        let filter = globs(&[], &["**/generated/**", "docs/**", "*.lock"]);
+        assert!(filter.excludes_dir("generated"));
+        assert!(filter.excludes_dir("src/generated"));
+        assert!(filter.excludes_dir("/docs"));
+        assert!(!filter.excludes_dir("docs-site"));
+        assert!(!filter.excludes_dir("src"));
+        // Un motif de fichier n'élague aucun répertoire
+        assert!(!filter.excludes_dir("Cargo.lock"));
+        assert!(!filter.accepts("Cargo.lock"));
+
+        let filter = PathFilter::disabled().with_globs(globs(&["src/**"], &["src/generated/**"]));
+        assert!(filter.filters_out_dir("src/generated"));
+        assert!(!filter.filters_out_dir("src"));
+        assert!(filter.filters_out("README.md"));
+    }
+
+    #[test]
+    fn default_dirs_match_directory_components_only() {
+        let filter = PathFilter::default();
+        assert!(filter.skips("vendor", true));
+        assert!(filter.skips("vendor/github.com/lib.go", false));
+        assert!(filter.skips("web/node_modules/react/index.js", false));
+        assert!(filter.skips("/target/debug/build.rs", false));
+        // Un fichier nommé comme un répertoire ignoré reste analysé
+        assert!(!filter.skips("vendor", false));
+        assert!(!filter.skips("src/target", false));
+        assert!(!filter.skips("vendored/lib.rs", false));
+        assert!(!filter.skips("src/dist.rs", false));
+
+        let disabled = PathFilter::disabled();
+        assert!(!disabled.skips("vendor", true));
+        assert!(!disabled.skips("node_modules/left-pad/index.js", false));
+    }
+
+    #[test]
+    fn gitignore_negations_and_parent_directories() {
+        let filter = PathFilter::disabled().with_gitignore("# build output\n*.log\n!keep.log\nbuild/\n/secret.txt\n");
+        assert!(filter.skips("debug.log", false));
+        assert!(filter.skips("logs/today.log", false));
+        assert!(!filter.skips("keep.log", false));
+        assert!(!filter.skips("logs/keep.log", false));
+        // Un fichier sous un répertoire ignoré l'est aussi
+        assert!(filter.skips("build/out/main.o", false));
+        assert!(filter.skips("crates/core/build/main.o", false));
+        // `build/` ne vise que des répertoires
+        assert!(!filter.skips("build", false));
+        assert!(filter.skips("secret.txt", false));
+        assert!(!filter.skips("config/secret.txt", false));
+        assert!(!filter.skips("src/main.rs", false));
+    }
+
+    #[test]
+    fn skips_from_counts_the_topmost_skipped_directory() {
+        let filter = PathFilter::default().with_gitignore("build/\n");
+        assert!(filter.skips_from("vendor"));
+        assert!(!filter.skips_from("vendor/github.com"));
+        assert!(!filter.skips_from("vendor/node_modules"));
+        assert!(filter.skips_from("web/node_modules"));
+        assert!(!filter.skips_from("web/node_modules/react"));
+        assert!(filter.skips_from("build"));
+        assert!(!filter.skips_from("build/cache"));
+        assert!(!filter.skips_from("src"));
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        let error = GlobFilter::new(&["src/[.rs".to_string()], &[]).unwrap_err();
+        assert!(error.contains("src/[.rs"), "{}", error);
+        assert!(GlobFilter::new(&[], &["{a,b".to_string()]).is_err());
+    }
+}
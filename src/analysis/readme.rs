@@ -0,0 +1,146 @@
+use regex::Regex;
+
+/// Puces de la liste des fonctionnalités conservées au plus
+const MAX_HIGHLIGHTS: usize = 15;
+
+/// Ce que le README dit du projet
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct ReadmeSummary {
+    /// Premier titre de niveau 1
+    pub title: Option<String>,
+    /// Premier paragraphe de texte, sans badges ni balisage
+    pub paragraph: Option<String>,
+    /// Puces de la section « Features » (ou « Fonctionnalités »)
+    pub features: Vec<String>,
+}
+
+impl ReadmeSummary {
+    /// Description d'une ligne : `titre — paragraphe`, ou l'un des deux
+    pub fn description(&self) -> Option<String> {
+        match (&self.title, &self.paragraph) {
+            (Some(title), Some(paragraph)) => Some(format!("{} — {}", title, paragraph)),
+            (Some(text), None) | (None, Some(text)) => Some(text.clone()),
+            (None, None) => None,
+        }
+    }
+}
+
+/// Indique si le fichier est un README
+pub fn is_readme(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    name.to_ascii_uppercase().starts_with("README")
+}
+
+/// Extrait le titre, le premier paragraphe et les fonctionnalités d'un README Markdown
+pub fn analyze_readme(content: &str) -> ReadmeSummary {
+    let inline = InlineMarkup::new();
+    let mut summary = ReadmeSummary::default();
+    let mut paragraph: Vec<String> = Vec::new();
+    let mut in_code_block = false;
+    // Niveau du titre de la section des fonctionnalités en cours de lecture
+    let mut features_level = None;
+
+    for line in content.lines() {
+        let trimmed = line.trim();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        if let Some((level, heading)) = heading(trimmed) {
+            close_paragraph(&mut paragraph, &mut summary);
+            let heading = inline.strip(heading);
+            if level == 1 && summary.title.is_none() && !heading.is_empty() {
+                summary.title = Some(heading.clone());
+            }
+            if features_level.is_some_and(|features_level| level <= features_level) {
+                features_level = None;
+            }
+            let lowercase = heading.to_lowercase();
+            if summary.features.is_empty() && (lowercase.contains("feature") || lowercase.contains("fonctionnalit")) {
+                features_level = Some(level);
+            }
+            continue;
+        }
+
+        if features_level.is_some() {
+            let item = trimmed.strip_prefix("- ").or_else(|| trimmed.strip_prefix("* "));
+            if let Some(item) = item.map(|item| inline.strip(item)).filter(|item| !item.is_empty()) {
+                if summary.features.len() < MAX_HIGHLIGHTS {
+                    summary.features.push(item);
+                }
+            }
+            continue;
+        }
+
+        if summary.paragraph.is_some() {
+            continue;
+        }
+        if trimmed.is_empty() {
+            close_paragraph(&mut paragraph, &mut summary);
+            continue;
+        }
+        // Blocs HTML, tableaux, listes et règles ne sont pas de la prose
+        let prose = trimmed.strip_prefix('>').map(str::trim).unwrap_or(trimmed);
+        if ['<', '|', '-', '*', '='].iter().any(|marker| prose.starts_with(*marker)) {
+            close_paragraph(&mut paragraph, &mut summary);
+            continue;
+        }
+        let text = inline.strip(prose);
+        if !text.is_empty() {
+            paragraph.push(text);
+        }
+    }
+    close_paragraph(&mut paragraph, &mut summary);
+    summary
+}
+
+/// Niveau et texte d'un titre ATX (`## Titre`)
+fn heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let text = &line[level..];
+    (text.is_empty() || text.starts_with(' ')).then(|| (level, text.trim().trim_end_matches('#').trim()))
+}
+
+/// Termine le paragraphe en cours ; le premier non vide devient la description
+fn close_paragraph(paragraph: &mut Vec<String>, summary: &mut ReadmeSummary) {
+    if summary.paragraph.is_none() && !paragraph.is_empty() {
+        summary.paragraph = Some(paragraph.join(" "));
+    }
+    paragraph.clear();
+}
+
+/// Retrait du balisage en ligne : badges et images supprimés, liens réduits à leur
+/// texte, emphase et code retirés
+struct InlineMarkup {
+    badge: Regex,
+    image: Regex,
+    link: Regex,
+}
+
+impl InlineMarkup {
+    fn new() -> Self {
+        Self {
+            badge: Regex::new(r"\[!\[[^\]]*\]\([^)]*\)\]\([^)]*\)").unwrap(),
+            image: Regex::new(r"!\[[^\]]*\]\([^)]*\)").unwrap(),
+            link: Regex::new(r"\[([^\]]*)\]\([^)]*\)").unwrap(),
+        }
+    }
+
+    fn strip(&self, text: &str) -> String {
+        let text = self.badge.replace_all(text, "");
+        let text = self.image.replace_all(&text, "");
+        let text = self.link.replace_all(&text, "$1");
+        let text = text.replace("**", "").replace('`', "");
+        // Les emojis décoratifs en tête des titres et des puces sont écartés
+        text.trim_start_matches(|c: char| !c.is_alphanumeric() && !c.is_ascii_punctuation())
+            .trim()
+            .to_string()
+    }
+}
@@ -0,0 +1,272 @@
+use regex::Regex;
+
+use crate::types::analysis::{ChangelogEntry, ReleaseReadiness};
+
+/// Nombre d'entrées de changelog conservées
+const CHANGELOG_ENTRIES: usize = 3;
+
+/// Clé de tri semver : version, version finale (après ses pré-versions), identifiants
+/// de pré-version
+type SemverKey = ((u64, u64, u64), bool, Vec<(bool, u64, String)>);
+
+/// Identifiants d'une pré-version comparables selon semver : les numériques par valeur
+/// et avant les alphanumériques, comparés en ASCII
+fn pre_release_key(pre_release: &str) -> Vec<(bool, u64, String)> {
+    pre_release
+        .split('.')
+        .map(|identifier| match identifier.parse::<u64>() {
+            Ok(number) => (false, number, String::new()),
+            Err(_) => (true, 0, identifier.to_string()),
+        })
+        .collect()
+}
+
+/// Détecte les marqueurs de stabilité d'API et de publication
+pub struct ReleaseDetector {
+    non_exhaustive_pattern: Regex,
+    pub_type_pattern: Regex,
+    stability_pattern: Regex,
+    changelog_pattern: Regex,
+    date_pattern: Regex,
+    rust_version_pattern: Regex,
+    semver_pattern: Regex,
+}
+
+impl Default for ReleaseDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ReleaseDetector {
+    pub fn new() -> Self {
+        Self {
+            non_exhaustive_pattern: Regex::new(r"^#\[non_exhaustive\]").unwrap(),
+            pub_type_pattern: Regex::new(r"^pub\s+(?:struct|enum)\s+\w+").unwrap(),
+            stability_pattern: Regex::new(r"^#\[((?:rustc_)?(?:stable|unstable)\b[^\]]*)\]").unwrap(),
+            changelog_pattern: Regex::new(
+                r"^#{1,3}\s*\[?v?(\d+\.\d+(?:\.\d+)?(?:-[0-9A-Za-z.-]+)?)\]?(.*)$"
+            ).unwrap(),
+            date_pattern: Regex::new(r"(\d{4}-\d{2}-\d{2})").unwrap(),
+            rust_version_pattern: Regex::new(r#"^rust-version\s*=\s*"([^"]+)""#).unwrap(),
+            semver_pattern: Regex::new(r"^v?(\d+)\.(\d+)\.(\d+)(?:-([0-9A-Za-z.-]+))?(?:\+[0-9A-Za-z.-]+)?$").unwrap(),
+        }
+    }
+
+    /// Analyse un fichier source Rust (non_exhaustive, attributs de stabilité)
+    pub fn scan_source(&self, content: &str, readiness: &mut ReleaseReadiness) {
+        let mut pending_non_exhaustive = false;
+
+        for line in content.lines() {
+            let line = line.trim();
+
+            if self.non_exhaustive_pattern.is_match(line) {
+                pending_non_exhaustive = true;
+                continue;
+            }
+
+            if let Some(captures) = self.stability_pattern.captures(line) {
+                let attribute = captures[1].to_string();
+                if !readiness.stability_attributes.contains(&attribute) {
+                    readiness.stability_attributes.push(attribute);
+                }
+                continue;
+            }
+
+            // Les autres attributs et la documentation peuvent séparer #[non_exhaustive]
+            // de la déclaration
+            if line.starts_with("#[") || line.starts_with("///") || line.is_empty() {
+                continue;
+            }
+
+            if pending_non_exhaustive && self.pub_type_pattern.is_match(line) {
+                readiness.non_exhaustive_public_types += 1;
+            }
+            pending_non_exhaustive = false;
+        }
+    }
+
+    /// Analyse un fichier CHANGELOG et conserve les dernières entrées
+    pub fn scan_changelog(&self, content: &str, readiness: &mut ReleaseReadiness) {
+        let entries: Vec<ChangelogEntry> = content
+            .lines()
+            .filter_map(|line| self.changelog_pattern.captures(line.trim()))
+            .map(|captures| ChangelogEntry {
+                version: captures[1].to_string(),
+                date: self.date_pattern
+                    .captures(&captures[2])
+                    .map(|date| date[1].to_string()),
+            })
+            .take(CHANGELOG_ENTRIES)
+            .collect();
+
+        if !entries.is_empty() {
+            readiness.changelog_maintained = true;
+            readiness.changelog_entries = entries;
+        }
+    }
+
+    /// Extrait la déclaration `rust-version` d'un Cargo.toml
+    pub fn scan_manifest(&self, content: &str, readiness: &mut ReleaseReadiness) {
+        if let Some(version) = content
+            .lines()
+            .find_map(|line| self.rust_version_pattern.captures(line.trim()))
+        {
+            readiness.rust_version = Some(version[1].to_string());
+        }
+    }
+
+    /// Conserve les tags ressemblant à des versions semver, du plus récent au plus ancien ;
+    /// une pré-version (`1.0.0-rc.1`) précède la version finale correspondante
+    pub fn record_tags(&self, tags: &[String], readiness: &mut ReleaseReadiness) {
+        let mut versions: Vec<(SemverKey, String)> = tags
+            .iter()
+            .filter_map(|tag| {
+                let captures = self.semver_pattern.captures(tag)?;
+                let part = |i: usize| captures[i].parse::<u64>().ok();
+                let pre_release = captures.get(4).map(|pre| pre_release_key(pre.as_str()));
+                let key = ((part(1)?, part(2)?, part(3)?), pre_release.is_none(), pre_release.unwrap_or_default());
+                Some((key, tag.clone()))
+            })
+            .collect();
+        versions.sort_by(|(a, _), (b, _)| b.cmp(a));
+
+        readiness.semver_tags = versions.into_iter().map(|(_, tag)| tag).collect();
+    }
+
+    /// Détermine la dernière version publiée et sa date
+    pub fn finalize(&self, readiness: &mut ReleaseReadiness) {
+        let changelog_latest = readiness.changelog_entries.first();
+
+        readiness.latest_version = readiness.semver_tags
+            .first()
+            .map(|tag| tag.trim_start_matches('v').to_string())
+            .or_else(|| changelog_latest.map(|entry| entry.version.clone()));
+
+        readiness.latest_release_date = readiness.latest_version.as_ref().and_then(|version| {
+            readiness.changelog_entries
+                .iter()
+                .find(|entry| &entry.version == version)
+                .and_then(|entry| entry.date.clone())
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tags(names: &[&str]) -> Vec<String> {
+        names.iter().map(|name| name.to_string()).collect()
+    }
+
+    #[test]
+    fn keep_a_changelog_skips_unreleased() {
+        let detector = ReleaseDetector::new();
+        let changelog = "# Changelog\n\
+            All notable changes to this project will be documented in this file.\n\n\
+            ## [Unreleased]\n### Added\n- Something new\n\n\
+            ## [1.2.0] - 2024-03-01\n### Fixed\n- A bug\n\n\
+            ## [1.1.0] - 2024-01-15\n\n\
+            ## [1.0.0-rc.1] - 2023-12-01\n\n\
+            ## [0.9.0] - 2023-10-01\n\n\
+            [Unreleased]: https://example.com/compare/v1.2.0...HEAD\n\
+            [1.2.0]: https://example.com/compare/v1.1.0...v1.2.0\n";
+        let mut readiness = ReleaseReadiness::default();
+        detector.scan_changelog(changelog, &mut readiness);
+
+        assert!(readiness.changelog_maintained);
+        let entries: Vec<(&str, Option<&str>)> = readiness.changelog_entries
+            .iter()
+            .map(|entry| (entry.version.as_str(), entry.date.as_deref()))
+            .collect();
+        assert_eq!(entries, [
+            ("1.2.0", Some("2024-03-01")),
+            ("1.1.0", Some("2024-01-15")),
+            ("1.0.0-rc.1", Some("2023-12-01")),
+        ]);
+
+        detector.finalize(&mut readiness);
+        assert_eq!(readiness.latest_version.as_deref(), Some("1.2.0"));
+        assert_eq!(readiness.latest_release_date.as_deref(), Some("2024-03-01"));
+    }
+
+    #[test]
+    fn changelog_without_unreleased_section() {
+        let detector = ReleaseDetector::new();
+        let mut readiness = ReleaseReadiness::default();
+        detector.scan_changelog("# Changes\n\n## v0.3.1 (2023-05-02)\n- fix\n\n## 0.3.0\n- initial\n", &mut readiness);
+
+        let versions: Vec<&str> = readiness.changelog_entries.iter().map(|entry| entry.version.as_str()).collect();
+        assert_eq!(versions, ["0.3.1", "0.3.0"]);
+        assert_eq!(readiness.changelog_entries[1].date, None);
+
+        let mut empty = ReleaseReadiness::default();
+        detector.scan_changelog("# Changelog\n\n## [Unreleased]\n- wip\n", &mut empty);
+        assert!(!empty.changelog_maintained);
+        assert!(empty.changelog_entries.is_empty());
+    }
+
+    #[test]
+    fn semver_tags_sort_with_pre_releases_before_their_release() {
+        let detector = ReleaseDetector::new();
+        let mut readiness = ReleaseReadiness::default();
+        detector.record_tags(&tags(&[
+            "v1.0.0-rc.2", "v0.9.0", "v1.0.0", "v1.0.0-rc.10", "v1.0.0-alpha", "v1.0.0-alpha.1",
+            "v0.10.0", "nightly", "v1.0", "release-2", "v1.0.0-beta", "v1.0.1+build.5",
+        ]), &mut readiness);
+
+        assert_eq!(readiness.semver_tags, [
+            "v1.0.1+build.5", "v1.0.0", "v1.0.0-rc.10", "v1.0.0-rc.2", "v1.0.0-beta",
+            "v1.0.0-alpha.1", "v1.0.0-alpha", "v0.10.0", "v0.9.0",
+        ]);
+
+        detector.finalize(&mut readiness);
+        assert_eq!(readiness.latest_version.as_deref(), Some("1.0.1+build.5"));
+    }
+
+    #[test]
+    fn non_exhaustive_counts_public_types_only() {
+        let detector = ReleaseDetector::new();
+        let source = r#"
+#[non_exhaustive]
+#[derive(Debug, Clone)]
+pub enum Error {
+    Io,
+}
+
+#[non_exhaustive]
+/// Documented after the attribute
+pub struct Options {
+    pub verbose: bool,
+}
+
+#[non_exhaustive]
+enum Private {
+    A,
+}
+
+#[non_exhaustive]
+pub(crate) enum Internal {
+    A,
+}
+
+pub enum Exhaustive {
+    A,
+}
+
+    #[non_exhaustive]
+    pub enum Nested {
+        A,
+    }
+
+#[stable(feature = "core", since = "1.0.0")]
+pub fn stable_api() {}
+"#;
+        let mut readiness = ReleaseReadiness::default();
+        detector.scan_source(source, &mut readiness);
+
+        assert_eq!(readiness.non_exhaustive_public_types, 3);
+        assert_eq!(readiness.stability_attributes, [r#"stable(feature = "core", since = "1.0.0")"#]);
+    }
+}
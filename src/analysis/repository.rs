@@ -1,20 +1,94 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Mutex;
 use async_recursion::async_recursion;
 
+#[cfg(feature = "archive")]
+use crate::analysis::archive::{Archive, ArchiveLimits};
+#[cfg(feature = "git")]
+use crate::analysis::clone::GitClone;
+
 use crate::{
     error::GithubAnalyzerError,
     types::{
-        analysis::{ProjectSummary, ProjectOverview, RepositoryStructure, FileSummary},
-        github::GithubContent,
+        analysis::{
+            ApiModule, BinaryFile, ComplexFunction, Dependency, LineCounts, DirectoryStats, ProjectSummary, ProjectOverview, RepositoryStructure, FileSummary, Provenance,
+            Hotspot, Hotspots, LanguageShare, PullRequestFile, PullRequestSummary, RateLimitUsage, ReleaseReadiness, SkipReason,
+            Import, ImportKind, MethodSignature, SkippedFile, SourceKind, Submodule, Symlink, UnsafeLocation,
+        },
+        github::{GithubContent, GithubRateLimitStatus, GithubRepoInfo, GithubTree},
+        locator::RepoLocator,
         FileCategory,
     },
-    api::client::GithubClient,
-    analysis::file::{categorize_file, FileAnalyzer},
+    api::client::CONTENTS_API_MAX_SIZE,
+    api::provider::RepositoryProvider,
+    api::scheduler::SizeAwareScheduler,
+    analysis::api_surface::public_items,
+    analysis::blob_cache::{BlobCache, CachedAnalysis},
+    analysis::boilerplate::HeaderDetector,
+    analysis::build_script::{analyze_build_script, is_build_script},
+    analysis::cargo_manifest::{cargo_dependencies, declared_features, package_name, proc_macro_package},
+    analysis::config::{is_config_template, ConfigCollector},
+    analysis::conversions::{link_conversions, ConversionCollector},
+    analysis::errors::error_taxonomy,
+    analysis::directory_tree::render_directory_tree,
+    analysis::features::FeatureGates,
+    analysis::targets::{conventional_target, TargetKind},
+    analysis::todos::scan_todos,
+    analysis::workspace::Workspace,
+    analysis::ci::analyze_ci,
+    analysis::file::{categorize_path, close_type_relations, is_binary_content, language_name, merge_type_relations, rank_key_types, truncate_doc, type_cycles, FileAnalyzer},
+    analysis::heuristic::{has_language_analyzer, HeuristicAnalyzer},
+    analysis::idioms::IdiomCollector,
+    analysis::macros::MacroCollector,
+    analysis::imports::CrateUsage,
+    analysis::index::TypeLocator,
+    analysis::intern::StringInterner,
+    analysis::license::{detect_license_text, is_license_file, manifest_license},
+    analysis::links::{github_submodule_url, parse_gitmodules, resolve_symlink, SYMLINK_MODE},
+    analysis::local::LocalTree,
+    analysis::modules::{module_path, ModuleGraph, ModuleTree},
+    analysis::options::{AnalysisOptions, BudgetPolicy},
+    analysis::path_filter::{GlobFilter, PathFilter},
+    analysis::readme::{analyze_readme, is_readme},
+    analysis::release::ReleaseDetector,
+    analysis::source::SourceTree,
+    analysis::test_count::is_integration_test,
+    analysis::unsafe_code::UnsafeScan,
+    analysis::usage::UsageIndex,
 };
 
+/// Fichiers retenus pour chaque mesure de `Hotspots`
+const HOTSPOT_ENTRIES: usize = 10;
+
+/// Résultats de l'analyse d'un fichier, intégrés au résumé du projet
+struct AnalyzedFile<'a> {
+    summary: &'a str,
+    type_relations: Vec<crate::types::analysis::TypeRelations>,
+    method_signatures: Vec<crate::types::analysis::MethodSignature>,
+    configuration: crate::types::analysis::Configuration,
+}
+
 pub struct RepositoryAnalyzer {
-    client: GithubClient,
+    client: Box<dyn RepositoryProvider>,
     file_analyzer: FileAnalyzer,
+    heuristic_analyzer: HeuristicAnalyzer,
+    release_detector: ReleaseDetector,
+    type_locator: TypeLocator,
+    interner: Mutex<StringInterner>,
+    header_detector: Mutex<HeaderDetector>,
+    config_collector: Mutex<ConfigCollector>,
+    module_graph: Mutex<ModuleGraph>,
+    module_tree: Mutex<ModuleTree>,
+    idioms: Mutex<IdiomCollector>,
+    macros: Mutex<MacroCollector>,
+    conversions: Mutex<ConversionCollector>,
+    crate_usage: Mutex<CrateUsage>,
+    usage: Mutex<UsageIndex>,
+    feature_gates: Mutex<FeatureGates>,
+    workspace: Mutex<Workspace>,
+    blob_cache: Option<BlobCache>,
+    glob_filter: GlobFilter,
+    options: AnalysisOptions,
 }
 
 impl Default for RepositoryAnalyzer {
@@ -25,18 +99,124 @@ impl Default for RepositoryAnalyzer {
 
 impl RepositoryAnalyzer {
     pub fn new() -> Self {
+        Self::with_options(AnalysisOptions::default())
+    }
+
+    /// Analyseur GitHub, ou de la forge imposée par `options.provider`
+    pub fn with_options(options: AnalysisOptions) -> Self {
+        Self::with_provider(options.provider_client(options.provider.unwrap_or_default()), options)
+    }
+
+    /// Analyseur adapté à la forge du dépôt (option `provider`, sinon hôte de l'URL)
+    pub fn for_repository(repo_url: &str, options: AnalysisOptions) -> Self {
+        Self::with_provider(options.repository_provider(repo_url), options)
+    }
+
+    /// Analyseur utilisant un client de forge donné
+    pub fn with_provider(client: Box<dyn RepositoryProvider>, options: AnalysisOptions) -> Self {
         Self {
-            client: GithubClient::new(),
-            file_analyzer: FileAnalyzer::new(),
+            client,
+            file_analyzer: FileAnalyzer::with_summary_limit(options.summary_entries),
+            heuristic_analyzer: HeuristicAnalyzer::new(),
+            release_detector: ReleaseDetector::new(),
+            type_locator: TypeLocator::new(),
+            interner: Mutex::new(StringInterner::new()),
+            header_detector: Mutex::new(HeaderDetector::new()),
+            config_collector: Mutex::new(ConfigCollector::new()),
+            module_graph: Mutex::new(ModuleGraph::new()),
+            module_tree: Mutex::new(ModuleTree::new()),
+            idioms: Mutex::new(IdiomCollector::new()),
+            macros: Mutex::new(MacroCollector::new()),
+            conversions: Mutex::new(ConversionCollector::new()),
+            crate_usage: Mutex::new(CrateUsage::new()),
+            usage: Mutex::new(UsageIndex::new()),
+            feature_gates: Mutex::new(FeatureGates::new()),
+            workspace: Mutex::new(Workspace::new()),
+            blob_cache: options.blob_cache(),
+            // Motifs déjà validés à la lecture de la ligne de commande
+            glob_filter: options.glob_filter().unwrap_or_default(),
+            options,
         }
     }
 
-    /// Analyse un dépôt GitHub complet
+    /// Analyse un dépôt GitHub complet, ou les fichiers d'une pull request
     pub async fn analyze(&self, repo_url: &str) -> Result<ProjectSummary, GithubAnalyzerError> {
+        let locator = RepoLocator::parse_as(repo_url, self.options.provider).ok();
+        if let Some(number) = locator.and_then(|locator| locator.pull_request) {
+            return self.analyze_pull_request(repo_url, number).await;
+        }
+
+        if self.options.tarball {
+            #[cfg(feature = "archive")]
+            return self.analyze_tarball(repo_url).await.map(|(summary, _)| summary);
+            #[cfg(not(feature = "archive"))]
+            return Err(GithubAnalyzerError::ArchiveError(
+                "Tarball mode requires the `archive` feature".to_string()
+            ));
+        }
+
+        if self.options.clone {
+            #[cfg(feature = "git")]
+            return self.analyze_clone(repo_url).await.map(|(summary, _, _)| summary);
+            #[cfg(not(feature = "git"))]
+            return Err(GithubAnalyzerError::NetworkError(
+                "Clone mode requires the `git` feature".to_string()
+            ));
+        }
+
+        // Métadonnées du dépôt ; l'API suit la redirection d'un dépôt renommé ou transféré
+        // et renvoie son nom actuel, sous lequel l'analyse se poursuit
+        let info = match self.client.get_repo_info(repo_url).await {
+            // Dépôt introuvable ou token refusé : inutile d'essayer d'autres branches
+            Err(e @ (GithubAnalyzerError::NotFound(_) | GithubAnalyzerError::AuthError(_))) => return Err(e),
+            info => info,
+        };
+        let metadata = info.as_ref().ok().map(GithubRepoInfo::metadata);
+        let mut summary = self.analyze_with_info(repo_url, info).await?;
+        summary.metadata = metadata;
+        Ok(summary)
+    }
+
+    /// Analyse la référence demandée, sinon la branche par défaut, sinon main puis master
+    async fn analyze_with_info(
+        &self,
+        repo_url: &str,
+        info: Result<GithubRepoInfo, GithubAnalyzerError>,
+    ) -> Result<ProjectSummary, GithubAnalyzerError> {
+        let locator = RepoLocator::parse_as(repo_url, self.options.provider)?;
+        let canonical_url = info
+            .as_ref()
+            .ok()
+            .and_then(|info| locator.moved_to(&info.full_name))
+            .map(|moved| {
+                println!("Repository {}/{} has moved to {}/{}", locator.owner, locator.repo, moved.owner, moved.repo);
+                moved.to_string()
+            });
+        let repo_url = canonical_url.as_deref().unwrap_or(repo_url);
+
+        // Une référence explicite (option ou URL) n'est jamais remplacée par la branche par défaut
+        if let Some(git_ref) = self.options.git_ref.as_ref().or(locator.git_ref.as_ref()) {
+            return self.try_analyze_branch(repo_url, git_ref).await.map_err(|e| match e {
+                GithubAnalyzerError::NotFound(message) => GithubAnalyzerError::NotFound(format!(
+                    "Reference '{}' does not exist: {}", git_ref, message
+                )),
+                GithubAnalyzerError::NetworkError(_) => GithubAnalyzerError::NetworkError(format!(
+                    "Reference '{}' does not exist or is not accessible: {}", git_ref, e
+                )),
+                e => e,
+            });
+        }
+
+        // Branche par défaut déclarée par le dépôt (develop, trunk, ...)
+        match info {
+            Ok(info) => return self.try_analyze_branch(repo_url, &info.default_branch).await,
+            Err(e) => println!("Warning: Could not read the default branch, trying main then master: {}", e),
+        }
+
         let branches = ["main", "master"];
         let mut last_error = None;
         
-        // Essaie chaque branche jusqu'à ce qu'une fonctionne
+        // En dernier recours, essaie chaque branche jusqu'à ce qu'une fonctionne
         for branch in branches {
             match self.try_analyze_branch(repo_url, branch).await {
                 Ok(summary) => return Ok(summary),
@@ -49,13 +229,26 @@ impl RepositoryAnalyzer {
         ))
     }
 
-    /// Tente d'analyser une branche spécifique du dépôt
-    async fn try_analyze_branch(
-        &self,
-        repo_url: &str,
-        branch: &str,
-    ) -> Result<ProjectSummary, GithubAnalyzerError> {
-        let mut project_summary = ProjectSummary {
+    /// Sous-répertoire à analyser : option `--path`, sinon chemin de l'URL
+    /// (`.../tree/<ref>/<sous-chemin>`)
+    fn subpath_for(&self, repo_url: &str) -> Option<String> {
+        self.options.subpath
+            .clone()
+            .or_else(|| RepoLocator::parse_as(repo_url, self.options.provider).ok()?.subpath)
+            .map(|subpath| subpath.trim_matches('/').to_string())
+            .filter(|subpath| !subpath.is_empty())
+    }
+
+    /// Limites de décompression : la taille maximale d'un fichier suit `max_file_size`
+    #[cfg(feature = "archive")]
+    fn archive_limits(&self) -> ArchiveLimits {
+        ArchiveLimits { max_file_size: self.options.max_file_size as u64, ..Default::default() }
+    }
+
+    /// Construit un résumé vide pour une source et une référence données
+    pub(crate) fn empty_summary(repo_url: &str, branch: &str) -> ProjectSummary {
+        ProjectSummary {
+            schema_version: crate::types::SCHEMA_VERSION.to_string(),
             repo_url: repo_url.to_string(),
             files_analyzed: Vec::new(),
             total_files: 0,
@@ -66,9 +259,14 @@ impl RepositoryAnalyzer {
                 total_public_types: 0,
                 total_public_functions: 0,
                 total_tests: 0,
+                unit_tests: 0,
+                integration_tests: 0,
+                integration_test_files: 0,
                 main_modules: Vec::new(),
+                module_tree: Vec::new(),
                 key_types: Vec::new(),
                 dependencies: Vec::new(),
+                external_crates_used: Vec::new(),
                 type_relations: Vec::new(),
                 method_signatures: Vec::new(),
                 configuration: crate::types::analysis::Configuration {
@@ -76,6 +274,29 @@ impl RepositoryAnalyzer {
                     feature_flags: Vec::new(),
                     custom_attributes: Vec::new(),
                 },
+                total_heuristic_files: 0,
+                total_heuristic_definitions: 0,
+                async_functions: 0,
+                directory_stats: Vec::new(),
+                description: None,
+                readme_highlights: Vec::new(),
+                dependency_cycles: Vec::new(),
+                lines_by_language: BTreeMap::new(),
+                unsafe_usage: Default::default(),
+                panic_points: Default::default(),
+                macros: Vec::new(),
+                proc_macro_crates: Vec::new(),
+                complex_functions: Vec::new(),
+                conversions: Vec::new(),
+                possibly_unused: Vec::new(),
+                feature_map: BTreeMap::new(),
+                declared_features: Vec::new(),
+                workspace_members: Vec::new(),
+                crate_metadata: None,
+                binary_targets: 0,
+                example_targets: 0,
+                bench_targets: 0,
+                error_types: Default::default(),
             },
             repository_structure: RepositoryStructure {
                 has_src_directory: false,
@@ -84,103 +305,1144 @@ impl RepositoryAnalyzer {
                 primary_language: None,
                 build_systems: Vec::new(),
                 branch_analyzed: branch.to_string(),
+                languages: Vec::new(),
+                skipped_directories: Vec::new(),
+                language_breakdown: Vec::new(),
+                ci_summary: None,
+                license: None,
+                submodules: Vec::new(),
+                symlinks: Vec::new(),
+                has_build_script: false,
+                build_script: None,
             },
+            release_readiness: ReleaseReadiness::default(),
+            type_index: BTreeMap::new(),
+            run_metrics: Default::default(),
+            boilerplate_headers: Vec::new(),
+            provenance: Default::default(),
+            config_matrix: Vec::new(),
+            metadata: None,
+            subpath: None,
+            pull_request: None,
+            filtered_files: 0,
+            skipped_files: Vec::new(),
+            binary_files: Vec::new(),
+            directory_tree: String::new(),
+            hotspots: Hotspots::default(),
+            api_surface: Vec::new(),
+            todos: Vec::new(),
+        }
+    }
+
+    /// Analyse une archive locale (.tar.gz ou .zip) sans accès réseau
+    #[cfg(feature = "archive")]
+    pub async fn analyze_archive(
+        &self,
+        path: &std::path::Path,
+    ) -> Result<(ProjectSummary, Archive), GithubAnalyzerError> {
+        let archive = Archive::open(path, self.archive_limits())?;
+        let file_name = path.file_name().and_then(|name| name.to_str()).unwrap_or("archive");
+        let label = [".tar.gz", ".tgz", ".tar", ".zip"]
+            .iter()
+            .find_map(|ext| file_name.strip_suffix(ext))
+            .unwrap_or(file_name);
+
+        let mut project_summary = Self::empty_summary(label, "archive");
+        project_summary.provenance = Provenance {
+            source_kind: SourceKind::Archive,
+            archive_path: Some(path.display().to_string()),
+            archive_sha256: Some(archive.sha256.clone()),
+            commit_sha: None,
+        };
+
+        self.ingest_source(&archive, &mut project_summary).await;
+        self.finalize_analysis(&mut project_summary);
+
+        Ok((project_summary, archive))
+    }
+
+    /// Analyse un dépôt GitHub à partir de son archive tarball (une seule requête)
+    #[cfg(feature = "archive")]
+    pub async fn analyze_tarball(
+        &self,
+        repo_url: &str,
+    ) -> Result<(ProjectSummary, Archive), GithubAnalyzerError> {
+        let locator = RepoLocator::parse_as(repo_url, self.options.provider)?;
+        let git_ref = self.options.git_ref.as_deref().or(locator.git_ref.as_deref());
+
+        let bytes = self.client.get_tarball(repo_url, git_ref).await?;
+        let archive = Archive::from_bytes(&bytes, self.archive_limits())?;
+
+        let mut project_summary = Self::empty_summary(repo_url, git_ref.unwrap_or("HEAD"));
+        project_summary.provenance = Provenance {
+            source_kind: SourceKind::GithubTarball,
+            archive_path: None,
+            archive_sha256: Some(archive.sha256.clone()),
+            commit_sha: None,
         };
+        project_summary.subpath = self.subpath_for(repo_url);
+
+        self.ingest_source(&archive, &mut project_summary).await;
+        self.record_tags(repo_url, &mut project_summary).await;
+        self.record_languages(repo_url, &mut project_summary).await;
+        self.finalize_analysis(&mut project_summary);
+        project_summary.run_metrics.api_requests = self.client.api_requests();
+        project_summary.run_metrics.rate_limit_waits = self.client.rate_limit_waits();
+
+        Ok((project_summary, archive))
+    }
+
+    /// Analyse un répertoire local sans accès réseau
+    pub async fn analyze_local(
+        &self,
+        path: &std::path::Path,
+    ) -> Result<(ProjectSummary, LocalTree), GithubAnalyzerError> {
+        let tree = LocalTree::open(path)?;
+
+        let mut project_summary = Self::empty_summary(&tree.root_url(), "local");
+        project_summary.provenance = Provenance {
+            source_kind: SourceKind::Local,
+            archive_path: None,
+            archive_sha256: None,
+            commit_sha: None,
+        };
+
+        self.ingest_source(&tree, &mut project_summary).await;
+        self.finalize_analysis(&mut project_summary);
+
+        Ok((project_summary, tree))
+    }
+
+    /// Analyse un clone superficiel du dépôt (SSH ou HTTPS), sans appel à l'API.
+    ///
+    /// Le clone est renvoyé pour l'export et supprimé quand il est détruit, sauf
+    /// avec `keep_clone`.
+    #[cfg(feature = "git")]
+    pub async fn analyze_clone(
+        &self,
+        repo_url: &str,
+    ) -> Result<(ProjectSummary, LocalTree, GitClone), GithubAnalyzerError> {
+        let locator = RepoLocator::parse_as(repo_url, self.options.provider)?;
+        let git_ref = self.options.git_ref.as_deref().or(locator.git_ref.as_deref());
+        let token = match crate::api::token::discover(self.options.token.as_deref())? {
+            Some(token) => Some(token),
+            None => crate::api::token::discover_gh_cli(&locator.host)?,
+        };
+
+        let clone = GitClone::shallow(
+            repo_url, &locator, git_ref, token.as_ref().map(|token| token.token.as_str()), self.options.keep_clone,
+        ).await?;
+        println!("Cloned {} at {} ({})", repo_url, clone.branch, clone.head_sha);
+
+        // Les URLs des fichiers pointent vers le commit analysé, pas vers le clone temporaire
+        let tree = LocalTree::open(clone.dir())?.with_url_base(locator.blob_url(&clone.head_sha));
+        let mut project_summary = Self::empty_summary(repo_url, &clone.branch);
+        project_summary.provenance = Provenance {
+            source_kind: SourceKind::GitClone,
+            archive_path: None,
+            archive_sha256: None,
+            commit_sha: Some(clone.head_sha.clone()),
+        };
+        project_summary.subpath = self.subpath_for(repo_url);
+
+        self.ingest_source(&tree, &mut project_summary).await;
+        self.finalize_analysis(&mut project_summary);
+
+        Ok((project_summary, tree, clone))
+    }
+
+    /// Analyse les seuls fichiers modifiés par une pull request, lus à son commit de tête.
+    ///
+    /// Les fichiers supprimés figurent dans `pull_request.files` mais ne sont ni
+    /// téléchargés ni analysés.
+    pub async fn analyze_pull_request(
+        &self,
+        repo_url: &str,
+        number: u64,
+    ) -> Result<ProjectSummary, GithubAnalyzerError> {
+        let pull = self.client.get_pull_request(repo_url, number).await?;
+        let changed = self.client.get_pull_request_files(repo_url, number).await?;
+        println!("Pull request #{}: {} ({} changed files)", pull.number, pull.title, changed.len());
+
+        let mut project_summary = Self::empty_summary(repo_url, &pull.head.sha);
+        project_summary.provenance = Provenance {
+            source_kind: SourceKind::PullRequest,
+            archive_path: None,
+            archive_sha256: None,
+            commit_sha: Some(pull.head.sha.clone()),
+        };
+        project_summary.metadata = self.client.get_repo_info(repo_url).await.ok().as_ref().map(GithubRepoInfo::metadata);
+
+        let mut files = Vec::new();
+        for file in changed.iter().filter(|file| file.status != "removed") {
+            if !self.glob_filter.accepts(&file.filename) {
+                project_summary.filtered_files += 1;
+                continue;
+            }
+            let mut path = file.filename.as_str();
+            while let Some((parent, _)) = path.rsplit_once('/') {
+                self.record_directory(parent, &mut project_summary);
+                path = parent;
+            }
+            files.push(GithubContent {
+                name: file.filename.rsplit('/').next().unwrap_or(&file.filename).to_string(),
+                url: self.client.contents_url(repo_url, &file.filename, &pull.head.sha)?,
+                path: file.filename.clone(),
+                sha: String::new(),
+                size: 0,
+                content: None,
+                encoding: None,
+                download_url: None,
+                content_type: "file".to_string(),
+            });
+        }
+        self.process_files(files, HashMap::new(), &mut project_summary).await;
+        self.finalize_analysis(&mut project_summary);
+
+        project_summary.pull_request = Some(PullRequestSummary {
+            number: pull.number,
+            title: pull.title,
+            body: pull.body,
+            base_ref: pull.base.git_ref,
+            head_ref: pull.head.git_ref,
+            head_sha: pull.head.sha,
+            files: changed
+                .into_iter()
+                .map(|file| PullRequestFile {
+                    path: file.filename,
+                    status: file.status,
+                    additions: file.additions,
+                    deletions: file.deletions,
+                    previous_path: file.previous_filename,
+                })
+                .collect(),
+        });
+        project_summary.run_metrics.api_requests = self.client.api_requests();
+        project_summary.run_metrics.rate_limit_waits = self.client.rate_limit_waits();
+        Ok(project_summary)
+    }
+
+    /// Fait passer les fichiers d'une source locale (archive, répertoire) par le
+    /// pipeline d'analyse habituel, limités au sous-répertoire du résumé s'il y en a un
+    pub(crate) async fn ingest_source(&self, source: &dyn SourceTree, project_summary: &mut ProjectSummary) {
+        let gitignore = source.files()
+            .iter()
+            .position(|file| file.path == ".gitignore")
+            .and_then(|index| source.read_text(index));
+        let filter = self.path_filter(gitignore.as_deref());
+
+        for directory in source.directories() {
+            if filter.skips(&directory, true) {
+                if filter.skips_from(&directory) && project_summary.relative_path(&directory).is_some() {
+                    project_summary.repository_structure.skipped_directories.push(directory);
+                }
+                continue;
+            }
+            if filter.filters_out_dir(&directory) {
+                continue;
+            }
+            if let Some(directory) = project_summary.relative_path(&directory).map(str::to_string) {
+                self.record_directory(&directory, project_summary);
+            }
+        }
+
+        for (index, file) in source.files().into_iter().enumerate() {
+            if filter.skips(&file.path, false) {
+                continue;
+            }
+            let Some(path) = project_summary.relative_path(&file.path).map(str::to_string) else {
+                continue;
+            };
+            if filter.filters_out(&file.path) {
+                project_summary.filtered_files += 1;
+                continue;
+            }
+            if file.size > self.options.max_file_size as u64 {
+                project_summary.skipped_files.push(SkippedFile { path, size: file.size, reason: SkipReason::TooLarge });
+                continue;
+            }
+            let content = GithubContent {
+                name: file.name().to_string(),
+                url: source.url(&file.path),
+                path,
+                sha: String::new(),
+                size: file.size.min(i32::MAX as u64) as i32,
+                content: None,
+                encoding: None,
+                download_url: None,
+                content_type: "file".to_string(),
+            };
+            // Seuls les fichiers dont le contenu est analysé sont lus
+            let bytes = Self::needs_content(&categorize_path(&file.path))
+                .then(|| source.read_bytes(index))
+                .flatten();
+            let text = match bytes {
+                Some(bytes) if is_binary_content(&bytes) => {
+                    Self::record_binary(&content, project_summary);
+                    None
+                },
+                Some(bytes) => String::from_utf8(bytes).ok(),
+                None => None,
+            };
+            self.ingest_file(&content, text, project_summary).await;
+        }
+    }
+
+    /// Tente d'analyser une branche spécifique du dépôt
+    async fn try_analyze_branch(
+        &self,
+        repo_url: &str,
+        branch: &str,
+    ) -> Result<ProjectSummary, GithubAnalyzerError> {
+        let mut project_summary = Self::empty_summary(repo_url, branch);
+        project_summary.subpath = self.subpath_for(repo_url);
+        let quota = self.read_quota(repo_url).await;
+
+        // Liste complète en une requête, avec repli sur le parcours récursif
+        match self.client.get_repo_tree(repo_url, branch).await {
+            Ok(mut tree) if !tree.truncated => {
+                tree.tree.retain(|entry| project_summary.relative_path(&entry.path).is_some());
+                if let (Some(subpath), true) = (&project_summary.subpath, tree.tree.is_empty()) {
+                    return Err(GithubAnalyzerError::NotFound(format!(
+                        "Path '{}' does not exist on '{}'", subpath, branch
+                    )));
+                }
+                let has_gitignore = tree.tree.iter().any(|entry| entry.path == ".gitignore");
+                let filter = self.remote_path_filter(repo_url, branch, has_gitignore).await;
+                project_summary.repository_structure.skipped_directories.extend(
+                    tree.tree
+                        .iter()
+                        .filter(|entry| entry.entry_type == "tree" && filter.skips_from(&entry.path))
+                        .map(|entry| entry.path.clone()),
+                );
+                tree.tree.retain(|entry| !filter.skips(&entry.path, entry.entry_type == "tree"));
+                project_summary.filtered_files += tree.tree
+                    .iter()
+                    .filter(|entry| entry.entry_type == "blob" && filter.filters_out(&entry.path))
+                    .count() as i32;
+                tree.tree.retain(|entry| match entry.entry_type.as_str() {
+                    "tree" => !filter.filters_out_dir(&entry.path),
+                    "blob" => !filter.filters_out(&entry.path),
+                    _ => true,
+                });
+                if let Some(quota) = &quota {
+                    self.check_budget(self.estimate_requests(&tree), quota)?;
+                }
+                self.analyze_tree(tree, branch, &filter, &mut project_summary).await?;
+            },
+            Ok(_) => {
+                println!("Warning: Repository tree truncated, falling back to per-directory listing");
+                let filter = self.remote_path_filter(repo_url, branch, true).await;
+                self.analyze_directories(branch, &filter, &mut project_summary).await?;
+            },
+            Err(_) => {
+                let filter = self.remote_path_filter(repo_url, branch, true).await;
+                self.analyze_directories(branch, &filter, &mut project_summary).await?;
+            },
+        }
 
-        // Analyse récursive du dépôt
-        self.analyze_directory("", branch, &mut project_summary).await?;
+        self.record_tags(repo_url, &mut project_summary).await;
+        self.record_languages(repo_url, &mut project_summary).await;
 
         // Finalise l'analyse
         self.finalize_analysis(&mut project_summary);
+        project_summary.run_metrics.api_requests = self.client.api_requests();
+        project_summary.run_metrics.rate_limit_waits = self.client.rate_limit_waits();
+        if let Some(quota) = quota {
+            let remaining_after = self.read_quota(repo_url).await.map_or(quota.remaining, |after| after.remaining);
+            project_summary.run_metrics.rate_limit = Some(RateLimitUsage {
+                limit: quota.limit,
+                remaining_before: quota.remaining,
+                remaining_after,
+                reset: quota.reset,
+            });
+        }
+        self.evict_cache();
 
         Ok(project_summary)
     }
 
-    /// Analyse récursivement un répertoire du dépôt
+    /// Filtre des chemins exclus selon les options et le `.gitignore` racine s'il est fourni
+    fn path_filter(&self, gitignore: Option<&str>) -> PathFilter {
+        let filter = if self.options.no_default_ignores {
+            PathFilter::disabled()
+        } else {
+            match gitignore {
+                Some(gitignore) => PathFilter::default().with_gitignore(gitignore),
+                None => PathFilter::default(),
+            }
+        };
+        filter.with_globs(self.glob_filter.clone())
+    }
+
+    /// Filtre des chemins exclus d'un dépôt distant ; le `.gitignore` racine n'est
+    /// téléchargé que s'il peut exister
+    async fn remote_path_filter(&self, repo_url: &str, branch: &str, has_gitignore: bool) -> PathFilter {
+        if self.options.no_default_ignores || !has_gitignore {
+            return self.path_filter(None);
+        }
+        let gitignore = match self.client.contents_url(repo_url, ".gitignore", branch) {
+            Ok(url) => self.client.get_file_content(&url).await.ok(),
+            Err(_) => None,
+        };
+        self.path_filter(gitignore.as_deref())
+    }
+
+    /// Ramène le répertoire de cache sous sa taille maximale
+    fn evict_cache(&self) {
+        if let Some(cache) = &self.blob_cache {
+            let freed = cache.evict(self.options.cache_max_size);
+            if freed > 0 {
+                println!("Evicted {} KiB of least recently used cache entries", freed / 1024);
+            }
+        }
+    }
+
+    /// Quota d'API restant (appel optionnel, non décompté)
+    async fn read_quota(&self, repo_url: &str) -> Option<GithubRateLimitStatus> {
+        if self.options.offline {
+            return None;
+        }
+        match self.client.get_rate_limit(repo_url).await {
+            Ok(quota) => quota,
+            Err(e) => {
+                println!("Warning: Could not read the API rate limit: {}", e);
+                None
+            },
+        }
+    }
+
+    /// Estime les appels REST de l'analyse et de l'export d'une arborescence
+    fn estimate_requests(&self, tree: &GithubTree) -> u64 {
+        let sizes: Vec<usize> = tree.tree
+            .iter()
+            .filter(|entry| entry.entry_type == "blob")
+            .filter(|entry| Self::needs_content(&categorize_path(&entry.path)))
+            .map(|entry| entry.size.unwrap_or(0).max(0) as usize)
+            .filter(|size| *size <= self.options.max_file_size)
+            .collect();
+
+        // Avec un token, les fichiers de moins de 1 Mo passent par GraphQL (quota distinct)
+        let per_stage = if self.client.batch_fetch_available() {
+            sizes.iter().filter(|size| **size > CONTENTS_API_MAX_SIZE).count()
+        } else {
+            sizes.len()
+        } as u64;
+        // Sans cache, l'export télécharge à nouveau chaque fichier ; avec, il revalide
+        // par ETag et les réponses 304 ne sont pas décomptées
+        let stages = if self.options.no_cache { 2 } else { 1 };
+        // Tags et répartition des langages
+        let optional = if self.options.offline { 0 } else { 2 };
+        per_stage * stages + optional
+    }
+
+    /// Compare l'estimation au quota restant et avertit ou interrompt selon l'option
+    fn check_budget(&self, estimate: u64, quota: &GithubRateLimitStatus) -> Result<(), GithubAnalyzerError> {
+        println!(
+            "API quota: {} of {} requests remaining, this run needs about {}",
+            quota.remaining, quota.limit, estimate
+        );
+        if estimate <= quota.remaining {
+            return Ok(());
+        }
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        let message = format!(
+            "this run needs about {} API requests but only {} remain; the quota resets in {} min{}",
+            estimate,
+            quota.remaining,
+            quota.reset.saturating_sub(now) / 60 + 1,
+            if self.client.has_token() { "" } else { " (set GITHUB_TOKEN to raise the limit)" }
+        );
+        match self.options.budget_policy {
+            BudgetPolicy::Warn => {
+                println!("Warning: {}", message);
+                Ok(())
+            },
+            BudgetPolicy::Abort => {
+                println!("Error: {} (aborting, see --on-low-budget)", message);
+                Err(GithubAnalyzerError::RateLimitError(quota.reset))
+            },
+        }
+    }
+
+    /// Liste des tags (appel API optionnel)
+    async fn record_tags(&self, repo_url: &str, project_summary: &mut ProjectSummary) {
+        if self.options.offline {
+            return;
+        }
+        match self.client.get_tags(repo_url).await {
+            Ok(tags) => {
+                self.release_detector.record_tags(&tags, &mut project_summary.release_readiness);
+                project_summary.release_readiness.tags_checked = true;
+            },
+            Err(e) => println!("Warning: Failed to list tags: {}", e),
+        }
+    }
+
+    /// Répartition des langages calculée par GitHub (appel optionnel)
+    async fn record_languages(&self, repo_url: &str, project_summary: &mut ProjectSummary) {
+        if self.options.offline {
+            return;
+        }
+        match self.client.get_languages(repo_url).await {
+            Ok(languages) => project_summary.repository_structure.languages = languages,
+            Err(e) => println!("Warning: Failed to read the language breakdown: {}", e),
+        }
+    }
+
+    /// Analyse le dépôt à partir de la liste à plat de la Git Trees API
+    async fn analyze_tree(
+        &self,
+        tree: GithubTree,
+        branch: &str,
+        filter: &PathFilter,
+        project_summary: &mut ProjectSummary,
+    ) -> Result<(), GithubAnalyzerError> {
+        // Les chemins sont ramenés au sous-répertoire analysé ; l'URL garde le chemin complet
+        let mut files = Vec::new();
+        let mut links = Vec::new();
+        for entry in tree.tree {
+            let Some(path) = project_summary.relative_path(&entry.path).map(str::to_string) else {
+                continue;
+            };
+            let content_type = match (entry.entry_type.as_str(), entry.mode.as_str()) {
+                ("tree", _) => {
+                    self.record_directory(&path, project_summary);
+                    continue;
+                },
+                ("blob", SYMLINK_MODE) => "symlink",
+                ("blob", _) => "file",
+                ("commit", _) => "submodule",
+                _ => continue,
+            };
+            let content = GithubContent {
+                name: entry.path.rsplit('/').next().unwrap_or(&entry.path).to_string(),
+                url: self.client.contents_url(&project_summary.repo_url, &entry.path, branch)?,
+                path,
+                sha: entry.sha,
+                size: entry.size.unwrap_or(0),
+                content: None,
+                encoding: None,
+                download_url: None,
+                content_type: content_type.to_string(),
+            };
+            if content_type == "file" {
+                files.push(content);
+            } else {
+                links.push(content);
+            }
+        }
+
+        // Avec un token, les contenus absents du cache sont récupérés par lots via GraphQL
+        let mut prefetched = self.cached_contents(&files);
+        if self.client.batch_fetch_available() {
+            let paths: Vec<String> = files
+                .iter()
+                .filter(|file| !prefetched.contains_key(&file.path))
+                .filter(|file| file.size as usize <= CONTENTS_API_MAX_SIZE && Self::needs_content(&categorize_path(&file.path)))
+                .map(|file| project_summary.full_path(&file.path))
+                .collect();
+            match self.client.get_files_batch(&project_summary.repo_url, branch, &paths).await {
+                Ok(contents) => prefetched.extend(contents.into_iter().filter_map(|(path, content)| {
+                    project_summary.relative_path(&path).map(|path| (path.to_string(), content))
+                })),
+                Err(e) => println!("Warning: GraphQL batch fetch failed, falling back to REST: {}", e),
+            }
+        }
+
+        // Les fichiers des sous-modules et les cibles des liens passent par l'API REST
+        let linked = self.resolve_links(links, branch, filter, &files, project_summary).await;
+        prefetched.extend(self.cached_contents(&linked));
+        files.extend(linked);
+
+        self.process_files(files, prefetched, project_summary).await;
+        Ok(())
+    }
+
+    /// Parcourt récursivement le dépôt par l'API Contents, puis analyse ses fichiers
+    async fn analyze_directories(
+        &self,
+        branch: &str,
+        filter: &PathFilter,
+        project_summary: &mut ProjectSummary,
+    ) -> Result<(), GithubAnalyzerError> {
+        let mut files = Vec::new();
+        let mut links = Vec::new();
+        let root = project_summary.subpath.clone().unwrap_or_default();
+        self.list_directory(&root, branch, filter, &mut files, &mut links, project_summary).await?;
+        let linked = self.resolve_links(links, branch, filter, &files, project_summary).await;
+        files.extend(linked);
+        let cached = self.cached_contents(&files);
+        self.process_files(files, cached, project_summary).await;
+        Ok(())
+    }
+
+    /// Liste récursivement les fichiers d'un répertoire du dépôt ; les sous-modules
+    /// et les liens symboliques sont mis de côté dans `links`
     #[async_recursion]
-    async fn analyze_directory(
+    async fn list_directory(
         &self,
         path: &str,
         branch: &str,
+        filter: &PathFilter,
+        files: &mut Vec<GithubContent>,
+        links: &mut Vec<GithubContent>,
         project_summary: &mut ProjectSummary,
     ) -> Result<(), GithubAnalyzerError> {
         let contents = self.client.get_repo_contents(&project_summary.repo_url, path, branch).await?;
         
-        for content in contents {
+        for mut content in contents {
+            let Some(relative) = project_summary.relative_path(&content.path).map(str::to_string) else {
+                continue;
+            };
             match content.content_type.as_str() {
+                // Les répertoires exclus ne sont pas listés : aucun appel API
+                "dir" if filter.skips(&content.path, true) => {
+                    project_summary.repository_structure.skipped_directories.push(content.path);
+                },
+                "dir" if filter.filters_out_dir(&content.path) => {},
                 "dir" => {
-                    self.process_directory(&content, branch, project_summary).await?;
+                    self.record_directory(&relative, project_summary);
+                    self.list_directory(&content.path, branch, filter, files, links, project_summary).await?;
                 },
+                "file" if filter.skips(&content.path, false) => {},
+                "file" if filter.filters_out(&content.path) => project_summary.filtered_files += 1,
                 "file" => {
-                    self.process_file(&content, project_summary).await?;
+                    content.path = relative;
+                    files.push(content);
+                },
+                "submodule" | "symlink" if filter.skips(&content.path, false) => {},
+                "submodule" | "symlink" => {
+                    content.path = relative;
+                    links.push(content);
                 },
-                _ => {} // Ignore other types
+                other => println!("Warning: Ignoring {} ({} entry)", content.path, other),
             }
         }
         
         Ok(())
     }
 
-    /// Traite un répertoire
-    async fn process_directory(
+    /// Enregistre les sous-modules et les liens symboliques du dépôt. Renvoie les
+    /// fichiers à analyser en plus : la cible de chaque lien résolu, sous le chemin du
+    /// lien, et avec `--recurse-submodules` les fichiers des sous-modules GitHub.
+    async fn resolve_links(
         &self,
-        content: &GithubContent,
+        links: Vec<GithubContent>,
         branch: &str,
+        filter: &PathFilter,
+        files: &[GithubContent],
         project_summary: &mut ProjectSummary,
-    ) -> Result<(), GithubAnalyzerError> {
-        // Met à jour la structure du projet
-        if content.path.starts_with("src/") {
-            let module_name = content.path.replace("src/", "").replace(".rs", "");
+    ) -> Vec<GithubContent> {
+        let mut linked = Vec::new();
+        let (submodules, symlinks): (Vec<_>, Vec<_>) = links
+            .into_iter()
+            .partition(|link| link.content_type == "submodule");
+
+        if !submodules.is_empty() {
+            let declared = self.gitmodules(&project_summary.repo_url, branch).await;
+            for submodule in submodules {
+                let mut record = Submodule {
+                    url: declared.get(&project_summary.full_path(&submodule.path)).cloned(),
+                    path: submodule.path,
+                    commit: submodule.sha,
+                    analyzed: false,
+                };
+                if self.options.recurse_submodules {
+                    match self.submodule_files(&record, filter, project_summary).await {
+                        Ok(submodule_files) => {
+                            record.analyzed = true;
+                            linked.extend(submodule_files);
+                        },
+                        Err(e) => println!("Warning: Submodule {} not analyzed: {}", record.path, e),
+                    }
+                } else {
+                    println!(
+                        "Warning: Submodule {} ({}) not analyzed, see --recurse-submodules",
+                        record.path,
+                        record.url.as_deref().unwrap_or("unknown URL")
+                    );
+                }
+                project_summary.repository_structure.submodules.push(record);
+            }
+        }
+
+        for symlink in symlinks {
+            // Le contenu du blob d'un lien symbolique est le chemin de sa cible
+            let target = match self.client.get_blob(&project_summary.repo_url, &symlink.sha).await {
+                Ok(target) => target.trim().to_string(),
+                Err(e) => {
+                    println!("Warning: Failed to read symlink {}: {}", symlink.path, e);
+                    continue;
+                },
+            };
+            let resolved = resolve_symlink(&project_summary.full_path(&symlink.path), &target)
+                .and_then(|resolved| project_summary.relative_path(&resolved).map(str::to_string))
+                .and_then(|resolved| files.iter().chain(&linked).find(|file| file.path == resolved));
+            let resolved = match resolved {
+                Some(file) => {
+                    let content = GithubContent {
+                        name: symlink.name,
+                        path: symlink.path.clone(),
+                        sha: file.sha.clone(),
+                        size: file.size,
+                        url: file.url.clone(),
+                        content: None,
+                        encoding: None,
+                        download_url: file.download_url.clone(),
+                        content_type: "file".to_string(),
+                    };
+                    let resolved = file.path.clone();
+                    linked.push(content);
+                    Some(resolved)
+                },
+                None => {
+                    println!(
+                        "Warning: Symlink {} -> {} does not point to an analyzed file",
+                        symlink.path, target
+                    );
+                    None
+                },
+            };
+            project_summary.repository_structure.symlinks.push(Symlink {
+                path: symlink.path,
+                target,
+                resolved,
+            });
+        }
+
+        linked
+    }
+
+    /// Sous-modules déclarés dans le `.gitmodules` racine (chemin complet vers URL)
+    async fn gitmodules(&self, repo_url: &str, branch: &str) -> HashMap<String, String> {
+        let content = match self.client.contents_url(repo_url, ".gitmodules", branch) {
+            Ok(url) => self.client.get_file_content(&url).await.ok(),
+            Err(_) => None,
+        };
+        content.map(|content| parse_gitmodules(&content)).unwrap_or_default()
+    }
+
+    /// Fichiers d'un sous-module hébergé sur le même hôte GitHub, au commit
+    /// référencé, préfixés par le chemin du sous-module
+    async fn submodule_files(
+        &self,
+        submodule: &Submodule,
+        filter: &PathFilter,
+        project_summary: &mut ProjectSummary,
+    ) -> Result<Vec<GithubContent>, GithubAnalyzerError> {
+        let parent: RepoLocator = project_summary.repo_url.parse()?;
+        let url = submodule.url.as_deref().unwrap_or_default();
+        let repo_url = github_submodule_url(&parent, url).ok_or_else(|| {
+            GithubAnalyzerError::ParseError(format!("'{}' is not a repository on {}", url, parent.host))
+        })?;
+        let tree = self.client.get_repo_tree(&repo_url, &submodule.commit).await?;
+        if tree.truncated {
+            println!("Warning: Tree of submodule {} truncated, some files are missing", submodule.path);
+        }
+
+        let mut files = Vec::new();
+        for entry in tree.tree {
+            if entry.entry_type != "blob" || entry.mode == SYMLINK_MODE {
+                continue;
+            }
+            let path = format!("{}/{}", submodule.path, entry.path);
+            let full_path = project_summary.full_path(&path);
+            if filter.skips(&full_path, false) {
+                continue;
+            }
+            if filter.filters_out(&full_path) {
+                project_summary.filtered_files += 1;
+                continue;
+            }
+            files.push(GithubContent {
+                name: entry.path.rsplit('/').next().unwrap_or(&entry.path).to_string(),
+                url: self.client.contents_url(&repo_url, &entry.path, &submodule.commit)?,
+                path,
+                sha: entry.sha,
+                size: entry.size.unwrap_or(0),
+                content: None,
+                encoding: None,
+                download_url: None,
+                content_type: "file".to_string(),
+            });
+        }
+        Ok(files)
+    }
+
+    /// Met à jour la structure du projet pour un répertoire
+    pub(crate) fn record_directory(&self, path: &str, project_summary: &mut ProjectSummary) {
+        if path.starts_with("src/") {
+            let module_name = path.replace("src/", "").replace(".rs", "");
             if !module_name.is_empty() && !project_summary.project_overview.main_modules.contains(&module_name) {
                 project_summary.project_overview.main_modules.push(module_name);
             }
             project_summary.repository_structure.has_src_directory = true;
         }
+    }
 
-        // Analyse récursive du répertoire
-        self.analyze_directory(&content.path, branch, project_summary).await
+    /// Contenus déjà en cache pour le SHA de chaque fichier analysé
+    fn cached_contents(&self, files: &[GithubContent]) -> HashMap<String, Option<String>> {
+        let Some(cache) = &self.blob_cache else {
+            return HashMap::new();
+        };
+        let cached: HashMap<_, _> = files
+            .iter()
+            .filter(|file| file.size.max(0) as usize <= self.options.max_file_size)
+            .filter(|file| Self::needs_content(&categorize_path(&file.path)))
+            .filter_map(|file| cache.content(&file.sha).map(|text| (file.path.clone(), Some(text))))
+            .collect();
+        if !cached.is_empty() {
+            println!("Reusing {} cached file contents", cached.len());
+        }
+        cached
     }
 
-    /// Traite un fichier individuel
-    async fn process_file(
+    /// Télécharge en parallèle les contenus manquants (même ordonnanceur que l'export),
+    /// puis intègre les fichiers par ordre de chemin pour un résumé reproductible
+    async fn process_files(
         &self,
-        content: &GithubContent,
+        files: Vec<GithubContent>,
+        mut prefetched: HashMap<String, Option<String>>,
         project_summary: &mut ProjectSummary,
-    ) -> Result<(), GithubAnalyzerError> {
-        // Skip des fichiers trop gros
-        if content.size > 1000000 {
-            return Ok(());
+    ) {
+        // Fichiers au-delà du plafond configuré : ni téléchargés, ni analysés
+        let (kept, too_large): (Vec<_>, Vec<_>) = files
+            .into_iter()
+            .partition(|file| file.size.max(0) as usize <= self.options.max_file_size);
+        project_summary.skipped_files.extend(too_large.into_iter().map(|file| SkippedFile {
+            path: file.path,
+            size: file.size.max(0) as u64,
+            reason: SkipReason::TooLarge,
+        }));
+        let mut files = kept;
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+
+        let pending: Vec<(usize, u64)> = files
+            .iter()
+            .enumerate()
+            .filter(|(_, file)| !prefetched.contains_key(&file.path))
+            .filter(|(_, file)| Self::needs_content(&categorize_path(&file.path)))
+            .map(|(index, file)| (index, file.size.max(0) as u64))
+            .collect();
+        let scheduler = SizeAwareScheduler::new(self.options.fetch_byte_budget, self.options.max_in_flight);
+        let repo_url = project_summary.repo_url.clone();
+        let mut fetched = HashMap::new();
+        scheduler.run(
+            pending,
+            |index| {
+                let (file, repo_url) = (&files[index], &repo_url);
+                async move { (index, self.fetch_content(file, repo_url).await) }
+            },
+            |(index, content)| {
+                fetched.insert(index, content);
+            },
+            &mut project_summary.run_metrics,
+        ).await;
+
+        for (index, file) in files.iter().enumerate() {
+            let via_blob = Self::uses_blob_api(file) && matches!(fetched.get(&index), Some(Ok(_)));
+            let (content, binary) = match prefetched.remove(&file.path) {
+                // Les blobs binaires arrivent de GraphQL sans texte
+                Some(text) => {
+                    let binary = text.is_none();
+                    (text, binary)
+                },
+                None => match fetched.remove(&index) {
+                    Some(Ok(text)) => (Some(text), false),
+                    Some(Err(GithubAnalyzerError::BinaryContent(_))) => (None, true),
+                    _ => (None, false),
+                },
+            };
+            if binary {
+                Self::record_binary(file, project_summary);
+            }
+            if let (Some(cache), Some(text)) = (&self.blob_cache, &content) {
+                cache.store_content(&file.sha, text);
+            }
+
+            self.ingest_file(file, content, project_summary).await;
+            if via_blob {
+                if let Some(file_summary) = project_summary.file_summaries.last_mut()
+                    .filter(|file_summary| *file_summary.path == *file.path)
+                {
+                    file_summary.fetched_via_blob = true;
+                }
+            }
         }
+    }
+
+    /// Au-delà de la limite de l'API Contents, le contenu passe par l'API Blobs
+    fn uses_blob_api(file: &GithubContent) -> bool {
+        file.size.max(0) as usize > CONTENTS_API_MAX_SIZE && !file.sha.is_empty()
+    }
+
+    /// Télécharge le contenu d'un fichier ; `BinaryContent` s'il est binaire
+    async fn fetch_content(&self, file: &GithubContent, repo_url: &str) -> Result<String, GithubAnalyzerError> {
+        if Self::uses_blob_api(file) {
+            self.client.get_blob(repo_url, &file.sha).await
+        } else {
+            self.client.get_file_content(&file.url).await
+        }
+    }
 
-        let category = categorize_file(&content.name);
+    /// Ajoute un fichier à la liste des fichiers binaires, dont le contenu n'est ni
+    /// analysé ni exporté
+    fn record_binary(content: &GithubContent, project_summary: &mut ProjectSummary) {
+        project_summary.binary_files.push(BinaryFile {
+            path: content.path.clone(),
+            size: content.size.max(0) as u64,
+        });
+    }
+
+    /// Indique si le contenu d'un fichier de cette catégorie est analysé
+    fn needs_content(category: &FileCategory) -> bool {
+        matches!(category, 
+            FileCategory::Source(_) | 
+            FileCategory::Configuration | 
+            FileCategory::Documentation |
+            FileCategory::CiConfig |
+            FileCategory::Example |
+            FileCategory::Benchmark
+        )
+    }
+
+    /// Intègre un fichier (et son contenu éventuel) au résumé du projet
+    pub(crate) async fn ingest_file(
+        &self,
+        content: &GithubContent,
+        file_content: Option<String>,
+        project_summary: &mut ProjectSummary,
+    ) {
+        let category = categorize_path(&content.path);
+        if category == FileCategory::Binary {
+            Self::record_binary(content, project_summary);
+        }
         
         // Mise à jour de la structure du projet selon le type de fichier
         self.update_project_structure(content, &category, project_summary);
 
         // Analyse du contenu pour certains types de fichiers
-        if matches!(category, 
-            FileCategory::Source(_) | 
-            FileCategory::Configuration | 
-            FileCategory::Documentation
-        ) {
-            if let Ok(file_content) = self.client.get_file_content(&content.url).await {
-                let (summary, type_relations, method_signatures, configuration) = 
-                    self.file_analyzer.analyze_content(&file_content, &content.path).await;
-
-                self.update_project_summary(
-                    content,
-                    &summary,
-                    type_relations,
-                    method_signatures,
-                    configuration,
-                    category,
-                    project_summary,
-                );
+        if Self::needs_content(&category) {
+            if let Some(file_content) = file_content {
+                let CachedAnalysis { mut summary, type_relations, method_signatures, configuration } =
+                    self.analyze_content(content, &file_content).await;
+
+                self.scan_release_markers(content, &category, &file_content, project_summary);
+                Self::record_license(content, &file_content, project_summary);
+                if content.name == "Cargo.toml" {
+                    self.record_dependencies(content, &file_content, project_summary);
+                }
+                if category == FileCategory::Documentation && is_readme(&content.path) {
+                    Self::record_readme(content, &file_content, project_summary);
+                }
+
+                if let FileCategory::Source(_) = category {
+                    self.header_detector.lock().unwrap().observe(&content.path, &file_content);
+                    self.config_collector.lock().unwrap().observe_source(&content.path, &file_content);
+                }
+                if is_config_template(&content.path) {
+                    self.config_collector.lock().unwrap().observe_template(&content.path, &file_content);
+                }
+
+                if matches!(category, FileCategory::Source(ref lang) if lang == "rs") {
+                    self.module_graph.lock().unwrap().observe(&content.path, &file_content);
+                    self.module_tree.lock().unwrap().observe(&content.path, &file_content);
+                    self.idioms.lock().unwrap().observe(&file_content);
+                    self.macros.lock().unwrap().observe(&content.path, &file_content);
+                    self.conversions.lock().unwrap().observe(&file_content);
+                    self.usage.lock().unwrap().observe(&content.path, &file_content);
+                    self.feature_gates.lock().unwrap().observe(&content.path, &file_content);
+                    Self::record_api_surface(content, &file_content, project_summary);
+                    project_summary.todos.extend(scan_todos(&content.path, &file_content));
+                    self.record_tests(&content.path, summary.tests, project_summary);
+                    Self::record_unsafe(&content.path, &summary.unsafe_code, project_summary);
+                    project_summary.project_overview.panic_points.counts.add(&summary.panic_points);
+                    self.crate_usage.lock().unwrap().observe(&summary.imports);
+                    for (type_name, location) in self.type_locator.locate(&file_content, &content.path) {
+                        project_summary.type_index.entry(type_name).or_default().push(location);
+                    }
+                }
+
+                // Les dépendances de développement servent souvent aux seuls exemples et bancs d'essai
+                if matches!(category, FileCategory::Example | FileCategory::Benchmark) {
+                    self.crate_usage.lock().unwrap().observe(&summary.imports);
+                }
+
+                // Analyse de dernier recours pour les langages sans analyseur dédié
+                let heuristic = match category {
+                    FileCategory::Source(ref lang) if !has_language_analyzer(lang) => {
+                        let analysis = self.heuristic_analyzer.analyze(&file_content, lang);
+                        summary.text.push_str(&self.heuristic_analyzer.summarize(&analysis));
+                        project_summary.project_overview.total_heuristic_files += 1;
+                        project_summary.project_overview.total_heuristic_definitions +=
+                            analysis.definitions.len() as i32;
+                        Some(analysis)
+                    },
+                    _ => None,
+                };
+
+                if is_build_script(&content.path) {
+                    let script = analyze_build_script(&content.path, &file_content);
+                    summary.text.push_str(&script.summarize());
+                    project_summary.repository_structure.build_script = Some(script);
+                }
+
+                if category == FileCategory::CiConfig {
+                    if let Some(pipeline) = analyze_ci(&content.path, &file_content) {
+                        summary.text.push_str(&pipeline.summarize());
+                        project_summary.repository_structure.ci_summary
+                            .get_or_insert_with(Default::default)
+                            .pipelines
+                            .push(pipeline);
+                    }
+                }
+
+                let analyzed = AnalyzedFile { summary: &summary.text, type_relations, method_signatures, configuration };
+                self.update_project_summary(content, analyzed, category, project_summary);
+
+                if let Some(file_summary) = project_summary.file_summaries.last_mut() {
+                    file_summary.heuristic = heuristic;
+                    file_summary.pattern_counts = summary.pattern_counts;
+                    file_summary.lines = file_content.lines().count();
+                    file_summary.loc = summary.loc;
+                    file_summary.unsafe_code = summary.unsafe_code.counts;
+                    file_summary.panic_points = summary.panic_points;
+                    file_summary.imports = summary.imports.imports;
+                }
             }
         }
 
-        project_summary.files_analyzed.push(content.path.clone());
-        Ok(())
+        let path = self.intern(&content.path);
+        project_summary.files_analyzed.push(path);
+    }
+
+    /// Analyse le contenu d'un fichier, ou reprend l'analyse en cache du même blob
+    async fn analyze_content(&self, content: &GithubContent, file_content: &str) -> CachedAnalysis {
+        let cache = self.blob_cache.as_ref().filter(|_| !content.sha.is_empty());
+        let settings = format!("summary_entries={};backend={}", self.options.summary_entries, FileAnalyzer::BACKEND);
+        let key = BlobCache::analysis_key(&content.sha, &content.path, &settings);
+        if let Some(analysis) = cache.and_then(|cache| cache.analysis(&key)) {
+            println!("\n📁 Reusing cached analysis: {}", content.path);
+            return analysis;
+        }
+
+        let (summary, type_relations, method_signatures, configuration) =
+            self.file_analyzer.analyze_content(file_content, &content.path).await;
+        let analysis = CachedAnalysis { summary, type_relations, method_signatures, configuration };
+        if let Some(cache) = cache {
+            cache.store_analysis(&key, &analysis);
+        }
+        analysis
+    }
+
+    /// Relève les éléments publics d'un fichier Rust de `src/`, rattachés à son module
+    fn record_api_surface(content: &GithubContent, file_content: &str, project_summary: &mut ProjectSummary) {
+        let Some(module) = module_path(&content.path) else {
+            return;
+        };
+        let items = public_items(file_content);
+        if !items.is_empty() {
+            project_summary.api_surface.push(ApiModule { module, path: content.path.clone(), items });
+        }
+    }
+
+    /// Relève la présentation du projet dans un README ; celui de la racine l'emporte
+    /// sur ceux des sous-répertoires
+    fn record_readme(content: &GithubContent, file_content: &str, project_summary: &mut ProjectSummary) {
+        let overview = &mut project_summary.project_overview;
+        if overview.description.is_some() && content.path.contains('/') {
+            return;
+        }
+        let readme = analyze_readme(file_content);
+        if let Some(description) = readme.description() {
+            overview.description = Some(description);
+            overview.readme_highlights = readme.features;
+        }
+    }
+
+    /// Relève la licence déclarée par un manifeste ou reconnue dans un fichier de
+    /// licence ; le manifeste racine a le dernier mot
+    fn record_license(content: &GithubContent, file_content: &str, project_summary: &mut ProjectSummary) {
+        let license = &mut project_summary.repository_structure.license;
+        if let Some(declared) = manifest_license(&content.name, file_content) {
+            if !content.path.contains('/') || license.is_none() {
+                *license = Some(declared);
+            }
+        } else if is_license_file(&content.path) && license.is_none() {
+            *license = detect_license_text(file_content).map(str::to_string);
+        }
+    }
+
+    /// Relève les dépendances déclarées par un `Cargo.toml`
+    fn record_dependencies(&self, content: &GithubContent, file_content: &str, project_summary: &mut ProjectSummary) {
+        let Some(dependencies) = cargo_dependencies(file_content) else {
+            println!("Warning: Could not parse {} as TOML", content.path);
+            return;
+        };
+        self.workspace.lock().unwrap().observe_manifest(&content.path, file_content);
+        if let Some(package) = package_name(file_content) {
+            self.crate_usage.lock().unwrap().observe_package(&package);
+        }
+        if let Some(package) = proc_macro_package(file_content) {
+            project_summary.project_overview.proc_macro_crates.push(package);
+        }
+        project_summary.project_overview.declared_features.extend(declared_features(file_content));
+        println!("📦 {} dependencies declared in {}", dependencies.len(), content.path);
+        project_summary.project_overview.dependencies.extend(dependencies.into_iter().map(|dependency| Dependency {
+            defined_in: content.path.clone(),
+            ..dependency
+        }));
+    }
+
+    /// Crates tierces importées (paquets du projet exclus), et dépendances déclarées
+    /// que le code n'utilise pas
+    fn record_external_crates(&self, project_summary: &mut ProjectSummary) {
+        let usage = self.crate_usage.lock().unwrap();
+        let overview = &mut project_summary.project_overview;
+        let used: BTreeSet<&str> = project_summary
+            .file_summaries
+            .iter()
+            .flat_map(|file| &file.imports)
+            .filter(|import| import.kind == ImportKind::External)
+            .map(Import::crate_name)
+            .filter(|name| !usage.is_local(name))
+            .collect();
+        overview.external_crates_used = used.into_iter().map(str::to_string).collect();
+
+        if overview.total_rust_files == 0 {
+            return;
+        }
+        for dependency in overview.dependencies.iter_mut() {
+            dependency.unused = !usage.is_referenced(&dependency.name);
+        }
+    }
+
+    /// Recherche les marqueurs de stabilité et de publication dans un fichier
+    fn scan_release_markers(
+        &self,
+        content: &GithubContent,
+        category: &FileCategory,
+        file_content: &str,
+        project_summary: &mut ProjectSummary,
+    ) {
+        let readiness = &mut project_summary.release_readiness;
+        match category {
+            FileCategory::Source(lang) if lang == "rs" => {
+                self.release_detector.scan_source(file_content, readiness);
+            },
+            _ if content.name.starts_with("CHANGELOG") => {
+                self.release_detector.scan_changelog(file_content, readiness);
+            },
+            _ if content.name == "Cargo.toml" => {
+                self.release_detector.scan_manifest(file_content, readiness);
+            },
+            _ => {}
+        }
     }
 
     /// Met à jour la structure du projet en fonction du type de fichier
@@ -195,6 +1457,9 @@ impl RepositoryAnalyzer {
                 if content.path.starts_with("src/") {
                     project_summary.repository_structure.has_src_directory = true;
                 }
+                if is_build_script(&content.path) {
+                    project_summary.repository_structure.has_build_script = true;
+                }
             },
             FileCategory::Test => {
                 project_summary.repository_structure.has_tests = true;
@@ -205,7 +1470,11 @@ impl RepositoryAnalyzer {
             FileCategory::Configuration => {
                 self.update_build_systems(&content.name, project_summary);
             },
-            FileCategory::Unknown => {}
+            FileCategory::Example
+            | FileCategory::Benchmark
+            | FileCategory::CiConfig
+            | FileCategory::Binary
+            | FileCategory::Unknown => {}
         }
     }
 
@@ -232,28 +1501,55 @@ impl RepositoryAnalyzer {
     fn update_project_summary(
         &self,
         content: &GithubContent,
-        summary: &str,
-        type_relations: Vec<crate::types::analysis::TypeRelations>,
-        method_signatures: Vec<crate::types::analysis::MethodSignature>,
-        configuration: crate::types::analysis::Configuration,
+        analyzed: AnalyzedFile<'_>,
         category: FileCategory,
         project_summary: &mut ProjectSummary,
     ) {
+        let AnalyzedFile { summary, mut type_relations, mut method_signatures, mut configuration } = analyzed;
+        let path = self.intern(&content.path);
+        let module = self.intern(&module_path(&content.path).unwrap_or_default());
+        let limit = self.options.doc_length;
+        for relation in type_relations.iter_mut() {
+            relation.defined_in = path.clone();
+            relation.module = module.clone();
+            relation.doc = relation.doc.take().and_then(|doc| truncate_doc(&doc, limit));
+        }
+        for signature in method_signatures.iter_mut() {
+            signature.defined_in = path.clone();
+            signature.module = module.clone();
+            signature.doc = signature.doc.take().and_then(|doc| truncate_doc(&doc, limit));
+        }
+        for constant in configuration.constants.iter_mut() {
+            constant.defined_in = content.path.clone();
+        }
+        let type_dependencies = type_relations.iter().map(|relations| relations.depends_on.len()).sum();
+
         // Met à jour les statistiques spécifiques au langage
         if let FileCategory::Source(ref lang) = category {
             if lang == "rs" {
                 project_summary.project_overview.total_rust_files += 1;
-                self.update_rust_stats(summary, &type_relations, &method_signatures, &configuration, project_summary);
+                self.update_rust_stats(summary, type_relations, method_signatures, configuration, project_summary);
             }
         }
 
         // Ajoute le résumé du fichier
         project_summary.file_summaries.push(FileSummary {
-            path: content.path.clone(),
+            path: self.intern(&content.path),
             size: content.size,
             summary: summary.to_string(),
             category,
             url: content.url.clone(), // Ajout de l'URL
+            sha: content.sha.clone(),
+            heuristic: None,
+            pattern_counts: Default::default(),
+            fetched_via_blob: false,
+            lines: 0,
+            type_dependencies,
+            loc: Default::default(),
+            unsafe_code: Default::default(),
+            panic_points: Default::default(),
+            imports: Vec::new(),
+            bin_target: false,
         });
     }
 
@@ -261,19 +1557,33 @@ impl RepositoryAnalyzer {
     fn update_rust_stats(
         &self,
         summary: &str,
-        type_relations: &[crate::types::analysis::TypeRelations],
-        method_signatures: &[crate::types::analysis::MethodSignature],
-        configuration: &crate::types::analysis::Configuration,
+        mut type_relations: Vec<crate::types::analysis::TypeRelations>,
+        mut method_signatures: Vec<crate::types::analysis::MethodSignature>,
+        configuration: crate::types::analysis::Configuration,
         project_summary: &mut ProjectSummary,
     ) {
+        // Partage les chaînes répétées entre fichiers
+        {
+            let mut interner = self.interner.lock().unwrap();
+            for relation in type_relations.iter_mut() {
+                interner.share_relation(relation);
+            }
+            for signature in method_signatures.iter_mut() {
+                interner.share_signature(signature);
+            }
+        }
+
         // Met à jour les relations de types et signatures
-        project_summary.project_overview.type_relations.extend_from_slice(type_relations);
-        project_summary.project_overview.method_signatures.extend_from_slice(method_signatures);
+        project_summary.project_overview.async_functions +=
+            method_signatures.iter().filter(|signature| signature.is_async).count() as i32;
+        project_summary.project_overview.type_relations.append(&mut type_relations);
+        project_summary.project_overview.method_signatures.append(&mut method_signatures);
         
         // Met à jour la configuration
-        project_summary.project_overview.configuration.constants.extend_from_slice(&configuration.constants);
-        project_summary.project_overview.configuration.feature_flags.extend_from_slice(&configuration.feature_flags);
-        project_summary.project_overview.configuration.custom_attributes.extend_from_slice(&configuration.custom_attributes);
+        let overview_config = &mut project_summary.project_overview.configuration;
+        overview_config.constants.extend(configuration.constants);
+        overview_config.feature_flags.extend(configuration.feature_flags);
+        overview_config.custom_attributes.extend(configuration.custom_attributes);
         
         // Met à jour les statistiques
         project_summary.project_overview.total_public_types += 
@@ -285,16 +1595,285 @@ impl RepositoryAnalyzer {
             summary.lines()
                 .filter(|line| line.contains("Public method: "))
                 .count() as i32;
-        
-        project_summary.project_overview.total_tests += 
-            summary.matches("Unit test: ").count() as i32;
+    }
+
+    /// Ajoute le code `unsafe` d'un fichier Rust à celui du projet
+    fn record_unsafe(path: &str, scan: &UnsafeScan, project_summary: &mut ProjectSummary) {
+        let usage = &mut project_summary.project_overview.unsafe_usage;
+        usage.counts.add(&scan.counts);
+        usage.locations.extend(scan.lines.iter().map(|(line, kind)| UnsafeLocation {
+            path: path.to_string(),
+            line: *line,
+            kind: *kind,
+        }));
+        if scan.forbidden {
+            usage.forbidden_in.push(path.to_string());
+        }
+    }
+
+    /// Compte les tests d'un fichier Rust, unitaires ou d'intégration selon son chemin
+    fn record_tests(&self, path: &str, tests: usize, project_summary: &mut ProjectSummary) {
+        let overview = &mut project_summary.project_overview;
+        if is_integration_test(path) {
+            overview.integration_tests += tests as i32;
+            overview.integration_test_files += 1;
+            project_summary.repository_structure.has_tests = true;
+        } else {
+            overview.unit_tests += tests as i32;
+        }
+        overview.total_tests = overview.unit_tests + overview.integration_tests;
+        if tests > 0 {
+            project_summary.repository_structure.has_tests = true;
+        }
+    }
+
+    /// Retourne l'instance partagée d'une chaîne
+    fn intern(&self, value: &str) -> crate::types::SharedStr {
+        self.interner.lock().unwrap().intern(value)
+    }
+
+    /// Relie les types de tous les fichiers en un seul graphe, relève les cycles entre
+    /// types et entre modules, puis recompte les dépendances de types de chaque fichier Rust
+    fn merge_type_graph(&self, project_summary: &mut ProjectSummary) {
+        let overview = &mut project_summary.project_overview;
+        overview.type_relations = merge_type_relations(std::mem::take(&mut overview.type_relations));
+        overview.conversions = self.conversions.lock().unwrap().conversions(&overview.type_relations);
+        link_conversions(&overview.conversions, &mut overview.type_relations);
+        overview.error_types = error_taxonomy(
+            &overview.type_relations,
+            &overview.conversions,
+            &overview.method_signatures,
+            &project_summary.file_summaries,
+        );
+        overview.key_types = rank_key_types(&overview.type_relations, &overview.method_signatures, self.options.key_types);
+        close_type_relations(&mut overview.type_relations);
+        project_summary.important_patterns =
+            self.idioms.lock().unwrap().patterns(&overview.type_relations, &overview.method_signatures);
+        let macros = self.macros.lock().unwrap();
+        overview.macros = macros.definitions();
+        macros.annotate(&mut overview.type_relations);
+        overview.dependency_cycles = type_cycles(&overview.type_relations);
+        overview.dependency_cycles.extend(self.module_graph.lock().unwrap().cycles());
+
+        let mut dependencies: HashMap<&str, usize> = HashMap::new();
+        for relation in &overview.type_relations {
+            *dependencies.entry(&*relation.defined_in).or_default() += relation.depends_on.len();
+        }
+        for file_summary in project_summary.file_summaries.iter_mut() {
+            if matches!(file_summary.category, FileCategory::Source(ref lang) if lang == "rs") {
+                file_summary.type_dependencies = dependencies.get(&*file_summary.path).copied().unwrap_or(0);
+            }
+        }
+    }
+
+    /// Arbre des modules déclarés ; ses modules de premier niveau remplacent les
+    /// répertoires de `src/` dans `main_modules`
+    fn record_module_tree(&self, overview: &mut ProjectOverview) {
+        overview.module_tree = self.module_tree.lock().unwrap().tree();
+        if overview.module_tree.is_empty() {
+            return;
+        }
+        overview.main_modules.clear();
+        for module in overview.module_tree.iter().flat_map(|root| &root.children) {
+            if !overview.main_modules.contains(&module.name) {
+                overview.main_modules.push(module.name.clone());
+            }
+        }
+    }
+
+    /// Statistiques par répertoire de premier niveau et par sous-module de `src/`
+    /// (un fichier de `src/api/` compte pour `src` et pour `src/api`), triées par
+    /// taille décroissante
+    fn directory_stats(file_summaries: &[FileSummary]) -> Vec<DirectoryStats> {
+        let mut stats: HashMap<String, DirectoryStats> = HashMap::new();
+        for file in file_summaries {
+            let components: Vec<&str> = file.path.split('/').collect();
+            if components.len() < 2 {
+                continue; // Fichier à la racine
+            }
+            let mut directories = vec![components[0].to_string()];
+            if components[0] == "src" && components.len() > 2 {
+                directories.push(format!("src/{}", components[1]));
+            }
+            for directory in directories {
+                let entry = stats.entry(directory.clone()).or_insert_with(|| DirectoryStats {
+                    path: directory,
+                    ..Default::default()
+                });
+                entry.file_count += 1;
+                entry.total_size += file.size.max(0) as u64;
+                entry.loc += file.lines;
+                if matches!(&file.category, FileCategory::Source(lang) if lang == "rs") {
+                    entry.rust_files += 1;
+                }
+            }
+        }
+
+        let mut stats: Vec<DirectoryStats> = stats.into_values().collect();
+        stats.sort_by(|a, b| b.total_size.cmp(&a.total_size).then_with(|| a.path.cmp(&b.path)));
+        stats
+    }
+
+    /// Marque les fichiers des exécutables et compte les cibles cargo : découvertes
+    /// d'après leur chemin ou déclarées par `[[bin]]`, `[[example]]` et `[[bench]]`
+    fn record_targets(declared: &[(TargetKind, String)], project_summary: &mut ProjectSummary) {
+        let overview = &mut project_summary.project_overview;
+        for file in project_summary.file_summaries.iter_mut() {
+            let kind = conventional_target(&file.path).or_else(|| {
+                declared.iter().find(|(_, path)| *path == *file.path).map(|(kind, _)| *kind)
+            });
+            match kind {
+                Some(TargetKind::Bin) => {
+                    file.bin_target = true;
+                    overview.binary_targets += 1;
+                },
+                Some(TargetKind::Example) => overview.example_targets += 1,
+                Some(TargetKind::Bench) => overview.bench_targets += 1,
+                None => {},
+            }
+        }
+    }
+
+    /// Répartition des fichiers source par langage, en octets ; la documentation et
+    /// la configuration n'y figurent pas
+    fn language_breakdown(file_summaries: &[FileSummary]) -> Vec<LanguageShare> {
+        let mut shares: HashMap<&str, LanguageShare> = HashMap::new();
+        for file in file_summaries {
+            let FileCategory::Source(lang) = &file.category else {
+                continue;
+            };
+            let language = language_name(lang);
+            let share = shares.entry(language).or_insert_with(|| LanguageShare {
+                language: language.to_string(),
+                ..Default::default()
+            });
+            share.files += 1;
+            share.bytes += file.size.max(0) as u64;
+            share.lines += file.lines;
+        }
+
+        let total: u64 = shares.values().map(|share| share.bytes).sum();
+        let mut shares: Vec<LanguageShare> = shares.into_values().collect();
+        for share in &mut shares {
+            if total > 0 {
+                share.percent = (share.bytes as f64 * 1000.0 / total as f64).round() / 10.0;
+            }
+        }
+        shares.sort_by(|a, b| b.bytes.cmp(&a.bytes).then_with(|| a.language.cmp(&b.language)));
+        shares
+    }
+
+    /// Lignes de code, de commentaires et vides des fichiers source, par langage
+    fn lines_by_language(file_summaries: &[FileSummary]) -> BTreeMap<String, LineCounts> {
+        let mut lines: BTreeMap<String, LineCounts> = BTreeMap::new();
+        for file in file_summaries {
+            if let FileCategory::Source(lang) = &file.category {
+                lines.entry(language_name(lang).to_string()).or_default().add(&file.loc);
+            }
+        }
+        lines.retain(|_, counts| !counts.is_empty());
+        lines
+    }
+
+    /// Les `HOTSPOT_ENTRIES` premiers fichiers selon chaque mesure ; les fichiers dont
+    /// la mesure est nulle n'apparaissent pas
+    fn hotspots(file_summaries: &[FileSummary]) -> Hotspots {
+        let top = |measure: &dyn Fn(&FileSummary) -> u64| -> Vec<Hotspot> {
+            let mut ranked: Vec<Hotspot> = file_summaries
+                .iter()
+                .map(|file| Hotspot { path: file.path.to_string(), value: measure(file) })
+                .filter(|hotspot| hotspot.value > 0)
+                .collect();
+            ranked.sort_by(|a, b| b.value.cmp(&a.value).then_with(|| a.path.cmp(&b.path)));
+            ranked.truncate(HOTSPOT_ENTRIES);
+            ranked
+        };
+        Hotspots {
+            largest_files: top(&|file| file.size.max(0) as u64),
+            longest_files: top(&|file| file.lines as u64),
+            most_public_functions: top(&|file| {
+                file.pattern_counts.get("public_method").copied().unwrap_or(0) as u64
+            }),
+            most_type_dependencies: top(&|file| file.type_dependencies as u64),
+        }
+    }
+
+    /// Les `HOTSPOT_ENTRIES` fichiers Rust du crate qui comptent le plus de points de
+    /// panique (exemples et bancs d'essai exclus)
+    fn panic_hotspots(file_summaries: &[FileSummary]) -> Vec<Hotspot> {
+        let mut ranked: Vec<Hotspot> = file_summaries
+            .iter()
+            .filter(|file| matches!(&file.category, FileCategory::Source(lang) if lang == "rs"))
+            .map(|file| Hotspot { path: file.path.to_string(), value: file.panic_points.total() as u64 })
+            .filter(|hotspot| hotspot.value > 0)
+            .collect();
+        ranked.sort_by(|a, b| b.value.cmp(&a.value).then_with(|| a.path.cmp(&b.path)));
+        ranked.truncate(HOTSPOT_ENTRIES);
+        ranked
+    }
+
+    /// Les `HOTSPOT_ENTRIES` fonctions de plus forte complexité cyclomatique, les plus
+    /// longues d'abord à complexité égale
+    fn complex_functions(signatures: &[MethodSignature]) -> Vec<ComplexFunction> {
+        let mut ranked: Vec<ComplexFunction> = signatures
+            .iter()
+            .filter(|signature| signature.complexity > 0)
+            .map(|signature| ComplexFunction {
+                name: signature.qualified_name(),
+                defined_in: signature.defined_in.to_string(),
+                complexity: signature.complexity,
+                body_lines: signature.body_lines,
+            })
+            .collect();
+        ranked.sort_by(|a, b| {
+            b.complexity
+                .cmp(&a.complexity)
+                .then_with(|| b.body_lines.cmp(&a.body_lines))
+                .then_with(|| a.defined_in.cmp(&b.defined_in))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+        ranked.truncate(HOTSPOT_ENTRIES);
+        ranked
     }
 
     /// Finalise l'analyse en calculant les statistiques globales
     fn finalize_analysis(&self, project_summary: &mut ProjectSummary) {
         project_summary.total_files = project_summary.files_analyzed.len() as i32;
+        self.merge_type_graph(project_summary);
+        self.record_module_tree(&mut project_summary.project_overview);
+        self.record_external_crates(project_summary);
+        project_summary.api_surface.sort_by(|a, b| a.module.cmp(&b.module).then_with(|| a.path.cmp(&b.path)));
+        let overview = &mut project_summary.project_overview;
+        overview.feature_map = self.feature_gates.lock().unwrap().feature_map();
+        overview.declared_features.sort();
+        overview.declared_features.dedup();
+        project_summary.project_overview.possibly_unused = self
+            .usage
+            .lock()
+            .unwrap()
+            .possibly_unused(&project_summary.api_surface, &project_summary.project_overview.type_relations);
+        let workspace = self.workspace.lock().unwrap();
+        project_summary.project_overview.workspace_members = workspace.members(project_summary);
+        project_summary.project_overview.crate_metadata = workspace.metadata("", &project_summary.files_analyzed);
+        Self::record_targets(&workspace.declared_targets(), project_summary);
+        project_summary.directory_tree = render_directory_tree(
+            project_summary.files_analyzed.iter().map(|path| &**path),
+            &workspace.crate_roots(),
+        );
+        project_summary.project_overview.directory_stats = Self::directory_stats(&project_summary.file_summaries);
+        project_summary.repository_structure.language_breakdown =
+            Self::language_breakdown(&project_summary.file_summaries);
+        project_summary.project_overview.lines_by_language = Self::lines_by_language(&project_summary.file_summaries);
+        project_summary.project_overview.unsafe_usage.locations.sort();
+        project_summary.todos.sort_by(|a, b| a.file.cmp(&b.file).then_with(|| a.line.cmp(&b.line)));
+        project_summary.project_overview.unsafe_usage.forbidden_in.sort();
+        project_summary.hotspots = Self::hotspots(&project_summary.file_summaries);
+        project_summary.project_overview.complex_functions =
+            Self::complex_functions(&project_summary.project_overview.method_signatures);
+        project_summary.project_overview.panic_points.top_files = Self::panic_hotspots(&project_summary.file_summaries);
         
-        // Détermine le langage principal
+        // Détermine le langage principal : le plus volumineux selon GitHub, sinon
+        // celui du plus grand nombre de fichiers (sources hors ligne ou locales)
         let mut language_counts: HashMap<String, usize> = HashMap::new();
         for summary in &project_summary.file_summaries {
             if let FileCategory::Source(ref lang) = summary.category {
@@ -302,14 +1881,244 @@ impl RepositoryAnalyzer {
             }
         }
         
-        if let Some((lang, _)) = language_counts.into_iter().max_by_key(|(_, count)| *count) {
-            project_summary.repository_structure.primary_language = Some(lang);
+        let structure = &mut project_summary.repository_structure;
+        if let Some((language, _)) = structure.languages.first() {
+            structure.primary_language = Some(language.clone());
+        } else if let Some((lang, _)) = language_counts.into_iter().max_by_key(|(_, count)| *count) {
+            structure.primary_language = Some(lang);
+        }
+
+        self.release_detector.finalize(&mut project_summary.release_readiness);
+
+        // Retire les en-têtes de licence répétés des résumés
+        let header_detector = self.header_detector.lock().unwrap();
+        let boilerplate = header_detector.boilerplate();
+        for file_summary in project_summary.file_summaries.iter_mut() {
+            header_detector.strip_from_summary(&file_summary.path, &mut file_summary.summary, &boilerplate);
         }
+        project_summary.boilerplate_headers = boilerplate;
+
+        project_summary.config_matrix = self.config_collector.lock().unwrap().matrix();
     }
 }
 
 /// Point d'entrée principal pour l'analyse d'un dépôt
-pub async fn analyze_repository(repo_url: &str) -> Result<ProjectSummary, GithubAnalyzerError> {
-    let analyzer = RepositoryAnalyzer::new();
+pub async fn analyze_repository(
+    repo_url: &str,
+    options: &AnalysisOptions,
+) -> Result<ProjectSummary, GithubAnalyzerError> {
+    let analyzer = RepositoryAnalyzer::for_repository(repo_url, options.clone());
     analyzer.analyze(repo_url).await
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::file::categorize_path;
+    use crate::types::analysis::{Configuration, TypeKind, TypeRelations};
+    use crate::types::{SharedStr, Visibility};
+
+    /// Résumé d'un fichier Rust catégorisé d'après son chemin, avec `unwraps` appels
+    fn file(path: &str, unwraps: usize) -> FileSummary {
+        let mut summary: FileSummary = serde_json::from_value(serde_json::json!({
+            "path": path,
+            "size": 0,
+            "summary": "",
+            "category": categorize_path(path),
+            "url": "",
+        }))
+        .unwrap();
+        summary.panic_points.unwrap = unwraps;
+        summary
+    }
+
+    #[test]
+    fn panic_hotspots_leave_out_examples_and_benches() {
+        let files = [
+            file("examples/demo.rs", 40),
+            file("benches/speed.rs", 30),
+            file("crates/core/examples/tour.rs", 20),
+            file("src/lib.rs", 3),
+            file("src/client.rs", 7),
+            file("src/quiet.rs", 0),
+            file("README.md", 9),
+        ];
+        let ranked: Vec<(String, u64)> =
+            RepositoryAnalyzer::panic_hotspots(&files).into_iter().map(|hotspot| (hotspot.path, hotspot.value)).collect();
+        assert_eq!(ranked, vec![("src/client.rs".to_string(), 7), ("src/lib.rs".to_string(), 3)]);
+    }
+
+    /// Occupation du tas par thread : seules les allocations du test mesuré comptent,
+    /// même quand d'autres tests s'exécutent en parallèle
+    mod heap {
+        use std::alloc::{GlobalAlloc, Layout, System};
+        use std::cell::Cell;
+
+        thread_local! {
+            static CURRENT: Cell<isize> = const { Cell::new(0) };
+            static PEAK: Cell<isize> = const { Cell::new(0) };
+        }
+
+        /// Allocateur système qui compte les octets demandés (`Layout::size`)
+        struct CountingAllocator;
+
+        fn change(bytes: isize) {
+            let _ = CURRENT.try_with(|current| {
+                current.set(current.get() + bytes);
+                let _ = PEAK.try_with(|peak| peak.set(peak.get().max(current.get())));
+            });
+        }
+
+        unsafe impl GlobalAlloc for CountingAllocator {
+            unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+                let pointer = System.alloc(layout);
+                if !pointer.is_null() {
+                    change(layout.size() as isize);
+                }
+                pointer
+            }
+
+            unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+                System.dealloc(pointer, layout);
+                change(-(layout.size() as isize));
+            }
+
+            unsafe fn realloc(&self, pointer: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+                let moved = System.realloc(pointer, layout, new_size);
+                if !moved.is_null() {
+                    change(new_size as isize - layout.size() as isize);
+                }
+                moved
+            }
+        }
+
+        #[global_allocator]
+        static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+        /// Octets au pic et octets conservés au-delà du point de départ pendant `run`
+        pub fn measure<T>(run: impl FnOnce() -> T) -> (usize, usize) {
+            let start = CURRENT.with(Cell::get);
+            PEAK.with(|peak| peak.set(start));
+            let kept = run();
+            let retained = CURRENT.with(Cell::get) - start;
+            let peak = PEAK.with(Cell::get) - start;
+            drop(kept);
+            (peak.max(0) as usize, retained.max(0) as usize)
+        }
+    }
+
+    /// Fichiers du dépôt synthétique
+    const FILES: usize = 2_000;
+    /// Types centraux, référencés depuis tout le dépôt
+    const CENTRAL_TYPES: usize = 300;
+    const TRAITS: [&str; 5] = ["Debug", "Clone", "Serialize", "Deserialize", "PartialEq"];
+    const RETURN_TYPES: [&str; 6] = ["()", "Self", "Result<(), GithubAnalyzerError>", "String", "bool", "Option<usize>"];
+    const METHODS: [&str; 8] = ["new", "fmt", "from", "default", "analyze", "build", "len", "is_empty"];
+    const PARAMS: [&str; 4] = ["&self", "path: &str", "content: &GithubContent", "project_summary: &mut ProjectSummary"];
+
+    /// Résultats d'analyse du fichier `index` tels que les rend `FileAnalyzer` : une
+    /// allocation par nom, trois types et huit méthodes
+    fn analyzed_file(index: usize) -> (GithubContent, Vec<TypeRelations>, Vec<MethodSignature>) {
+        let path = format!("src/module_{}/file_{}.rs", index / 50, index);
+        let content: GithubContent = serde_json::from_value(serde_json::json!({
+            "name": format!("file_{}.rs", index),
+            "path": path,
+            "sha": "",
+            "size": 2048,
+            "url": "",
+            "content": null,
+            "encoding": null,
+            "type": "file",
+        }))
+        .unwrap();
+        let name = |text: &str| SharedStr::from(text);
+        let central = |offset: usize| name(&format!("CentralType{}", (index * 7 + offset) % CENTRAL_TYPES));
+        let relations = (0..3)
+            .map(|position| TypeRelations {
+                type_name: name(&format!("Type{}", index * 3 + position)),
+                kind: TypeKind::Struct,
+                implemented_traits: TRAITS.iter().take(2 + position).map(|trait_name| name(trait_name)).collect(),
+                used_by: Vec::new(),
+                depends_on: (0..3).map(central).collect(),
+                defined_in: SharedStr::default(),
+                module: SharedStr::default(),
+                references: vec![name("String"), name("Vec"), name("Option")],
+                implementors: Vec::new(),
+                generics: Vec::new(),
+                doc: None,
+                macros: Vec::new(),
+                variants: Vec::new(),
+                fields: Vec::new(),
+                alias_of: None,
+            })
+            .collect();
+        let signatures = (0..8)
+            .map(|position| MethodSignature {
+                name: name(METHODS[position]),
+                params: PARAMS.iter().take(1 + position % 3).map(|param| name(param)).collect(),
+                return_type: name(RETURN_TYPES[(index + position) % RETURN_TYPES.len()]),
+                visibility: if position % 2 == 0 { Visibility::Public } else { Visibility::Private },
+                defined_in: SharedStr::default(),
+                module: SharedStr::default(),
+                owner: Some(name(&format!("Type{}", index * 3 + position % 3))),
+                trait_impl: None,
+                is_async: false,
+                is_unsafe: false,
+                is_const: false,
+                generics: Vec::new(),
+                where_bounds: Vec::new(),
+                doc: None,
+                complexity: 1,
+                body_lines: 3,
+            })
+            .collect();
+        (content, relations, signatures)
+    }
+
+    /// Agrège le dépôt synthétique par le même chemin que `process_file`, puis finalise
+    fn aggregate(interner: StringInterner) -> ProjectSummary {
+        let mut analyzer = RepositoryAnalyzer::new();
+        analyzer.interner = Mutex::new(interner);
+        let mut summary = RepositoryAnalyzer::empty_summary("https://github.com/owner/synthetic", "main");
+        for index in 0..FILES {
+            let (content, type_relations, method_signatures) = analyzed_file(index);
+            let configuration = Configuration { constants: Vec::new(), feature_flags: Vec::new(), custom_attributes: Vec::new() };
+            let analyzed = AnalyzedFile { summary: "", type_relations, method_signatures, configuration };
+            analyzer.update_project_summary(&content, analyzed, FileCategory::Source("rs".to_string()), &mut summary);
+            let path = analyzer.intern(&content.path);
+            summary.files_analyzed.push(path);
+        }
+        analyzer.finalize_analysis(&mut summary);
+        summary
+    }
+
+    #[test]
+    fn interning_reduces_the_heap_of_the_aggregated_summary() {
+        let (peak_before, retained_before) = heap::measure(|| aggregate(StringInterner::disabled()));
+        let (peak_after, retained_after) = heap::measure(|| aggregate(StringInterner::new()));
+
+        let reduction = |before: usize, after: usize| 100.0 * (1.0 - after as f64 / before as f64);
+        println!(
+            "{} files: peak {} -> {} bytes ({:.1}% less), retained {} -> {} bytes ({:.1}% less)",
+            FILES,
+            peak_before,
+            peak_after,
+            reduction(peak_before, peak_after),
+            retained_before,
+            retained_after,
+            reduction(retained_before, retained_after),
+        );
+        // Relevé : pic -11,4 %, mémoire conservée -24,0 % ; le pic vient surtout des
+        // structures temporaires de `finalize_analysis`, que l'internement ne touche pas
+        assert!(reduction(peak_before, peak_after) >= 10.0);
+        assert!(reduction(retained_before, retained_after) >= 20.0);
+    }
+
+    #[test]
+    fn panic_hotspots_keep_the_worst_files() {
+        let files: Vec<FileSummary> = (1..=HOTSPOT_ENTRIES + 5).map(|i| file(&format!("src/m{:02}.rs", i), i)).collect();
+        let ranked = RepositoryAnalyzer::panic_hotspots(&files);
+        assert_eq!(ranked.len(), HOTSPOT_ENTRIES);
+        assert_eq!(ranked[0].path, format!("src/m{:02}.rs", HOTSPOT_ENTRIES + 5));
+        assert!(ranked.windows(2).all(|pair| pair[0].value >= pair[1].value));
+    }
+}
@@ -0,0 +1,45 @@
+use std::collections::BTreeSet;
+
+use crate::analysis::file::is_binary_content;
+
+/// Fichier d'une source déjà disponible localement
+#[derive(Debug, Clone)]
+pub struct SourceFile {
+    /// Chemin relatif à la racine, séparé par `/`
+    pub path: String,
+    /// Taille en octets
+    pub size: u64,
+}
+
+impl SourceFile {
+    /// Nom du fichier (dernier segment du chemin)
+    pub fn name(&self) -> &str {
+        self.path.rsplit('/').next().unwrap_or(&self.path)
+    }
+}
+
+/// Arborescence de fichiers lisible sans passer par l'API GitHub (archive,
+/// répertoire local) : l'analyse et l'export la parcourent de la même façon
+pub trait SourceTree: Send + Sync {
+    /// Répertoires contenant au moins un fichier
+    fn directories(&self) -> BTreeSet<String>;
+
+    /// Fichiers de la source, dans un ordre stable
+    fn files(&self) -> Vec<SourceFile>;
+
+    /// Contenu brut du fichier d'indice `index` dans `files()` ; `None` s'il est illisible
+    fn read_bytes(&self, index: usize) -> Option<Vec<u8>>;
+
+    /// Contenu texte du fichier d'indice `index` dans `files()` ; `None` s'il est
+    /// illisible, binaire ou n'est pas en UTF-8
+    fn read_text(&self, index: usize) -> Option<String> {
+        let bytes = self.read_bytes(index)?;
+        if is_binary_content(&bytes) {
+            return None;
+        }
+        String::from_utf8(bytes).ok()
+    }
+
+    /// URL enregistrée dans `FileSummary.url` pour un chemin de la source
+    fn url(&self, path: &str) -> String;
+}
@@ -0,0 +1,631 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use quote::ToTokens;
+use regex::Regex;
+use syn::visit::{self, Visit};
+
+use crate::types::{
+    analysis::{Configuration, ConstantDefinition, EnumVariant, MethodSignature, StructField, TypeKind, TypeRelations, VariantShape},
+    SharedStr, Visibility,
+};
+
+/// Analyse d'un fichier Rust à partir de son arbre syntaxique : mêmes résultats que
+/// les motifs de `CodePatterns`, éléments sur plusieurs lignes et bornes génériques
+/// compris.
+///
+/// `None` si le fichier ne se laisse pas analyser (erreur de syntaxe, syntaxe
+/// instable) : l'appelant se rabat alors sur les motifs. Les relations de types
+/// rendues sont directes ; leur fermeture transitive reste à faire.
+pub fn analyze(content: &str) -> Option<(Vec<TypeRelations>, Vec<MethodSignature>, Configuration)> {
+    let file = syn::parse_file(content).ok()?;
+    let mut collector = Collector::new();
+    collector.visit_file(&file);
+    Some(collector.finish())
+}
+
+/// Type déclaré dans le fichier
+#[derive(Default)]
+struct DeclaredType {
+    kind: TypeKind,
+    generics: Vec<String>,
+    doc: Option<String>,
+    traits: Vec<String>,
+    references: BTreeSet<String>,
+    variants: Vec<EnumVariant>,
+    fields: Vec<StructField>,
+    alias_of: Option<String>,
+}
+
+struct Collector {
+    /// Types dans l'ordre de déclaration
+    order: Vec<String>,
+    types: BTreeMap<String, DeclaredType>,
+    /// Traits implémentés et types référencés par des blocs `impl`, rattachés en fin
+    /// d'analyse (le type peut être déclaré après son `impl`)
+    impls: Vec<(String, Option<String>, BTreeSet<String>)>,
+    signatures: Vec<MethodSignature>,
+    /// Type du bloc `impl` (et trait implémenté) ou trait en cours de visite
+    context: Option<(String, Option<String>)>,
+    configuration: Configuration,
+    feature: Regex,
+}
+
+impl Collector {
+    fn new() -> Self {
+        Self {
+            order: Vec::new(),
+            types: BTreeMap::new(),
+            impls: Vec::new(),
+            signatures: Vec::new(),
+            context: None,
+            configuration: Configuration {
+                constants: Vec::new(),
+                feature_flags: Vec::new(),
+                custom_attributes: Vec::new(),
+            },
+            feature: Regex::new(r#"feature\s*=\s*"([^"]+)""#).unwrap(),
+        }
+    }
+
+    /// Déclare un type ou un trait, avec ses paramètres génériques, ses dérivations et
+    /// les types qu'il référence (bornes des paramètres comprises)
+    fn declare(
+        &mut self,
+        kind: TypeKind,
+        name: &syn::Ident,
+        generics: &syn::Generics,
+        attributes: &[syn::Attribute],
+        mut references: BTreeSet<String>,
+    ) {
+        let name = name.to_string();
+        references.extend(type_names(|names| names.visit_generics(generics)));
+        let declared = self.types.entry(name.clone()).or_insert_with(|| {
+            self.order.push(name.clone());
+            DeclaredType {
+                kind,
+                generics: generics.params.iter().map(compact).collect(),
+                doc: doc_text(attributes),
+                ..Default::default()
+            }
+        });
+        for attribute in attributes {
+            derived_traits(&attribute.meta, &mut declared.traits);
+        }
+        declared.references.extend(references);
+    }
+
+    /// Relève une signature et son doc-comment ; `owner` est le contexte `impl` ou trait
+    /// qui la contient
+    fn push_signature(
+        &mut self,
+        signature: &syn::Signature,
+        visibility: &syn::Visibility,
+        attributes: &[syn::Attribute],
+        owner: Option<(String, Option<String>)>,
+    ) {
+        let params = signature
+            .inputs
+            .iter()
+            .map(|input| match input {
+                syn::FnArg::Receiver(receiver) => compact(receiver),
+                syn::FnArg::Typed(typed) => format!("{}: {}", compact(&typed.pat), compact(&typed.ty)),
+            })
+            .map(SharedStr::from)
+            .collect();
+        let (owner, trait_impl) = owner.unzip();
+        let return_type = match &signature.output {
+            syn::ReturnType::Default => "()".to_string(),
+            syn::ReturnType::Type(_, ty) => compact(ty),
+        };
+        self.signatures.push(MethodSignature {
+            name: SharedStr::from(signature.ident.to_string()),
+            params,
+            return_type: SharedStr::from(return_type.as_str()),
+            visibility: self::visibility(visibility),
+            defined_in: SharedStr::from(""),
+            module: SharedStr::from(""),
+            owner: owner.map(SharedStr::from),
+            trait_impl: trait_impl.flatten().map(SharedStr::from),
+            is_async: signature.asyncness.is_some(),
+            is_unsafe: signature.unsafety.is_some(),
+            is_const: signature.constness.is_some(),
+            generics: signature.generics.params.iter().map(compact).collect(),
+            where_bounds: signature
+                .generics
+                .where_clause
+                .iter()
+                .flat_map(|clause| clause.predicates.iter().map(compact))
+                .collect(),
+            doc: doc_text(attributes),
+            complexity: 0,
+            body_lines: 0,
+        });
+    }
+
+    fn push_constant(&mut self, name: &syn::Ident, ty: &syn::Type, value: &syn::Expr) {
+        self.configuration.constants.push(ConstantDefinition {
+            name: name.to_string(),
+            const_type: compact(ty),
+            value: compact(value),
+            defined_in: String::new(),
+        });
+    }
+
+    /// Relations de chaque type : traits, types du fichier utilisés et utilisateurs,
+    /// types déclarés ailleurs
+    fn finish(mut self) -> (Vec<TypeRelations>, Vec<MethodSignature>, Configuration) {
+        for (self_type, implemented, references) in std::mem::take(&mut self.impls) {
+            if let Some(declared) = self.types.get_mut(&self_type) {
+                // Un trait du projet implémenté par le type en devient une dépendance
+                declared.references.extend(implemented.clone());
+                declared.traits.extend(implemented);
+                declared.references.extend(references);
+            }
+        }
+
+        let mut used_by: BTreeMap<&str, Vec<SharedStr>> = BTreeMap::new();
+        for name in &self.order {
+            for reference in &self.types[name].references {
+                if reference != name && self.types.contains_key(reference) {
+                    used_by.entry(reference.as_str()).or_default().push(SharedStr::from(name.as_str()));
+                }
+            }
+        }
+
+        let relations = self
+            .order
+            .iter()
+            .map(|name| {
+                let declared = &self.types[name];
+                let (depends_on, references): (Vec<&String>, Vec<&String>) = declared
+                    .references
+                    .iter()
+                    .filter(|reference| *reference != name)
+                    .partition(|reference| self.types.contains_key(*reference));
+                TypeRelations {
+                    type_name: SharedStr::from(name.as_str()),
+                    kind: declared.kind,
+                    implemented_traits: declared.traits.iter().map(|t| SharedStr::from(t.as_str())).collect(),
+                    used_by: used_by.remove(name.as_str()).unwrap_or_default(),
+                    depends_on: depends_on.into_iter().map(|t| SharedStr::from(t.as_str())).collect(),
+                    defined_in: SharedStr::from(""),
+                    module: SharedStr::from(""),
+                    references: references.into_iter().map(|t| SharedStr::from(t.as_str())).collect(),
+                    implementors: Vec::new(),
+                    generics: declared.generics.clone(),
+                    doc: declared.doc.clone(),
+                    macros: Vec::new(),
+                    variants: declared.variants.clone(),
+                    fields: declared.fields.clone(),
+                    alias_of: declared.alias_of.clone(),
+                }
+            })
+            .collect();
+        (relations, self.signatures, self.configuration)
+    }
+}
+
+impl<'ast> Visit<'ast> for Collector {
+    fn visit_item_struct(&mut self, node: &'ast syn::ItemStruct) {
+        self.declare(TypeKind::Struct, &node.ident, &node.generics, &node.attrs, type_names(|names| names.visit_fields(&node.fields)));
+        if let Some(declared) = self.types.get_mut(&node.ident.to_string()).filter(|declared| declared.fields.is_empty()) {
+            declared.fields = node.fields.iter().enumerate().map(|(position, field)| struct_field(position, field)).collect();
+        }
+        visit::visit_item_struct(self, node);
+    }
+
+    fn visit_item_enum(&mut self, node: &'ast syn::ItemEnum) {
+        let references = type_names(|names| {
+            for variant in &node.variants {
+                names.visit_fields(&variant.fields);
+            }
+        });
+        self.declare(TypeKind::Enum, &node.ident, &node.generics, &node.attrs, references);
+        if let Some(declared) = self.types.get_mut(&node.ident.to_string()).filter(|declared| declared.variants.is_empty()) {
+            declared.variants = node.variants.iter().map(enum_variant).collect();
+        }
+        visit::visit_item_enum(self, node);
+    }
+
+    fn visit_item_union(&mut self, node: &'ast syn::ItemUnion) {
+        self.declare(TypeKind::Struct, &node.ident, &node.generics, &node.attrs, type_names(|names| names.visit_fields_named(&node.fields)));
+        visit::visit_item_union(self, node);
+    }
+
+    fn visit_item_type(&mut self, node: &'ast syn::ItemType) {
+        self.declare(TypeKind::Alias, &node.ident, &node.generics, &node.attrs, type_names(|names| names.visit_type(&node.ty)));
+        if let Some(declared) = self.types.get_mut(&node.ident.to_string()) {
+            declared.alias_of.get_or_insert_with(|| compact(&node.ty));
+        }
+        visit::visit_item_type(self, node);
+    }
+
+    fn visit_item_trait(&mut self, node: &'ast syn::ItemTrait) {
+        // Supertraits et types des signatures des éléments du trait
+        let references = type_names(|names| {
+            for bound in &node.supertraits {
+                names.visit_type_param_bound(bound);
+            }
+            for item in &node.items {
+                match item {
+                    syn::TraitItem::Fn(method) => names.visit_signature(&method.sig),
+                    syn::TraitItem::Const(constant) => names.visit_type(&constant.ty),
+                    _ => {},
+                }
+            }
+        });
+        self.declare(TypeKind::Trait, &node.ident, &node.generics, &node.attrs, references);
+        let outer = self.context.replace((node.ident.to_string(), None));
+        visit::visit_item_trait(self, node);
+        self.context = outer;
+    }
+
+    fn visit_item_impl(&mut self, node: &'ast syn::ItemImpl) {
+        let implemented = node.trait_.as_ref().and_then(|(_, path, _)| path.segments.last());
+        let implemented = implemented.map(|segment| segment.ident.to_string());
+        let outer = self.context.take();
+        if let Some(self_type) = last_segment(&node.self_ty) {
+            // Bornes du bloc, types des signatures et des éléments associés, corps des
+            // méthodes exclus
+            let references = type_names(|names| {
+                names.visit_generics(&node.generics);
+                for item in &node.items {
+                    match item {
+                        syn::ImplItem::Fn(method) => names.visit_signature(&method.sig),
+                        syn::ImplItem::Const(constant) => names.visit_type(&constant.ty),
+                        syn::ImplItem::Type(alias) => names.visit_type(&alias.ty),
+                        _ => {},
+                    }
+                }
+            });
+            self.context = Some((self_type.clone(), implemented.clone()));
+            self.impls.push((self_type, implemented, references));
+        }
+        visit::visit_item_impl(self, node);
+        self.context = outer;
+    }
+
+    fn visit_item_fn(&mut self, node: &'ast syn::ItemFn) {
+        self.push_signature(&node.sig, &node.vis, &node.attrs, None);
+        // Les éléments déclarés dans un corps de fonction n'appartiennent pas au bloc englobant
+        let outer = self.context.take();
+        visit::visit_item_fn(self, node);
+        self.context = outer;
+    }
+
+    fn visit_impl_item_fn(&mut self, node: &'ast syn::ImplItemFn) {
+        self.push_signature(&node.sig, &node.vis, &node.attrs, self.context.clone());
+        let outer = self.context.take();
+        visit::visit_impl_item_fn(self, node);
+        self.context = outer;
+    }
+
+    fn visit_trait_item_fn(&mut self, node: &'ast syn::TraitItemFn) {
+        self.push_signature(&node.sig, &syn::Visibility::Inherited, &node.attrs, self.context.clone());
+        let outer = self.context.take();
+        visit::visit_trait_item_fn(self, node);
+        self.context = outer;
+    }
+
+    fn visit_item_const(&mut self, node: &'ast syn::ItemConst) {
+        self.push_constant(&node.ident, &node.ty, &node.expr);
+        visit::visit_item_const(self, node);
+    }
+
+    fn visit_impl_item_const(&mut self, node: &'ast syn::ImplItemConst) {
+        self.push_constant(&node.ident, &node.ty, &node.expr);
+        visit::visit_impl_item_const(self, node);
+    }
+
+    fn visit_attribute(&mut self, node: &'ast syn::Attribute) {
+        let text = compact(&node.meta);
+        if node.path().is_ident("cfg") {
+            let flags = self.feature.captures_iter(&text).map(|captures| captures[1].to_string());
+            self.configuration.feature_flags.extend(flags);
+        }
+        if !text.starts_with("cfg") && !text.starts_with("test") && !text.starts_with("doc") {
+            self.configuration.custom_attributes.push(text);
+        }
+        visit::visit_attribute(self, node);
+    }
+}
+
+/// Noms commençant par une majuscule dans les chemins visités (`Vec<Foo>` → `Vec`, `Foo`)
+#[derive(Default)]
+struct TypeNames(BTreeSet<String>);
+
+impl<'ast> Visit<'ast> for TypeNames {
+    fn visit_path_segment(&mut self, node: &'ast syn::PathSegment) {
+        let name = node.ident.to_string();
+        if name.starts_with(|c: char| c.is_ascii_uppercase()) && name != "Self" {
+            self.0.insert(name);
+        }
+        visit::visit_path_segment(self, node);
+    }
+}
+
+/// Visibilité d'un élément ou d'un champ, toute portée `pub(..)` valant `pub(crate)`
+fn visibility(visibility: &syn::Visibility) -> Visibility {
+    match visibility {
+        syn::Visibility::Public(_) => Visibility::Public,
+        syn::Visibility::Restricted(_) => Visibility::PublicCrate,
+        syn::Visibility::Inherited => Visibility::Private,
+    }
+}
+
+/// Champ d'une structure, nommé par sa position dans une structure tuple
+fn struct_field(position: usize, field: &syn::Field) -> StructField {
+    StructField {
+        name: field.ident.as_ref().map_or_else(|| position.to_string(), ToString::to_string),
+        field_type: compact(&field.ty),
+        visibility: visibility(&field.vis),
+    }
+}
+
+/// Variante d'une énumération et types ou champs de sa charge
+fn enum_variant(variant: &syn::Variant) -> EnumVariant {
+    let (shape, fields) = match &variant.fields {
+        syn::Fields::Unit => (VariantShape::Unit, Vec::new()),
+        syn::Fields::Unnamed(fields) => {
+            (VariantShape::Tuple, fields.unnamed.iter().map(|field| compact(&field.ty)).collect())
+        },
+        syn::Fields::Named(fields) => (
+            VariantShape::Struct,
+            fields
+                .named
+                .iter()
+                .map(|field| {
+                    let name = field.ident.as_ref().map(ToString::to_string).unwrap_or_default();
+                    format!("{}: {}", name, compact(&field.ty))
+                })
+                .collect(),
+        ),
+    };
+    EnumVariant { name: variant.ident.to_string(), shape, fields }
+}
+
+/// Traits listés par un attribut `derive(..)`, y compris sous `cfg_attr(predicate, derive(..))`
+fn derived_traits(meta: &syn::Meta, traits: &mut Vec<String>) {
+    let syn::Meta::List(list) = meta else { return };
+    if list.path.is_ident("derive") {
+        let derived =
+            list.parse_args_with(syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated);
+        traits.extend(derived.into_iter().flatten().map(|path| compact(&path)));
+    } else if list.path.is_ident("cfg_attr") {
+        // Le premier argument est le prédicat, les suivants les attributs appliqués
+        let nested =
+            list.parse_args_with(syn::punctuated::Punctuated::<syn::Meta, syn::Token![,]>::parse_terminated);
+        for meta in nested.iter().flatten().skip(1) {
+            derived_traits(meta, traits);
+        }
+    }
+}
+
+/// Texte des doc-comments extérieurs (`///`, `#[doc = ".."]`), une ligne par commentaire
+fn doc_text(attributes: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = attributes
+        .iter()
+        .filter(|attribute| attribute.path().is_ident("doc") && matches!(attribute.style, syn::AttrStyle::Outer))
+        .filter_map(|attribute| match &attribute.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value: syn::Expr::Lit(syn::ExprLit { lit: syn::Lit::Str(text), .. }),
+                ..
+            }) => Some(text.value()),
+            _ => None,
+        })
+        .map(|line| line.strip_prefix(' ').map(str::to_string).unwrap_or(line))
+        .collect();
+    let doc = lines.join("\n");
+    (!doc.trim().is_empty()).then(|| doc.trim().to_string())
+}
+
+fn type_names(visit: impl FnOnce(&mut TypeNames)) -> BTreeSet<String> {
+    let mut names = TypeNames::default();
+    visit(&mut names);
+    names.0
+}
+
+/// Nom du type d'un bloc `impl` (`impl<T> a::Foo<T>` → `Foo`)
+fn last_segment(ty: &syn::Type) -> Option<String> {
+    match ty {
+        syn::Type::Path(path) => path.path.segments.last().map(|segment| segment.ident.to_string()),
+        syn::Type::Reference(reference) => last_segment(&reference.elem),
+        _ => None,
+    }
+}
+
+/// Texte d'un nœud sans les espaces que `to_token_stream` place entre tous les jetons
+/// (`Vec < & 'a str >` → `Vec<&'a str>`)
+fn compact(node: &impl ToTokens) -> String {
+    let text = node.to_token_stream().to_string();
+    let chars: Vec<char> = text.chars().collect();
+    let mut compacted = String::with_capacity(text.len());
+    for (i, c) in chars.iter().enumerate() {
+        if *c == ' ' {
+            let previous = compacted.chars().last().unwrap_or(' ');
+            let next = chars.get(i + 1).copied().unwrap_or(' ');
+            let after_next = chars.get(i + 2).copied().unwrap_or(' ');
+            // `?` d'une borne (`T: ?Sized`) garde son espace et se colle au trait
+            let relaxed_bound = next == '?' && (previous == ':' || previous == '+');
+            let glued_before = (",;)]>.".contains(next) || (next == '?' && !relaxed_bound))
+                || (next == ':' && after_next == ':')
+                || (next == ':' && after_next == ' ' && previous != ':')
+                || ((next == '(' || next == '<') && (previous.is_alphanumeric() || previous == '_' || previous == '>'));
+            let rest: String = chars[i + 1..].iter().take(6).collect();
+            let glued_after = "(<[&#!".contains(previous)
+                || compacted.ends_with("::")
+                || compacted.ends_with(": ?")
+                || compacted.ends_with("+ ?")
+                // Pointeur brut (`*const u8`, `*mut T`)
+                || (previous == '*' && (rest.starts_with("const ") || rest.starts_with("mut ")));
+            if glued_before || glued_after {
+                continue;
+            }
+        }
+        compacted.push(*c);
+    }
+    compacted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::file::FileAnalyzer;
+
+
+    const FIXTURE: &str = r#"use std::fmt;
+
+/// Registre générique
+#[derive(Debug,
+         Clone)]
+pub struct Registry<K, V>
+where
+    K: Ord,
+{
+    entries: Vec<(K, V)>,
+}
+
+impl<K: Ord + Clone, V> Registry<K, V> {
+    pub fn new() -> Self {
+        Self { entries: Vec::new() }
+    }
+
+    pub async fn insert(&mut self, key: K, value: V) -> Option<V> where V: Clone {
+        None
+    }
+}
+
+impl<K: Ord, V> fmt::Display for Registry<K, V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.entries.len())
+    }
+}
+
+pub const SEPARATOR: &str = "a;b";
+pub const MAX: usize = 16;
+
+#[cfg(feature = "serde")]
+pub fn to_json() {}
+"#;
+
+    type Analysis = (Vec<TypeRelations>, Vec<MethodSignature>, Configuration);
+
+    fn backends(content: &str) -> (Analysis, Analysis) {
+        let precise = analyze(content).expect("fixture parses");
+        (precise, FileAnalyzer::new().analyze_with_patterns(content))
+    }
+
+    fn traits_of<'a>(relations: &'a [TypeRelations], name: &str) -> Vec<&'a str> {
+        let relation = relations.iter().find(|relation| &*relation.type_name == name).unwrap();
+        relation.implemented_traits.iter().map(|name| &**name).collect()
+    }
+
+    #[test]
+    fn impl_headers_wrapped_by_rustfmt_are_only_seen_by_syn() {
+        let content = "pub struct Wrapper(String);
+
+impl<T> From<T>
+    for Wrapper
+where
+    T: Into<String>,
+{
+    fn from(value: T) -> Self { Wrapper(value.into()) }
+}
+
+pub struct Node { value: u32 }
+
+impl
+    Default for Node {
+    fn default() -> Self { Node { value: 0 } }
+}
+";
+        let ((syn_types, syn_signatures, _), (_, regex_signatures, _)) = backends(content);
+        let owners = |signatures: &[MethodSignature]| -> Vec<(String, Option<String>, Option<String>)> {
+            signatures
+                .iter()
+                .map(|signature| {
+                    let owner = signature.owner.as_deref().map(str::to_string);
+                    (signature.name.to_string(), owner, signature.trait_impl.as_deref().map(str::to_string))
+                })
+                .collect()
+        };
+        let some = |name: &str| Some(name.to_string());
+
+        assert_eq!(traits_of(&syn_types, "Wrapper"), vec!["From"]);
+        assert_eq!(traits_of(&syn_types, "Node"), vec!["Default"]);
+        assert_eq!(
+            owners(&syn_signatures),
+            vec![("from".to_string(), some("Wrapper"), some("From")), ("default".to_string(), some("Node"), some("Default"))]
+        );
+        // Les motifs lisent `From` comme le type du bloc et perdent l'en-tête coupé après `impl`
+        assert_eq!(
+            owners(&regex_signatures),
+            vec![("from".to_string(), some("From"), None), ("default".to_string(), None, None)]
+        );
+    }
+
+    #[test]
+    fn constant_values_keep_semicolons_inside_strings_with_syn() {
+        let ((_, _, syn_config), (_, _, regex_config)) = backends(FIXTURE);
+        let value = |config: &Configuration, name: &str| {
+            config.constants.iter().find(|constant| constant.name == name).map(|constant| constant.value.clone())
+        };
+        assert_eq!(value(&syn_config, "SEPARATOR").as_deref(), Some("\"a;b\""));
+        assert_eq!(value(&regex_config, "SEPARATOR").as_deref(), Some("\"a"));
+        assert_eq!(value(&syn_config, "MAX"), value(&regex_config, "MAX"));
+    }
+
+    #[test]
+    fn attributes_spanning_lines_are_only_seen_by_syn() {
+        let ((_, _, syn_config), (_, _, regex_config)) = backends(FIXTURE);
+        assert_eq!(syn_config.custom_attributes, vec!["derive(Debug, Clone)"]);
+        assert!(regex_config.custom_attributes.is_empty());
+        assert_eq!(syn_config.feature_flags, regex_config.feature_flags);
+    }
+
+    #[test]
+    fn both_backends_agree_on_ordinary_signatures() {
+        let ((_, syn_signatures, _), (_, regex_signatures, _)) = backends(FIXTURE);
+        let shape = |signatures: &[MethodSignature]| -> Vec<String> {
+            signatures
+                .iter()
+                .map(|signature| {
+                    format!(
+                        "{:?} {} ({}) -> {} owner={:?} trait={:?} async={} where={:?}",
+                        signature.visibility,
+                        signature.name,
+                        signature.params.join(", "),
+                        signature.return_type,
+                        signature.owner,
+                        signature.trait_impl,
+                        signature.is_async,
+                        signature.where_bounds,
+                    )
+                })
+                .collect()
+        };
+        assert_eq!(shape(&syn_signatures), shape(&regex_signatures));
+        assert_eq!(syn_signatures.len(), 4);
+    }
+
+    #[test]
+    fn compacted_tokens_read_like_source() {
+        let ty = |text: &str| compact(&syn::parse_str::<syn::Type>(text).unwrap());
+        assert_eq!(ty("*const u8"), "*const u8");
+        assert_eq!(ty("*mut Vec<T>"), "*mut Vec<T>");
+        assert_eq!(ty("Box<dyn Fn(&str) -> bool + Send>"), "Box<dyn Fn(&str) -> bool + Send>");
+        let generics = syn::parse_str::<syn::Generics>("<T: ?Sized + Clone, U: Send + ?Sized>").unwrap();
+        assert_eq!(compact(&generics.params), "T: ?Sized + Clone, U: Send + ?Sized");
+        let value = |text: &str| compact(&syn::parse_str::<syn::Expr>(text).unwrap());
+        assert_eq!(value("16 * 2"), "16 * 2");
+        assert_eq!(value("parse(x)?"), "parse(x)?");
+    }
+
+    #[test]
+    fn unparsable_files_fall_back_to_the_patterns() {
+        let broken = "pub struct Broken {\n    field: u32,\n\npub fn still_listed(x: u32) -> u32 { x }\n";
+        assert!(analyze(broken).is_none());
+        let (_, signatures, _) = FileAnalyzer::new().analyze_with_patterns(broken);
+        assert_eq!(signatures.iter().map(|signature| &*signature.name).collect::<Vec<_>>(), vec!["still_listed"]);
+    }
+}
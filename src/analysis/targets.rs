@@ -0,0 +1,57 @@
+/// Cible cargo autre que la bibliothèque
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    Bin,
+    Example,
+    Bench,
+}
+
+impl TargetKind {
+    /// Table du `Cargo.toml` déclarant ce type de cible (`[[bin]]`)
+    pub fn manifest_key(&self) -> &'static str {
+        match self {
+            TargetKind::Bin => "bin",
+            TargetKind::Example => "example",
+            TargetKind::Bench => "bench",
+        }
+    }
+}
+
+/// Premier répertoire cargo du chemin (`src`, `examples`, `benches`) et la suite du
+/// chemin ; les répertoires qui le précèdent sont ceux de la crate
+fn cargo_directory(path: &str) -> Option<(&str, Vec<&str>)> {
+    let components: Vec<&str> = path.split('/').collect();
+    let index = components.iter().position(|component| matches!(*component, "src" | "examples" | "benches"))?;
+    Some((components[index], components[index + 1..].to_vec()))
+}
+
+/// Fichier Rust rangé sous `examples/` ou `benches/` (cibles et modules qu'elles
+/// partagent), hors `src/`
+pub fn target_directory(path: &str) -> Option<TargetKind> {
+    if !path.ends_with(".rs") {
+        return None;
+    }
+    match cargo_directory(path)? {
+        ("examples", _) => Some(TargetKind::Example),
+        ("benches", _) => Some(TargetKind::Bench),
+        _ => None,
+    }
+}
+
+/// Cible découverte automatiquement par cargo d'après le chemin : `src/main.rs`,
+/// `src/bin/x.rs`, `src/bin/x/main.rs`, `examples/x.rs`, `examples/x/main.rs`,
+/// `benches/x.rs`, `benches/x/main.rs`
+pub fn conventional_target(path: &str) -> Option<TargetKind> {
+    if !path.ends_with(".rs") {
+        return None;
+    }
+    let (directory, rest) = cargo_directory(path)?;
+    let is_target = |rest: &[&str]| matches!(rest, [_] | [_, "main.rs"]);
+    match (directory, rest.as_slice()) {
+        ("src", ["main.rs"]) => Some(TargetKind::Bin),
+        ("src", ["bin", rest @ ..]) if is_target(rest) => Some(TargetKind::Bin),
+        ("examples", rest) if is_target(rest) => Some(TargetKind::Example),
+        ("benches", rest) if is_target(rest) => Some(TargetKind::Bench),
+        _ => None,
+    }
+}
@@ -0,0 +1,99 @@
+use regex::Regex;
+
+use crate::analysis::file::braces;
+
+/// Attributs sans rapport avec le lanceur de tests
+const HOUSEKEEPING_ATTRIBUTES: [&str; 7] = ["doc", "allow", "cfg", "ignore", "should_panic", "inline", "must_use"];
+
+/// Compte les fonctions de test d'un fichier Rust : celles marquées `#[test]`,
+/// `#[tokio::test]`, `#[rstest]`, `#[test_case(..)]`… partout dans le fichier, et
+/// dans un module `#[cfg(test)]` celles portant un attribut de lanceur moins courant
+/// (`#[wasm_bindgen_test]`, `#[sqlx::test]`, `#[serial]`).
+pub fn count_tests(content: &str) -> usize {
+    let declaration = Regex::new(r#"^(?:pub(?:\([^)]*\))?\s+)?(?:(?:const|async|unsafe|extern\s+"[^"]*")\s+)*(fn|mod)\s+\w+"#).unwrap();
+    let mut count = 0;
+    // Chaque accolade ouverte indique si elle ouvre un module de test
+    let mut blocks: Vec<bool> = Vec::new();
+    let mut attributes: Vec<String> = Vec::new();
+    let mut opens_test_module = false;
+
+    for line in content.lines() {
+        let mut rest = line.split("//").next().unwrap_or("").trim();
+        while rest.starts_with("#[") {
+            let Some(end) = attribute_end(rest) else { break };
+            attributes.push(rest[2..end].trim().to_string());
+            rest = rest[end + 1..].trim_start();
+        }
+
+        if let Some(captures) = declaration.captures(rest) {
+            let in_test_module = blocks.last().copied().unwrap_or(false);
+            if &captures[1] == "fn" {
+                let is_test = attributes.iter().any(|attribute| {
+                    is_test_attribute(attribute) || (in_test_module && !HOUSEKEEPING_ATTRIBUTES.contains(&attribute_name(attribute)))
+                });
+                count += usize::from(is_test);
+            } else {
+                opens_test_module = in_test_module || attributes.iter().any(|attribute| is_cfg_test(attribute));
+            }
+        }
+        if !rest.is_empty() {
+            attributes.clear();
+        }
+
+        for brace in braces(rest) {
+            if brace == '{' {
+                blocks.push(std::mem::take(&mut opens_test_module));
+            } else {
+                blocks.pop();
+            }
+        }
+        // `mod tests;` : module déclaré dans un autre fichier
+        if rest.ends_with(';') {
+            opens_test_module = false;
+        }
+    }
+    count
+}
+
+/// Indique si un fichier est un test d'intégration : sous le répertoire `tests/` d'un
+/// crate, à la racine ou dans un membre de l'espace de travail
+pub fn is_integration_test(path: &str) -> bool {
+    path.ends_with(".rs") && (path.starts_with("tests/") || path.contains("/tests/")) && !path.contains("/src/")
+}
+
+/// Position du `]` fermant l'attribut ouvert en tête de `text`
+fn attribute_end(text: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (position, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(position);
+                }
+            },
+            _ => {},
+        }
+    }
+    None
+}
+
+/// Dernier segment du chemin d'un attribut (`tokio::test(flavor = ..)` → `test`)
+fn attribute_name(attribute: &str) -> &str {
+    let path = attribute.split(['(', '=', ' ']).next().unwrap_or(attribute);
+    path.rsplit("::").next().unwrap_or(path)
+}
+
+/// Attribut de test reconnu quel que soit le module : `test`, `*::test`, `rstest`,
+/// `test_case`, `quickcheck`, `proptest`, ou un nom en `_test`
+fn is_test_attribute(attribute: &str) -> bool {
+    let name = attribute_name(attribute);
+    ["test", "rstest", "test_case", "quickcheck", "proptest"].contains(&name) || name.ends_with("_test")
+}
+
+/// `#[cfg(test)]`, éventuellement combiné (`cfg(all(test, feature = ".."))`)
+fn is_cfg_test(attribute: &str) -> bool {
+    let compact: String = attribute.chars().filter(|c| !c.is_whitespace()).collect();
+    compact == "cfg(test)" || compact.starts_with("cfg(all(test")
+}
@@ -0,0 +1,30 @@
+use regex::Regex;
+
+use crate::analysis::masking::comments;
+use crate::types::analysis::TodoItem;
+
+/// Marqueurs de travail en suspens reconnus dans les commentaires
+pub const TODO_MARKERS: [&str; 5] = ["TODO", "FIXME", "HACK", "XXX", "BUG"];
+
+/// Relève les `TODO`, `FIXME`, `HACK`, `XXX` et `BUG` des commentaires d'un fichier
+/// Rust (pas ceux des chaînes), avec le texte qui les suit sur la même ligne.
+///
+/// Le marqueur doit être écrit en capitales ; un auteur entre parenthèses
+/// (`TODO(alice):`) et le `:` qui suit sont retirés du texte.
+pub fn scan_todos(path: &str, content: &str) -> Vec<TodoItem> {
+    let marker = Regex::new(r"\b(TODO|FIXME|HACK|XXX|BUG)\b(?:\([^)]*\))?:?(.*)").unwrap();
+    let mut todos = Vec::new();
+    for (line, comment) in comments(content) {
+        for (offset, text) in comment.lines().enumerate() {
+            let Some(captures) = marker.captures(text) else { continue };
+            let text = captures[2].trim().trim_end_matches("*/").trim_end();
+            todos.push(TodoItem {
+                file: path.to_string(),
+                line: line + offset,
+                marker: captures[1].to_string(),
+                text: text.to_string(),
+            });
+        }
+    }
+    todos
+}
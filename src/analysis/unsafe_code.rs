@@ -0,0 +1,150 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use crate::analysis::masking::mask_code;
+use crate::analysis::panics::test_code_lines;
+use crate::types::analysis::{UnsafeCounts, UnsafeKind};
+
+/// Code `unsafe` relevé dans un fichier Rust
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnsafeScan {
+    pub counts: UnsafeCounts,
+    /// Ligne (à partir de 1) et forme de chaque usage
+    pub lines: Vec<(usize, UnsafeKind)>,
+    /// Le fichier interdit le code `unsafe` (`#![forbid(unsafe_code)]`, `#![deny(unsafe_code)]`)
+    pub forbidden: bool,
+}
+
+/// Relève les `unsafe fn`, `unsafe impl`, `unsafe trait` et blocs `unsafe { .. }` d'un
+/// fichier Rust, hors commentaires, chaînes et code de test (voir `scan_panics`).
+///
+/// Les types pointeurs de fonction (`unsafe fn(u8)`) et les blocs `unsafe extern`
+/// ne sont pas comptés.
+pub fn scan_unsafe(content: &str) -> UnsafeScan {
+    let code = mask_code(content);
+    let keyword = Regex::new(r"\bunsafe\b").unwrap();
+    let lint = Regex::new(r"#!\[\s*(?:forbid|deny)\s*\(([^)]*)\)\s*\]").unwrap();
+
+    let test_lines = test_code_lines(&code);
+    let mut scan = UnsafeScan::default();
+    for found in keyword.find_iter(&code) {
+        let Some(kind) = unsafe_kind(&code[found.end()..]) else { continue };
+        let line = code[..found.start()].matches('\n').count() + 1;
+        if test_lines.get(line - 1).copied().unwrap_or(false) {
+            continue;
+        }
+        scan.counts.record(kind);
+        scan.lines.push((line, kind));
+    }
+    scan.forbidden = lint
+        .captures_iter(&code)
+        .any(|captures| captures[1].split(',').any(|name| name.trim() == "unsafe_code"));
+    scan
+}
+
+/// Forme de l'élément qui suit le mot-clé `unsafe`
+fn unsafe_kind(after: &str) -> Option<UnsafeKind> {
+    let after = after.trim_start();
+    if after.starts_with('{') {
+        return Some(UnsafeKind::Block);
+    }
+    if strip_word(after, "impl").is_some() {
+        return Some(UnsafeKind::Impl);
+    }
+    if strip_word(after, "trait").or_else(|| strip_word(after, "auto").and_then(|rest| strip_word(rest, "trait"))).is_some() {
+        return Some(UnsafeKind::Trait);
+    }
+    // `unsafe extern "C" fn name` : l'ABI, masquée, est sautée
+    let after = strip_word(after, "extern")
+        .map(|rest| rest.trim_start_matches(|c: char| c == '"' || c.is_whitespace()))
+        .unwrap_or(after);
+    let name = strip_word(after, "fn")?;
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_').then_some(UnsafeKind::Function)
+}
+
+/// Reste du texte après `word`, s'il commence par ce mot entier
+fn strip_word<'a>(text: &'a str, word: &str) -> Option<&'a str> {
+    let rest = text.strip_prefix(word)?;
+    (!rest.starts_with(|c: char| c.is_alphanumeric() || c == '_')).then(|| rest.trim_start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// (fonctions, blocs, impl, traits) et lignes relevées
+    fn scan(content: &str) -> ((usize, usize, usize, usize), Vec<usize>) {
+        let scan = scan_unsafe(content);
+        let counts = &scan.counts;
+        (
+            (counts.functions, counts.blocks, counts.impls, counts.traits),
+            scan.lines.iter().map(|(line, _)| *line).collect(),
+        )
+    }
+
+    #[test]
+    fn each_form_of_unsafe_is_counted() {
+        let content = r#"pub unsafe fn raw(ptr: *const u8) -> u8 {
+    unsafe { *ptr }
+}
+unsafe impl Send for Handle {}
+pub unsafe trait Zeroable {}
+pub unsafe extern "C" fn callback() {}
+type Callback = unsafe fn(u8);
+unsafe extern "C" {
+    fn abs(x: i32) -> i32;
+}
+"#;
+        assert_eq!(scan(content), ((2, 1, 1, 1), vec![1, 2, 4, 5, 6]));
+    }
+
+    #[test]
+    fn comments_and_strings_are_ignored() {
+        let content = r##"/// Appelle `unsafe { read() }` si besoin
+fn f() {
+    // unsafe { x() }
+    /* unsafe impl Send for X {} */
+    let help = "unsafe fn hidden() {}";
+    let raw = r#"unsafe { y() }"#;
+    println!("{}", help);
+}
+"##;
+        assert_eq!(scan(content), ((0, 0, 0, 0), vec![]));
+    }
+
+    #[test]
+    fn test_code_is_excluded() {
+        let content = r#"pub fn kept() { unsafe { a() } }
+
+#[cfg(test)]
+mod tests {
+    unsafe fn helper() {}
+
+    #[test]
+    fn case() {
+        unsafe { helper() }
+    }
+}
+
+#[test]
+fn top_level() { unsafe { b() } }
+
+#[cfg(all(test, feature = "slow"))]
+unsafe impl Send for Fixture {}
+
+pub fn after() {
+    unsafe { c() }
+}
+"#;
+        assert_eq!(scan(content), ((0, 2, 0, 0), vec![1, 20]));
+        assert_eq!(scan("#![cfg(test)]\n\nunsafe fn f() {}\n"), ((0, 0, 0, 0), vec![]));
+        assert_eq!(scan("#[cfg(test)]\nmod tests;\n\nunsafe fn kept() {}\n"), ((1, 0, 0, 0), vec![4]));
+    }
+
+    #[test]
+    fn forbid_and_deny_lints_are_detected() {
+        assert!(scan_unsafe("#![forbid(unsafe_code)]\n").forbidden);
+        assert!(scan_unsafe("#![deny(missing_docs, unsafe_code)]\n").forbidden);
+        assert!(!scan_unsafe("#![deny(missing_docs)]\n// #![forbid(unsafe_code)]\n").forbidden);
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashMap;
+
+use regex::Regex;
+
+use crate::analysis::masking::mask_code;
+use crate::types::analysis::{ApiItemKind, ApiModule, PossiblyUnused, TypeRelations};
+use crate::types::SharedStr;
+
+/// Identifiants écrits dans les fichiers Rust du projet, hors commentaires et chaînes,
+/// pour repérer les éléments publics que rien ne mentionne hors de leur fichier.
+///
+/// Deux fichiers par identifiant suffisent : un élément est mentionné ailleurs dès
+/// qu'un fichier autre que le sien contient son nom.
+#[derive(Debug)]
+pub struct UsageIndex {
+    files: HashMap<String, Vec<SharedStr>>,
+    identifier: Regex,
+}
+
+impl Default for UsageIndex {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UsageIndex {
+    pub fn new() -> Self {
+        Self { files: HashMap::new(), identifier: Regex::new(r"\b[A-Za-z_]\w*\b").unwrap() }
+    }
+
+    /// Relève les identifiants d'un fichier Rust
+    pub fn observe(&mut self, path: &str, content: &str) {
+        let code = mask_code(content);
+        let path = SharedStr::from(path);
+        for found in self.identifier.find_iter(&code) {
+            let files = self.files.entry(found.as_str().to_string()).or_default();
+            if files.len() < 2 && !files.contains(&path) {
+                files.push(path.clone());
+            }
+        }
+    }
+
+    /// Types et fonctions `pub` dont le nom n'apparaît dans aucun autre fichier (et,
+    /// pour un type, qu'aucun type du projet n'utilise), par fichier puis par nom.
+    ///
+    /// Heuristique : les usages par réexportation globale (`pub use module::*`), par
+    /// des crates extérieures au dépôt ou générés par des macros ne sont pas vus.
+    pub fn possibly_unused(&self, api_surface: &[ApiModule], relations: &[TypeRelations]) -> Vec<PossiblyUnused> {
+        let used_types: HashMap<(&str, &str), bool> = relations
+            .iter()
+            .map(|relation| ((&*relation.defined_in, &*relation.type_name), !relation.used_by.is_empty()))
+            .collect();
+
+        let mut unused = Vec::new();
+        for module in api_surface {
+            for item in &module.items {
+                // Élément d'un module en ligne (`inner::Item`)
+                let name = item.name.rsplit("::").next().unwrap_or(&item.name);
+                let is_type = matches!(
+                    item.kind,
+                    ApiItemKind::Struct | ApiItemKind::Enum | ApiItemKind::Union | ApiItemKind::Trait | ApiItemKind::Type
+                );
+                if !(is_type || item.kind == ApiItemKind::Fn) || name == "main" {
+                    continue;
+                }
+                if is_type && used_types.get(&(module.path.as_str(), name)).copied().unwrap_or(false) {
+                    continue;
+                }
+                let mentioned_elsewhere = self
+                    .files
+                    .get(name)
+                    .is_some_and(|files| files.iter().any(|file| **file != *module.path));
+                if !mentioned_elsewhere {
+                    unused.push(PossiblyUnused {
+                        name: match &item.owner {
+                            Some(owner) => format!("{}::{}", owner, item.name),
+                            None => item.name.clone(),
+                        },
+                        kind: item.kind,
+                        defined_in: module.path.clone(),
+                    });
+                }
+            }
+        }
+        unused.sort_by(|a, b| a.defined_in.cmp(&b.defined_in).then_with(|| a.name.cmp(&b.name)));
+        unused
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::BTreeMap;
+
+use crate::analysis::cargo_manifest::{declared_targets, package_metadata, package_name, workspace_members};
+use crate::analysis::targets::TargetKind;
+use crate::analysis::path_filter::GlobFilter;
+use crate::types::analysis::{ApiItemKind, CrateMetadata, CrateOverview, ProjectSummary};
+use crate::types::{FileCategory, SharedStr};
+
+/// Espace de travail cargo : membres déclarés par le manifeste racine (`members`,
+/// motifs glob compris, moins `exclude`) et paquets des `Cargo.toml` du dépôt
+#[derive(Debug, Default)]
+pub struct Workspace {
+    members: Vec<String>,
+    exclude: Vec<String>,
+    /// Le `Cargo.toml` racine déclare un `[workspace]`
+    declared: bool,
+    /// Répertoire (vide à la racine) et nom de paquet de chaque manifeste
+    packages: Vec<(String, String)>,
+    /// Contenu des manifestes de paquet, par répertoire
+    manifests: BTreeMap<String, String>,
+    /// Manifeste racine, dont `[workspace.package]` fournit les valeurs héritées
+    root_manifest: Option<String>,
+}
+
+impl Workspace {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Relève l'espace de travail ou le paquet déclaré par un `Cargo.toml`
+    pub fn observe_manifest(&mut self, path: &str, content: &str) {
+        let directory = path.strip_suffix("Cargo.toml").unwrap_or_default().trim_end_matches('/');
+        if directory.is_empty() {
+            self.root_manifest = Some(content.to_string());
+            if let Some((members, exclude)) = workspace_members(content) {
+                self.declared = true;
+                self.members = members;
+                self.exclude = exclude;
+            }
+        }
+        if let Some(name) = package_name(content) {
+            self.packages.push((directory.to_string(), name));
+            self.manifests.insert(directory.to_string(), content.to_string());
+        }
+    }
+
+    /// Répertoire et nom des crates membres, par chemin ; le paquet racine d'un espace
+    /// de travail en fait partie. Vide hors espace de travail.
+    pub fn crate_roots(&self) -> BTreeMap<String, String> {
+        if !self.declared {
+            return BTreeMap::new();
+        }
+        // Sans `members`, seul le paquet racine appartient à l'espace de travail
+        let filter = GlobFilter::new(&self.members, &self.exclude).ok().filter(|_| !self.members.is_empty());
+        self.packages
+            .iter()
+            .filter(|(directory, _)| {
+                directory.is_empty() || filter.as_ref().is_some_and(|filter| filter.accepts(directory))
+            })
+            .map(|(directory, name)| (directory.clone(), name.clone()))
+            .collect()
+    }
+
+    /// Métadonnées du paquet de `directory` (vide à la racine) ; ses cibles tiennent
+    /// compte des fichiers analysés (`src/lib.rs`, `src/main.rs`, `src/bin/`)
+    pub fn metadata(&self, directory: &str, files: &[SharedStr]) -> Option<CrateMetadata> {
+        let content = self.manifests.get(directory)?;
+        let mut metadata = package_metadata(content, self.root_manifest.as_deref())?;
+        let src = if directory.is_empty() { "src/".to_string() } else { format!("{}/src/", directory) };
+        let in_src = |file: &SharedStr| file.strip_prefix(src.as_str()).map(str::to_string);
+        let sources: Vec<String> = files.iter().filter_map(in_src).collect();
+        metadata.lib |= sources.iter().any(|file| file == "lib.rs");
+        metadata.bin |= sources.iter().any(|file| file == "main.rs" || file.starts_with("bin/"));
+        Some(metadata)
+    }
+
+    /// Cibles déclarées avec un chemin par les manifestes de paquet, chemins relatifs à
+    /// la racine du dépôt
+    pub fn declared_targets(&self) -> Vec<(TargetKind, String)> {
+        self.manifests
+            .iter()
+            .flat_map(|(directory, content)| {
+                declared_targets(content).into_iter().map(move |(kind, path)| {
+                    if directory.is_empty() {
+                        (kind, path)
+                    } else {
+                        (kind, format!("{}/{}", directory, path))
+                    }
+                })
+            })
+            .collect()
+    }
+
+    /// Statistiques de chaque crate membre : un fichier revient à la crate dont le
+    /// répertoire est le plus long préfixe de son chemin
+    pub fn members(&self, summary: &ProjectSummary) -> Vec<CrateOverview> {
+        let roots = self.crate_roots();
+        let mut members: BTreeMap<&str, CrateOverview> = roots
+            .iter()
+            .map(|(directory, name)| {
+                let manifest = manifest_path(directory);
+                let dependencies = summary
+                    .project_overview
+                    .dependencies
+                    .iter()
+                    .filter(|dependency| dependency.defined_in == manifest)
+                    .cloned()
+                    .collect();
+                let member = CrateOverview {
+                    name: name.clone(),
+                    path: directory.clone(),
+                    dependencies,
+                    metadata: self.metadata(directory, &summary.files_analyzed),
+                    ..Default::default()
+                };
+                (directory.as_str(), member)
+            })
+            .collect();
+
+        for file in &summary.file_summaries {
+            let Some(member) = crate_root(&roots, &file.path).and_then(|root| members.get_mut(root)) else {
+                continue;
+            };
+            member.files += 1;
+            if matches!(&file.category, FileCategory::Source(lang) if lang == "rs") {
+                member.rust_files += 1;
+                member.rust_code_lines += file.loc.code;
+            }
+        }
+        for module in &summary.api_surface {
+            let Some(member) = crate_root(&roots, &module.path).and_then(|root| members.get_mut(root)) else {
+                continue;
+            };
+            for item in &module.items {
+                match item.kind {
+                    ApiItemKind::Fn => member.public_functions += 1,
+                    ApiItemKind::Struct | ApiItemKind::Enum | ApiItemKind::Union | ApiItemKind::Trait | ApiItemKind::Type => {
+                        member.public_types += 1
+                    },
+                    _ => {},
+                }
+            }
+        }
+        members.into_values().collect()
+    }
+}
+
+/// Répertoire de la crate membre contenant `path` (le plus long préfixe)
+pub fn crate_root<'a>(roots: &'a BTreeMap<String, String>, path: &str) -> Option<&'a str> {
+    roots
+        .keys()
+        .filter(|root| root.is_empty() || path.strip_prefix(root.as_str()).is_some_and(|rest| rest.starts_with('/')))
+        .max_by_key(|root| root.len())
+        .map(String::as_str)
+}
+
+fn manifest_path(directory: &str) -> String {
+    if directory.is_empty() {
+        "Cargo.toml".to_string()
+    } else {
+        format!("{}/Cargo.toml", directory)
+    }
+}
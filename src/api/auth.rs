@@ -0,0 +1,268 @@
+use reqwest::{Client, StatusCode, header};
+use serde::Deserialize;
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+use tokio::sync::Mutex;
+
+use crate::api::client::network_error;
+use crate::error::GithubAnalyzerError;
+
+/// Marge avant expiration en deçà de laquelle le token d'installation est renouvelé
+const REFRESH_MARGIN_SECS: u64 = 300;
+/// Validité du JWT de l'application (GitHub refuse plus de 10 minutes)
+#[cfg(feature = "github-app")]
+const JWT_LIFETIME_SECS: u64 = 540;
+/// Décalage appliqué à `iat` pour tolérer une horloge locale en avance
+#[cfg(feature = "github-app")]
+const JWT_CLOCK_DRIFT_SECS: u64 = 60;
+/// Validité supposée d'un token dont l'expiration n'a pas pu être lue
+const DEFAULT_TOKEN_LIFETIME_SECS: u64 = 3600;
+
+/// Identifiants d'une GitHub App utilisés pour obtenir des tokens d'installation
+#[derive(Debug, Clone)]
+pub struct AppCredentials {
+    pub app_id: String,
+    /// Clé privée de l'application, au format PEM
+    pub private_key: Vec<u8>,
+    /// Installation ciblée ; sinon l'unique installation de l'application
+    pub installation_id: Option<u64>,
+}
+
+impl AppCredentials {
+    pub fn new(app_id: impl Into<String>, private_key: Vec<u8>) -> Self {
+        Self {
+            app_id: app_id.into(),
+            private_key,
+            installation_id: None,
+        }
+    }
+
+    /// Identifiants avec la clé privée lue depuis un fichier PEM
+    pub fn from_pem_file(app_id: impl Into<String>, path: &Path) -> Result<Self, GithubAnalyzerError> {
+        let private_key = fs::read(path).map_err(|e| GithubAnalyzerError::AuthError(
+            format!("Cannot read GitHub App private key {}: {}", path.display(), e)
+        ))?;
+        Ok(Self::new(app_id, private_key))
+    }
+
+    pub fn with_installation(mut self, installation_id: u64) -> Self {
+        self.installation_id = Some(installation_id);
+        self
+    }
+
+    /// Lit `GITHUB_APP_ID`, `GITHUB_APP_PRIVATE_KEY` (chemin du PEM ou contenu PEM)
+    /// et `GITHUB_APP_INSTALLATION_ID` ; `None` si `GITHUB_APP_ID` n'est pas défini
+    pub fn from_env() -> Option<Result<Self, GithubAnalyzerError>> {
+        let app_id = env::var("GITHUB_APP_ID").ok().filter(|id| !id.is_empty())?;
+        Some(Self::read_env(app_id))
+    }
+
+    fn read_env(app_id: String) -> Result<Self, GithubAnalyzerError> {
+        let private_key = env::var("GITHUB_APP_PRIVATE_KEY").map_err(|_| GithubAnalyzerError::AuthError(
+            "GITHUB_APP_ID is set but GITHUB_APP_PRIVATE_KEY is missing".to_string()
+        ))?;
+        let credentials = if private_key.trim_start().starts_with("-----BEGIN") {
+            Self::new(app_id, private_key.into_bytes())
+        } else {
+            Self::from_pem_file(app_id, Path::new(&private_key))?
+        };
+
+        match env::var("GITHUB_APP_INSTALLATION_ID") {
+            Ok(id) => id.trim().parse()
+                .map(|id| credentials.with_installation(id))
+                .map_err(|_| GithubAnalyzerError::ParseError(format!("Invalid GITHUB_APP_INSTALLATION_ID: {}", id))),
+            Err(_) => Ok(credentials),
+        }
+    }
+}
+
+#[cfg(feature = "github-app")]
+#[derive(serde::Serialize)]
+struct JwtClaims {
+    iat: u64,
+    exp: u64,
+    iss: String,
+}
+
+#[derive(Deserialize)]
+struct AccessTokenResponse {
+    token: String,
+    expires_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Installation {
+    id: u64,
+}
+
+/// Token d'installation et son expiration (timestamp Unix)
+struct InstallationToken {
+    token: String,
+    expires_at: u64,
+}
+
+/// Fournit un token d'installation valide, renouvelé avant son expiration.
+///
+/// Les tokens d'installation expirent au bout d'une heure : une analyse longue
+/// en obtient un nouveau sans interruption.
+pub struct AppTokenProvider {
+    credentials: AppCredentials,
+    current: Mutex<Option<InstallationToken>>,
+}
+
+impl AppTokenProvider {
+    pub fn new(credentials: AppCredentials) -> Self {
+        Self {
+            credentials,
+            current: Mutex::new(None),
+        }
+    }
+
+    pub fn app_id(&self) -> &str {
+        &self.credentials.app_id
+    }
+
+    /// Token d'installation courant, échangé à nouveau s'il expire bientôt
+    pub async fn token(&self, client: &Client, api_base: &str) -> Result<String, GithubAnalyzerError> {
+        let mut current = self.current.lock().await;
+        if let Some(token) = current.as_ref() {
+            if token.expires_at > now() + REFRESH_MARGIN_SECS {
+                return Ok(token.token.clone());
+            }
+        }
+
+        let fresh = self.exchange(client, api_base).await?;
+        let token = fresh.token.clone();
+        *current = Some(fresh);
+        Ok(token)
+    }
+
+    /// Signe un JWT et l'échange contre un token d'installation
+    async fn exchange(&self, client: &Client, api_base: &str) -> Result<InstallationToken, GithubAnalyzerError> {
+        let jwt = sign_jwt(&self.credentials)?;
+        let installation_id = match self.credentials.installation_id {
+            Some(id) => id,
+            None => self.find_installation(client, api_base, &jwt).await?,
+        };
+
+        let url = format!("{}/app/installations/{}/access_tokens", api_base, installation_id);
+        let response = client.post(&url)
+            .headers(app_headers(&jwt)?)
+            .send()
+            .await
+            .map_err(|e| network_error(e, &url))?;
+        let response = check_status(response, &url).await?;
+        let body: AccessTokenResponse = response.json().await
+            .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()))?;
+
+        let expires_at = body.expires_at
+            .as_deref()
+            .and_then(parse_timestamp)
+            .unwrap_or_else(|| now() + DEFAULT_TOKEN_LIFETIME_SECS);
+        println!("Obtained GitHub App installation token (installation {})", installation_id);
+        Ok(InstallationToken { token: body.token, expires_at })
+    }
+
+    /// Installation de l'application quand elle est unique
+    async fn find_installation(&self, client: &Client, api_base: &str, jwt: &str) -> Result<u64, GithubAnalyzerError> {
+        let url = format!("{}/app/installations", api_base);
+        let response = client.get(&url)
+            .headers(app_headers(jwt)?)
+            .send()
+            .await
+            .map_err(|e| network_error(e, &url))?;
+        let response = check_status(response, &url).await?;
+        let installations: Vec<Installation> = response.json().await
+            .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()))?;
+
+        match installations.as_slice() {
+            [installation] => Ok(installation.id),
+            [] => Err(GithubAnalyzerError::AuthError(
+                format!("GitHub App {} has no installation", self.credentials.app_id)
+            )),
+            _ => Err(GithubAnalyzerError::AuthError(format!(
+                "GitHub App {} has {} installations; set GITHUB_APP_INSTALLATION_ID",
+                self.credentials.app_id, installations.len()
+            ))),
+        }
+    }
+}
+
+/// En-têtes des appels authentifiés en tant qu'application (JWT) ; le user-agent
+/// est porté par le client HTTP
+fn app_headers(jwt: &str) -> Result<header::HeaderMap, GithubAnalyzerError> {
+    let mut headers = header::HeaderMap::new();
+    headers.insert(header::ACCEPT, header::HeaderValue::from_static("application/vnd.github+json"));
+    let authorization = header::HeaderValue::from_str(&format!("Bearer {}", jwt))
+        .map_err(|e| GithubAnalyzerError::AuthError(format!("Invalid GitHub App JWT: {}", e)))?;
+    headers.insert(header::AUTHORIZATION, authorization);
+    Ok(headers)
+}
+
+/// Convertit un refus de l'API en erreur explicite
+async fn check_status(response: reqwest::Response, url: &str) -> Result<reqwest::Response, GithubAnalyzerError> {
+    match response.status() {
+        status if status.is_success() => Ok(response),
+        status @ (StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN | StatusCode::NOT_FOUND) => {
+            let body: serde_json::Value = response.json().await.unwrap_or_default();
+            Err(GithubAnalyzerError::AuthError(format!(
+                "GitHub App authentication failed with status {}: {} ({})",
+                status, body["message"].as_str().unwrap_or("no message"), url
+            )))
+        },
+        status => Err(GithubAnalyzerError::NetworkError(
+            format!("GitHub App token exchange returned status {}", status)
+        )),
+    }
+}
+
+#[cfg(feature = "github-app")]
+fn sign_jwt(credentials: &AppCredentials) -> Result<String, GithubAnalyzerError> {
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+
+    let key = EncodingKey::from_rsa_pem(&credentials.private_key)
+        .map_err(|e| GithubAnalyzerError::AuthError(format!("Invalid GitHub App private key: {}", e)))?;
+    let issued = now().saturating_sub(JWT_CLOCK_DRIFT_SECS);
+    let claims = JwtClaims {
+        iat: issued,
+        exp: issued + JWT_LIFETIME_SECS,
+        iss: credentials.app_id.clone(),
+    };
+    encode(&Header::new(Algorithm::RS256), &claims, &key)
+        .map_err(|e| GithubAnalyzerError::AuthError(format!("Cannot sign GitHub App JWT: {}", e)))
+}
+
+#[cfg(not(feature = "github-app"))]
+fn sign_jwt(_credentials: &AppCredentials) -> Result<String, GithubAnalyzerError> {
+    Err(GithubAnalyzerError::AuthError(
+        "GitHub App authentication requires the `github-app` subsystem (rebuild with `cargo build --features github-app`)".to_string()
+    ))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Timestamp Unix d'une date ISO 8601 en UTC (`2024-05-01T12:00:00Z`)
+fn parse_timestamp(value: &str) -> Option<u64> {
+    let (date, time) = value.trim().trim_end_matches('Z').split_once('T')?;
+    let mut date = date.split('-').map(|part| part.parse::<i64>().ok());
+    let (year, month, day) = (date.next()??, date.next()??, date.next()??);
+    let mut time = time.split(':').map(|part| part.split('.').next()?.parse::<i64>().ok());
+    let (hour, minute, second) = (time.next()??, time.next()??, time.next()??);
+
+    // Nombre de jours depuis 1970-01-01 (calendrier grégorien proleptique)
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    let days = era * 146_097 + day_of_era - 719_468;
+
+    u64::try_from(days * 86_400 + hour * 3600 + minute * 60 + second).ok()
+}
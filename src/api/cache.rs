@@ -0,0 +1,127 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::analysis::boilerplate::stable_hash;
+
+/// Réponse mise en cache, rejouée sur un `304 Not Modified`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CachedResponse {
+    pub url: String,
+    pub etag: String,
+    pub body: String,
+    /// Page suivante annoncée par l'en-tête `Link` (listes paginées)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_page: Option<String>,
+}
+
+/// Cache HTTP sur disque indexé par URL (requêtes conditionnelles `If-None-Match`).
+///
+/// Les réponses `304` ne sont pas décomptées du quota de l'API GitHub : une
+/// nouvelle exécution sur un dépôt inchangé ne consomme presque rien.
+#[derive(Debug, Clone)]
+pub struct HttpCache {
+    dir: PathBuf,
+}
+
+impl HttpCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `$XDG_CACHE_HOME/repo_analyzer`, sinon `~/.cache/repo_analyzer`
+    pub fn default_dir() -> Option<PathBuf> {
+        let base = std::env::var_os("XDG_CACHE_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".cache")))?;
+        Some(base.join("repo_analyzer"))
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path(&self, url: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", stable_hash(url)))
+    }
+
+    /// Réponse en cache pour cette URL (collisions de hash écartées)
+    pub fn get(&self, url: &str) -> Option<CachedResponse> {
+        let json = fs::read_to_string(self.entry_path(url)).ok()?;
+        let cached: CachedResponse = serde_json::from_str(&json).ok()?;
+        (cached.url == url).then_some(cached)
+    }
+
+    /// Enregistre une réponse ; les erreurs d'écriture sont ignorées (cache au mieux)
+    pub fn store(&self, url: &str, etag: &str, body: &str, next_page: Option<&str>) {
+        let cached = CachedResponse {
+            url: url.to_string(),
+            etag: etag.to_string(),
+            body: body.to_string(),
+            next_page: next_page.map(str::to_string),
+        };
+        if let Ok(json) = serde_json::to_string(&cached) {
+            let _ = fs::create_dir_all(&self.dir).and_then(|_| fs::write(self.entry_path(url), json));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "https://api.github.com/repos/owner/repo/contents/src?ref=main";
+
+    #[test]
+    fn stored_responses_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        // Le répertoire est créé au premier enregistrement
+        let cache = HttpCache::new(dir.path().join("http"));
+        assert!(cache.get(URL).is_none());
+
+        cache.store(URL, "\"abc\"", "[1, 2]", Some("https://api.github.com/next?page=2"));
+        let cached = cache.get(URL).unwrap();
+        assert_eq!(cached.url, URL);
+        assert_eq!(cached.etag, "\"abc\"");
+        assert_eq!(cached.body, "[1, 2]");
+        assert_eq!(cached.next_page.as_deref(), Some("https://api.github.com/next?page=2"));
+
+        // Un nouvel enregistrement remplace le précédent
+        cache.store(URL, "W/\"def\"", "[]", None);
+        let cached = cache.get(URL).unwrap();
+        assert_eq!((cached.etag.as_str(), cached.body.as_str(), cached.next_page), ("W/\"def\"", "[]", None));
+    }
+
+    #[test]
+    fn entries_are_keyed_by_url_hash() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = HttpCache::new(dir.path());
+        let other = format!("{}&page=2", URL);
+        cache.store(URL, "a", "first", None);
+        cache.store(&other, "b", "second", None);
+
+        let mut names: Vec<String> = fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        let mut expected = vec![format!("{}.json", stable_hash(URL)), format!("{}.json", stable_hash(&other))];
+        expected.sort();
+        assert_eq!(names, expected);
+        assert_eq!(cache.get(&other).unwrap().body, "second");
+    }
+
+    #[test]
+    fn hash_collisions_and_corrupt_entries_are_misses() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = HttpCache::new(dir.path());
+        let other = "https://api.github.com/repos/owner/other";
+        cache.store(other, "a", "body", None);
+        // Entrée d'une autre URL sous le hash de `URL`
+        fs::rename(cache.entry_path(other), cache.entry_path(URL)).unwrap();
+        assert!(cache.get(URL).is_none());
+
+        fs::write(cache.entry_path(URL), "{not json").unwrap();
+        assert!(cache.get(URL).is_none());
+    }
+}
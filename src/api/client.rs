@@ -2,55 +2,446 @@ use reqwest::{Client, StatusCode, header};
 use tokio::time::{sleep, Duration};
 use serde::de::DeserializeOwned;
 use base64;
+use std::collections::HashMap;
 use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
 
+use crate::analysis::boilerplate::stable_hash;
+use crate::analysis::file::is_binary_content;
+use crate::api::auth::{AppCredentials, AppTokenProvider};
+use crate::api::cache::HttpCache;
+use crate::api::token;
 use crate::error::GithubAnalyzerError;
-use crate::types::github::GithubContent;
+use crate::types::github::{
+    GithubBlob, GithubContent, GithubOrgRepo, GithubPullFile, GithubPullRequest, GithubRateLimit, GithubRateLimitStatus,
+    GithubRepoInfo, GithubTag, GithubTree,
+};
+use crate::types::locator::{RepoLocator, GITHUB_HOST};
+
+/// Base de l'API de github.com
+const GITHUB_API_BASE: &str = "https://api.github.com";
+
+/// Taille maximale d'un fichier servi en base64 par l'API Contents
+pub const CONTENTS_API_MAX_SIZE: usize = 1_000_000;
+/// Taille maximale par défaut d'un fichier analysé (au-delà de `CONTENTS_API_MAX_SIZE`,
+/// le contenu passe par l'API Blobs ou le téléchargement brut)
+pub const DEFAULT_MAX_FILE_SIZE: usize = 10 * 1024 * 1024;
+
+/// User-agent envoyé par défaut avec chaque requête
+pub const DEFAULT_USER_AGENT: &str = "GitHub-Repository-Analyzer";
+/// Délai maximal d'établissement d'une connexion, en secondes
+pub const DEFAULT_CONNECT_TIMEOUT_SECS: u64 = 10;
+/// Délai maximal d'une requête complète (corps compris), en secondes
+pub const DEFAULT_REQUEST_TIMEOUT_SECS: u64 = 300;
+
+/// Quota horaire des requêtes non authentifiées
+const UNAUTHENTICATED_RATE_LIMIT: u64 = 60;
+
+/// Nombre maximal d'entrées d'un répertoire renvoyées par page de l'API Contents
+const CONTENTS_PAGE_MAX_ENTRIES: usize = 1000;
+
+/// Nombre de fichiers demandés par requête GraphQL
+pub const GRAPHQL_BATCH_SIZE: usize = 50;
+
+/// Attente maximale sur une limite secondaire (`Retry-After`), en secondes
+const MAX_RETRY_AFTER_SECS: u64 = 300;
+/// Attente par défaut sur une limite secondaire sans `Retry-After`
+const DEFAULT_RETRY_AFTER_SECS: u64 = 60;
+
+/// Cause d'une réponse 403/429 de l'API
+pub(crate) enum Forbidden {
+    /// Limite secondaire (abus) : réessayer après ce délai
+    SecondaryLimit(u64),
+    /// Quota principal épuisé : timestamp de réinitialisation
+    PrimaryLimit(u64),
+    /// Accès refusé (dépôt privé, token invalide ou insuffisant)
+    Permission(String),
+}
+
+impl Forbidden {
+    /// Distingue limite secondaire, quota épuisé et refus d'accès
+    /// à partir des en-têtes et du champ `message` du corps JSON
+    pub(crate) async fn classify(response: reqwest::Response) -> Self {
+        let headers = response.headers().clone();
+        let header = |name: &str| headers.get(name).and_then(|h| h.to_str().ok()).and_then(|s| s.parse::<u64>().ok());
+        let retry_after = header("retry-after");
+        let remaining = header("x-ratelimit-remaining");
+        let reset = header("x-ratelimit-reset").unwrap_or(0);
+
+        let body: serde_json::Value = response.json().await.unwrap_or_default();
+        let message = body["message"].as_str().unwrap_or("").to_string();
+        let lowercase = message.to_lowercase();
+
+        if retry_after.is_some() || lowercase.contains("secondary rate limit") || lowercase.contains("abuse") {
+            let wait = retry_after.unwrap_or(DEFAULT_RETRY_AFTER_SECS).min(MAX_RETRY_AFTER_SECS);
+            Forbidden::SecondaryLimit(wait)
+        } else if remaining == Some(0) || lowercase.contains("rate limit") {
+            Forbidden::PrimaryLimit(reset)
+        } else if message.is_empty() {
+            Forbidden::Permission("access forbidden".to_string())
+        } else {
+            Forbidden::Permission(message)
+        }
+    }
+
+    /// Erreur renvoyée quand la requête n'est pas réessayée
+    pub(crate) fn into_error(self, url: &str) -> GithubAnalyzerError {
+        match self {
+            Forbidden::SecondaryLimit(wait) => GithubAnalyzerError::RateLimitError(unix_now() + wait),
+            Forbidden::PrimaryLimit(reset) => GithubAnalyzerError::RateLimitError(reset),
+            Forbidden::Permission(message) => GithubAnalyzerError::Forbidden(format!("{} ({})", message, url)),
+        }
+    }
+}
 
 pub struct GithubClient {
     client: Client,
     token: Option<String>,
+    /// Token trouvé mais invalide : renvoyé en `AuthError` à chaque requête
+    token_error: Option<String>,
+    /// Configuration réseau invalide (proxy, ...) : renvoyée en `NetworkError` à chaque requête
+    setup_error: Option<String>,
+    requests: AtomicU64,
+    rate_limit_waits: AtomicU64,
+    cache: Option<HttpCache>,
+    /// Base de l'API imposée (GitHub Enterprise) ; sinon déduite de l'hôte du dépôt
+    api_base: Option<String>,
+    /// Authentification GitHub App, utilisée en l'absence de token personnel
+    app_auth: Option<AppTokenProvider>,
+    /// Taille maximale d'un contenu téléchargé (blob ou fichier brut)
+    max_file_size: usize,
+    /// Fin de la pause imposée par une limite d'API (timestamp Unix), commune à
+    /// toutes les requêtes concurrentes
+    paused_until: AtomicU64,
 }
 
-impl GithubClient {
-    pub fn new() -> Self {
-        let token = env::var("GITHUB_TOKEN").ok();
+/// Construit un `GithubClient` : délais réseau, proxy, user-agent et authentification
+pub struct GithubClientBuilder {
+    cache: Option<HttpCache>,
+    token: Option<String>,
+    connect_timeout: Duration,
+    timeout: Duration,
+    proxy: Option<String>,
+    user_agent: String,
+    api_base: Option<String>,
+    host: Option<String>,
+    max_file_size: usize,
+}
 
-        if token.is_some() {
-            println!("Using authenticated GitHub API requests");
-        } else {
+impl Default for GithubClientBuilder {
+    fn default() -> Self {
+        Self {
+            cache: HttpCache::default_dir().map(HttpCache::new),
+            token: None,
+            connect_timeout: Duration::from_secs(DEFAULT_CONNECT_TIMEOUT_SECS),
+            timeout: Duration::from_secs(DEFAULT_REQUEST_TIMEOUT_SECS),
+            proxy: None,
+            user_agent: DEFAULT_USER_AGENT.to_string(),
+            api_base: None,
+            host: None,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+}
+
+impl fmt::Debug for GithubClientBuilder {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("GithubClientBuilder")
+            .field("token", &self.token.as_ref().map(|_| "***"))
+            .field("connect_timeout", &self.connect_timeout)
+            .field("timeout", &self.timeout)
+            .field("proxy", &self.proxy)
+            .field("user_agent", &self.user_agent)
+            .field("api_base", &self.api_base)
+            .field("host", &self.host)
+            .finish()
+    }
+}
+
+impl GithubClientBuilder {
+    /// Cache HTTP à utiliser, ou aucun (`None`)
+    pub fn cache(mut self, cache: Option<HttpCache>) -> Self {
+        self.cache = cache;
+        self
+    }
+
+    /// Token explicite, prioritaire sur l'environnement
+    pub fn token(mut self, token: Option<String>) -> Self {
+        self.token = token;
+        self
+    }
+
+    /// Délai maximal d'établissement d'une connexion
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// Délai maximal d'une requête, téléchargement du corps compris
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Proxy HTTP(S) pour toutes les requêtes (`http://proxy.example.com:3128`)
+    pub fn proxy(mut self, proxy: impl Into<String>) -> Self {
+        self.proxy = Some(proxy.into());
+        self
+    }
+
+    pub fn user_agent(mut self, user_agent: impl Into<String>) -> Self {
+        self.user_agent = user_agent.into();
+        self
+    }
+
+    /// Base de l'API imposée, prioritaire sur `GITHUB_API_URL`
+    pub fn api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = Some(api_base.into());
+        self
+    }
+
+    /// Hôte du dépôt (`github.com`, hôte GitHub Enterprise), dont le token est
+    /// cherché dans la configuration du CLI `gh` ; sinon déduit de la base de l'API
+    pub fn host(mut self, host: impl Into<String>) -> Self {
+        self.host = Some(host.into());
+        self
+    }
+
+    pub fn max_file_size(mut self, max_file_size: usize) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Construit le client.
+    ///
+    /// Sans token explicite, l'authentification est cherchée dans l'ordre :
+    /// `GITHUB_TOKEN`, fichier `GITHUB_TOKEN_FILE`, GitHub App (`GITHUB_APP_ID` et
+    /// `GITHUB_APP_PRIVATE_KEY`), puis configuration du CLI `gh`. Un token invalide
+    /// fait échouer chaque requête avec une `AuthError`, un proxy invalide avec une
+    /// `NetworkError`.
+    pub fn build(self) -> GithubClient {
+        let (client, setup_error) = match self.http_client() {
+            Ok(client) => (client, None),
+            Err(message) => {
+                println!("Warning: {}", message);
+                (Client::new(), Some(message))
+            },
+        };
+        let api_base = self.api_base
+            .or_else(|| env::var("GITHUB_API_URL").ok())
+            .filter(|url| !url.is_empty())
+            .map(|url| url.trim_end_matches('/').to_string());
+        let mut token_error = None;
+        let mut app_auth = None;
+
+        let mut discovered = token::discover(self.token.as_deref());
+        if matches!(discovered, Ok(None)) {
+            match AppCredentials::from_env() {
+                Some(Ok(credentials)) => app_auth = Some(AppTokenProvider::new(credentials)),
+                Some(Err(e)) => println!("Warning: Ignoring GitHub App configuration: {}", e),
+                None => {},
+            }
+            if app_auth.is_none() {
+                let host = self.host.clone().or_else(|| api_base.as_deref().and_then(api_host));
+                discovered = token::discover_gh_cli(host.as_deref().unwrap_or(GITHUB_HOST));
+            }
+        }
+        let token = match discovered {
+            Ok(token) => token,
+            Err(e) => {
+                println!("Warning: {}", e);
+                token_error = Some(match e {
+                    GithubAnalyzerError::AuthError(message) => message,
+                    other => other.to_string(),
+                });
+                None
+            },
+        };
+
+        if let Some(token) = &token {
+            println!("Using authenticated GitHub API requests (token from {})", token.source);
+        } else if let Some(app_auth) = &app_auth {
+            println!("Using GitHub App authentication (app {})", app_auth.app_id());
+        } else if token_error.is_none() {
             println!("Warning: Using unauthenticated GitHub API requests. Consider setting GITHUB_TOKEN environment variable to increase rate limits.");
         }
 
-        Self {
-            client: Client::new(),
-            token,
+        GithubClient {
+            client,
+            token: token.map(|token| token.token),
+            token_error,
+            setup_error,
+            requests: AtomicU64::new(0),
+            rate_limit_waits: AtomicU64::new(0),
+            cache: self.cache,
+            api_base,
+            app_auth,
+            max_file_size: self.max_file_size,
+            paused_until: AtomicU64::new(0),
         }
     }
 
-    fn build_headers(&self) -> header::HeaderMap {
+    /// Client HTTP avec les délais, le proxy et le user-agent configurés
+    fn http_client(&self) -> Result<Client, String> {
+        http_client(self.connect_timeout, self.timeout, &self.user_agent, self.proxy.as_deref())
+    }
+}
+
+/// Client HTTP avec délais, user-agent et proxy éventuel ; l'erreur décrit la
+/// configuration refusée
+pub(crate) fn http_client(
+    connect_timeout: Duration,
+    timeout: Duration,
+    user_agent: &str,
+    proxy: Option<&str>,
+) -> Result<Client, String> {
+    let mut builder = Client::builder()
+        .connect_timeout(connect_timeout)
+        .timeout(timeout)
+        .user_agent(user_agent);
+    if let Some(proxy) = proxy {
+        let proxy = reqwest::Proxy::all(proxy)
+            .map_err(|e| format!("Invalid proxy URL {}: {}", proxy, e))?;
+        builder = builder.proxy(proxy);
+    }
+    builder.build().map_err(|e| format!("Cannot build HTTP client: {}", e))
+}
+
+impl Default for GithubClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl GithubClient {
+    /// Client avec le cache HTTP dans son emplacement par défaut
+    pub fn new() -> Self {
+        Self::with_cache(HttpCache::default_dir().map(HttpCache::new))
+    }
+
+    /// Client avec un cache HTTP donné, ou sans cache (`None`).
+    ///
+    /// La base de l'API est lue dans `GITHUB_API_URL` si la variable est définie.
+    pub fn with_cache(cache: Option<HttpCache>) -> Self {
+        Self::with_token(cache, None)
+    }
+
+    /// Client avec un token explicite (`--token`), prioritaire sur les autres sources.
+    ///
+    /// Voir `GithubClientBuilder::build` pour l'ordre de recherche de l'authentification.
+    pub fn with_token(cache: Option<HttpCache>, token: Option<String>) -> Self {
+        Self::builder().cache(cache).token(token).build()
+    }
+
+    /// Configuration détaillée du client (délais, proxy, user-agent, authentification)
+    pub fn builder() -> GithubClientBuilder {
+        GithubClientBuilder::default()
+    }
+
+    /// Authentifie les requêtes avec les tokens d'installation d'une GitHub App,
+    /// à la place de tout token personnel
+    pub fn with_app_auth(mut self, credentials: AppCredentials) -> Self {
+        println!("Using GitHub App authentication (app {})", credentials.app_id);
+        self.token = None;
+        self.token_error = None;
+        self.app_auth = Some(AppTokenProvider::new(credentials));
+        self
+    }
+
+    /// Plafonne la taille des contenus téléchargés par l'API Blobs ou en brut
+    pub fn with_max_file_size(mut self, max_file_size: usize) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    /// Impose la base de l'API (`https://ghe.example.com/api/v3`) pour tous les dépôts
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = Some(api_base.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Indique si les requêtes sont authentifiées (requis pour GraphQL)
+    pub fn has_token(&self) -> bool {
+        self.token.is_some() || self.app_auth.is_some()
+    }
+
+    /// Nombre de requêtes HTTP envoyées par ce client
+    pub fn api_requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    /// Nombre d'attentes dues à l'épuisement du quota d'API
+    pub fn rate_limit_waits(&self) -> u64 {
+        self.rate_limit_waits.load(Ordering::Relaxed)
+    }
+
+    /// En-têtes d'authentification ; le user-agent est porté par le client HTTP
+    async fn build_headers(&self) -> Result<header::HeaderMap, GithubAnalyzerError> {
         let mut headers = header::HeaderMap::new();
-        headers.insert(
-            header::USER_AGENT,
-            header::HeaderValue::from_static("GitHub-Repository-Analyzer")
-        );
-        
-        if let Some(token) = &self.token {
-            headers.insert(
-                header::AUTHORIZATION,
-                header::HeaderValue::from_str(&format!("token {}", token))
-                    .expect("Invalid token format")
-            );
+
+        if let Some(message) = &self.setup_error {
+            return Err(GithubAnalyzerError::NetworkError(message.clone()));
+        }
+        if let Some(message) = &self.token_error {
+            return Err(GithubAnalyzerError::AuthError(message.clone()));
+        }
+
+        let token = match (&self.token, &self.app_auth) {
+            (Some(token), _) => Some(token.clone()),
+            // Les tokens d'installation sont échangés auprès de l'instance configurée
+            (None, Some(app_auth)) => {
+                let api_base = self.api_base.as_deref().unwrap_or(GITHUB_API_BASE);
+                Some(app_auth.token(&self.client, api_base).await?)
+            },
+            (None, None) => None,
+        };
+        if let Some(token) = token {
+            let value = header::HeaderValue::from_str(&format!("{} {}", auth_scheme(&token), token))
+                .map_err(|e| GithubAnalyzerError::AuthError(format!("Invalid token format: {}", e)))?;
+            headers.insert(header::AUTHORIZATION, value);
         }
         
-        headers
+        Ok(headers)
+    }
+
+    /// Suspend toutes les requêtes jusqu'à `until`.
+    ///
+    /// Renvoie `true` pour la seule requête qui prolonge la pause : les autres
+    /// tâches la rejoignent sans la compter ni l'annoncer à nouveau.
+    fn pause_until(&self, until: u64) -> bool {
+        self.paused_until.fetch_max(until, Ordering::Relaxed) < until
+    }
+
+    /// Attend la fin d'une pause en cours, avec un décalage propre à chaque URL
+    /// pour que les tâches concurrentes ne reprennent pas toutes en même temps
+    async fn wait_for_pause(&self, url: &str) {
+        let until = self.paused_until.load(Ordering::Relaxed);
+        let now = unix_now();
+        if until > now {
+            let jitter = u64::from_str_radix(&stable_hash(url)[..4], 16).unwrap_or(0) % 1000;
+            sleep(Duration::from_secs(until - now) + Duration::from_millis(jitter)).await;
+        }
+    }
+
+    pub async fn get_with_retry<T>(&self, url: &str, max_retries: u32) -> Result<T, GithubAnalyzerError>
+    where
+        T: DeserializeOwned
+    {
+        self.get_page_with_retry(url, max_retries).await.map(|(body, _)| body)
     }
 
-    pub async fn get_with_retry<T>(&self, url: &str, max_retries: u32) -> Result<T, GithubAnalyzerError> 
+    /// Comme `get_with_retry`, avec l'URL de la page suivante (`Link: rel="next"`)
+    pub async fn get_page_with_retry<T>(
+        &self,
+        url: &str,
+        max_retries: u32,
+    ) -> Result<(T, Option<String>), GithubAnalyzerError>
 where 
     T: DeserializeOwned
 {
     let mut retries = 0;
+    let mut secondary_waits = 0;
     let mut last_error = None;
 
     while retries <= max_retries {
@@ -60,11 +451,15 @@ where
             sleep(Duration::from_secs(wait_time)).await;
         }
 
-        match self.client.get(url)
-            .headers(self.build_headers())
-            .send()
-            .await
-        {
+        self.wait_for_pause(url).await;
+        let cached = self.cache.as_ref().and_then(|cache| cache.get(url));
+        let mut request = self.client.get(url).headers(self.build_headers().await?);
+        if let Some(cached) = &cached {
+            request = request.header(header::IF_NONE_MATCH, cached.etag.as_str());
+        }
+
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        match request.send().await {
             Ok(response) => {
                 // Gérer les limites de rate
                 if let Some(remaining) = response.headers()
@@ -78,15 +473,13 @@ where
                             .and_then(|h| h.to_str().ok())
                             .and_then(|s| s.parse::<u64>().ok())
                         {
-                            let now = std::time::SystemTime::now()
-                                .duration_since(std::time::UNIX_EPOCH)
-                                .unwrap()
-                                .as_secs();
+                            let now = unix_now();
                             
                             if reset > now {
-                                let wait_time = reset - now + 1;
-                                println!("Rate limit exceeded. Waiting {} seconds for reset...", wait_time);
-                                sleep(Duration::from_secs(wait_time)).await;
+                                if self.pause_until(reset + 1) {
+                                    println!("Rate limit exceeded. Waiting {} seconds for reset...", reset - now + 1);
+                                    self.rate_limit_waits.fetch_add(1, Ordering::Relaxed);
+                                }
                                 continue;
                             }
                         }
@@ -95,17 +488,58 @@ where
 
                 // Vérifier le statut de la réponse
                 match response.status() {
+                    // Contenu inchangé : réponse rejouée depuis le cache, hors quota
+                    StatusCode::NOT_MODIFIED if cached.is_some() => {
+                        let (body, next_page) = cached
+                            .map(|cached| (cached.body, cached.next_page))
+                            .unwrap_or_default();
+                        return serde_json::from_str::<T>(&body)
+                            .map(|body| (body, next_page))
+                            .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()));
+                    },
                     status if status.is_success() => {
-                        return response.json::<T>().await
+                        let etag = response.headers()
+                            .get(header::ETAG)
+                            .and_then(|h| h.to_str().ok())
+                            .map(str::to_string);
+                        let next_page = response.headers()
+                            .get(header::LINK)
+                            .and_then(|h| h.to_str().ok())
+                            .and_then(next_page_url);
+                        let body = response.text().await
+                            .map_err(|e| network_error(e, url))?;
+                        if let (Some(cache), Some(etag)) = (&self.cache, etag) {
+                            cache.store(url, &etag, &body, next_page.as_deref());
+                        }
+                        return serde_json::from_str::<T>(&body)
+                            .map(|body| (body, next_page))
                             .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()));
                     },
-                    StatusCode::FORBIDDEN => {
-                        return Err(GithubAnalyzerError::RateLimitError(
-                            response.headers()
-                                .get("x-ratelimit-reset")
-                                .and_then(|h| h.to_str().ok())
-                                .and_then(|s| s.parse::<u64>().ok())
-                                .unwrap_or(0)
+                    StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+                        match Forbidden::classify(response).await {
+                            Forbidden::SecondaryLimit(wait) if secondary_waits < max_retries => {
+                                if self.pause_until(unix_now() + wait) {
+                                    println!("Secondary rate limit hit. Waiting {} seconds before retrying...", wait);
+                                    self.rate_limit_waits.fetch_add(1, Ordering::Relaxed);
+                                }
+                                secondary_waits += 1;
+                                continue;
+                            },
+                            forbidden => return Err(forbidden.into_error(url)),
+                        }
+                    },
+                    // Ressource absente ou token refusé : réessayer ne changerait rien
+                    StatusCode::UNAUTHORIZED | StatusCode::NOT_FOUND => {
+                        return Err(client_error(response, url).await);
+                    },
+                    // Redirection non suivie (dépôt déplacé) : réessayer ne changerait rien
+                    status if status.is_redirection() => {
+                        let location = response.headers()
+                            .get(header::LOCATION)
+                            .and_then(|h| h.to_str().ok())
+                            .unwrap_or("an unknown location");
+                        return Err(GithubAnalyzerError::NetworkError(
+                            format!("GitHub API returned status {}: {} moved to {}", status, url, location)
                         ));
                     },
                     status => {
@@ -116,7 +550,7 @@ where
                 }
             },
             Err(e) => {
-                last_error = Some(GithubAnalyzerError::NetworkError(e.to_string()));
+                last_error = Some(network_error(e, url));
             }
         }
 
@@ -128,32 +562,125 @@ where
     ))
 }
 
+    /// Base de l'API pour un dépôt : celle imposée, sinon celle de son hôte
+    fn api_base(&self, locator: &RepoLocator) -> String {
+        self.api_base.clone().unwrap_or_else(|| locator.default_api_base())
+    }
+
+    /// URL de l'API pour un dépôt (`https://api.github.com/repos/owner/repo`)
+    fn api_repo_url(&self, repo_url: &str) -> Result<String, GithubAnalyzerError> {
+        let locator: RepoLocator = repo_url.parse()?;
+        Ok(locator.api_url_with_base(&self.api_base(&locator)))
+    }
+
+    /// URL de l'API GraphQL (`/api/graphql` sur GitHub Enterprise Server)
+    fn graphql_url(&self, locator: &RepoLocator) -> String {
+        let api_base = self.api_base(locator);
+        match api_base.strip_suffix("/v3") {
+            Some(enterprise) => format!("{}/graphql", enterprise),
+            None => format!("{}/graphql", api_base),
+        }
+    }
+
+    /// URL de l'API Contents pour un chemin du dépôt à une référence donnée
+    pub fn contents_url(&self, repo_url: &str, path: &str, branch: &str) -> Result<String, GithubAnalyzerError> {
+        Ok(self.api_repo_url(repo_url)? + "/contents/" + path + "?ref=" + branch)
+    }
+
+    /// Quota restant de l'API REST pour l'hôte du dépôt.
+    ///
+    /// Cet appel n'est pas décompté du quota ; il n'est jamais mis en cache.
+    pub async fn get_rate_limit(&self, repo_url: &str) -> Result<GithubRateLimitStatus, GithubAnalyzerError> {
+        let locator: RepoLocator = repo_url.parse()?;
+        let api_url = self.api_base(&locator) + "/rate_limit";
+
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let response = self.client.get(&api_url)
+            .headers(self.build_headers().await?)
+            .send()
+            .await
+            .map_err(|e| network_error(e, &api_url))?;
+        match response.status() {
+            status if status.is_success() => {},
+            StatusCode::UNAUTHORIZED | StatusCode::NOT_FOUND => return Err(client_error(response, &api_url).await),
+            status => return Err(GithubAnalyzerError::NetworkError(
+                format!("Rate limit endpoint returned status {}", status)
+            )),
+        }
+        let rate_limit: GithubRateLimit = response.json().await
+            .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()))?;
+        Ok(rate_limit.rate)
+    }
+
+    /// Vérifie l'authentification auprès de l'API de l'hôte du dépôt (`/rate_limit`,
+    /// hors quota) et renvoie le quota qui s'applique.
+    ///
+    /// Un token refusé donne une `AuthError` avant toute analyse.
+    pub async fn verify_auth(&self, repo_url: &str) -> Result<GithubRateLimitStatus, GithubAnalyzerError> {
+        let quota = self.get_rate_limit(repo_url).await?;
+        if self.has_token() && quota.limit <= UNAUTHENTICATED_RATE_LIMIT {
+            println!(
+                "Warning: The token was accepted but the API applies the unauthenticated limit ({} requests/hour)",
+                quota.limit
+            );
+        }
+        Ok(quota)
+    }
+
+    /// Métadonnées du dépôt, dont sa branche par défaut
+    pub async fn get_repo_info(&self, repo_url: &str) -> Result<GithubRepoInfo, GithubAnalyzerError> {
+        let api_url = self.api_repo_url(repo_url)?;
+        self.get_with_retry(&api_url, 3).await
+    }
+
+    /// Liste l'arborescence complète du dépôt en une seule requête (Git Trees API)
+    pub async fn get_repo_tree(
+        &self,
+        repo_url: &str,
+        branch: &str,
+    ) -> Result<GithubTree, GithubAnalyzerError> {
+        let api_url = self.api_repo_url(repo_url)? + "/git/trees/" + branch + "?recursive=1";
+        self.get_with_retry(&api_url, 3).await
+    }
+
     pub async fn get_repo_contents(
         &self,
         repo_url: &str,
         path: &str,
         branch: &str,
     ) -> Result<Vec<GithubContent>, GithubAnalyzerError> {
-        let api_url = repo_url
-            .replace("github.com", "api.github.com/repos")
-            .replace("tree/main", "")
-            .replace("tree/master", "") 
-            + "/contents/"
-            + path
-            + "?ref="
-            + branch;
+        let api_url = self.contents_url(repo_url, path, branch)?;
 
         // Try parsing as array first, then as single item
-        match self.get_with_retry::<Vec<GithubContent>>(&api_url, 3).await {
-            Ok(contents) => Ok(contents),
+        let (mut contents, mut next_page) = match self.get_page_with_retry::<Vec<GithubContent>>(&api_url, 3).await {
+            Ok(page) => page,
             Err(_e) => {
                 // Try parsing as single item
                 match self.get_with_retry::<GithubContent>(&api_url, 3).await {
-                    Ok(item) => Ok(vec![item]),
-                    Err(e) => Err(e)
+                    Ok(item) => return Ok(vec![item]),
+                    Err(e) => return Err(e)
                 }
             }
+        };
+
+        // Les grands répertoires sont paginés : suit `Link: rel="next"` jusqu'au bout
+        let mut pages = 1;
+        while let Some(page_url) = next_page {
+            let (page, next) = self.get_page_with_retry::<Vec<GithubContent>>(&page_url, 3).await?;
+            contents.extend(page);
+            next_page = next;
+            pages += 1;
+        }
+        let directory = if path.is_empty() { "/" } else { path };
+        if pages > 1 {
+            println!("Listed {} entries of {} in {} pages", contents.len(), directory, pages);
+        } else if contents.len() >= CONTENTS_PAGE_MAX_ENTRIES {
+            println!(
+                "Warning: {} lists {} entries without a next page; the contents API may have truncated it",
+                directory, contents.len()
+            );
         }
+        Ok(contents)
     }
 
     pub async fn get_file_content(
@@ -162,14 +689,539 @@ where
     ) -> Result<String, GithubAnalyzerError> {
         let content: GithubContent = self.get_with_retry(content_url, 3).await?;
         
-        match (content.content, content.encoding) {
-            (Some(content), Some(encoding)) if encoding == "base64" => {
-                let decoded = base64::decode(content.replace("\n", ""))
-                    .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()))?;
-                String::from_utf8(decoded)
-                    .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()))
+        match (content.content, content.encoding, content.download_url) {
+            (Some(content), Some(encoding), _) if encoding == "base64" && !content.is_empty() => {
+                decode_base64_text(&content)
             },
+            // Au-delà de 1 Mo, l'API renvoie un contenu vide (`encoding: none`)
+            (_, _, Some(download_url)) => self.get_raw_content(&download_url).await,
             _ => Err(GithubAnalyzerError::ParseError("Content or encoding unavailable".into())),
         }
     }
-}
\ No newline at end of file
+
+    /// Récupère un blob par son SHA (API Blobs, jusqu'à 100 Mo), dans la limite
+    /// de la taille maximale configurée
+    pub async fn get_blob(&self, repo_url: &str, sha: &str) -> Result<String, GithubAnalyzerError> {
+        let api_url = self.api_repo_url(repo_url)? + "/git/blobs/" + sha;
+        let blob: GithubBlob = self.get_with_retry(&api_url, 3).await?;
+
+        if blob.size > self.max_file_size as u64 {
+            return Err(GithubAnalyzerError::ParseError(format!(
+                "Blob {} exceeds {} bytes", sha, self.max_file_size
+            )));
+        }
+        match blob.encoding.as_str() {
+            "base64" => decode_base64_text(&blob.content),
+            "utf-8" => decode_text(blob.content.into_bytes()),
+            other => Err(GithubAnalyzerError::ParseError(format!("Unsupported blob encoding: {}", other))),
+        }
+    }
+
+    /// Télécharge le contenu brut d'un fichier (`download_url`) par morceaux,
+    /// dans la limite de la taille maximale configurée
+    pub async fn get_raw_content(&self, download_url: &str) -> Result<String, GithubAnalyzerError> {
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let mut response = self.client.get(download_url)
+            .headers(self.build_headers().await?)
+            .send()
+            .await
+            .map_err(|e| network_error(e, download_url))?;
+
+        match response.status() {
+            status if status.is_success() => {},
+            StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+                return Err(Forbidden::classify(response).await.into_error(download_url));
+            },
+            StatusCode::UNAUTHORIZED | StatusCode::NOT_FOUND => return Err(client_error(response, download_url).await),
+            status => return Err(GithubAnalyzerError::NetworkError(
+                format!("Raw content request returned status {}", status)
+            )),
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await
+            .map_err(|e| network_error(e, download_url))?
+        {
+            if bytes.len() + chunk.len() > self.max_file_size {
+                return Err(GithubAnalyzerError::ParseError(format!(
+                    "Raw content exceeds {} bytes: {}", self.max_file_size, download_url
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        decode_text(bytes)
+    }
+
+    /// Récupère le contenu de plusieurs fichiers via l'API GraphQL.
+    ///
+    /// Les chemins sont demandés par lots de `GRAPHQL_BATCH_SIZE` alias
+    /// `object(expression: "ref:path")`. Le résultat associe chaque chemin
+    /// trouvé à son texte, ou à `None` pour un blob binaire. Requiert un token.
+    pub async fn get_files_graphql(
+        &self,
+        repo_url: &str,
+        git_ref: &str,
+        paths: &[String],
+    ) -> Result<HashMap<String, Option<String>>, GithubAnalyzerError> {
+        if !self.has_token() {
+            return Err(GithubAnalyzerError::NetworkError(
+                "The GraphQL API requires a GITHUB_TOKEN".to_string()
+            ));
+        }
+        let locator: RepoLocator = repo_url.parse()?;
+        let graphql_url = self.graphql_url(&locator);
+        let mut files = HashMap::new();
+
+        for batch in paths.chunks(GRAPHQL_BATCH_SIZE) {
+            let declarations: String = (0..batch.len()).map(|i| format!(", $e{}: String!", i)).collect();
+            let fields: String = (0..batch.len())
+                .map(|i| format!(" f{0}: object(expression: $e{0}) {{ ... on Blob {{ text isBinary }} }}", i))
+                .collect();
+            let query = format!(
+                "query($owner: String!, $name: String!{}) {{ repository(owner: $owner, name: $name) {{{} }} }}",
+                declarations, fields
+            );
+
+            let mut variables = serde_json::Map::new();
+            variables.insert("owner".to_string(), locator.owner.clone().into());
+            variables.insert("name".to_string(), locator.repo.clone().into());
+            for (i, path) in batch.iter().enumerate() {
+                variables.insert(format!("e{}", i), format!("{}:{}", git_ref, path).into());
+            }
+
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            let response = self.client.post(&graphql_url)
+                .headers(self.build_headers().await?)
+                .json(&serde_json::json!({ "query": query, "variables": variables }))
+                .send()
+                .await
+                .map_err(|e| network_error(e, &graphql_url))?;
+
+            match response.status() {
+                status if status.is_success() => {},
+                StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+                    return Err(Forbidden::classify(response).await.into_error(&graphql_url));
+                },
+                StatusCode::UNAUTHORIZED => return Err(client_error(response, &graphql_url).await),
+                status => return Err(GithubAnalyzerError::NetworkError(
+                    format!("GitHub GraphQL API returned status {}", status)
+                )),
+            }
+
+            let body: serde_json::Value = response.json().await
+                .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()))?;
+            let repository = &body["data"]["repository"];
+            if repository.is_null() {
+                return Err(GithubAnalyzerError::ParseError(format!(
+                    "GraphQL query failed: {}", body["errors"]
+                )));
+            }
+
+            for (i, path) in batch.iter().enumerate() {
+                let blob = &repository[format!("f{}", i)];
+                if blob.is_null() {
+                    continue; // Chemin absent à cette référence
+                }
+                let text = match blob["isBinary"].as_bool() {
+                    Some(true) => None,
+                    _ => blob["text"].as_str().map(str::to_string),
+                };
+                files.insert(path.clone(), text);
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Télécharge l'archive tarball du dépôt (branche par défaut si aucune référence)
+    pub async fn get_tarball(
+        &self,
+        repo_url: &str,
+        git_ref: Option<&str>,
+    ) -> Result<Vec<u8>, GithubAnalyzerError> {
+        let mut api_url = self.api_repo_url(repo_url)? + "/tarball";
+        if let Some(git_ref) = git_ref {
+            api_url = api_url + "/" + git_ref;
+        }
+
+        self.requests.fetch_add(1, Ordering::Relaxed);
+        let response = self.client.get(&api_url)
+            .headers(self.build_headers().await?)
+            .send()
+            .await
+            .map_err(|e| network_error(e, &api_url))?;
+
+        match response.status() {
+            status if status.is_success() => response.bytes().await
+                .map(|bytes| bytes.to_vec())
+                .map_err(|e| network_error(e, &api_url)),
+            StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+                Err(Forbidden::classify(response).await.into_error(&api_url))
+            },
+            StatusCode::UNAUTHORIZED | StatusCode::NOT_FOUND => Err(client_error(response, &api_url).await),
+            status => Err(GithubAnalyzerError::NetworkError(
+                format!("GitHub API returned status {}: {}", status, api_url)
+            )),
+        }
+    }
+
+    /// Répartition des langages du dépôt en octets, du plus au moins représenté
+    pub async fn get_languages(&self, repo_url: &str) -> Result<Vec<(String, u64)>, GithubAnalyzerError> {
+        let api_url = self.api_repo_url(repo_url)? + "/languages";
+
+        let languages: HashMap<String, u64> = self.get_with_retry(&api_url, 3).await?;
+        let mut languages: Vec<(String, u64)> = languages.into_iter().collect();
+        languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        Ok(languages)
+    }
+
+    /// Liste tous les dépôts d'une organisation (ou, à défaut, d'un utilisateur),
+    /// en suivant la pagination
+    pub async fn get_org_repos(&self, org: &str) -> Result<Vec<GithubOrgRepo>, GithubAnalyzerError> {
+        let api_base = self.api_base.as_deref().unwrap_or(GITHUB_API_BASE);
+        let org_url = format!("{}/orgs/{}/repos?per_page=100", api_base, org);
+
+        let (mut repos, mut next_page) = match self.get_page_with_retry::<Vec<GithubOrgRepo>>(&org_url, 3).await {
+            Ok(page) => page,
+            // Un compte utilisateur n'est pas une organisation
+            Err(GithubAnalyzerError::NotFound(_)) => {
+                let user_url = format!("{}/users/{}/repos?per_page=100", api_base, org);
+                self.get_page_with_retry(&user_url, 3).await?
+            },
+            Err(e) => return Err(e),
+        };
+        while let Some(page_url) = next_page {
+            let (page, next) = self.get_page_with_retry::<Vec<GithubOrgRepo>>(&page_url, 3).await?;
+            repos.extend(page);
+            next_page = next;
+        }
+        Ok(repos)
+    }
+
+    /// Titre, description et branches d'une pull request
+    pub async fn get_pull_request(&self, repo_url: &str, number: u64) -> Result<GithubPullRequest, GithubAnalyzerError> {
+        let api_url = format!("{}/pulls/{}", self.api_repo_url(repo_url)?, number);
+        self.get_with_retry(&api_url, 3).await
+    }
+
+    /// Fichiers modifiés par une pull request, en suivant la pagination
+    pub async fn get_pull_request_files(
+        &self,
+        repo_url: &str,
+        number: u64,
+    ) -> Result<Vec<GithubPullFile>, GithubAnalyzerError> {
+        let api_url = format!("{}/pulls/{}/files?per_page=100", self.api_repo_url(repo_url)?, number);
+        let (mut files, mut next_page) = self.get_page_with_retry::<Vec<GithubPullFile>>(&api_url, 3).await?;
+        while let Some(page_url) = next_page {
+            let (page, next) = self.get_page_with_retry::<Vec<GithubPullFile>>(&page_url, 3).await?;
+            files.extend(page);
+            next_page = next;
+        }
+        Ok(files)
+    }
+
+    /// Liste les noms des tags du dépôt (une seule page de 100 tags)
+    pub async fn get_tags(&self, repo_url: &str) -> Result<Vec<String>, GithubAnalyzerError> {
+        let api_url = self.api_repo_url(repo_url)? + "/tags?per_page=100";
+
+        let tags: Vec<GithubTag> = self.get_with_retry(&api_url, 3).await?;
+        Ok(tags.into_iter().map(|tag| tag.name).collect())
+    }
+}
+
+/// Schéma de l'en-tête `Authorization` selon le type de token : `token` pour les
+/// tokens personnels classiques (`ghp_` ou hexadécimal historique), `Bearer` pour
+/// les tokens fine-grained (`github_pat_`), d'installation et OAuth
+fn auth_scheme(token: &str) -> &'static str {
+    let legacy = token.len() == 40 && token.chars().all(|c| c.is_ascii_hexdigit());
+    if token.starts_with("ghp_") || legacy {
+        "token"
+    } else {
+        "Bearer"
+    }
+}
+
+/// Hôte GitHub d'une base d'API : `api.github.com` désigne github.com,
+/// `https://<hôte>/api/v3` un GitHub Enterprise Server
+fn api_host(api_base: &str) -> Option<String> {
+    let url = reqwest::Url::parse(api_base).ok()?;
+    match url.host_str()? {
+        "api.github.com" => Some(GITHUB_HOST.to_string()),
+        host => Some(host.to_string()),
+    }
+}
+
+/// Catégorie de quota correspondant à une limite horaire de l'API REST
+pub fn rate_limit_bucket(limit: u64) -> &'static str {
+    match limit {
+        0..=UNAUTHENTICATED_RATE_LIMIT => "unauthenticated",
+        5000 => "authenticated user",
+        15000 => "GitHub App or Enterprise Cloud",
+        _ => "custom",
+    }
+}
+
+/// Erreur d'une réponse 401 ou 404, détaillée par le champ `message` du corps JSON
+pub(crate) async fn client_error(response: reqwest::Response, url: &str) -> GithubAnalyzerError {
+    let status = response.status();
+    let body: serde_json::Value = response.json().await.unwrap_or_default();
+    let detail = format!("{} ({})", body["message"].as_str().unwrap_or("no message"), url);
+    match status {
+        StatusCode::UNAUTHORIZED => GithubAnalyzerError::AuthError(detail),
+        _ => GithubAnalyzerError::NotFound(detail),
+    }
+}
+
+/// URL de la page suivante dans un en-tête `Link` (`<url>; rel="next", ...`)
+pub(crate) fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|param| matches!(param.trim(), "rel=\"next\"" | "rel=next"))
+            .then(|| url.trim().trim_start_matches('<').trim_end_matches('>').to_string())
+    })
+}
+
+/// Erreur réseau d'une requête, avec l'URL en cause
+pub(crate) fn network_error(error: reqwest::Error, url: &str) -> GithubAnalyzerError {
+    if error.is_timeout() {
+        GithubAnalyzerError::NetworkError(format!("Request timed out: {}", url))
+    } else {
+        GithubAnalyzerError::NetworkError(format!("{} ({})", error.without_url(), url))
+    }
+}
+
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Décode un contenu base64 de l'API (avec retours à la ligne) en texte UTF-8
+pub(crate) fn decode_base64_text(content: &str) -> Result<String, GithubAnalyzerError> {
+    let decoded = base64::decode(content.replace('\n', ""))
+        .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()))?;
+    decode_text(decoded)
+}
+
+/// Texte UTF-8 d'un contenu téléchargé ; un contenu binaire est signalé par
+/// `BinaryContent` plutôt que par une erreur de décodage
+pub(crate) fn decode_text(bytes: Vec<u8>) -> Result<String, GithubAnalyzerError> {
+    if is_binary_content(&bytes) {
+        return Err(GithubAnalyzerError::BinaryContent(format!("{} bytes", bytes.len())));
+    }
+    String::from_utf8(bytes).map_err(|e| GithubAnalyzerError::ParseError(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    /// Serveur HTTP local : chaque requête reçoit `respond(base, chemin)`, statut,
+    /// en-têtes supplémentaires et corps JSON. Renvoie l'URL de base et les chemins reçus.
+    async fn mock_api(
+        respond: impl Fn(&str, &str) -> (u16, Vec<String>, String) + Send + 'static,
+    ) -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        let received = Arc::new(Mutex::new(Vec::new()));
+        let (server_base, log) = (base.clone(), received.clone());
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut buffer = [0u8; 4096];
+                while !request.windows(4).any(|window| window == b"\r\n\r\n") {
+                    match socket.read(&mut buffer).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(read) => request.extend_from_slice(&buffer[..read]),
+                    }
+                }
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or("/").to_string();
+                let (status, headers, body) = respond(&server_base, &path);
+                log.lock().unwrap().push(path);
+                let mut response = format!(
+                    "HTTP/1.1 {} Mock\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+                    status,
+                    body.len()
+                );
+                for header in headers {
+                    response.push_str(&header);
+                    response.push_str("\r\n");
+                }
+                response.push_str("\r\n");
+                response.push_str(&body);
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        (base, received)
+    }
+
+    /// Page de l'API Contents : fichiers `generated/file_<n>.rs` pour `range`
+    fn contents_page(range: std::ops::Range<usize>) -> String {
+        let entries: Vec<serde_json::Value> = range
+            .map(|index| {
+                serde_json::json!({
+                    "name": format!("file_{}.rs", index),
+                    "path": format!("generated/file_{}.rs", index),
+                    "sha": format!("{:040x}", index),
+                    "size": 10,
+                    "url": format!("https://api.github.com/repos/owner/repo/contents/generated/file_{}.rs", index),
+                    "content": null,
+                    "encoding": null,
+                    "type": "file",
+                })
+            })
+            .collect();
+        serde_json::to_string(&entries).unwrap()
+    }
+
+    fn client(base: &str) -> GithubClient {
+        GithubClient::builder()
+            .cache(None)
+            .token(Some("test-token".to_string()))
+            .api_base(base)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn directory_listing_follows_every_next_page() {
+        let (base, received) = mock_api(|base, path| {
+            let page: usize = path.split("page=").nth(1).and_then(|page| page.parse().ok()).unwrap_or(1);
+            let link = format!("{}/repos/owner/repo/contents/generated?ref=main", base);
+            let headers = match page {
+                1 | 2 => vec![format!("Link: <{}&page={}>; rel=\"next\", <{}&page=3>; rel=\"last\"", link, page + 1, link)],
+                _ => vec![format!("Link: <{}&page=1>; rel=\"first\", <{}&page=2>; rel=\"prev\"", link, link)],
+            };
+            let range = match page {
+                1 => 0..1000,
+                2 => 1000..2000,
+                _ => 2000..2250,
+            };
+            (200, headers, contents_page(range))
+        })
+        .await;
+
+        let contents = client(&base)
+            .get_repo_contents("https://github.com/owner/repo", "generated", "main")
+            .await
+            .unwrap();
+
+        assert_eq!(contents.len(), 2250);
+        assert_eq!(contents[0].path, "generated/file_0.rs");
+        assert_eq!(contents[1000].path, "generated/file_1000.rs");
+        assert_eq!(contents[2249].path, "generated/file_2249.rs");
+        assert_eq!(
+            *received.lock().unwrap(),
+            vec![
+                "/repos/owner/repo/contents/generated?ref=main",
+                "/repos/owner/repo/contents/generated?ref=main&page=2",
+                "/repos/owner/repo/contents/generated?ref=main&page=3",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn not_modified_replays_the_cached_body_and_next_page() {
+        let requests = AtomicU64::new(0);
+        let (base, received) = mock_api(move |base, _| match requests.fetch_add(1, Ordering::Relaxed) {
+            0 => (200, vec![
+                "ETag: \"v1\"".to_string(),
+                format!("Link: <{}/repos/owner/repo/tags?page=2>; rel=\"next\"", base),
+            ], r#"[{"name":"v1.0.0"}]"#.to_string()),
+            _ => (304, Vec::new(), String::new()),
+        })
+        .await;
+        let dir = tempfile::tempdir().unwrap();
+        let client = GithubClient::builder()
+            .cache(Some(HttpCache::new(dir.path())))
+            .token(Some("test-token".to_string()))
+            .api_base(base.as_str())
+            .build();
+        let url = format!("{}/repos/owner/repo/tags", base);
+
+        let fresh: (serde_json::Value, Option<String>) = client.get_page_with_retry(&url, 0).await.unwrap();
+        let replayed: (serde_json::Value, Option<String>) = client.get_page_with_retry(&url, 0).await.unwrap();
+
+        assert_eq!(replayed, fresh);
+        assert_eq!(replayed.0[0]["name"], "v1.0.0");
+        assert_eq!(replayed.1, Some(format!("{}/repos/owner/repo/tags?page=2", base)));
+        assert_eq!(received.lock().unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn single_page_listing_makes_one_request() {
+        let (base, received) = mock_api(|_, _| (200, Vec::new(), contents_page(0..3))).await;
+        let contents = client(&base)
+            .get_repo_contents("https://github.com/owner/repo", "", "main")
+            .await
+            .unwrap();
+        assert_eq!(contents.len(), 3);
+        assert_eq!(received.lock().unwrap().len(), 1);
+    }
+
+    /// Erreur renvoyée pour une réponse unique, et nombre de requêtes envoyées
+    async fn error_for(status: u16, headers: &[&str], message: &str) -> (GithubAnalyzerError, usize) {
+        let headers: Vec<String> = headers.iter().map(|header| header.to_string()).collect();
+        let body = serde_json::json!({ "message": message }).to_string();
+        let (base, received) = mock_api(move |_, _| (status, headers.clone(), body.clone())).await;
+        let error = client(&base)
+            .get_with_retry::<serde_json::Value>(&format!("{}/repos/owner/repo", base), 3)
+            .await
+            .unwrap_err();
+        let requests = received.lock().unwrap().len();
+        (error, requests)
+    }
+
+    #[tokio::test]
+    async fn refused_access_is_forbidden_and_not_retried() {
+        let (error, requests) = error_for(403, &["x-ratelimit-remaining: 4999"], "Resource not accessible by integration").await;
+        assert!(matches!(&error, GithubAnalyzerError::Forbidden(message) if message.starts_with("Resource not accessible by integration (")), "{:?}", error);
+        assert_eq!(error.kind(), "Forbidden");
+        assert!(error.hint().unwrap().starts_with("access forbidden"));
+        assert_eq!(requests, 1);
+
+        let (error, _) = error_for(403, &[], "").await;
+        assert!(matches!(&error, GithubAnalyzerError::Forbidden(message) if message.starts_with("access forbidden (")), "{:?}", error);
+    }
+
+    #[tokio::test]
+    async fn exhausted_quota_is_a_rate_limit_not_a_permission_error() {
+        let (error, requests) = error_for(403, &["x-ratelimit-reset: 1"], "API rate limit exceeded for user").await;
+        assert!(matches!(error, GithubAnalyzerError::RateLimitError(1)), "{:?}", error);
+        assert_eq!(requests, 1);
+    }
+
+    #[tokio::test]
+    async fn missing_repository_and_rejected_token_fail_without_retries() {
+        let (error, requests) = error_for(404, &[], "Not Found").await;
+        assert!(matches!(&error, GithubAnalyzerError::NotFound(message) if message.starts_with("Not Found (")), "{:?}", error);
+        assert_eq!(requests, 1);
+
+        let (error, requests) = error_for(401, &[], "Bad credentials").await;
+        assert!(matches!(&error, GithubAnalyzerError::AuthError(message) if message.starts_with("Bad credentials (")), "{:?}", error);
+        assert_eq!(requests, 1);
+    }
+
+    #[test]
+    fn next_page_is_read_from_the_link_header() {
+        let link = r#"<https://api.github.com/x?page=2>; rel="next", <https://api.github.com/x?page=5>; rel="last""#;
+        assert_eq!(next_page_url(link).as_deref(), Some("https://api.github.com/x?page=2"));
+        let reordered = r#"<https://api.github.com/x?page=1>; rel="prev", <https://api.github.com/x?page=3>; rel=next"#;
+        assert_eq!(next_page_url(reordered).as_deref(), Some("https://api.github.com/x?page=3"));
+        assert_eq!(next_page_url(r#"<https://api.github.com/x?page=1>; rel="first""#), None);
+        assert_eq!(next_page_url(""), None);
+    }
+
+    #[test]
+    fn gh_host_is_derived_from_the_api_base() {
+        assert_eq!(api_host("https://api.github.com").as_deref(), Some(GITHUB_HOST));
+        assert_eq!(api_host("https://ghe.example.com/api/v3").as_deref(), Some("ghe.example.com"));
+        assert_eq!(api_host("not a url"), None);
+    }
+}
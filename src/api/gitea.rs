@@ -0,0 +1,235 @@
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+use crate::api::client::decode_base64_text;
+use crate::api::provider::RepositoryProvider;
+use crate::api::rest::{discover_token, RestClient};
+use crate::error::GithubAnalyzerError;
+use crate::types::github::{
+    GithubBlob, GithubContent, GithubRateLimitStatus, GithubRepoInfo, GithubTag, GithubTree,
+};
+use crate::types::locator::{Provider, RepoLocator};
+
+/// Dépôt renvoyé par `GET /repos/{owner}/{repo}` ; seuls les noms de champs
+/// diffèrent de l'API GitHub
+#[derive(Debug, Deserialize)]
+struct GiteaRepo {
+    full_name: String,
+    #[serde(default)]
+    default_branch: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    stars_count: u64,
+    #[serde(default)]
+    topics: Vec<String>,
+    #[serde(default)]
+    updated_at: Option<String>,
+    #[serde(default)]
+    archived: bool,
+}
+
+impl From<GiteaRepo> for GithubRepoInfo {
+    fn from(repo: GiteaRepo) -> Self {
+        GithubRepoInfo {
+            full_name: repo.full_name,
+            default_branch: repo.default_branch
+                .filter(|branch| !branch.is_empty())
+                .unwrap_or_else(|| "main".to_string()),
+            description: repo.description.filter(|description| !description.is_empty()),
+            stargazers_count: repo.stars_count,
+            topics: repo.topics,
+            license: None,
+            pushed_at: repo.updated_at,
+            archived: repo.archived,
+        }
+    }
+}
+
+/// Page de `GET /repos/{owner}/{repo}/git/trees/{ref}` : `truncated` annonce une page suivante
+#[derive(Debug, Deserialize)]
+struct GiteaTreePage {
+    #[serde(flatten)]
+    tree: GithubTree,
+    #[serde(default)]
+    page: u32,
+}
+
+/// Client de l'API v1 de Gitea et Forgejo (codeberg.org ou instance auto-hébergée).
+///
+/// Les réponses suivent celles de l'API GitHub et réutilisent ses types.
+pub struct GiteaClient {
+    rest: RestClient,
+    api_base: Option<String>,
+}
+
+impl GiteaClient {
+    /// Client authentifié par le token donné, sinon par `GITEA_TOKEN`
+    pub fn new(http: Result<Client, String>, token: Option<String>) -> Self {
+        let token = discover_token(token, "GITEA_TOKEN", "Gitea");
+        Self {
+            rest: RestClient::new(http, "Gitea", token.map(|token| ("Authorization", format!("token {}", token)))),
+            api_base: None,
+        }
+    }
+
+    /// Base de l'instance (`https://git.example.com`) ou de son API (`.../api/v1`)
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        let api_base = api_base.into().trim_end_matches('/').to_string();
+        self.api_base = Some(if api_base.ends_with("/api/v1") {
+            api_base
+        } else {
+            api_base + "/api/v1"
+        });
+        self
+    }
+
+    pub fn with_max_file_size(mut self, max_file_size: usize) -> Self {
+        self.rest.set_max_file_size(max_file_size);
+        self
+    }
+
+    /// URL de l'API du dépôt (`<base>/repos/owner/repo`)
+    fn repo_api_url(&self, repo_url: &str) -> Result<String, GithubAnalyzerError> {
+        let locator = RepoLocator::parse_as(repo_url, Some(Provider::Gitea))?;
+        Ok(match &self.api_base {
+            Some(api_base) => locator.api_url_with_base(api_base),
+            None => locator.api_url(),
+        })
+    }
+
+    /// Contenu d'un fichier ou d'un blob, encodé en base64 ou déjà en texte
+    fn decode(&self, content: &str, encoding: Option<&str>) -> Result<String, GithubAnalyzerError> {
+        match encoding {
+            Some("base64") => decode_base64_text(content),
+            _ => Ok(content.to_string()),
+        }
+    }
+}
+
+impl RepositoryProvider for GiteaClient {
+    fn has_token(&self) -> bool {
+        self.rest.has_token()
+    }
+
+    fn api_requests(&self) -> u64 {
+        self.rest.api_requests()
+    }
+
+    fn rate_limit_waits(&self) -> u64 {
+        self.rest.rate_limit_waits()
+    }
+
+    fn get_repo_info<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<GithubRepoInfo, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let repo: GiteaRepo = self.rest.get_json(&self.repo_api_url(repo_url)?).await?;
+            Ok(repo.into())
+        })
+    }
+
+    /// L'arborescence récursive est paginée : les pages sont suivies tant que
+    /// la réponse est tronquée
+    fn get_repo_tree<'a>(
+        &'a self,
+        repo_url: &'a str,
+        branch: &'a str,
+    ) -> BoxFuture<'a, Result<GithubTree, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let base_url = format!("{}/git/trees/{}?recursive=true&per_page=1000", self.repo_api_url(repo_url)?, branch);
+            let mut page: GiteaTreePage = self.rest.get_json(&base_url).await?;
+            let mut tree = GithubTree { sha: page.tree.sha.clone(), tree: Vec::new(), truncated: false };
+            loop {
+                tree.tree.append(&mut page.tree.tree);
+                if !page.tree.truncated {
+                    break;
+                }
+                let url = format!("{}&page={}", base_url, page.page.max(1) + 1);
+                page = self.rest.get_json(&url).await?;
+            }
+            Ok(tree)
+        })
+    }
+
+    fn get_repo_contents<'a>(
+        &'a self,
+        repo_url: &'a str,
+        path: &'a str,
+        branch: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<GithubContent>, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let url = self.contents_url(repo_url, path, branch)?;
+            let mut contents: Vec<GithubContent> = self.rest.get_json(&url).await?;
+            // L'URL renvoyée par l'instance peut ignorer une base d'API imposée
+            for content in contents.iter_mut() {
+                content.url = self.contents_url(repo_url, &content.path, branch)?;
+            }
+            Ok(contents)
+        })
+    }
+
+    fn contents_url(&self, repo_url: &str, path: &str, branch: &str) -> Result<String, GithubAnalyzerError> {
+        Ok(self.repo_api_url(repo_url)? + "/contents/" + path + "?ref=" + branch)
+    }
+
+    fn get_file_content<'a>(&'a self, content_url: &'a str) -> BoxFuture<'a, Result<String, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let content: GithubContent = self.rest.get_json(content_url).await?;
+            let encoded = content.content.as_deref().ok_or_else(|| {
+                GithubAnalyzerError::ParseError(format!("No content returned for {}", content.path))
+            })?;
+            self.decode(encoded, content.encoding.as_deref())
+        })
+    }
+
+    fn get_blob<'a>(&'a self, repo_url: &'a str, sha: &'a str) -> BoxFuture<'a, Result<String, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let url = format!("{}/git/blobs/{}", self.repo_api_url(repo_url)?, sha);
+            let blob: GithubBlob = self.rest.get_json(&url).await?;
+            self.decode(&blob.content, Some(&blob.encoding))
+        })
+    }
+
+    /// Gitea n'expose pas de quota d'API
+    fn get_rate_limit<'a>(
+        &'a self,
+        _repo_url: &'a str,
+    ) -> BoxFuture<'a, Result<Option<GithubRateLimitStatus>, GithubAnalyzerError>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    fn get_tags<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<Vec<String>, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let url = format!("{}/tags?limit=50", self.repo_api_url(repo_url)?);
+            let tags: Vec<GithubTag> = self.rest.get_json(&url).await?;
+            Ok(tags.into_iter().map(|tag| tag.name).collect())
+        })
+    }
+
+    fn get_languages<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<Vec<(String, u64)>, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let url = format!("{}/languages", self.repo_api_url(repo_url)?);
+            let languages: HashMap<String, u64> = self.rest.get_json(&url).await?;
+            let mut languages: Vec<(String, u64)> = languages.into_iter().collect();
+            languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            Ok(languages)
+        })
+    }
+
+    /// L'archive exige une référence : la branche par défaut est lue si aucune n'est donnée
+    fn get_tarball<'a>(
+        &'a self,
+        repo_url: &'a str,
+        git_ref: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let git_ref = match git_ref {
+                Some(git_ref) => git_ref.to_string(),
+                None => self.get_repo_info(repo_url).await?.default_branch,
+            };
+            let url = format!("{}/archive/{}.tar.gz", self.repo_api_url(repo_url)?, git_ref);
+            self.rest.get_bytes(&url).await
+        })
+    }
+}
@@ -0,0 +1,348 @@
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::env;
+
+use crate::api::provider::RepositoryProvider;
+use crate::api::rest::{discover_token, RestClient};
+use crate::error::GithubAnalyzerError;
+use crate::types::github::{
+    GithubContent, GithubRateLimitStatus, GithubRepoInfo, GithubTag, GithubTree, GithubTreeEntry,
+};
+use crate::types::locator::{encode_component, Provider, RepoLocator};
+
+/// Projet renvoyé par `GET /projects/{id}`
+#[derive(Debug, Deserialize)]
+struct GitlabProject {
+    path_with_namespace: String,
+    /// Absente pour un projet vide
+    #[serde(default)]
+    default_branch: Option<String>,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    star_count: u64,
+    #[serde(default)]
+    topics: Vec<String>,
+    #[serde(default)]
+    last_activity_at: Option<String>,
+    #[serde(default)]
+    archived: bool,
+}
+
+impl From<GitlabProject> for GithubRepoInfo {
+    fn from(project: GitlabProject) -> Self {
+        GithubRepoInfo {
+            full_name: project.path_with_namespace,
+            default_branch: project.default_branch.unwrap_or_else(|| "main".to_string()),
+            description: project.description,
+            stargazers_count: project.star_count,
+            topics: project.topics,
+            license: None,
+            pushed_at: project.last_activity_at,
+            archived: project.archived,
+        }
+    }
+}
+
+/// Entrée de `GET /projects/{id}/repository/tree`
+#[derive(Debug, Deserialize)]
+struct GitlabTreeEntry {
+    id: String,
+    name: String,
+    /// `blob`, `tree` ou `commit` (sous-module)
+    #[serde(rename = "type")]
+    entry_type: String,
+    path: String,
+    mode: String,
+}
+
+/// Client de l'API REST v4 de GitLab (gitlab.com ou instance auto-hébergée).
+///
+/// Le projet est désigné par son chemin complet encodé (`group%2Fsubgroup%2Fproject`).
+pub struct GitlabClient {
+    rest: RestClient,
+    api_base: Option<String>,
+}
+
+impl GitlabClient {
+    /// Client authentifié par le token donné, sinon par `GITLAB_TOKEN`
+    pub fn new(http: Result<Client, String>, token: Option<String>) -> Self {
+        let token = discover_token(token, "GITLAB_TOKEN", "GitLab");
+        Self {
+            rest: RestClient::new(http, "GitLab", token.map(|token| ("PRIVATE-TOKEN", token))),
+            api_base: env::var("GITLAB_API_URL").ok().filter(|url| !url.is_empty()),
+        }
+    }
+
+    /// Base de l'API imposée (`https://gitlab.example.com/api/v4`)
+    pub fn with_api_base(mut self, api_base: impl Into<String>) -> Self {
+        self.api_base = Some(api_base.into().trim_end_matches('/').to_string());
+        self
+    }
+
+    pub fn with_max_file_size(mut self, max_file_size: usize) -> Self {
+        self.rest.set_max_file_size(max_file_size);
+        self
+    }
+
+    /// URL de l'API du projet (`<base>/projects/group%2Fproject`)
+    fn project_url(&self, repo_url: &str) -> Result<String, GithubAnalyzerError> {
+        let locator = RepoLocator::parse_as(repo_url, Some(Provider::Gitlab))?;
+        let api_base = self.api_base.clone().unwrap_or_else(|| locator.default_api_base());
+        Ok(format!("{}/projects/{}", api_base, locator.project_id()))
+    }
+
+    /// Arborescence du dépôt (`recursive=true`) ou d'un seul répertoire
+    async fn list_tree(
+        &self,
+        repo_url: &str,
+        path: &str,
+        branch: &str,
+        recursive: bool,
+    ) -> Result<Vec<GitlabTreeEntry>, GithubAnalyzerError> {
+        let mut url = format!(
+            "{}/repository/tree?ref={}&per_page=100&recursive={}",
+            self.project_url(repo_url)?, encode_component(branch), recursive
+        );
+        if !path.is_empty() {
+            url = url + "&path=" + &encode_component(path);
+        }
+        self.rest.get_all(&url).await
+    }
+}
+
+impl RepositoryProvider for GitlabClient {
+    fn has_token(&self) -> bool {
+        self.rest.has_token()
+    }
+
+    fn api_requests(&self) -> u64 {
+        self.rest.api_requests()
+    }
+
+    fn rate_limit_waits(&self) -> u64 {
+        self.rest.rate_limit_waits()
+    }
+
+    fn get_repo_info<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<GithubRepoInfo, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let project: GitlabProject = self.rest.get_json(&self.project_url(repo_url)?).await?;
+            Ok(project.into())
+        })
+    }
+
+    /// L'API GitLab ne donne pas la taille des fichiers dans l'arborescence
+    fn get_repo_tree<'a>(
+        &'a self,
+        repo_url: &'a str,
+        branch: &'a str,
+    ) -> BoxFuture<'a, Result<GithubTree, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let entries = self.list_tree(repo_url, "", branch, true).await?;
+            Ok(GithubTree {
+                sha: String::new(),
+                tree: entries
+                    .into_iter()
+                    .map(|entry| GithubTreeEntry {
+                        path: entry.path,
+                        mode: entry.mode,
+                        entry_type: entry.entry_type,
+                        sha: entry.id,
+                        size: None,
+                    })
+                    .collect(),
+                truncated: false,
+            })
+        })
+    }
+
+    fn get_repo_contents<'a>(
+        &'a self,
+        repo_url: &'a str,
+        path: &'a str,
+        branch: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<GithubContent>, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let entries = self.list_tree(repo_url, path, branch, false).await?;
+            entries
+                .into_iter()
+                .map(|entry| {
+                    let content_type = match entry.entry_type.as_str() {
+                        "tree" => "dir",
+                        "blob" => "file",
+                        other => other,
+                    };
+                    Ok(GithubContent {
+                        url: self.contents_url(repo_url, &entry.path, branch)?,
+                        content_type: content_type.to_string(),
+                        name: entry.name,
+                        path: entry.path,
+                        sha: entry.id,
+                        size: 0,
+                        content: None,
+                        encoding: None,
+                        download_url: None,
+                    })
+                })
+                .collect()
+        })
+    }
+
+    /// URL du contenu brut (`/repository/files/<chemin encodé>/raw?ref=<branche>`)
+    fn contents_url(&self, repo_url: &str, path: &str, branch: &str) -> Result<String, GithubAnalyzerError> {
+        Ok(format!(
+            "{}/repository/files/{}/raw?ref={}",
+            self.project_url(repo_url)?, encode_component(path), encode_component(branch)
+        ))
+    }
+
+    fn get_file_content<'a>(&'a self, content_url: &'a str) -> BoxFuture<'a, Result<String, GithubAnalyzerError>> {
+        Box::pin(self.rest.get_text(content_url))
+    }
+
+    fn get_blob<'a>(&'a self, repo_url: &'a str, sha: &'a str) -> BoxFuture<'a, Result<String, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let url = format!("{}/repository/blobs/{}/raw", self.project_url(repo_url)?, sha);
+            self.rest.get_text(&url).await
+        })
+    }
+
+    /// GitLab n'expose pas de point d'accès équivalent à `/rate_limit`
+    fn get_rate_limit<'a>(
+        &'a self,
+        _repo_url: &'a str,
+    ) -> BoxFuture<'a, Result<Option<GithubRateLimitStatus>, GithubAnalyzerError>> {
+        Box::pin(async { Ok(None) })
+    }
+
+    fn get_tags<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<Vec<String>, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let url = format!("{}/repository/tags?per_page=100", self.project_url(repo_url)?);
+            let tags: Vec<GithubTag> = self.rest.get_json(&url).await?;
+            Ok(tags.into_iter().map(|tag| tag.name).collect())
+        })
+    }
+
+    /// GitLab donne des pourcentages : ils sont convertis en centièmes de pourcent,
+    /// seules les proportions comptant
+    fn get_languages<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<Vec<(String, u64)>, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let url = format!("{}/languages", self.project_url(repo_url)?);
+            let languages: HashMap<String, f64> = self.rest.get_json(&url).await?;
+            let mut languages: Vec<(String, u64)> = languages
+                .into_iter()
+                .map(|(language, percent)| (language, (percent * 100.0).round() as u64))
+                .collect();
+            languages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+            Ok(languages)
+        })
+    }
+
+    fn get_tarball<'a>(
+        &'a self,
+        repo_url: &'a str,
+        git_ref: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, GithubAnalyzerError>> {
+        Box::pin(async move {
+            let mut url = format!("{}/repository/archive.tar.gz", self.project_url(repo_url)?);
+            if let Some(git_ref) = git_ref {
+                url = url + "?sha=" + &encode_component(git_ref);
+            }
+            self.rest.get_bytes(&url).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const API: &str = "https://gitlab.example.com/api/v4";
+
+    fn client() -> GitlabClient {
+        GitlabClient::new(Ok(Client::new()), Some("glpat-test".to_string())).with_api_base(API)
+    }
+
+    fn locate(url: &str) -> RepoLocator {
+        url.parse().unwrap_or_else(|error| panic!("{}: {:?}", url, error))
+    }
+
+    #[test]
+    fn subgroup_paths_form_the_whole_namespace() {
+        for url in [
+            "https://gitlab.com/group/subgroup/project",
+            "https://gitlab.com/group/subgroup/project/",
+            "https://gitlab.com/group/subgroup/project.git",
+            "git@gitlab.com:group/subgroup/project.git",
+            "ssh://git@gitlab.com/group/subgroup/project.git",
+        ] {
+            let locator = locate(url);
+            assert_eq!(locator.provider, Provider::Gitlab, "{}", url);
+            assert_eq!((locator.owner.as_str(), locator.repo.as_str()), ("group/subgroup", "project"), "{}", url);
+            assert_eq!(locator.project_id(), "group%2Fsubgroup%2Fproject", "{}", url);
+            assert_eq!(locator.default_api_base(), "https://gitlab.com/api/v4", "{}", url);
+        }
+    }
+
+    #[test]
+    fn dash_separates_the_project_from_its_ref_and_subpath() {
+        let locator = locate("https://gitlab.com/a/b/c/d/project/-/tree/release/1.x/src/lib");
+        assert_eq!(locator.project_path(), "a/b/c/d/project");
+        assert_eq!(locator.git_ref.as_deref(), Some("release"));
+        assert_eq!(locator.subpath.as_deref(), Some("1.x/src/lib"));
+        assert_eq!(locator.output_dir_name(), "a_b_c_d_project");
+        assert_eq!(locator.to_string(), "https://gitlab.com/a/b/c/d/project/-/tree/release/1.x/src/lib");
+        assert_eq!(locator.blob_url("main"), "https://gitlab.com/a/b/c/d/project/-/blob/main");
+    }
+
+    #[test]
+    fn self_managed_hosts_need_the_provider_when_unrecognized() {
+        let url = "https://code.example.com/platform/tools/cli";
+        // Lue comme une URL GitHub, le troisième segment est refusé
+        assert!(url.parse::<RepoLocator>().is_err());
+        let locator = RepoLocator::parse_as(url, Some(Provider::Gitlab)).unwrap();
+        assert_eq!(locator.project_id(), "platform%2Ftools%2Fcli");
+        assert_eq!(locator.default_api_base(), "https://code.example.com/api/v4");
+    }
+
+    #[test]
+    fn project_and_file_urls_encode_every_component() {
+        let client = client();
+        let repo = "https://gitlab.com/group/sub.group/my-project";
+        assert_eq!(
+            client.project_url(repo).unwrap(),
+            format!("{}/projects/group%2Fsub.group%2Fmy-project", API)
+        );
+        assert_eq!(
+            client.contents_url(repo, "src/dir with space/mod+1.rs", "feature/login").unwrap(),
+            format!(
+                "{}/projects/group%2Fsub.group%2Fmy-project/repository/files/src%2Fdir%20with%20space%2Fmod%2B1.rs/raw?ref=feature%2Flogin",
+                API
+            )
+        );
+    }
+
+    #[test]
+    fn components_keep_only_unreserved_characters() {
+        assert_eq!(encode_component("a-b_c.d~e"), "a-b_c.d~e");
+        assert_eq!(encode_component("g/s/p"), "g%2Fs%2Fp");
+        assert_eq!(encode_component("é#?&%"), "%C3%A9%23%3F%26%25");
+        assert_eq!(encode_component(""), "");
+    }
+
+    #[test]
+    fn project_metadata_maps_to_the_repository_info() {
+        let project: GitlabProject = serde_json::from_value(serde_json::json!({
+            "path_with_namespace": "group/subgroup/project",
+            "default_branch": "develop",
+            "star_count": 7,
+        }))
+        .unwrap();
+        let info = GithubRepoInfo::from(project);
+        assert_eq!(info.full_name, "group/subgroup/project");
+        assert_eq!(info.default_branch, "develop");
+        assert_eq!(info.stargazers_count, 7);
+    }
+}
@@ -1 +1,9 @@
-pub mod client;
\ No newline at end of file
+pub mod auth;
+pub mod cache;
+pub mod client;
+pub mod gitea;
+pub mod gitlab;
+pub mod provider;
+pub(crate) mod rest;
+pub mod scheduler;
+pub mod token;
@@ -0,0 +1,203 @@
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+
+use crate::api::client::GithubClient;
+use crate::error::GithubAnalyzerError;
+use crate::types::github::{
+    GithubContent, GithubPullFile, GithubPullRequest, GithubRateLimitStatus, GithubRepoInfo, GithubTree,
+};
+
+/// Opérations d'une forge (GitHub, GitLab) utilisées par l'analyse et l'export.
+///
+/// Les réponses sont converties dans les types GitHub : l'analyse ne dépend pas
+/// de la forge d'où vient le contenu.
+pub trait RepositoryProvider: Send + Sync {
+    /// Indique si les requêtes sont authentifiées
+    fn has_token(&self) -> bool;
+
+    /// Nombre de requêtes envoyées à l'API
+    fn api_requests(&self) -> u64;
+
+    /// Nombre d'attentes dues à une limite de débit
+    fn rate_limit_waits(&self) -> u64;
+
+    /// Indique si plusieurs fichiers peuvent être récupérés en une requête
+    /// (`get_files_batch`)
+    fn batch_fetch_available(&self) -> bool {
+        false
+    }
+
+    /// Métadonnées du dépôt, dont sa branche par défaut
+    fn get_repo_info<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<GithubRepoInfo, GithubAnalyzerError>>;
+
+    /// Arborescence complète du dépôt à une référence
+    fn get_repo_tree<'a>(
+        &'a self,
+        repo_url: &'a str,
+        branch: &'a str,
+    ) -> BoxFuture<'a, Result<GithubTree, GithubAnalyzerError>>;
+
+    /// Entrées d'un répertoire du dépôt (`""` pour la racine)
+    fn get_repo_contents<'a>(
+        &'a self,
+        repo_url: &'a str,
+        path: &'a str,
+        branch: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<GithubContent>, GithubAnalyzerError>>;
+
+    /// URL à passer à `get_file_content` pour un fichier du dépôt
+    fn contents_url(&self, repo_url: &str, path: &str, branch: &str) -> Result<String, GithubAnalyzerError>;
+
+    /// Contenu texte d'un fichier à partir de son URL (`contents_url`)
+    fn get_file_content<'a>(&'a self, content_url: &'a str) -> BoxFuture<'a, Result<String, GithubAnalyzerError>>;
+
+    /// Contenu texte d'un blob par son SHA
+    fn get_blob<'a>(&'a self, repo_url: &'a str, sha: &'a str) -> BoxFuture<'a, Result<String, GithubAnalyzerError>>;
+
+    /// Contenus de plusieurs fichiers en lot ; `None` pour un fichier binaire
+    fn get_files_batch<'a>(
+        &'a self,
+        _repo_url: &'a str,
+        _git_ref: &'a str,
+        _paths: &'a [String],
+    ) -> BoxFuture<'a, Result<HashMap<String, Option<String>>, GithubAnalyzerError>> {
+        Box::pin(async { Err(GithubAnalyzerError::NetworkError("Batch fetch is not supported".to_string())) })
+    }
+
+    /// Quota restant de l'API ; `None` si la forge ne l'expose pas
+    fn get_rate_limit<'a>(
+        &'a self,
+        repo_url: &'a str,
+    ) -> BoxFuture<'a, Result<Option<GithubRateLimitStatus>, GithubAnalyzerError>>;
+
+    /// Noms des tags du dépôt
+    fn get_tags<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<Vec<String>, GithubAnalyzerError>>;
+
+    /// Répartition des langages, du plus au moins représenté
+    fn get_languages<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<Vec<(String, u64)>, GithubAnalyzerError>>;
+
+    /// Archive tar.gz du dépôt (branche par défaut si aucune référence)
+    fn get_tarball<'a>(
+        &'a self,
+        repo_url: &'a str,
+        git_ref: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, GithubAnalyzerError>>;
+
+    /// Titre, description et branches d'une pull request
+    fn get_pull_request<'a>(
+        &'a self,
+        _repo_url: &'a str,
+        _number: u64,
+    ) -> BoxFuture<'a, Result<GithubPullRequest, GithubAnalyzerError>> {
+        Box::pin(async { Err(GithubAnalyzerError::NetworkError("Pull requests are not supported by this provider".to_string())) })
+    }
+
+    /// Fichiers modifiés par une pull request
+    fn get_pull_request_files<'a>(
+        &'a self,
+        _repo_url: &'a str,
+        _number: u64,
+    ) -> BoxFuture<'a, Result<Vec<GithubPullFile>, GithubAnalyzerError>> {
+        Box::pin(async { Err(GithubAnalyzerError::NetworkError("Pull requests are not supported by this provider".to_string())) })
+    }
+}
+
+impl RepositoryProvider for GithubClient {
+    fn has_token(&self) -> bool {
+        GithubClient::has_token(self)
+    }
+
+    fn api_requests(&self) -> u64 {
+        GithubClient::api_requests(self)
+    }
+
+    fn rate_limit_waits(&self) -> u64 {
+        GithubClient::rate_limit_waits(self)
+    }
+
+    /// Avec un token, les fichiers passent par lots par l'API GraphQL
+    fn batch_fetch_available(&self) -> bool {
+        GithubClient::has_token(self)
+    }
+
+    fn get_repo_info<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<GithubRepoInfo, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_repo_info(self, repo_url))
+    }
+
+    fn get_repo_tree<'a>(
+        &'a self,
+        repo_url: &'a str,
+        branch: &'a str,
+    ) -> BoxFuture<'a, Result<GithubTree, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_repo_tree(self, repo_url, branch))
+    }
+
+    fn get_repo_contents<'a>(
+        &'a self,
+        repo_url: &'a str,
+        path: &'a str,
+        branch: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<GithubContent>, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_repo_contents(self, repo_url, path, branch))
+    }
+
+    fn contents_url(&self, repo_url: &str, path: &str, branch: &str) -> Result<String, GithubAnalyzerError> {
+        GithubClient::contents_url(self, repo_url, path, branch)
+    }
+
+    fn get_file_content<'a>(&'a self, content_url: &'a str) -> BoxFuture<'a, Result<String, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_file_content(self, content_url))
+    }
+
+    fn get_blob<'a>(&'a self, repo_url: &'a str, sha: &'a str) -> BoxFuture<'a, Result<String, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_blob(self, repo_url, sha))
+    }
+
+    fn get_files_batch<'a>(
+        &'a self,
+        repo_url: &'a str,
+        git_ref: &'a str,
+        paths: &'a [String],
+    ) -> BoxFuture<'a, Result<HashMap<String, Option<String>>, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_files_graphql(self, repo_url, git_ref, paths))
+    }
+
+    fn get_rate_limit<'a>(
+        &'a self,
+        repo_url: &'a str,
+    ) -> BoxFuture<'a, Result<Option<GithubRateLimitStatus>, GithubAnalyzerError>> {
+        Box::pin(async move { GithubClient::get_rate_limit(self, repo_url).await.map(Some) })
+    }
+
+    fn get_tags<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<Vec<String>, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_tags(self, repo_url))
+    }
+
+    fn get_languages<'a>(&'a self, repo_url: &'a str) -> BoxFuture<'a, Result<Vec<(String, u64)>, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_languages(self, repo_url))
+    }
+
+    fn get_tarball<'a>(
+        &'a self,
+        repo_url: &'a str,
+        git_ref: Option<&'a str>,
+    ) -> BoxFuture<'a, Result<Vec<u8>, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_tarball(self, repo_url, git_ref))
+    }
+
+    fn get_pull_request<'a>(
+        &'a self,
+        repo_url: &'a str,
+        number: u64,
+    ) -> BoxFuture<'a, Result<GithubPullRequest, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_pull_request(self, repo_url, number))
+    }
+
+    fn get_pull_request_files<'a>(
+        &'a self,
+        repo_url: &'a str,
+        number: u64,
+    ) -> BoxFuture<'a, Result<Vec<GithubPullFile>, GithubAnalyzerError>> {
+        Box::pin(GithubClient::get_pull_request_files(self, repo_url, number))
+    }
+}
@@ -0,0 +1,191 @@
+use reqwest::{Client, StatusCode, header};
+use serde::de::DeserializeOwned;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokio::time::{sleep, Duration};
+
+use crate::api::client::{client_error, decode_text, network_error, next_page_url, Forbidden, DEFAULT_MAX_FILE_SIZE};
+use crate::error::GithubAnalyzerError;
+
+/// Nombre maximal de nouvelles tentatives d'une requête
+const MAX_RETRIES: u32 = 3;
+
+/// Requêtes REST communes aux forges autres que GitHub (GitLab, Gitea) :
+/// authentification par en-tête, nouvelles tentatives, pagination et comptage
+pub(crate) struct RestClient {
+    client: Client,
+    /// Configuration réseau invalide (proxy, ...) : renvoyée en `NetworkError` à chaque requête
+    setup_error: Option<String>,
+    /// Nom de la forge dans les messages d'erreur
+    forge: &'static str,
+    /// En-tête portant le token (`PRIVATE-TOKEN: <token>`, `Authorization: token <token>`)
+    auth_header: Option<(&'static str, String)>,
+    max_file_size: usize,
+    requests: AtomicU64,
+    rate_limit_waits: AtomicU64,
+}
+
+impl RestClient {
+    pub(crate) fn new(
+        http: Result<Client, String>,
+        forge: &'static str,
+        auth_header: Option<(&'static str, String)>,
+    ) -> Self {
+        let (client, setup_error) = match http {
+            Ok(client) => (client, None),
+            Err(message) => {
+                println!("Warning: {}", message);
+                (Client::new(), Some(message))
+            },
+        };
+        Self {
+            client,
+            setup_error,
+            forge,
+            auth_header,
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+            requests: AtomicU64::new(0),
+            rate_limit_waits: AtomicU64::new(0),
+        }
+    }
+
+    pub(crate) fn set_max_file_size(&mut self, max_file_size: usize) {
+        self.max_file_size = max_file_size;
+    }
+
+    pub(crate) fn has_token(&self) -> bool {
+        self.auth_header.is_some()
+    }
+
+    pub(crate) fn api_requests(&self) -> u64 {
+        self.requests.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn rate_limit_waits(&self) -> u64 {
+        self.rate_limit_waits.load(Ordering::Relaxed)
+    }
+
+    fn headers(&self) -> Result<header::HeaderMap, GithubAnalyzerError> {
+        if let Some(message) = &self.setup_error {
+            return Err(GithubAnalyzerError::NetworkError(message.clone()));
+        }
+        let mut headers = header::HeaderMap::new();
+        if let Some((name, value)) = &self.auth_header {
+            let value = header::HeaderValue::from_str(value)
+                .map_err(|e| GithubAnalyzerError::AuthError(format!("Invalid token format: {}", e)))?;
+            headers.insert(*name, value);
+        }
+        Ok(headers)
+    }
+
+    /// Envoie une requête GET, réessayée sur les erreurs réseau, les réponses 5xx
+    /// et les limites de débit annoncées par `Retry-After`
+    pub(crate) async fn send(&self, url: &str) -> Result<reqwest::Response, GithubAnalyzerError> {
+        let mut last_error = None;
+
+        for attempt in 0..=MAX_RETRIES {
+            if attempt > 0 {
+                let wait_time = 2u64.pow(attempt);
+                println!("Request failed, retrying in {} seconds... ({}/{})", wait_time, attempt, MAX_RETRIES);
+                sleep(Duration::from_secs(wait_time)).await;
+            }
+
+            self.requests.fetch_add(1, Ordering::Relaxed);
+            let response = match self.client.get(url).headers(self.headers()?).send().await {
+                Ok(response) => response,
+                Err(e) => {
+                    last_error = Some(network_error(e, url));
+                    continue;
+                },
+            };
+            match response.status() {
+                status if status.is_success() => return Ok(response),
+                StatusCode::UNAUTHORIZED | StatusCode::NOT_FOUND => return Err(client_error(response, url).await),
+                StatusCode::FORBIDDEN | StatusCode::TOO_MANY_REQUESTS => {
+                    match Forbidden::classify(response).await {
+                        Forbidden::SecondaryLimit(wait) if attempt < MAX_RETRIES => {
+                            println!("Rate limit exceeded. Waiting {} seconds...", wait);
+                            self.rate_limit_waits.fetch_add(1, Ordering::Relaxed);
+                            sleep(Duration::from_secs(wait)).await;
+                        },
+                        forbidden => return Err(forbidden.into_error(url)),
+                    }
+                },
+                status => {
+                    last_error = Some(GithubAnalyzerError::NetworkError(
+                        format!("{} API returned status {}: {}", self.forge, status, url)
+                    ));
+                },
+            }
+        }
+
+        Err(last_error.unwrap_or_else(||
+            GithubAnalyzerError::NetworkError("Maximum retries exceeded".to_string())
+        ))
+    }
+
+    /// Page JSON et URL de la page suivante (`Link: rel="next"`)
+    pub(crate) async fn get_page<T: DeserializeOwned>(&self, url: &str) -> Result<(T, Option<String>), GithubAnalyzerError> {
+        let response = self.send(url).await?;
+        let next_page = response.headers()
+            .get(header::LINK)
+            .and_then(|h| h.to_str().ok())
+            .and_then(next_page_url);
+        let body = response.json().await
+            .map_err(|e| GithubAnalyzerError::ParseError(format!("{} ({})", e, url)))?;
+        Ok((body, next_page))
+    }
+
+    /// Corps JSON d'une réponse
+    pub(crate) async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, GithubAnalyzerError> {
+        self.get_page(url).await.map(|(body, _)| body)
+    }
+
+    /// Toutes les pages d'une liste
+    pub(crate) async fn get_all<T: DeserializeOwned>(&self, url: &str) -> Result<Vec<T>, GithubAnalyzerError> {
+        let (mut items, mut next_page) = self.get_page::<Vec<T>>(url).await?;
+        while let Some(page_url) = next_page {
+            let (page, next) = self.get_page::<Vec<T>>(&page_url).await?;
+            items.extend(page);
+            next_page = next;
+        }
+        Ok(items)
+    }
+
+    /// Texte brut d'une réponse, dans la limite de la taille maximale configurée
+    pub(crate) async fn get_text(&self, url: &str) -> Result<String, GithubAnalyzerError> {
+        let mut response = self.send(url).await?;
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await.map_err(|e| network_error(e, url))? {
+            if bytes.len() + chunk.len() > self.max_file_size {
+                return Err(GithubAnalyzerError::ParseError(format!(
+                    "Raw content exceeds {} bytes: {}", self.max_file_size, url
+                )));
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        decode_text(bytes)
+    }
+
+    /// Corps binaire complet d'une réponse (archive)
+    pub(crate) async fn get_bytes(&self, url: &str) -> Result<Vec<u8>, GithubAnalyzerError> {
+        let response = self.send(url).await?;
+        response.bytes().await
+            .map(|bytes| bytes.to_vec())
+            .map_err(|e| network_error(e, url))
+    }
+}
+
+/// Token d'une forge : celui passé explicitement, sinon la variable d'environnement
+/// donnée ; la source retenue est affichée
+pub(crate) fn discover_token(explicit: Option<String>, env_var: &str, forge: &str) -> Option<String> {
+    let token = explicit
+        .or_else(|| std::env::var(env_var).ok())
+        .map(|token| token.trim().to_string())
+        .filter(|token| !token.is_empty());
+    if token.is_some() {
+        println!("Using authenticated {} API requests", forge);
+    } else {
+        println!("Warning: Using unauthenticated {} API requests. Set {} to access private repositories.", forge, env_var);
+    }
+    token
+}
@@ -0,0 +1,205 @@
+use futures::stream::{FuturesUnordered, StreamExt};
+use std::collections::BTreeMap;
+use std::future::Future;
+
+use crate::types::analysis::RunMetrics;
+
+/// Budget par défaut des tailles déclarées en vol (≈ 8 Mo)
+pub const DEFAULT_BYTE_BUDGET: u64 = 8 * 1024 * 1024;
+/// Nombre maximal de requêtes simultanées par défaut
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 8;
+
+/// Ordonnanceur de téléchargements tenant compte de la taille des fichiers
+///
+/// La somme des tailles déclarées des requêtes en vol reste sous `byte_budget`,
+/// dans la limite de `max_in_flight` requêtes. Un fichier plus gros que le budget
+/// est téléchargé seul.
+#[derive(Debug, Clone, Copy)]
+pub struct SizeAwareScheduler {
+    pub byte_budget: u64,
+    pub max_in_flight: usize,
+}
+
+impl Default for SizeAwareScheduler {
+    fn default() -> Self {
+        Self::new(DEFAULT_BYTE_BUDGET, DEFAULT_MAX_IN_FLIGHT)
+    }
+}
+
+impl SizeAwareScheduler {
+    pub fn new(byte_budget: u64, max_in_flight: usize) -> Self {
+        Self {
+            byte_budget,
+            max_in_flight: max_in_flight.max(1),
+        }
+    }
+
+    /// Indique si une requête de `size` octets peut démarrer
+    pub fn admits(&self, in_flight: usize, in_flight_bytes: u64, size: u64) -> bool {
+        in_flight == 0
+            || (in_flight < self.max_in_flight && in_flight_bytes + size <= self.byte_budget)
+    }
+
+    /// Exécute `fetch` sur chaque élément et transmet les résultats dans l'ordre
+    /// d'origine à `on_result`
+    ///
+    /// Un résultat arrivé en avance reste compté dans le budget jusqu'à sa
+    /// transmission : la mémoire retenue par les résultats en attente est bornée
+    /// comme celle des requêtes en vol.
+    pub async fn run<T, R, F, Fut>(
+        &self,
+        items: Vec<(T, u64)>,
+        fetch: F,
+        mut on_result: impl FnMut(R),
+        metrics: &mut RunMetrics,
+    ) where
+        F: Fn(T) -> Fut,
+        Fut: Future<Output = R>,
+    {
+        let mut pending = items.into_iter().enumerate().peekable();
+        let mut in_flight = FuturesUnordered::new();
+        // Octets des requêtes en vol et des résultats pas encore transmis
+        let mut held_bytes = 0u64;
+        let mut ready: BTreeMap<usize, (u64, R)> = BTreeMap::new();
+        let mut next_index = 0;
+
+        loop {
+            // Démarre autant de requêtes que le budget le permet
+            while let Some((_, (_, size))) = pending.peek() {
+                if !self.admits(in_flight.len(), held_bytes, *size) {
+                    break;
+                }
+                let (index, (item, size)) = pending.next().unwrap();
+                held_bytes += size;
+                metrics.fetch_requests += 1;
+                metrics.peak_in_flight_bytes = metrics.peak_in_flight_bytes.max(held_bytes);
+                metrics.peak_in_flight_requests =
+                    metrics.peak_in_flight_requests.max(in_flight.len() as u64 + 1);
+
+                let future = fetch(item);
+                in_flight.push(async move { (index, size, future.await) });
+            }
+
+            let Some((index, size, result)) = in_flight.next().await else {
+                break;
+            };
+            ready.insert(index, (size, result));
+
+            // Restitue les résultats dans l'ordre d'origine et libère leur budget
+            while let Some((size, result)) = ready.remove(&next_index) {
+                held_bytes -= size;
+                on_result(result);
+                next_index += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use std::time::Duration;
+
+    /// Durée de téléchargement simulée : les premiers éléments sont les plus lents,
+    /// pour que les résultats arrivent dans le désordre
+    fn latency(index: usize, count: usize) -> Duration {
+        Duration::from_millis(((count - index) % 7) as u64 * 3)
+    }
+
+    /// Lance l'ordonnanceur sur des tailles synthétiques et vérifie à chaque étape que
+    /// les octets retenus (en vol ou en attente de transmission) respectent le budget
+    async fn run_sizes(scheduler: SizeAwareScheduler, sizes: &[u64]) -> (Vec<usize>, RunMetrics) {
+        let held = Cell::new(0u64);
+        let in_flight = Cell::new(0usize);
+        let mut delivered = Vec::new();
+        let mut metrics = RunMetrics::default();
+        let items = sizes.iter().copied().enumerate().map(|(index, size)| ((index, size), size)).collect();
+        let count = sizes.len();
+
+        scheduler
+            .run(
+                items,
+                |(index, size)| {
+                    held.set(held.get() + size);
+                    in_flight.set(in_flight.get() + 1);
+                    assert!(
+                        held.get() <= scheduler.byte_budget || held.get() == size,
+                        "{} bytes held with a budget of {}",
+                        held.get(),
+                        scheduler.byte_budget
+                    );
+                    assert!(in_flight.get() <= scheduler.max_in_flight);
+                    let in_flight = &in_flight;
+                    async move {
+                        tokio::time::sleep(latency(index, count)).await;
+                        in_flight.set(in_flight.get() - 1);
+                        (index, size)
+                    }
+                },
+                |(index, size)| {
+                    held.set(held.get() - size);
+                    delivered.push(index);
+                },
+                &mut metrics,
+            )
+            .await;
+
+        (delivered, metrics)
+    }
+
+    #[test]
+    fn admits_within_budget_and_request_limit() {
+        let scheduler = SizeAwareScheduler::new(1_000, 2);
+
+        assert!(scheduler.admits(0, 0, 5_000), "an oversized file runs alone");
+        assert!(scheduler.admits(1, 400, 600));
+        assert!(!scheduler.admits(1, 400, 601));
+        assert!(!scheduler.admits(2, 0, 1));
+        assert_eq!(SizeAwareScheduler::new(1_000, 0).max_in_flight, 1);
+    }
+
+    #[tokio::test]
+    async fn uniform_small_files_fill_the_request_limit() {
+        let scheduler = SizeAwareScheduler::new(1_000_000, 4);
+        let (delivered, metrics) = run_sizes(scheduler, &[1_000; 40]).await;
+
+        assert_eq!(delivered, (0..40).collect::<Vec<_>>());
+        assert_eq!(metrics.fetch_requests, 40);
+        assert_eq!(metrics.peak_in_flight_requests, 4);
+        assert!(metrics.peak_in_flight_bytes <= 1_000_000);
+    }
+
+    #[tokio::test]
+    async fn large_files_are_limited_by_the_byte_budget() {
+        let scheduler = SizeAwareScheduler::new(2_000_000, 16);
+        let (delivered, metrics) = run_sizes(scheduler, &[900_000; 16]).await;
+
+        assert_eq!(delivered, (0..16).collect::<Vec<_>>());
+        assert_eq!(metrics.peak_in_flight_requests, 2);
+        assert_eq!(metrics.peak_in_flight_bytes, 1_800_000);
+    }
+
+    #[tokio::test]
+    async fn oversized_file_is_fetched_alone() {
+        let scheduler = SizeAwareScheduler::new(1_000, 8);
+        let (delivered, metrics) = run_sizes(scheduler, &[100, 5_000, 100]).await;
+
+        assert_eq!(delivered, vec![0, 1, 2]);
+        assert_eq!(metrics.peak_in_flight_bytes, 5_000);
+    }
+
+    #[tokio::test]
+    async fn early_results_stay_within_the_budget_until_delivered() {
+        // Distribution mêlant petits et gros fichiers ; le premier, lent, retient la
+        // transmission de tous les suivants
+        let sizes: Vec<u64> = (0..200u64).map(|index| [200, 3_000, 50, 40_000, 700][index as usize % 5] + index).collect();
+        let scheduler = SizeAwareScheduler::new(60_000, 8);
+        let (delivered, metrics) = run_sizes(scheduler, &sizes).await;
+
+        assert_eq!(delivered, (0..200).collect::<Vec<_>>());
+        assert_eq!(metrics.fetch_requests, 200);
+        assert!(metrics.peak_in_flight_bytes <= 60_000);
+        assert!(metrics.peak_in_flight_requests <= 8);
+    }
+}
@@ -0,0 +1,216 @@
+use std::env;
+use std::ffi::OsString;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::error::GithubAnalyzerError;
+
+/// Origine du token personnel utilisé pour l'API
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    /// Option `--token`
+    Flag,
+    /// Variable `GITHUB_TOKEN`
+    Env,
+    /// Fichier désigné par `GITHUB_TOKEN_FILE`
+    File(PathBuf),
+    /// CLI `gh` (`gh auth token` ou `hosts.yml`)
+    GhCli,
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenSource::Flag => write!(f, "--token"),
+            TokenSource::Env => write!(f, "GITHUB_TOKEN"),
+            TokenSource::File(path) => write!(f, "GITHUB_TOKEN_FILE ({})", path.display()),
+            TokenSource::GhCli => write!(f, "gh CLI"),
+        }
+    }
+}
+
+/// Token personnel et son origine
+#[derive(Debug, Clone)]
+pub struct DiscoveredToken {
+    pub token: String,
+    pub source: TokenSource,
+}
+
+/// Recherche un token configuré explicitement, dans l'ordre : option `--token`,
+/// `GITHUB_TOKEN`, puis fichier `GITHUB_TOKEN_FILE`.
+///
+/// Une source présente mais invalide est une erreur : on ne passe pas
+/// silencieusement à la suivante.
+pub fn discover(flag: Option<&str>) -> Result<Option<DiscoveredToken>, GithubAnalyzerError> {
+    discover_with(flag, |name| env::var_os(name))
+}
+
+/// `discover` avec les variables d'environnement lues par `var`
+fn discover_with(
+    flag: Option<&str>,
+    var: impl Fn(&str) -> Option<OsString>,
+) -> Result<Option<DiscoveredToken>, GithubAnalyzerError> {
+    if let Some(token) = flag {
+        return validate(token, TokenSource::Flag).map(Some);
+    }
+    if let Some(token) = var("GITHUB_TOKEN").filter(|token| !token.is_empty()) {
+        let token = token.into_string().map_err(|_| {
+            GithubAnalyzerError::AuthError(format!("Malformed token from {}: not valid UTF-8", TokenSource::Env))
+        })?;
+        return validate(&token, TokenSource::Env).map(Some);
+    }
+    if let Some(path) = var("GITHUB_TOKEN_FILE").map(PathBuf::from) {
+        let token = fs::read_to_string(&path).map_err(|e| GithubAnalyzerError::AuthError(
+            format!("Cannot read GITHUB_TOKEN_FILE {}: {}", path.display(), e)
+        ))?;
+        return validate(&token, TokenSource::File(path)).map(Some);
+    }
+    Ok(None)
+}
+
+/// Token du CLI `gh` déjà authentifié pour `host` (`github.com` ou l'hôte d'un
+/// GitHub Enterprise Server), dernier recours avant l'accès anonyme
+pub fn discover_gh_cli(host: &str) -> Result<Option<DiscoveredToken>, GithubAnalyzerError> {
+    match gh_token(host) {
+        Some(token) => validate(&token, TokenSource::GhCli).map(Some),
+        None => Ok(None),
+    }
+}
+
+/// Vérifie qu'un token peut figurer dans l'en-tête `Authorization`
+pub fn validate(token: &str, source: TokenSource) -> Result<DiscoveredToken, GithubAnalyzerError> {
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(GithubAnalyzerError::AuthError(format!("Empty token from {}", source)));
+    }
+    if !token.chars().all(|c| c.is_ascii_graphic()) {
+        return Err(GithubAnalyzerError::AuthError(format!(
+            "Malformed token from {}: only printable ASCII without spaces is allowed", source
+        )));
+    }
+    Ok(DiscoveredToken { token: token.to_string(), source })
+}
+
+/// Token du CLI `gh` pour un hôte : `gh auth token`, sinon le fichier `hosts.yml`
+fn gh_token(host: &str) -> Option<String> {
+    let output = Command::new("gh").args(["auth", "token", "--hostname", host]).output().ok();
+    if let Some(output) = output.filter(|output| output.status.success()) {
+        let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        if !token.is_empty() {
+            return Some(token);
+        }
+    }
+
+    let config_dir = env::var_os("GH_CONFIG_DIR")
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("XDG_CONFIG_HOME").map(|dir| Path::new(&dir).join("gh")))
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config").join("gh")))?;
+    let hosts = fs::read_to_string(config_dir.join("hosts.yml")).ok()?;
+    hosts_yml_token(&hosts, host)
+}
+
+/// Lit `oauth_token` sous l'hôte donné dans le `hosts.yml` de `gh`
+fn hosts_yml_token(hosts: &str, host: &str) -> Option<String> {
+    let mut in_host = false;
+    for line in hosts.lines() {
+        if line.trim().is_empty() || line.trim_start().starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t']) {
+            let key = line.split(" #").next().unwrap_or_default().trim_end().trim_end_matches(':');
+            in_host = yaml_scalar(key) == host;
+        } else if in_host {
+            if let Some(value) = line.trim().strip_prefix("oauth_token:").map(yaml_scalar) {
+                if !value.is_empty() {
+                    return Some(value.to_string());
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Valeur YAML scalaire : contenu des guillemets, sinon texte avant un commentaire ` #`
+fn yaml_scalar(raw: &str) -> &str {
+    let raw = raw.trim();
+    for quote in ['"', '\''] {
+        if let Some(quoted) = raw.strip_prefix(quote) {
+            return quoted.split(quote).next().unwrap_or_default();
+        }
+    }
+    raw.split(" #").next().unwrap_or_default().trim_end()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn discover_in(flag: Option<&str>, vars: &[(&str, &str)]) -> Result<Option<DiscoveredToken>, GithubAnalyzerError> {
+        let vars: HashMap<&str, &str> = vars.iter().copied().collect();
+        discover_with(flag, |name| vars.get(name).map(OsString::from))
+    }
+
+    #[test]
+    fn discovery_prefers_flag_then_env_then_file() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        std::io::Write::write_all(&mut file, b"ghp_from_file\n").unwrap();
+        let path = file.path().to_str().unwrap();
+        let all = [("GITHUB_TOKEN", "ghp_from_env"), ("GITHUB_TOKEN_FILE", path)];
+
+        let found = discover_in(Some("ghp_from_flag"), &all).unwrap().unwrap();
+        assert_eq!((found.token.as_str(), found.source), ("ghp_from_flag", TokenSource::Flag));
+
+        let found = discover_in(None, &all).unwrap().unwrap();
+        assert_eq!((found.token.as_str(), found.source), ("ghp_from_env", TokenSource::Env));
+
+        let found = discover_in(None, &[("GITHUB_TOKEN", ""), ("GITHUB_TOKEN_FILE", path)]).unwrap().unwrap();
+        assert_eq!(found.token, "ghp_from_file");
+        assert_eq!(found.source, TokenSource::File(file.path().to_path_buf()));
+
+        assert!(discover_in(None, &[]).unwrap().is_none());
+    }
+
+    #[test]
+    fn an_unreadable_token_file_is_an_error() {
+        let result = discover_in(None, &[("GITHUB_TOKEN_FILE", "/nonexistent/token")]);
+        assert!(matches!(result, Err(GithubAnalyzerError::AuthError(message)) if message.contains("/nonexistent/token")));
+    }
+
+    #[test]
+    fn tokens_with_inner_whitespace_are_rejected() {
+        for token in ["ghp_abc def", "ghp_abc\ndef", "ghp_abc\tdef", "   ", ""] {
+            let result = discover_in(Some(token), &[]);
+            assert!(matches!(result, Err(GithubAnalyzerError::AuthError(_))), "{:?}", token);
+        }
+        let result = discover_in(None, &[("GITHUB_TOKEN", "ghp_abc\ndef")]);
+        assert!(matches!(result, Err(GithubAnalyzerError::AuthError(message)) if message.contains("GITHUB_TOKEN")));
+
+        let found = discover_in(Some("  ghp_abc\n"), &[]).unwrap().unwrap();
+        assert_eq!(found.token, "ghp_abc");
+    }
+
+    #[test]
+    fn hosts_yml_token_reads_the_requested_host() {
+        let hosts = r#"# gh hosts
+github.com:
+    user: octocat
+    oauth_token: "gho_public" # main account
+    git_protocol: https
+"ghe.example.com": # enterprise
+    # oauth_token: gho_commented
+    oauth_token: 'gho_enterprise'
+other.example.com:
+    oauth_token: gho_other # trailing comment
+empty.example.com:
+    oauth_token: ""
+"#;
+        assert_eq!(hosts_yml_token(hosts, "github.com").as_deref(), Some("gho_public"));
+        assert_eq!(hosts_yml_token(hosts, "ghe.example.com").as_deref(), Some("gho_enterprise"));
+        assert_eq!(hosts_yml_token(hosts, "other.example.com").as_deref(), Some("gho_other"));
+        assert_eq!(hosts_yml_token(hosts, "empty.example.com"), None);
+        assert_eq!(hosts_yml_token(hosts, "missing.example.com"), None);
+    }
+}
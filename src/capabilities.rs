@@ -0,0 +1,111 @@
+use std::collections::BTreeMap;
+
+/// Sous-système optionnel de l'analyseur, activé par une feature cargo
+#[derive(Debug, Clone, Copy)]
+pub struct Capability {
+    pub name: &'static str,
+    pub cargo_feature: &'static str,
+    pub description: &'static str,
+    pub enabled: bool,
+}
+
+/// Registre des sous-systèmes optionnels compilés (ou non) dans ce binaire
+pub const CAPABILITIES: [Capability; 4] = [
+    Capability {
+        name: "archive",
+        cargo_feature: "archive",
+        description: "Tarball and zip archive analysis",
+        enabled: cfg!(feature = "archive"),
+    },
+    Capability {
+        name: "github-app",
+        cargo_feature: "github-app",
+        description: "GitHub App installation token authentication",
+        enabled: cfg!(feature = "github-app"),
+    },
+    Capability {
+        name: "git",
+        cargo_feature: "git",
+        description: "Clone-based analysis of git repositories",
+        enabled: cfg!(feature = "git"),
+    },
+    Capability {
+        name: "syn",
+        cargo_feature: "syn-backend",
+        description: "syn-based Rust analysis backend",
+        enabled: cfg!(feature = "syn-backend"),
+    },
+];
+
+/// Recherche une capacité par son nom
+pub fn capability(name: &str) -> Option<&'static Capability> {
+    CAPABILITIES.iter().find(|capability| capability.name == name)
+}
+
+/// Vérifie qu'un flag dépendant d'une capacité peut être utilisé
+pub fn require(flag: &str, name: &str) -> Result<(), String> {
+    match capability(name) {
+        Some(capability) if capability.enabled => Ok(()),
+        Some(capability) => Err(format!(
+            "{} requires the `{}` subsystem, which this binary was built without \
+            (rebuild with `cargo build --features {}`)",
+            flag, capability.name, capability.cargo_feature
+        )),
+        None => Err(format!("{} requires the unknown subsystem `{}`", flag, name)),
+    }
+}
+
+/// État de chaque capacité, par nom
+pub fn enabled() -> BTreeMap<String, bool> {
+    CAPABILITIES.iter().map(|capability| (capability.name.to_string(), capability.enabled)).collect()
+}
+
+/// Rendu console de la liste des capacités
+pub fn render() -> String {
+    let mut output = String::from("Optional subsystems:\n");
+    for capability in &CAPABILITIES {
+        output.push_str(&format!(
+            "  [{}] {:<10} {} (feature `{}`)\n",
+            if capability.enabled { "x" } else { " " },
+            capability.name,
+            capability.description,
+            capability.cargo_feature
+        ));
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn registry_reflects_the_compiled_features() {
+        let enabled = enabled();
+        assert_eq!(enabled.len(), CAPABILITIES.len());
+        assert_eq!(enabled["archive"], cfg!(feature = "archive"));
+        assert_eq!(enabled["github-app"], cfg!(feature = "github-app"));
+        assert_eq!(enabled["git"], cfg!(feature = "git"));
+        assert_eq!(enabled["syn"], cfg!(feature = "syn-backend"));
+    }
+
+    #[test]
+    fn missing_capability_names_the_cargo_feature() {
+        let result = require("--clone", "git");
+        assert_eq!(result.is_ok(), cfg!(feature = "git"));
+        if let Err(message) = result {
+            assert!(message.starts_with("--clone requires the `git` subsystem"));
+            assert!(message.contains("cargo build --features git"));
+        }
+        assert_eq!(require("--x", "tui").unwrap_err(), "--x requires the unknown subsystem `tui`");
+    }
+
+    #[test]
+    fn render_marks_enabled_capabilities() {
+        let rendered = render();
+        for capability in &CAPABILITIES {
+            let mark = if capability.enabled { "[x]" } else { "[ ]" };
+            assert!(rendered.lines().any(|line| line.contains(mark) && line.contains(capability.description)));
+        }
+    }
+}
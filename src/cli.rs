@@ -0,0 +1,359 @@
+use crate::analysis::options::{AnalysisOptions, BudgetPolicy};
+use crate::capabilities;
+use std::path::{Path, PathBuf};
+
+use crate::export::{BundleMode, ExportOptions, NeverExportPolicy, NEVER_EXPORT_FILE};
+use crate::types::locator::Provider;
+
+/// Options de la ligne de commande
+#[derive(Debug, Clone, Default)]
+pub struct CliOptions {
+    pub repos: Vec<String>,
+    /// Organisations (ou utilisateurs) dont tous les dépôts sont analysés
+    pub orgs: Vec<String>,
+    /// Inclut les dépôts archivés d'une organisation
+    pub include_archived: bool,
+    /// Inclut les forks d'une organisation
+    pub include_forks: bool,
+    /// Traite chaque argument comme un répertoire local
+    pub local: bool,
+    /// Branches comparées (`--compare base..head`)
+    pub compare: Option<(String, String)>,
+    pub analysis: AnalysisOptions,
+    pub export: ExportOptions,
+    /// Affiche les sous-systèmes optionnels compilés puis quitte
+    pub show_capabilities: bool,
+    /// Remplace les symboles Unicode par de l'ASCII dans la sortie console
+    pub ascii: bool,
+    /// Archive locale (.tar.gz ou .zip) à analyser au lieu d'un dépôt distant
+    pub from_archive: Option<String>,
+    /// Emplacement de `run_status.json` (défaut : `output/run_status.json`)
+    pub status_file: Option<String>,
+    /// Affiche aussi le rapport d'exécution en JSON sur la sortie standard
+    pub summary_json: bool,
+    /// Sous-commande `migrate` : met à niveau un analysis.json existant
+    pub migrate: Option<MigrateArgs>,
+}
+
+impl CliOptions {
+    /// Chemin du répertoire local désigné par un argument, s'il en désigne un :
+    /// `file://`, `--local` ou répertoire existant
+    pub fn local_path(&self, repo: &str) -> Option<PathBuf> {
+        match repo.strip_prefix("file://") {
+            Some(path) => Some(PathBuf::from(path)),
+            None if self.local || Path::new(repo).is_dir() => Some(PathBuf::from(repo)),
+            None => None,
+        }
+    }
+
+    /// Chemin de l'archive désignée par un argument, s'il s'agit d'un fichier
+    /// `.zip`, `.tar.gz`, `.tgz` ou `.tar` existant
+    pub fn archive_path(&self, repo: &str) -> Option<PathBuf> {
+        let path = Path::new(repo);
+        let is_archive = [".zip", ".tar.gz", ".tgz", ".tar"]
+            .iter()
+            .any(|ext| repo.to_ascii_lowercase().ends_with(ext));
+        (is_archive && path.is_file()).then(|| path.to_path_buf())
+    }
+
+    /// Vérifie que les sous-systèmes demandés par les options sont compilés
+    pub fn require_capabilities(&self) -> Result<(), String> {
+        let required = [
+            ("--from-archive", self.from_archive.is_some(), "archive"),
+            ("An archive input", self.repos.iter().any(|repo| self.archive_path(repo).is_some()), "archive"),
+            ("--tarball", self.analysis.tarball, "archive"),
+            ("--clone", self.analysis.clone, "git"),
+        ];
+        required
+            .into_iter()
+            .filter(|(_, used, _)| *used)
+            .try_for_each(|(flag, _, name)| capabilities::require(flag, name))
+    }
+}
+
+/// Arguments de la sous-commande `migrate <input> [output]`
+#[derive(Debug, Clone)]
+pub struct MigrateArgs {
+    pub input: String,
+    /// Fichier de sortie ; l'entrée est réécrite sur place si absent
+    pub output: Option<String>,
+}
+
+/// Texte d'aide affiché en cas d'arguments invalides
+pub fn usage(program: &str) -> String {
+    format!(
+        "Usage: {0} [options] <repo_url1> [repo_url2] ...\n       \
+        {0} [options] --org <name> (or org:<name>)\n       \
+        {0} migrate <analysis.json> [output.json]\n\n\
+        Options:\n  \
+        --ascii                Use ASCII instead of Unicode symbols in console output\n  \
+        --capabilities         List the optional subsystems compiled into this binary\n  \
+        --org <name>           Analyze every repository of this organization or user (repeatable)\n  \
+        --include-archived     Also analyze archived repositories of an organization\n  \
+        --include-forks        Also analyze forks in an organization\n  \
+        --local                Treat every argument as a local directory (existing directories are detected anyway)\n  \
+        --ref <ref>            Analyze this branch, tag or commit SHA (default: the repository default branch)\n  \
+        --compare <a>..<b>     Analyze both refs and write branch_comparison.json (types, functions, files)\n  \
+        --path <dir>           Analyze only this subdirectory, e.g. services/billing (or .../tree/<ref>/<dir>)\n  \
+        --offline              Skip optional API calls (tag listing)\n  \
+        --tarball              Download the repository as one tarball instead of one call per file\n  \
+        --clone                Analyze a shallow git clone (SSH or HTTPS) instead of using the API (feature `git`)\n  \
+        --keep-clone           Keep the temporary clone directory after the analysis\n  \
+        --no-cache             Disable the on-disk HTTP and file content caches\n  \
+        --no-default-ignores   Also analyze vendored directories and .gitignore'd paths\n  \
+        --recurse-submodules   Also analyze submodules hosted on the same GitHub host\n  \
+        --include <glob>       Analyze only matching repository paths, e.g. 'src/**/*.rs' (repeatable)\n  \
+        --exclude <glob>       Skip matching repository paths, e.g. '**/generated/**' (repeatable, wins over --include)\n  \
+        --cache-dir <path>     Cache location (default: ~/.cache/repo_analyzer)\n  \
+        --cache-max-size <size> Cache size limit, e.g. 1G, least recently used entries evicted (default: 512M)\n  \
+        --api-url <url>        API base URL, e.g. https://ghe.example.com/api/v3 (or GITHUB_API_URL, GITLAB_API_URL)\n  \
+        --provider <name>      `github`, `gitlab` or `gitea` (default: detected from the repository host)\n  \
+        --token <token>        GitHub token (default: GITHUB_TOKEN, GITHUB_TOKEN_FILE, then the gh CLI)\n  \
+        --proxy <url>          HTTP(S) proxy for all requests (default: HTTPS_PROXY/HTTP_PROXY)\n  \
+        --user-agent <ua>      User-Agent header sent with every request\n  \
+        --connect-timeout <s>  Connection timeout in seconds (default: 10)\n  \
+        --timeout <s>          Whole-request timeout in seconds, body included (default: 300)\n  \
+        --bundle-by <mode>     Split the bundle by `topic` (default: single)\n  \
+        --from-archive <path>  Analyze a local .tar.gz or .zip archive; a path ending in .zip/.tar.gz also works as a plain argument (feature `archive`)\n  \
+        --fetch-budget <size>  Max declared bytes downloaded at once, in bytes or with a K, M or G suffix (default: 8M)\n  \
+        --max-in-flight <n>    Max simultaneous downloads (default: 8)\n  \
+        --summary-entries <n>  Entries kept per pattern in file summaries (default: 10)\n  \
+        --key-types <n>        Most central types listed in key_types (default: 10)\n  \
+        --doc-length <n>       Characters kept per doc comment on types and functions, 0 to drop them (default: 400)\n  \
+        --max-file-size <size> Skip files larger than this, in bytes or with a K, M or G suffix (default: 10M)\n  \
+        --on-low-budget <mode> `warn` or `abort` when the API quota cannot cover the run (default: warn)\n  \
+        --strip-headers        Strip repeated license headers from exported files\n  \
+        --include-licenses     Export full license texts instead of a one-line placeholder\n  \
+        --skip-examples        Leave examples/ and benches/ out of the export\n  \
+        --never-export <glob>  Never export the content of matching paths (repeatable)\n  \
+        --never-export-file <path>\n                         \
+        Read never-export patterns from a file (default: ./.never-export)\n  \
+        --status-file <path>   Write the run status report here (default: output/run_status.json)\n  \
+        --summary-json         Also print the run status report, with the compiled subsystems, as JSON",
+        program
+    )
+}
+
+/// Analyse les arguments de la ligne de commande (sans le nom du programme)
+pub fn parse_args(args: &[String]) -> Result<CliOptions, String> {
+    let mut options = CliOptions::default();
+
+    if args.first().map(String::as_str) == Some("migrate") {
+        let input = args.get(1).ok_or("Missing input file for migrate")?;
+        if args.len() > 3 {
+            return Err("Too many arguments for migrate".to_string());
+        }
+        options.migrate = Some(MigrateArgs {
+            input: input.clone(),
+            output: args.get(2).cloned(),
+        });
+        return Ok(options);
+    }
+
+    let mut iter = args.iter();
+
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--ascii" => options.ascii = true,
+            "--capabilities" => options.show_capabilities = true,
+            "--offline" => options.analysis.offline = true,
+            "--compare" => {
+                let range = value(&mut iter, arg)?;
+                let (base, head) = range
+                    .split_once("..")
+                    .filter(|(base, head)| !base.is_empty() && !head.is_empty())
+                    .ok_or_else(|| format!("Invalid --compare value: {} (expected <base>..<head>)", range))?;
+                options.compare = Some((base.to_string(), head.to_string()));
+            }
+            "--path" => options.analysis.subpath = Some(value(&mut iter, arg)?.clone()),
+            "--tarball" => options.analysis.tarball = true,
+            "--clone" => options.analysis.clone = true,
+            "--keep-clone" => options.analysis.keep_clone = true,
+            "--no-cache" => options.analysis.no_cache = true,
+            "--no-default-ignores" => options.analysis.no_default_ignores = true,
+            "--recurse-submodules" => options.analysis.recurse_submodules = true,
+            "--include" => options.analysis.include.push(value(&mut iter, arg)?.clone()),
+            "--exclude" => options.analysis.exclude.push(value(&mut iter, arg)?.clone()),
+            "--api-url" => options.analysis.api_base = Some(value(&mut iter, arg)?.clone()),
+            "--provider" => options.analysis.provider = Some(value(&mut iter, arg)?.parse::<Provider>()?),
+            "--token" => options.analysis.token = Some(value(&mut iter, arg)?.clone()),
+            "--proxy" => options.analysis.proxy = Some(value(&mut iter, arg)?.clone()),
+            "--user-agent" => options.analysis.user_agent = Some(value(&mut iter, arg)?.clone()),
+            "--cache-dir" => options.analysis.cache_dir = Some(value(&mut iter, arg)?.into()),
+            "--org" => options.orgs.push(value(&mut iter, arg)?.clone()),
+            "--include-archived" => options.include_archived = true,
+            "--include-forks" => options.include_forks = true,
+            "--local" => options.local = true,
+            "--ref" => options.analysis.git_ref = Some(value(&mut iter, arg)?.clone()),
+            "--bundle-by" => {
+                options.export.bundle_by = value(&mut iter, arg)?.parse::<BundleMode>()?;
+            }
+            "--from-archive" => options.from_archive = Some(value(&mut iter, arg)?.clone()),
+            "--cache-max-size" => {
+                options.analysis.cache_max_size = parse_size(value(&mut iter, arg)?, arg)?;
+            }
+            "--fetch-budget" => {
+                options.analysis.fetch_byte_budget = parse_size(value(&mut iter, arg)?, arg)?;
+            }
+            "--max-in-flight" => {
+                options.analysis.max_in_flight = parse_number(value(&mut iter, arg)?, arg)?;
+            }
+            "--on-low-budget" => {
+                options.analysis.budget_policy = value(&mut iter, arg)?.parse::<BudgetPolicy>()?;
+            }
+            "--connect-timeout" => {
+                options.analysis.connect_timeout_secs = parse_number(value(&mut iter, arg)?, arg)?;
+            }
+            "--timeout" => {
+                options.analysis.timeout_secs = parse_number(value(&mut iter, arg)?, arg)?;
+            }
+            "--max-file-size" => {
+                options.analysis.max_file_size = parse_size(value(&mut iter, arg)?, arg)? as usize;
+            }
+            "--summary-entries" => {
+                options.analysis.summary_entries = parse_number(value(&mut iter, arg)?, arg)?;
+            }
+            "--key-types" => {
+                options.analysis.key_types = parse_number(value(&mut iter, arg)?, arg)?;
+            }
+            "--doc-length" => {
+                options.analysis.doc_length = parse_number(value(&mut iter, arg)?, arg)?;
+            }
+            "--strip-headers" => options.export.strip_headers = true,
+            "--include-licenses" => options.export.include_licenses = true,
+            "--skip-examples" => options.export.skip_examples = true,
+            "--never-export" => {
+                options.export.never_export.push(value(&mut iter, arg)?.clone());
+            }
+            "--never-export-file" => {
+                let path = value(&mut iter, arg)?;
+                let patterns = NeverExportPolicy::read_patterns(Path::new(path))
+                    .map_err(|e| format!("Cannot read {}: {}", path, e))?;
+                options.export.never_export.extend(patterns);
+            }
+            "--status-file" => options.status_file = Some(value(&mut iter, arg)?.clone()),
+            "--summary-json" => options.summary_json = true,
+            flag if flag.starts_with("--") => return Err(format!("Unknown flag: {}", flag)),
+            org if org.starts_with("org:") => options.orgs.push(org["org:".len()..].to_string()),
+            repo => options.repos.push(repo.to_string()),
+        }
+    }
+
+    options.analysis.glob_filter()?;
+
+    let default_policy = Path::new(NEVER_EXPORT_FILE);
+    if default_policy.exists() {
+        let patterns = NeverExportPolicy::read_patterns(default_policy)
+            .map_err(|e| format!("Cannot read {}: {}", NEVER_EXPORT_FILE, e))?;
+        options.export.never_export.extend(patterns);
+    }
+
+    Ok(options)
+}
+
+/// Récupère la valeur obligatoire d'un flag
+fn value<'a>(iter: &mut impl Iterator<Item = &'a String>, flag: &str) -> Result<&'a String, String> {
+    iter.next().ok_or_else(|| format!("Missing value for {}", flag))
+}
+
+/// Convertit la valeur numérique d'un flag
+fn parse_number<T: std::str::FromStr>(value: &str, flag: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("Invalid number for {}: {}", flag, value))
+}
+
+/// Convertit une taille en octets, avec un suffixe binaire facultatif
+/// (`512K`, `5M`, `1G`, `5MiB`, `5MB`)
+fn parse_size(value: &str, flag: &str) -> Result<u64, String> {
+    let invalid = || format!("Invalid size for {}: {} (expected e.g. 1048576, 512K, 5M or 1G)", flag, value);
+    let trimmed = value.trim();
+    let digits_end = trimmed.find(|c: char| !c.is_ascii_digit()).unwrap_or(trimmed.len());
+    let (number, unit) = trimmed.split_at(digits_end);
+    let multiplier: u64 = match unit.trim().to_ascii_lowercase().as_str() {
+        "" | "b" => 1,
+        "k" | "kb" | "kib" => 1 << 10,
+        "m" | "mb" | "mib" => 1 << 20,
+        "g" | "gb" | "gib" => 1 << 30,
+        _ => return Err(invalid()),
+    };
+    number.parse::<u64>()
+        .ok()
+        .and_then(|number| number.checked_mul(multiplier))
+        .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<CliOptions, String> {
+        parse_args(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn summary_json_flag_is_recognized() {
+        let options = parse(&["--summary-json", "owner/repo"]).unwrap();
+        assert!(options.summary_json);
+        assert_eq!(options.repos, vec!["owner/repo"]);
+        assert!(!parse(&["owner/repo"]).unwrap().summary_json);
+    }
+
+    #[test]
+    fn fetch_budget_accepts_size_suffixes() {
+        for (value, expected) in [("8388608", 8 << 20), ("512K", 512 << 10), ("8M", 8 << 20), ("1G", 1 << 30), ("2MiB", 2 << 20)] {
+            let options = parse(&["--fetch-budget", value, "owner/repo"]).unwrap();
+            assert_eq!(options.analysis.fetch_byte_budget, expected, "{}", value);
+        }
+        assert_eq!(
+            parse(&["--fetch-budget", "8X"]).unwrap_err(),
+            "Invalid size for --fetch-budget: 8X (expected e.g. 1048576, 512K, 5M or 1G)"
+        );
+        assert_eq!(parse(&["--fetch-budget"]).unwrap_err(), "Missing value for --fetch-budget");
+    }
+
+    #[cfg(not(feature = "git"))]
+    #[test]
+    fn clone_without_git_names_the_cargo_feature() {
+        assert_eq!(
+            parse(&["--clone", "owner/repo"]).unwrap().require_capabilities().unwrap_err(),
+            "--clone requires the `git` subsystem, which this binary was built without \
+            (rebuild with `cargo build --features git`)"
+        );
+    }
+
+    #[cfg(feature = "git")]
+    #[test]
+    fn clone_with_git_is_accepted() {
+        assert_eq!(parse(&["--clone", "owner/repo"]).unwrap().require_capabilities(), Ok(()));
+    }
+
+    #[cfg(not(feature = "archive"))]
+    #[test]
+    fn archive_inputs_without_archive_name_the_cargo_feature() {
+        let expected = |flag: &str| format!(
+            "{} requires the `archive` subsystem, which this binary was built without \
+            (rebuild with `cargo build --features archive`)",
+            flag
+        );
+        let options = parse(&["--from-archive", "repo.tar.gz"]).unwrap();
+        assert_eq!(options.require_capabilities().unwrap_err(), expected("--from-archive"));
+        let options = parse(&["--tarball", "owner/repo"]).unwrap();
+        assert_eq!(options.require_capabilities().unwrap_err(), expected("--tarball"));
+
+        let archive = tempfile::Builder::new().suffix(".zip").tempfile().unwrap();
+        let options = parse(&[archive.path().to_str().unwrap()]).unwrap();
+        assert_eq!(options.require_capabilities().unwrap_err(), expected("An archive input"));
+    }
+
+    #[cfg(feature = "archive")]
+    #[test]
+    fn archive_inputs_with_archive_are_accepted() {
+        let options = parse(&["--from-archive", "repo.tar.gz", "--tarball", "owner/repo"]).unwrap();
+        assert_eq!(options.require_capabilities(), Ok(()));
+    }
+
+    #[test]
+    fn capabilities_flag_is_recognized() {
+        assert!(parse(&["--capabilities"]).unwrap().show_capabilities);
+        assert_eq!(parse(&["--no-such-flag"]).unwrap_err(), "Unknown flag: --no-such-flag");
+    }
+}
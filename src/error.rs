@@ -6,6 +6,44 @@ pub enum GithubAnalyzerError {
     NetworkError(String),
     ParseError(String),
     RateLimitError(u64),  // Contains reset timestamp
+    ArchiveError(String),
+    /// Accès refusé par l'API (403) : dépôt privé ou token sans les droits requis
+    Forbidden(String),
+    AuthError(String),
+    NotFound(String),
+    /// Contenu binaire (octet NUL en tête), non analysable comme texte
+    BinaryContent(String),
+}
+
+impl GithubAnalyzerError {
+    /// Nom de la variante, stable pour les consommateurs machine
+    pub fn kind(&self) -> &'static str {
+        match self {
+            GithubAnalyzerError::NetworkError(_) => "NetworkError",
+            GithubAnalyzerError::ParseError(_) => "ParseError",
+            GithubAnalyzerError::RateLimitError(_) => "RateLimitError",
+            GithubAnalyzerError::ArchiveError(_) => "ArchiveError",
+            GithubAnalyzerError::Forbidden(_) => "Forbidden",
+            GithubAnalyzerError::AuthError(_) => "AuthError",
+            GithubAnalyzerError::NotFound(_) => "NotFound",
+            GithubAnalyzerError::BinaryContent(_) => "BinaryContent",
+        }
+    }
+
+    /// Conseil affiché à l'utilisateur pour corriger l'erreur
+    pub fn hint(&self) -> Option<&'static str> {
+        match self {
+            GithubAnalyzerError::NotFound(_) =>
+                Some("repository not found — check the URL (private repositories also need a token with access)"),
+            GithubAnalyzerError::AuthError(_) =>
+                Some("token rejected — set GITHUB_TOKEN (or --token) to a valid token"),
+            GithubAnalyzerError::Forbidden(_) =>
+                Some("access forbidden — the token lacks permission on this repository (or its SSO authorization)"),
+            GithubAnalyzerError::RateLimitError(_) =>
+                Some("API quota exhausted — wait for the reset or authenticate to raise the limit"),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for GithubAnalyzerError {
@@ -15,6 +53,11 @@ impl fmt::Display for GithubAnalyzerError {
             GithubAnalyzerError::ParseError(msg) => write!(f, "Parse error: {}", msg),
             GithubAnalyzerError::RateLimitError(reset_time) => 
                 write!(f, "Rate limit exceeded. Resets at timestamp: {}", reset_time),
+            GithubAnalyzerError::ArchiveError(msg) => write!(f, "Archive error: {}", msg),
+            GithubAnalyzerError::Forbidden(msg) => write!(f, "Forbidden: {}", msg),
+            GithubAnalyzerError::AuthError(msg) => write!(f, "Authentication error: {}", msg),
+            GithubAnalyzerError::NotFound(msg) => write!(f, "Not found: {}", msg),
+            GithubAnalyzerError::BinaryContent(msg) => write!(f, "Binary content: {}", msg),
         }
     }
 }
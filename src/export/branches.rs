@@ -0,0 +1,167 @@
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::paths::sanitize_file_name;
+use crate::types::analysis::{MethodSignature, ProjectSummary, TypeRelations};
+use crate::types::locator::RepoLocator;
+
+/// Type présent dans les deux branches dont les relations diffèrent
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct TypeChange {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub traits_added: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub traits_removed: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies_added: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies_removed: Vec<String>,
+}
+
+/// Fonction présente dans les deux branches dont la signature diffère ; une
+/// fonction définie plusieurs fois (méthodes homonymes) garde toutes ses signatures
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FunctionChange {
+    pub name: String,
+    pub base: Vec<String>,
+    pub head: Vec<String>,
+}
+
+/// Différences structurelles entre deux branches d'un dépôt (`branch_comparison.json`)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct BranchComparison {
+    pub repo_url: String,
+    pub base: String,
+    pub head: String,
+    pub types_added: Vec<String>,
+    pub types_removed: Vec<String>,
+    pub types_changed: Vec<TypeChange>,
+    pub functions_added: Vec<String>,
+    pub functions_removed: Vec<String>,
+    pub functions_changed: Vec<FunctionChange>,
+    pub files_only_in_base: Vec<String>,
+    pub files_only_in_head: Vec<String>,
+}
+
+impl BranchComparison {
+    /// Compare l'analyse de `head` à celle de `base` ; types et fonctions sont
+    /// appariés par nom
+    pub fn compute(base: &ProjectSummary, head: &ProjectSummary) -> Self {
+        let (base_types, head_types) = (types_by_name(base), types_by_name(head));
+        let types_changed = base_types
+            .iter()
+            .filter_map(|(name, before)| {
+                let after = head_types.get(name)?;
+                let (traits_added, traits_removed) = diff(&before.implemented_traits, &after.implemented_traits);
+                let (dependencies_added, dependencies_removed) = diff(&before.depends_on, &after.depends_on);
+                let change = TypeChange {
+                    name: name.clone(),
+                    traits_added,
+                    traits_removed,
+                    dependencies_added,
+                    dependencies_removed,
+                };
+                let changed = !change.traits_added.is_empty()
+                    || !change.traits_removed.is_empty()
+                    || !change.dependencies_added.is_empty()
+                    || !change.dependencies_removed.is_empty();
+                changed.then_some(change)
+            })
+            .collect();
+
+        let (base_functions, head_functions) = (functions_by_name(base), functions_by_name(head));
+        let functions_changed = base_functions
+            .iter()
+            .filter_map(|(name, before)| {
+                let after = head_functions.get(name)?;
+                (before != after).then(|| FunctionChange {
+                    name: name.clone(),
+                    base: before.iter().cloned().collect(),
+                    head: after.iter().cloned().collect(),
+                })
+            })
+            .collect();
+
+        let files = |summary: &ProjectSummary| -> BTreeSet<String> {
+            summary.files_analyzed.iter().map(|path| path.to_string()).collect()
+        };
+        let (base_files, head_files) = (files(base), files(head));
+
+        Self {
+            repo_url: head.repo_url.clone(),
+            base: base.repository_structure.branch_analyzed.clone(),
+            head: head.repository_structure.branch_analyzed.clone(),
+            types_added: only_in(&head_types, &base_types),
+            types_removed: only_in(&base_types, &head_types),
+            types_changed,
+            functions_added: only_in(&head_functions, &base_functions),
+            functions_removed: only_in(&base_functions, &head_functions),
+            functions_changed,
+            files_only_in_base: base_files.difference(&head_files).cloned().collect(),
+            files_only_in_head: head_files.difference(&base_files).cloned().collect(),
+        }
+    }
+
+    /// Écrit `<output_dir>/<owner_repo>/branch_comparison.json` et renvoie son chemin
+    pub fn write(&self, output_dir: &Path) -> std::io::Result<PathBuf> {
+        let name = self.repo_url
+            .parse::<RepoLocator>()
+            .map(|locator| locator.output_dir_name())
+            .unwrap_or_else(|_| self.repo_url.clone());
+        let dir = output_dir.join(sanitize_file_name(&name));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("branch_comparison.json");
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+        Ok(path)
+    }
+}
+
+fn types_by_name(summary: &ProjectSummary) -> BTreeMap<String, &TypeRelations> {
+    summary.project_overview.type_relations
+        .iter()
+        .map(|relations| (relations.type_name.to_string(), relations))
+        .collect()
+}
+
+fn functions_by_name(summary: &ProjectSummary) -> BTreeMap<String, BTreeSet<String>> {
+    let mut functions: BTreeMap<String, BTreeSet<String>> = BTreeMap::new();
+    for signature in &summary.project_overview.method_signatures {
+        functions.entry(signature.qualified_name()).or_default().insert(render_signature(signature));
+    }
+    functions
+}
+
+/// Signature lisible (`public async fn name(a: u32) -> bool`)
+fn render_signature(signature: &MethodSignature) -> String {
+    let mut rendered = format!(
+        "{} {}fn {}{}({}) -> {}",
+        signature.visibility,
+        signature.qualifiers(),
+        signature.name,
+        signature.generic_list(),
+        signature.params.join(", "),
+        signature.return_type
+    );
+    if !signature.where_bounds.is_empty() {
+        rendered.push_str(&format!(" where {}", signature.where_bounds.join(", ")));
+    }
+    rendered
+}
+
+/// Clés de `left` absentes de `right`
+fn only_in<V>(left: &BTreeMap<String, V>, right: &BTreeMap<String, V>) -> Vec<String> {
+    left.keys().filter(|name| !right.contains_key(*name)).cloned().collect()
+}
+
+/// Éléments ajoutés puis retirés entre deux listes
+fn diff<T: ToString>(before: &[T], after: &[T]) -> (Vec<String>, Vec<String>) {
+    let before: BTreeSet<String> = before.iter().map(ToString::to_string).collect();
+    let after: BTreeSet<String> = after.iter().map(ToString::to_string).collect();
+    (
+        after.difference(&before).cloned().collect(),
+        before.difference(&after).cloned().collect(),
+    )
+}
@@ -0,0 +1,140 @@
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Métriques comparées d'une exécution à l'autre : (libellé, chemin JSON)
+const TRACKED_METRICS: [(&str, &[&str]); 6] = [
+    ("Files analyzed", &["total_files"]),
+    ("Rust files", &["project_overview", "total_rust_files"]),
+    ("Public types", &["project_overview", "total_public_types"]),
+    ("Public functions", &["project_overview", "total_public_functions"]),
+    ("Tests", &["project_overview", "total_tests"]),
+    ("Heuristic files", &["project_overview", "total_heuristic_files"]),
+];
+
+/// Évolution d'une métrique entre deux exécutions
+#[derive(Debug, Clone)]
+pub struct MetricChange {
+    pub name: String,
+    pub previous: f64,
+    pub current: f64,
+}
+
+impl MetricChange {
+    /// Variation formatée (`+3 (+1.2%)`)
+    pub fn delta(&self) -> String {
+        let delta = self.current - self.previous;
+        let percent = if self.previous != 0.0 {
+            format!(" ({:+.1}%)", delta / self.previous * 100.0)
+        } else {
+            String::new()
+        };
+        format!("{:+}{}", delta, percent)
+    }
+}
+
+/// Différences entre l'analyse courante et celle de l'exécution précédente
+#[derive(Debug, Clone, Default)]
+pub struct RunChanges {
+    pub previous_found: bool,
+    pub added_files: Vec<String>,
+    pub removed_files: Vec<String>,
+    pub metric_changes: Vec<MetricChange>,
+}
+
+impl RunChanges {
+    /// Compare deux documents `analysis.json` (le précédent étant optionnel)
+    pub fn compute(previous: Option<&Value>, current: &Value) -> Self {
+        let Some(previous) = previous else {
+            return Self::default();
+        };
+
+        let files = |value: &Value| -> BTreeSet<String> {
+            value["files_analyzed"]
+                .as_array()
+                .map(|files| files.iter().filter_map(|f| f.as_str().map(str::to_string)).collect())
+                .unwrap_or_default()
+        };
+        let (before, after) = (files(previous), files(current));
+
+        let metric = |value: &Value, path: &[&str]| -> Option<f64> {
+            path.iter().try_fold(value, |v, key| v.get(key))?.as_f64()
+        };
+        let metric_changes = TRACKED_METRICS
+            .iter()
+            .filter_map(|(name, path)| {
+                let previous = metric(previous, path)?;
+                let current = metric(current, path)?;
+                (previous != current).then(|| MetricChange {
+                    name: name.to_string(),
+                    previous,
+                    current,
+                })
+            })
+            .collect();
+
+        Self {
+            previous_found: true,
+            added_files: after.difference(&before).cloned().collect(),
+            removed_files: before.difference(&after).cloned().collect(),
+            metric_changes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added_files.is_empty() && self.removed_files.is_empty() && self.metric_changes.is_empty()
+    }
+
+    /// Rendu condensé pour la console
+    pub fn render_console(&self) -> String {
+        if !self.previous_found {
+            return "Changes since last run: no previous run found, nothing to compare\n".to_string();
+        }
+        if self.is_empty() {
+            return "Changes since last run: none\n".to_string();
+        }
+
+        let mut output = format!(
+            "Changes since last run: {} file(s) added, {} removed\n",
+            self.added_files.len(),
+            self.removed_files.len()
+        );
+        for change in &self.metric_changes {
+            output.push_str(&format!("  - {}: {}\n", change.name, change.delta()));
+        }
+        output
+    }
+
+    /// Rendu Markdown (`changes.md`)
+    pub fn render_markdown(&self) -> String {
+        let mut output = String::from("# Changes since the previous run\n\n");
+        if !self.previous_found {
+            output.push_str("No previous run was found in this output directory; there is nothing to compare.\n");
+            return output;
+        }
+        if self.is_empty() {
+            output.push_str("No changes detected.\n");
+            return output;
+        }
+
+        if !self.metric_changes.is_empty() {
+            output.push_str("## Metrics\n\n| Metric | Previous | Current | Change |\n|---|---|---|---|\n");
+            for change in &self.metric_changes {
+                output.push_str(&format!(
+                    "| {} | {} | {} | {} |\n",
+                    change.name, change.previous, change.current, change.delta()
+                ));
+            }
+            output.push('\n');
+        }
+        for (title, files) in [("Added files", &self.added_files), ("Removed files", &self.removed_files)] {
+            if !files.is_empty() {
+                output.push_str(&format!("## {}\n\n", title));
+                for file in files {
+                    output.push_str(&format!("- {}\n", file));
+                }
+                output.push('\n');
+            }
+        }
+        output
+    }
+}
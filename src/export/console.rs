@@ -0,0 +1,650 @@
+use std::collections::BTreeSet;
+
+use crate::analysis::todos::TODO_MARKERS;
+use crate::types::analysis::{BuildScript, DependencyKind, MacroKind, ProjectSummary};
+
+/// Largeur de rendu par défaut (colonnes)
+pub const DEFAULT_WIDTH: usize = 80;
+
+/// Cycles de dépendances listés au plus dans la console
+const MAX_LISTED_CYCLES: usize = 10;
+
+/// Rendu console des sections d'un `ProjectSummary`
+///
+/// Utilisé par le binaire et disponible pour les intégrateurs, afin que les
+/// deux sorties ne divergent pas.
+#[derive(Debug, Clone)]
+pub struct SummaryRenderer {
+    /// Largeur maximale d'une ligne
+    pub width: usize,
+    /// Utilise les symboles Unicode (✓, ✗, →) plutôt que leurs équivalents ASCII
+    pub unicode: bool,
+}
+
+impl Default for SummaryRenderer {
+    fn default() -> Self {
+        Self {
+            width: DEFAULT_WIDTH,
+            unicode: true,
+        }
+    }
+}
+
+impl SummaryRenderer {
+    pub fn new(width: usize, unicode: bool) -> Self {
+        Self { width, unicode }
+    }
+
+    /// Ligne de succès (`✓ message`)
+    pub fn success(&self, message: &str) -> String {
+        format!("{} {}", if self.unicode { "✓" } else { "[ok]" }, message)
+    }
+
+    /// Ligne d'échec (`✗ message`)
+    pub fn failure(&self, message: &str) -> String {
+        format!("{} {}", if self.unicode { "✗" } else { "[error]" }, message)
+    }
+
+    /// Ligne d'indication indentée (`  → message`)
+    pub fn hint(&self, message: &str) -> String {
+        format!("  {} {}", if self.unicode { "→" } else { "->" }, message)
+    }
+
+    /// Bloc "Quick stats" affiché en fin d'analyse
+    pub fn render_quick_stats(&self, summary: &ProjectSummary) -> String {
+        let structure = &summary.repository_structure;
+        let overview = &summary.project_overview;
+        let readiness = &summary.release_readiness;
+
+        let mut rows = Vec::new();
+        if let Some(metadata) = &summary.metadata {
+            if let Some(description) = &metadata.description {
+                rows.push(("Description", description.clone()));
+            }
+            rows.push(("Stars", metadata.stargazers_count.to_string()));
+        }
+        if let Some(description) = &overview.description {
+            rows.push(("Description", description.clone()));
+        }
+        rows.extend([
+            ("Files analyzed", summary.files_analyzed_label()),
+            ("Primary language", structure.primary_language.as_deref().unwrap_or("Unknown").to_string()),
+            ("Build systems", structure.build_systems.join(", ")),
+        ]);
+        if !structure.language_breakdown.is_empty() {
+            let breakdown: Vec<String> = structure.language_breakdown
+                .iter()
+                .map(|share| format!("{} {:.1}%", share.language, share.percent))
+                .collect();
+            rows.push(("Code languages", breakdown.join(", ")));
+        }
+        rows.push((
+            "CI",
+            match &structure.ci_summary {
+                Some(ci) => format!("yes, {}", ci.describe()),
+                None => "no".to_string(),
+            },
+        ));
+        if structure.has_build_script {
+            let description = structure.build_script.as_ref().map_or("build.rs".to_string(), BuildScript::describe);
+            rows.push(("Build script", format!("yes, {}", description)));
+        }
+        if let Some(license) = &structure.license {
+            rows.push(("License", license.clone()));
+        }
+        if !overview.dependencies.is_empty() {
+            let count = |kind| overview.dependencies.iter().filter(|dependency| dependency.kind == kind).count();
+            let optional = overview.dependencies.iter().filter(|dependency| dependency.optional).count();
+            rows.push((
+                "Dependencies",
+                format!(
+                    "{} ({} optional), {} dev, {} build",
+                    count(DependencyKind::Normal),
+                    optional,
+                    count(DependencyKind::Dev),
+                    count(DependencyKind::Build)
+                ),
+            ));
+            let unused: Vec<&str> = overview
+                .dependencies
+                .iter()
+                .filter(|dependency| dependency.unused)
+                .map(|dependency| dependency.name.as_str())
+                .collect();
+            if !unused.is_empty() {
+                rows.push(("Unused dependencies", unused.join(", ")));
+            }
+        }
+        if let Some(metadata) = &overview.crate_metadata {
+            let mut description = metadata.name.clone();
+            if let Some(version) = &metadata.version {
+                description.push_str(&format!(" {}", version));
+            }
+            rows.push(("Crate", format!("{} ({})", description, metadata.targets())));
+            rows.push((
+                "Edition / MSRV",
+                format!(
+                    "{} / {}",
+                    metadata.edition.as_deref().unwrap_or("2015"),
+                    metadata.rust_version.as_deref().unwrap_or("not declared")
+                ),
+            ));
+        }
+        let targets: Vec<String> = [
+            (overview.binary_targets, "binary", "binaries"),
+            (overview.example_targets, "example", "examples"),
+            (overview.bench_targets, "bench", "benches"),
+        ]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, one, many)| format!("{} {}", count, if count == 1 { one } else { many }))
+        .collect();
+        if !targets.is_empty() {
+            rows.push(("Targets", targets.join(", ")));
+        }
+        if !overview.workspace_members.is_empty() {
+            let names: Vec<&str> = overview.workspace_members.iter().map(|member| member.name.as_str()).collect();
+            rows.push(("Workspace crates", format!("{} ({})", names.len(), names.join(", "))));
+        }
+        if !structure.submodules.is_empty() {
+            let analyzed = structure.submodules.iter().filter(|submodule| submodule.analyzed).count();
+            rows.push(("Submodules", format!("{} ({} analyzed)", structure.submodules.len(), analyzed)));
+        }
+        if !structure.symlinks.is_empty() {
+            let resolved = structure.symlinks.iter().filter(|symlink| symlink.resolved.is_some()).count();
+            rows.push(("Symlinks", format!("{} ({} resolved)", structure.symlinks.len(), resolved)));
+        }
+        if !structure.skipped_directories.is_empty() {
+            rows.push(("Skipped directories", structure.skipped_directories.len().to_string()));
+        }
+        // Langages les plus volumineux en lignes (détail dans analysis.json)
+        let mut languages: Vec<_> = overview.lines_by_language.iter().collect();
+        languages.sort_by(|a, b| b.1.total.cmp(&a.1.total).then_with(|| a.0.cmp(b.0)));
+        for (language, lines) in languages.into_iter().take(3) {
+            rows.push((
+                "Lines",
+                format!(
+                    "{} lines of {} ({} code / {} comments)",
+                    self.approximate(lines.total),
+                    language,
+                    self.approximate(lines.code),
+                    self.approximate(lines.comments)
+                ),
+            ));
+        }
+        if overview.total_rust_files > 0 {
+            rows.push(("Rust files", overview.total_rust_files.to_string()));
+            rows.push(("Public types", overview.total_public_types.to_string()));
+            rows.push(("Public functions", overview.total_public_functions.to_string()));
+            rows.push(("Unsafe", self.unsafe_summary(summary)));
+            rows.push(("TODOs", self.todo_summary(summary)));
+            let panics = &overview.panic_points.counts;
+            if !panics.is_empty() {
+                rows.push(("Panic points", format!("{} ({})", panics.total(), panics.describe())));
+            }
+            if !overview.error_types.is_empty() {
+                rows.push(("Error types", self.error_summary(summary)));
+            }
+            if !overview.macros.is_empty() {
+                rows.push(("Macros", self.macro_summary(summary)));
+            }
+            if !overview.proc_macro_crates.is_empty() {
+                rows.push(("Proc-macro crates", overview.proc_macro_crates.join(", ")));
+            }
+            if let Some(function) = overview.complex_functions.first() {
+                rows.push(("Most complex fn", format!("{} ({})", function.name, function.complexity)));
+            }
+            if !overview.possibly_unused.is_empty() {
+                rows.push(("Possibly unused", format!("{} public items (heuristic)", overview.possibly_unused.len())));
+            }
+        }
+        if !overview.key_types.is_empty() {
+            let names: Vec<&str> = overview.key_types.iter().take(5).map(|key_type| key_type.name.as_str()).collect();
+            let more = if overview.key_types.len() > names.len() { "…" } else { "" };
+            rows.push(("Key types", format!("{}{}", names.join(", "), more)));
+        }
+        if !summary.important_patterns.is_empty() {
+            let names: Vec<&str> = summary.important_patterns.iter().map(|pattern| pattern.name.as_str()).collect();
+            rows.push(("Patterns", names.join(", ")));
+        }
+        rows.push((
+            "Last release",
+            format!(
+                "{}{}",
+                readiness.latest_version.as_deref().unwrap_or("none"),
+                readiness.latest_release_date.as_deref()
+                    .map(|date| format!(" ({})", date))
+                    .unwrap_or_default()
+            ),
+        ));
+        rows.push((
+            "Changelog maintained",
+            if readiness.changelog_maintained { "yes" } else { "no" }.to_string(),
+        ));
+        rows.push(("Non-exhaustive public types", readiness.non_exhaustive_public_types.to_string()));
+        // Répertoires les plus volumineux (liste complète dans analysis.json)
+        for stats in overview.directory_stats.iter().take(5) {
+            rows.push((
+                stats.path.as_str(),
+                format!("{} files, {} lines, {} bytes", stats.file_count, stats.loc, stats.total_size),
+            ));
+        }
+        if let Some(usage) = &summary.run_metrics.rate_limit {
+            let now = std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            rows.push((
+                "API quota",
+                format!(
+                    "{}/{} left, {} used, resets in {} min",
+                    usage.remaining_after,
+                    usage.limit,
+                    usage.consumed(),
+                    usage.reset.saturating_sub(now) / 60 + 1
+                ),
+            ));
+        }
+
+        self.section("Quick stats", &rows)
+    }
+
+    /// Les trois fichiers les plus volumineux (vide s'il n'y en a aucun)
+    pub fn render_hotspots(&self, summary: &ProjectSummary) -> String {
+        if summary.hotspots.largest_files.is_empty() {
+            return String::new();
+        }
+        let rows: Vec<(&str, String)> = summary.hotspots.largest_files
+            .iter()
+            .take(3)
+            .map(|hotspot| (hotspot.path.as_str(), format!("{} bytes", hotspot.value)))
+            .collect();
+        self.section("Largest files", &rows)
+    }
+
+    /// Section listant les fichiers laissés de côté (vide s'il n'y en a aucun)
+    pub fn render_skipped_files(&self, summary: &ProjectSummary) -> String {
+        if summary.skipped_files.is_empty() {
+            return String::new();
+        }
+        let rows: Vec<(&str, String)> = summary.skipped_files
+            .iter()
+            .map(|file| (file.path.as_str(), format!("{} bytes, {}", file.size, file.reason)))
+            .collect();
+        self.section("Skipped files", &rows)
+    }
+
+    /// Avertissement listant les cycles de dépendances ; vide s'il n'y en a aucun
+    pub fn render_dependency_cycles(&self, summary: &ProjectSummary) -> String {
+        let cycles = &summary.project_overview.dependency_cycles;
+        if cycles.is_empty() {
+            return String::new();
+        }
+        let mut output = format!(
+            "\nWarning: {} dependency cycle{} found\n",
+            cycles.len(),
+            if cycles.len() == 1 { "" } else { "s" }
+        );
+        let separator = if self.unicode { " ↔ " } else { " <-> " };
+        for cycle in cycles.iter().take(MAX_LISTED_CYCLES) {
+            output.push_str(&self.truncate(&self.hint(&cycle.join(separator))));
+            output.push('\n');
+        }
+        if cycles.len() > MAX_LISTED_CYCLES {
+            output.push_str(&self.hint(&format!("... and {} more in analysis.json", cycles.len() - MAX_LISTED_CYCLES)));
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Fichiers binaires, avec leur taille ; vide s'il n'y en a aucun
+    pub fn render_binary_files(&self, summary: &ProjectSummary) -> String {
+        if summary.binary_files.is_empty() {
+            return String::new();
+        }
+        let rows: Vec<(&str, String)> = summary.binary_files
+            .iter()
+            .map(|file| (file.path.as_str(), format!("{} bytes", file.size)))
+            .collect();
+        self.section("Binary files", &rows)
+    }
+
+    /// Section décrivant la structure du dépôt
+    pub fn render_structure(&self, summary: &ProjectSummary) -> String {
+        let structure = &summary.repository_structure;
+        let yes_no = |value: bool| if value { "yes" } else { "no" }.to_string();
+
+        let mut rows = vec![
+            ("Branch analyzed", structure.branch_analyzed.clone()),
+            ("src/ directory", yes_no(structure.has_src_directory)),
+            ("Tests", yes_no(structure.has_tests)),
+            ("Documentation", yes_no(structure.has_docs)),
+            ("Primary language", structure.primary_language.as_deref().unwrap_or("Unknown").to_string()),
+            ("Build systems", structure.build_systems.join(", ")),
+        ];
+        if !structure.languages.is_empty() {
+            let total: u64 = structure.languages.iter().map(|(_, bytes)| bytes).sum();
+            let breakdown: Vec<String> = structure.languages
+                .iter()
+                .take(4)
+                .map(|(language, bytes)| format!("{} {}%", language, bytes * 100 / total.max(1)))
+                .collect();
+            rows.push(("Languages", breakdown.join(", ")));
+        }
+        self.section("Repository structure", &rows)
+    }
+
+    /// Section résumant la vue d'ensemble du projet
+    pub fn render_overview(&self, summary: &ProjectSummary) -> String {
+        let overview = &summary.project_overview;
+        // Méthodes regroupées par type ou trait propriétaire
+        let owners: BTreeSet<&str> = overview.method_signatures
+            .iter()
+            .filter_map(|signature| signature.owner.as_deref())
+            .collect();
+        let free_functions = overview.method_signatures.iter().filter(|signature| signature.owner.is_none()).count();
+
+        let rows = vec![
+            ("Rust files", overview.total_rust_files.to_string()),
+            ("Public types", overview.total_public_types.to_string()),
+            ("Public functions", overview.total_public_functions.to_string()),
+            (
+                "Tests",
+                format!(
+                    "{} ({} unit, {} integration in {} files)",
+                    overview.total_tests, overview.unit_tests, overview.integration_tests, overview.integration_test_files
+                ),
+            ),
+            ("Main modules", overview.main_modules.join(", ")),
+            ("Type relations", overview.type_relations.len().to_string()),
+            (
+                "Method signatures",
+                format!(
+                    "{} ({} in {} types and traits, {} free functions)",
+                    overview.method_signatures.len(),
+                    overview.method_signatures.len() - free_functions,
+                    owners.len(),
+                    free_functions
+                ),
+            ),
+            ("Async functions", overview.async_functions.to_string()),
+            ("Heuristic files", overview.total_heuristic_files.to_string()),
+        ];
+        self.section("Project overview", &rows)
+    }
+
+    /// Rendu d'une section titrée avec des libellés alignés
+    fn section(&self, title: &str, rows: &[(&str, String)]) -> String {
+        let label_width = rows.iter().map(|(label, _)| label.chars().count()).max().unwrap_or(0);
+        let bullet = if self.unicode { "•" } else { "-" };
+
+        let mut output = format!("{}:\n", title);
+        for (label, value) in rows {
+            let line = format!("  {} {:<width$} : {}", bullet, label, value, width = label_width);
+            output.push_str(&self.truncate(&line));
+            output.push('\n');
+        }
+        output
+    }
+
+    /// Usage de `unsafe` sur une ligne (`3 blocks, 1 fn, 1 impl in 2 files`), précédé de
+    /// `unsafe forbidden` si un fichier l'interdit
+    fn unsafe_summary(&self, summary: &ProjectSummary) -> String {
+        let usage = &summary.project_overview.unsafe_usage;
+        let counts = &usage.counts;
+        let forbidden = if usage.forbidden_in.is_empty() { "" } else { "unsafe forbidden" };
+        if counts.is_empty() {
+            return if forbidden.is_empty() { "none".to_string() } else { forbidden.to_string() };
+        }
+        let parts: Vec<String> = [
+            (counts.blocks, "block", "blocks"),
+            (counts.functions, "fn", "fns"),
+            (counts.impls, "impl", "impls"),
+            (counts.traits, "trait", "traits"),
+        ]
+        .iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, one, many)| format!("{} {}", count, if *count == 1 { one } else { many }))
+        .collect();
+        let files = summary.file_summaries.iter().filter(|file| !file.unsafe_code.is_empty()).count();
+        let mut line = format!("{} in {} file{}", parts.join(", "), files, if files == 1 { "" } else { "s" });
+        if !forbidden.is_empty() {
+            line = format!("{}; {}", forbidden, line);
+        }
+        line
+    }
+
+    /// Types d'erreur (trois premiers noms) et crates d'erreurs dynamiques
+    fn error_summary(&self, summary: &ProjectSummary) -> String {
+        let errors = &summary.project_overview.error_types;
+        let mut text = if errors.types.is_empty() {
+            "none".to_string()
+        } else {
+            let names: Vec<&str> = errors.types.iter().take(3).map(|error| error.name.as_str()).collect();
+            let more = if errors.types.len() > names.len() { ", …" } else { "" };
+            format!("{} ({}{})", errors.types.len(), names.join(", "), more)
+        };
+        for boundary in &errors.boundaries {
+            text.push_str(&format!("; {} in {} fns", boundary.crate_name, boundary.functions));
+        }
+        text
+    }
+
+    /// Commentaires de travail en suspens, par marqueur (`12 (TODO ×8, FIXME ×4)`)
+    fn todo_summary(&self, summary: &ProjectSummary) -> String {
+        if summary.todos.is_empty() {
+            return "none".to_string();
+        }
+        let parts: Vec<String> = TODO_MARKERS
+            .iter()
+            .map(|marker| (marker, summary.todos.iter().filter(|todo| todo.marker == *marker).count()))
+            .filter(|(_, count)| *count > 0)
+            .map(|(marker, count)| format!("{} ×{}", marker, count))
+            .collect();
+        format!("{} ({})", summary.todos.len(), parts.join(", "))
+    }
+
+    /// Macros du projet, déclaratives et procédurales, et leurs invocations
+    /// (`3 (2 macro_rules, 1 procedural), 14 uses`)
+    fn macro_summary(&self, summary: &ProjectSummary) -> String {
+        let macros = &summary.project_overview.macros;
+        let declarative = macros.iter().filter(|definition| definition.kind == MacroKind::Declarative).count();
+        let uses: usize = macros.iter().map(|definition| definition.invocations).sum();
+        format!(
+            "{} ({} macro_rules, {} procedural), {} use{}",
+            macros.len(),
+            declarative,
+            macros.len() - declarative,
+            uses,
+            if uses == 1 { "" } else { "s" }
+        )
+    }
+
+    /// Nombre arrondi à la centaine au-delà de 1 000, chiffres groupés (`≈12,400`)
+    fn approximate(&self, count: usize) -> String {
+        if count < 1000 {
+            return count.to_string();
+        }
+        let rounded = (count + 50) / 100 * 100;
+        let digits = rounded.to_string();
+        let mut grouped = String::new();
+        for (position, digit) in digits.chars().enumerate() {
+            if position > 0 && (digits.len() - position).is_multiple_of(3) {
+                grouped.push(',');
+            }
+            grouped.push(digit);
+        }
+        format!("{}{}", if self.unicode { "≈" } else { "~" }, grouped)
+    }
+
+    /// Tronque une ligne à la largeur configurée
+    fn truncate(&self, line: &str) -> String {
+        if line.chars().count() <= self.width || self.width < 4 {
+            return line.to_string();
+        }
+        let ellipsis = if self.unicode { "…" } else { "..." };
+        let keep = self.width - ellipsis.chars().count();
+        format!("{}{}", line.chars().take(keep).collect::<String>(), ellipsis)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::options::AnalysisOptions;
+    use crate::analysis::repository::RepositoryAnalyzer;
+
+    /// Dépôt de référence : une bibliothèque avec un binaire, des tests et un README
+    async fn fixture_summary() -> ProjectSummary {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |path: &str, content: &str| {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "Cargo.toml",
+            "[package]\nname = \"fixture\"\nversion = \"0.2.0\"\nedition = \"2021\"\nlicense = \"MIT\"\n\n\
+            [dependencies]\nserde = \"1\"\n\n[dev-dependencies]\ntempfile = \"3\"\n",
+        );
+        write("README.md", "# Fixture\n\nA small fixture crate.\n");
+        write(
+            "src/lib.rs",
+            "//! Fixture crate\n\npub mod shapes;\n\n/// Error of the fixture\n#[derive(Debug)]\n\
+            pub enum FixtureError {\n    Empty,\n}\n\nimpl std::fmt::Display for FixtureError {\n    \
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {\n        write!(f, \"empty\")\n    }\n}\n\n\
+            impl std::error::Error for FixtureError {}\n",
+        );
+        write(
+            "src/shapes.rs",
+            "use serde::Serialize;\n\n/// A point\n#[derive(Debug, Clone, Serialize)]\npub struct Point {\n    pub x: i32,\n    pub y: i32,\n}\n\n\
+            impl Point {\n    pub fn new(x: i32, y: i32) -> Self {\n        Point { x, y }\n    }\n\n    \
+            pub fn norm(&self) -> i32 {\n        if self.x > self.y { self.x } else { self.y }\n    }\n}\n\n\
+            // TODO: support 3D points\npub async fn load() -> Option<Point> {\n    None.unwrap()\n}\n\n\
+            #[cfg(test)]\nmod tests {\n    #[test]\n    fn origin() {\n        assert_eq!(super::Point::new(0, 0).x, 0);\n    }\n}\n",
+        );
+        write("src/main.rs", "fn main() {\n    println!(\"{:?}\", fixture::shapes::Point::new(1, 2));\n}\n");
+        write("tests/smoke.rs", "#[test]\nfn smoke() {\n    assert!(true);\n}\n");
+
+        let options = AnalysisOptions { no_cache: true, ..Default::default() };
+        let (summary, _) = RepositoryAnalyzer::with_options(options).analyze_local(dir.path()).await.unwrap();
+        summary
+    }
+
+    #[tokio::test]
+    async fn quick_stats_snapshot() {
+        let summary = fixture_summary().await;
+        assert_eq!(
+            SummaryRenderer::default().render_quick_stats(&summary),
+            "\
+Quick stats:
+  • Description                 : Fixture — A small fixture crate.
+  • Files analyzed              : 6
+  • Primary language            : rs
+  • Build systems               : Rust/Cargo
+  • Code languages              : Rust 100.0%
+  • CI                          : no
+  • License                     : MIT
+  • Dependencies                : 1 (0 optional), 1 dev, 0 build
+  • Unused dependencies         : tempfile
+  • Crate                       : fixture 0.2.0 (lib + bin)
+  • Edition / MSRV              : 2021 / not declared
+  • Targets                     : 1 binary
+  • Lines                       : 55 lines of Rust (42 code / 4 comments)
+  • Rust files                  : 4
+  • Public types                : 2
+  • Public functions            : 0
+  • Unsafe                      : none
+  • TODOs                       : 1 (TODO ×1)
+  • Panic points                : 1 (.unwrap ×1)
+  • Error types                 : 1 (FixtureError)
+  • Most complex fn             : Point::norm (2)
+  • Possibly unused             : 3 public items (heuristic)
+  • Patterns                    : Error type
+  • Last release                : none
+  • Changelog maintained        : no
+  • Non-exhaustive public types : 0
+  • src                         : 3 files, 51 lines, 884 bytes
+  • tests                       : 1 files, 4 lines, 42 bytes
+"
+        );
+    }
+
+    #[tokio::test]
+    async fn structure_and_overview_snapshots() {
+        let summary = fixture_summary().await;
+        let renderer = SummaryRenderer::default();
+        assert_eq!(
+            renderer.render_structure(&summary),
+            "\
+Repository structure:
+  • Branch analyzed  : local
+  • src/ directory   : yes
+  • Tests            : yes
+  • Documentation    : yes
+  • Primary language : rs
+  • Build systems    : Rust/Cargo
+"
+        );
+        assert_eq!(
+            renderer.render_overview(&summary),
+            "\
+Project overview:
+  • Rust files        : 4
+  • Public types      : 2
+  • Public functions  : 0
+  • Tests             : 2 (1 unit, 1 integration in 1 files)
+  • Main modules      : shapes
+  • Type relations    : 2
+  • Method signatures : 7 (3 in 2 types and traits, 4 free functions)
+  • Async functions   : 1
+  • Heuristic files   : 0
+"
+        );
+    }
+
+    #[tokio::test]
+    async fn narrow_ascii_rendering_truncates_lines() {
+        let summary = fixture_summary().await;
+        let renderer = SummaryRenderer::new(40, false);
+        assert_eq!(
+            renderer.render_overview(&summary),
+            "\
+Project overview:
+  - Rust files        : 4
+  - Public types      : 2
+  - Public functions  : 0
+  - Tests             : 2 (1 unit, 1 ...
+  - Main modules      : shapes
+  - Type relations    : 2
+  - Method signatures : 7 (3 in 2 typ...
+  - Async functions   : 1
+  - Heuristic files   : 0
+"
+        );
+        let quick_stats = renderer.render_quick_stats(&summary);
+        assert!(quick_stats.is_ascii());
+        assert!(quick_stats.lines().all(|line| line.chars().count() <= 40));
+        assert!(quick_stats.contains("  - Description                 : Fix...\n"));
+    }
+
+    #[test]
+    fn status_lines_follow_the_symbol_setting() {
+        let unicode = SummaryRenderer::default();
+        let ascii = SummaryRenderer::new(DEFAULT_WIDTH, false);
+        assert_eq!(unicode.success("done"), "✓ done");
+        assert_eq!(ascii.success("done"), "[ok] done");
+        assert_eq!(unicode.failure("failed"), "✗ failed");
+        assert_eq!(ascii.failure("failed"), "[error] failed");
+        assert_eq!(unicode.hint("retry"), "  → retry");
+        assert_eq!(ascii.hint("retry"), "  -> retry");
+    }
+
+    #[test]
+    fn approximate_groups_digits_above_a_thousand() {
+        let renderer = SummaryRenderer::default();
+        assert_eq!(renderer.approximate(999), "999");
+        assert_eq!(renderer.approximate(12_345), "≈12,300");
+        assert_eq!(SummaryRenderer::new(DEFAULT_WIDTH, false).approximate(1_234_567), "~1,234,600");
+    }
+}
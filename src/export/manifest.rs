@@ -0,0 +1,24 @@
+use serde::Serialize;
+
+/// Estimation grossière du nombre de tokens (≈ 4 caractères par token)
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Description des artefacts produits par un export (`manifest.json`)
+#[derive(Debug, Serialize, Default)]
+pub struct ExportManifest {
+    pub files: Vec<String>,
+    pub bundles: Vec<BundleEntry>,
+    /// Fichiers remplacés par un bloc d'omission (politique never-export)
+    pub omitted: Vec<String>,
+}
+
+/// Bundle thématique et fichiers qu'il contient
+#[derive(Debug, Serialize)]
+pub struct BundleEntry {
+    pub topic: String,
+    pub bundle_file: String,
+    pub files: Vec<String>,
+    pub estimated_tokens: usize,
+}
@@ -1,2 +1,22 @@
+mod branches;
+mod changes;
+mod console;
+mod manifest;
+mod options;
+mod org;
+mod paths;
+mod policy;
 mod project;
-pub use project::ProjectExporter;
\ No newline at end of file
+mod report;
+mod status;
+mod topics;
+pub use options::{BundleMode, ExportOptions};
+pub use org::{OrgRepoSummary, OrgSummary, OrgTotals};
+pub use project::ProjectExporter;
+pub use report::{render_api_surface, render_report, render_type_index};
+pub use policy::{NeverExportPolicy, NEVER_EXPORT_FILE, OMISSION_STUB};
+pub use console::{SummaryRenderer, DEFAULT_WIDTH as DEFAULT_RENDER_WIDTH};
+pub use changes::{MetricChange, RunChanges};
+pub use branches::{BranchComparison, FunctionChange, TypeChange};
+pub use status::{RepoOutcome, RepoStatus, RunStatus, StatusRecorder, DEFAULT_STATUS_FILE};
+pub use paths::sanitize_file_name;
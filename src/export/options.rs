@@ -0,0 +1,41 @@
+use std::str::FromStr;
+
+/// Mode de découpage du bundle complet
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BundleMode {
+    /// Un seul fichier `complete_analysis.txt`
+    #[default]
+    Single,
+    /// Un bundle supplémentaire par thème détecté
+    Topic,
+}
+
+impl FromStr for BundleMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "single" | "none" => Ok(BundleMode::Single),
+            "topic" => Ok(BundleMode::Topic),
+            other => Err(format!("Invalid bundle mode: {} (expected single or topic)", other)),
+        }
+    }
+}
+
+/// Options contrôlant la génération des artefacts d'export
+#[derive(Debug, Clone, Default)]
+pub struct ExportOptions {
+    pub bundle_by: BundleMode,
+    /// Motifs gitignore des chemins dont le contenu n'est jamais exporté
+    pub never_export: Vec<String>,
+    /// Retire les en-têtes de licence répétés du contenu exporté
+    pub strip_headers: bool,
+    /// Sous-répertoire analysé, ajouté au nom du dossier d'export
+    pub subpath: Option<String>,
+    /// Branche comparée (`--compare`), ajoutée au nom du dossier d'export
+    pub branch: Option<String>,
+    /// Exporte le texte intégral des fichiers de licence au lieu d'une ligne de remplacement
+    pub include_licenses: bool,
+    /// Laisse les exemples (`examples/`) et bancs d'essai (`benches/`) hors de l'export
+    pub skip_examples: bool,
+}
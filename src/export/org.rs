@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::paths::sanitize_file_name;
+use crate::types::analysis::ProjectSummary;
+
+/// Chiffres clés d'un dépôt de l'organisation
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct OrgRepoSummary {
+    pub repo_url: String,
+    /// `false` si l'analyse du dépôt a échoué (détails dans `run_status.json`)
+    pub analyzed: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub primary_language: Option<String>,
+    pub total_files: i32,
+    pub total_rust_files: i32,
+    pub total_public_types: i32,
+    pub total_public_functions: i32,
+    pub total_tests: i32,
+}
+
+/// Totaux sur l'ensemble des dépôts analysés
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OrgTotals {
+    pub repositories_analyzed: usize,
+    pub repositories_failed: usize,
+    pub total_files: i64,
+    pub total_rust_files: i64,
+    pub total_public_types: i64,
+    pub total_public_functions: i64,
+    pub total_tests: i64,
+    /// Octets par langage (répartition GitHub) cumulés sur les dépôts
+    pub languages: BTreeMap<String, u64>,
+    /// Nombre de dépôts par langage principal
+    pub primary_languages: BTreeMap<String, usize>,
+}
+
+/// Agrégat des analyses d'une organisation (`output/<org>/org_summary.json`)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct OrgSummary {
+    pub org: String,
+    pub repositories: Vec<OrgRepoSummary>,
+    pub totals: OrgTotals,
+}
+
+impl OrgSummary {
+    pub fn new(org: &str) -> Self {
+        Self {
+            org: org.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Ajoute un dépôt analysé
+    pub fn record(&mut self, summary: &ProjectSummary) {
+        let overview = &summary.project_overview;
+        let structure = &summary.repository_structure;
+        let totals = &mut self.totals;
+
+        totals.repositories_analyzed += 1;
+        totals.total_files += summary.total_files as i64;
+        totals.total_rust_files += overview.total_rust_files as i64;
+        totals.total_public_types += overview.total_public_types as i64;
+        totals.total_public_functions += overview.total_public_functions as i64;
+        totals.total_tests += overview.total_tests as i64;
+        for (language, bytes) in &structure.languages {
+            *totals.languages.entry(language.clone()).or_default() += bytes;
+        }
+        if let Some(language) = &structure.primary_language {
+            *totals.primary_languages.entry(language.clone()).or_default() += 1;
+        }
+
+        self.repositories.push(OrgRepoSummary {
+            repo_url: summary.repo_url.clone(),
+            analyzed: true,
+            primary_language: structure.primary_language.clone(),
+            total_files: summary.total_files,
+            total_rust_files: overview.total_rust_files,
+            total_public_types: overview.total_public_types,
+            total_public_functions: overview.total_public_functions,
+            total_tests: overview.total_tests,
+        });
+    }
+
+    /// Ajoute un dépôt dont l'analyse a échoué
+    pub fn record_failure(&mut self, repo_url: &str) {
+        self.totals.repositories_failed += 1;
+        self.repositories.push(OrgRepoSummary {
+            repo_url: repo_url.to_string(),
+            analyzed: false,
+            primary_language: None,
+            total_files: 0,
+            total_rust_files: 0,
+            total_public_types: 0,
+            total_public_functions: 0,
+            total_tests: 0,
+        });
+    }
+
+    /// Écrit `<output_dir>/<org>/org_summary.json` et renvoie son chemin
+    pub fn write(&self, output_dir: &Path) -> std::io::Result<PathBuf> {
+        let dir = output_dir.join(sanitize_file_name(&self.org));
+        fs::create_dir_all(&dir)?;
+        let path = dir.join("org_summary.json");
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        fs::write(&path, json)?;
+        Ok(path)
+    }
+}
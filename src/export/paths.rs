@@ -0,0 +1,76 @@
+/// Caractères interdits dans un nom de fichier Windows (en plus des caractères de contrôle)
+const INVALID_CHARS: [char; 9] = ['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Noms réservés par Windows, quelle que soit l'extension (`aux.rs`, `con.txt`, ...)
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL",
+    "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8", "COM9",
+    "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Rend un composant de chemin valide sur toutes les plateformes.
+///
+/// Les règles Windows sont appliquées partout pour que les exports restent
+/// portables : caractères interdits remplacés par `_`, points et espaces finaux
+/// retirés, noms réservés préfixés par `_`.
+pub fn sanitize_file_name(name: &str) -> String {
+    let mut sanitized: String = name
+        .chars()
+        .map(|c| if c.is_control() || INVALID_CHARS.contains(&c) { '_' } else { c })
+        .collect();
+
+    let trimmed_len = sanitized.trim_end_matches(['.', ' ']).len();
+    sanitized.truncate(trimmed_len);
+
+    if sanitized.is_empty() {
+        return "_".to_string();
+    }
+
+    let stem = sanitized.split('.').next().unwrap_or("").trim_end();
+    if RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem)) {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reserved_names_are_prefixed_whatever_the_extension_or_case() {
+        assert_eq!(sanitize_file_name("aux.rs"), "_aux.rs");
+        assert_eq!(sanitize_file_name("CON.txt"), "_CON.txt");
+        assert_eq!(sanitize_file_name("com1"), "_com1");
+        assert_eq!(sanitize_file_name("Lpt9.tar.gz"), "_Lpt9.tar.gz");
+        assert_eq!(sanitize_file_name("nul ."), "_nul");
+        // Seul le nom exact est réservé
+        assert_eq!(sanitize_file_name("auxiliary.rs"), "auxiliary.rs");
+        assert_eq!(sanitize_file_name("com10"), "com10");
+        assert_eq!(sanitize_file_name("my.con"), "my.con");
+    }
+
+    #[test]
+    fn forbidden_and_control_characters_become_underscores() {
+        assert_eq!(sanitize_file_name(r#"a<b>c:d"e/f\g|h?i*j"#), "a_b_c_d_e_f_g_h_i_j");
+        assert_eq!(sanitize_file_name("tab\there\n"), "tab_here_");
+        assert_eq!(sanitize_file_name("owner_repo_feature/foo"), "owner_repo_feature_foo");
+        assert_eq!(sanitize_file_name("naïve – ünïcode.rs"), "naïve – ünïcode.rs");
+    }
+
+    #[test]
+    fn trailing_dots_and_spaces_are_removed() {
+        assert_eq!(sanitize_file_name("notes."), "notes");
+        assert_eq!(sanitize_file_name("notes. . "), "notes");
+        assert_eq!(sanitize_file_name(" leading.rs"), " leading.rs");
+        assert_eq!(sanitize_file_name(".gitignore"), ".gitignore");
+    }
+
+    #[test]
+    fn empty_results_fall_back_to_an_underscore() {
+        assert_eq!(sanitize_file_name(""), "_");
+        assert_eq!(sanitize_file_name("..."), "_");
+        assert_eq!(sanitize_file_name(" . "), "_");
+        assert_eq!(sanitize_file_name("?"), "_");
+    }
+}
@@ -0,0 +1,61 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use std::fs;
+use std::path::Path;
+
+/// Contenu de remplacement des fichiers bloqués par la politique
+pub const OMISSION_STUB: &str = "[content omitted: path matches the never-export policy]";
+
+/// Fichier de configuration lu automatiquement s'il existe
+pub const NEVER_EXPORT_FILE: &str = ".never-export";
+
+/// Chemins dont le contenu ne doit jamais apparaître dans un artefact d'export
+///
+/// Les motifs suivent la syntaxe gitignore.
+#[derive(Debug, Clone)]
+pub struct NeverExportPolicy {
+    matcher: Gitignore,
+}
+
+impl Default for NeverExportPolicy {
+    fn default() -> Self {
+        Self { matcher: Gitignore::empty() }
+    }
+}
+
+impl NeverExportPolicy {
+    /// Construit la politique à partir de motifs gitignore
+    pub fn from_patterns(patterns: &[String]) -> std::io::Result<Self> {
+        let mut builder = GitignoreBuilder::new("");
+        for pattern in patterns {
+            builder
+                .add_line(None, pattern)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        }
+        let matcher = builder
+            .build()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        Ok(Self { matcher })
+    }
+
+    /// Lit les motifs d'un fichier au format gitignore
+    pub fn read_patterns(path: &Path) -> std::io::Result<Vec<String>> {
+        Ok(fs::read_to_string(path)?
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(str::to_string)
+            .collect())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.matcher.is_empty()
+    }
+
+    /// Indique si le contenu de `path` doit être retenu
+    pub fn blocks(&self, path: &str) -> bool {
+        !self.is_empty()
+            && self.matcher
+                .matched_path_or_any_parents(path.trim_start_matches('/'), false)
+                .is_ignore()
+    }
+}
@@ -1,53 +1,212 @@
+use std::collections::BTreeMap;
 use std::path::{Path, PathBuf};
 use std::fs;
 use std::io::Write;
-use serde::Serialize;
+
+use super::changes::RunChanges;
+use super::manifest::{estimate_tokens, BundleEntry, ExportManifest};
+use super::options::{BundleMode, ExportOptions};
+use super::paths::sanitize_file_name;
+use super::policy::{NeverExportPolicy, OMISSION_STUB};
+use super::report::{render_api_surface, render_report, render_todos};
+use crate::types::analysis::{
+    BoilerplateHeader, BuildScript, CrateOverview, Hotspots, MethodSignature, ProjectSummary, PullRequestSummary,
+    RepositoryMetadata, TypeRelations,
+};
+use crate::types::locator::{Provider, RepoLocator};
+use super::topics::TopicClassifier;
+use crate::analysis::license::{detect_license_text, is_license_file};
+use crate::analysis::file::categorize_path;
+use crate::analysis::workspace::crate_root;
+use crate::types::{FileCategory, SharedStr};
 
 const DELIMITER: &str = "\n<document>\n<source>{}</source>\n<document_content>\n{}\n</document_content>\n</document>\n";
 const CHUNK_SIZE: usize = 5;
 
 pub struct ProjectExporter {
     project_dir: PathBuf,
-    current_files: Vec<(String, String)>,
+    /// Fichiers en attente d'écriture, par crate membre (répertoire, vide hors espace
+    /// de travail) : un chunk ne mélange pas deux crates
+    current_files: BTreeMap<String, Vec<(String, SharedStr)>>,
+    /// Racines des crates membres de l'espace de travail (répertoire → nom)
+    crate_roots: BTreeMap<String, String>,
     chunk_counter: usize,
+    options: ExportOptions,
+    manifest: ExportManifest,
+    /// Fichiers des bundles thématiques (`--bundle-by topic`), contenu partagé avec les chunks
+    bundle_files: Vec<(String, SharedStr)>,
+    topic_classifier: TopicClassifier,
+    policy: NeverExportPolicy,
+    boilerplate_headers: Vec<BoilerplateHeader>,
+    metadata: Option<RepositoryMetadata>,
+    pull_request: Option<PullRequestSummary>,
+    directory_tree: String,
+    hotspots: Hotspots,
+    description: Option<String>,
+    build_script: Option<BuildScript>,
 }
 
 impl ProjectExporter {
     pub fn new(repo_url: &str) -> std::io::Result<Self> {
+        Self::with_options(repo_url, ExportOptions::default())
+    }
 
-        let repo_name = repo_url
-            .split('/')
-            .last()
-            .unwrap_or("unknown_repo")
-            .replace(".git", "");
+    pub fn with_options(repo_url: &str, options: ExportOptions) -> std::io::Result<Self> {
+
+        // `owner_repo` pour un dépôt GitHub, le nom du répertoire pour une source
+        // locale (`file://`), le libellé tel quel sinon (archive)
+        let repo_name = match repo_url.strip_prefix("file://") {
+            Some(path) => Path::new(path)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_else(|| "local".to_string()),
+            // Forge non reconnue à l'hôte (`--provider`) : forme Gitea (`src/branch/...`),
+            // puis projet GitLab dans un sous-groupe
+            None => repo_url
+                .parse::<RepoLocator>()
+                .or_else(|_| RepoLocator::parse_as(repo_url, Some(Provider::Gitea)))
+                .or_else(|_| RepoLocator::parse_as(repo_url, Some(Provider::Gitlab)))
+                .map(|locator| locator.output_dir_name())
+                .unwrap_or_else(|_| repo_url.to_string()),
+        };
+        // Deux sous-répertoires ou deux branches comparées d'un même dépôt ont
+        // chacun leur dossier
+        let mut repo_name = repo_name;
+        for suffix in [&options.subpath, &options.branch].into_iter().flatten() {
+            repo_name = format!("{}_{}", repo_name, suffix.replace('/', "_"));
+        }
+        let repo_name = sanitize_file_name(&repo_name);
 
         let project_dir = Path::new("output").join(&repo_name);
         fs::create_dir_all(&project_dir)?;
+        let policy = NeverExportPolicy::from_patterns(&options.never_export)?;
         
         Ok(Self {
             project_dir,
-            current_files: Vec::new(),
+            current_files: BTreeMap::new(),
+            crate_roots: BTreeMap::new(),
             chunk_counter: 0,
+            options,
+            manifest: ExportManifest::default(),
+            bundle_files: Vec::new(),
+            topic_classifier: TopicClassifier::new(),
+            policy,
+            boilerplate_headers: Vec::new(),
+            metadata: None,
+            pull_request: None,
+            directory_tree: String::new(),
+            hotspots: Hotspots::default(),
+            description: None,
+            build_script: None,
         })
     }
+
+    /// Déclare l'arborescence des fichiers, présentée juste après les métadonnées du dépôt
+    pub fn set_directory_tree(&mut self, directory_tree: &str) {
+        self.directory_tree = directory_tree.to_string();
+    }
+
+    /// Déclare les fichiers les plus lourds, présentés après l'arborescence
+    pub fn set_hotspots(&mut self, hotspots: &Hotspots) {
+        self.hotspots = hotspots.clone();
+    }
+
+    /// Déclare la présentation du projet tirée du README, ajoutée aux métadonnées du dépôt
+    pub fn set_description(&mut self, description: Option<&str>) {
+        self.description = description.map(str::to_string);
+    }
+
+    /// Déclare le script de build du dépôt, signalé avec les métadonnées : une partie du
+    /// code peut n'exister qu'à la compilation
+    pub fn set_build_script(&mut self, build_script: Option<&BuildScript>) {
+        self.build_script = build_script.cloned();
+    }
+
+    /// Déclare le graphe de types du projet, qui rattache aux bundles thématiques les
+    /// fichiers déclarant les types utilisés par un thème
+    pub fn set_type_graph(&mut self, relations: &[TypeRelations], signatures: &[MethodSignature]) {
+        if self.options.bundle_by == BundleMode::Topic {
+            self.topic_classifier = TopicClassifier::from_type_graph(relations, signatures);
+        }
+    }
+
+    /// Déclare les crates membres de l'espace de travail : les chunks sont regroupés par crate
+    pub fn set_workspace_members(&mut self, members: &[CrateOverview]) {
+        self.crate_roots = members.iter().map(|member| (member.path.clone(), member.name.clone())).collect();
+    }
+
+    /// Déclare la pull request analysée, présentée après les métadonnées du dépôt
+    pub fn set_pull_request(&mut self, pull_request: Option<&PullRequestSummary>) {
+        self.pull_request = pull_request.cloned();
+    }
+
+    /// Déclare les métadonnées du dépôt, placées en tête de l'export complet
+    pub fn set_metadata(&mut self, metadata: Option<&RepositoryMetadata>) {
+        self.metadata = metadata.cloned();
+    }
+
+    /// Déclare les en-têtes boilerplate à retirer (avec `strip_headers`)
+    pub fn set_boilerplate_headers(&mut self, headers: &[BoilerplateHeader]) {
+        self.boilerplate_headers = headers.to_vec();
+    }
+
+    /// Retire l'en-tête boilerplate en tête du contenu, s'il est présent
+    fn strip_boilerplate(&self, content: String) -> String {
+        if !self.options.strip_headers {
+            return content;
+        }
+        let normalized = content.replace("\r\n", "\n");
+        for header in &self.boilerplate_headers {
+            if let Some(rest) = normalized.strip_prefix(&header.text) {
+                return rest.trim_start_matches('\n').to_string();
+            }
+        }
+        content
+    }
     
     pub fn add_file(&mut self, filename: String, content: String) -> std::io::Result<()> {
-        self.current_files.push((filename, content));
+        if self.options.skip_examples
+            && matches!(categorize_path(&filename), FileCategory::Example | FileCategory::Benchmark)
+        {
+            return Ok(());
+        }
+        let content = if self.policy.blocks(&filename) {
+            println!("  ⊘ Content of {} withheld (never-export policy)", filename);
+            self.manifest.omitted.push(filename.clone());
+            OMISSION_STUB.to_string()
+        } else if !self.options.include_licenses && is_license_file(&filename) {
+            // Texte standard sans intérêt pour l'analyse : seule son identité est gardée
+            match detect_license_text(&content) {
+                Some(spdx) => format!("[{} license text omitted]", spdx),
+                None => "[license text omitted]".to_string(),
+            }
+        } else {
+            self.strip_boilerplate(content)
+        };
+
+        self.manifest.files.push(filename.clone());
+        let content = SharedStr::from(content);
+        if self.options.bundle_by == BundleMode::Topic {
+            self.bundle_files.push((filename.clone(), content.clone()));
+        }
+        let root = crate_root(&self.crate_roots, &filename).unwrap_or_default().to_string();
+        let files = self.current_files.entry(root.clone()).or_default();
+        files.push((filename, content));
         
-        if self.current_files.len() >= CHUNK_SIZE {
-            self.write_chunk()?;
+        if files.len() >= CHUNK_SIZE {
+            self.write_chunk(&root)?;
         }
         
         Ok(())
     }
     
-    fn write_chunk(&mut self) -> std::io::Result<()> {
-        if self.current_files.is_empty() {
+    fn write_chunk(&mut self, root: &str) -> std::io::Result<()> {
+        let Some(files) = self.current_files.remove(root).filter(|files| !files.is_empty()) else {
             return Ok(());
-        }
+        };
         
         let mut chunk_content = String::new();
-        for (filename, content) in &self.current_files {
+        for (filename, content) in &files {
             chunk_content.push_str(&DELIMITER.replace("{}", filename));
             chunk_content.push_str(content);
         }
@@ -58,28 +217,139 @@ impl ProjectExporter {
         let mut file = fs::File::create(chunk_filename)?;
         file.write_all(chunk_content.as_bytes())?;
         
-        self.current_files.clear();
         self.chunk_counter += 1;
         
         Ok(())
     }
     
-    pub fn write_summary<T: Serialize>(&self, summary: &T) -> std::io::Result<()> {
-        let json = serde_json::to_string_pretty(summary)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    pub fn write_summary(&self, summary: &ProjectSummary) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(&self.redact(summary))
+            .map_err(std::io::Error::other)?;
         
+        // Conserve l'analyse de l'exécution précédente pour comparaison
         let summary_path = self.project_dir.join("analysis.json");
+        let previous_path = self.project_dir.join("analysis.previous.json");
+        if summary_path.exists() {
+            fs::rename(&summary_path, &previous_path)?;
+        } else if previous_path.exists() {
+            fs::remove_file(&previous_path)?;
+        }
         fs::write(summary_path, json)?;
         
         Ok(())
     }
     
+    /// Écrit le rapport Markdown (`report.md`)
+    pub fn write_report(&self, summary: &ProjectSummary) -> std::io::Result<()> {
+        fs::write(self.project_dir.join("report.md"), render_report(&self.redact(summary)))
+    }
+
+    /// Écrit la liste des `TODO` et `FIXME` du code, fichier par fichier (`todos.md`)
+    pub fn write_todos(&self, summary: &ProjectSummary) -> std::io::Result<()> {
+        fs::write(self.project_dir.join("todos.md"), render_todos(&self.redact(summary)))
+    }
+
+    /// Écrit l'API publique du projet, module par module (`api_surface.md`)
+    pub fn write_api_surface(&self, summary: &ProjectSummary) -> std::io::Result<()> {
+        fs::write(self.project_dir.join("api_surface.md"), render_api_surface(&self.redact(summary)))
+    }
+
+    /// Répertoire d'export du dépôt
+    pub fn project_dir(&self) -> &Path {
+        &self.project_dir
+    }
+
+    /// Compare l'analyse écrite à celle de l'exécution précédente et écrit `changes.md`
+    pub fn write_changes(&self) -> std::io::Result<RunChanges> {
+        // Les analyses d'anciennes versions sont migrées avant comparaison
+        let read = |name: &str| -> Option<serde_json::Value> {
+            let path = self.project_dir.join(name);
+            match crate::schema::load(&path) {
+                Ok(summary) => serde_json::to_value(summary).ok(),
+                Err(_) => serde_json::from_str(&fs::read_to_string(path).ok()?).ok(),
+            }
+        };
+        let current = read("analysis.json").unwrap_or_default();
+        let changes = RunChanges::compute(read("analysis.previous.json").as_ref(), &current);
+
+        fs::write(self.project_dir.join("changes.md"), changes.render_markdown())?;
+        Ok(changes)
+    }
+
+    /// Retire des résumés de fichiers tout extrait de contenu bloqué par la politique
+    fn redact(&self, summary: &ProjectSummary) -> ProjectSummary {
+        let mut redacted = summary.clone();
+        if self.policy.is_empty() {
+            return redacted;
+        }
+        for file_summary in redacted.file_summaries.iter_mut() {
+            if self.policy.blocks(&file_summary.path) {
+                file_summary.summary = OMISSION_STUB.to_string();
+                file_summary.heuristic = None;
+            }
+        }
+        redacted.api_surface.retain(|module| !self.policy.blocks(&module.path));
+        redacted.todos.retain(|todo| !self.policy.blocks(&todo.file));
+        redacted.boilerplate_headers.retain(|header| !self.policy.blocks(&header.source));
+
+        let overview = &mut redacted.project_overview;
+        overview.configuration.constants.retain(|constant| !self.policy.blocks(&constant.defined_in));
+        for relation in overview.type_relations.iter_mut().filter(|relation| self.policy.blocks(&relation.defined_in)) {
+            relation.doc = None;
+        }
+        for signature in overview.method_signatures.iter_mut().filter(|signature| self.policy.blocks(&signature.defined_in)) {
+            signature.doc = None;
+        }
+        for key in redacted.config_matrix.iter_mut() {
+            if key.template.as_deref().is_some_and(|template| self.policy.blocks(template)) {
+                key.default = None;
+                key.comment = None;
+            }
+        }
+        redacted
+    }
+    
     pub fn finish(mut self) -> std::io::Result<()> {
-        // Écrire le dernier chunk si nécessaire
-        self.write_chunk()?;
+        // Écrire les derniers chunks si nécessaire, crate par crate
+        let roots: Vec<String> = self.current_files.keys().cloned().collect();
+        for root in roots {
+            self.write_chunk(&root)?;
+        }
         
         // Créer l'index qui combine tous les chunks
         let mut index_content = String::new();
+
+        // Présenter d'abord le dépôt analysé
+        let mut repository: Vec<String> = self.metadata.iter().map(RepositoryMetadata::render).collect();
+        if let Some(description) = &self.description {
+            repository.push(format!("About (from README): {}", description));
+        }
+        if let Some(build_script) = &self.build_script {
+            repository.push(format!(
+                "Build script: {} (runs at compile time, part of the code may be generated)",
+                build_script.describe()
+            ));
+        }
+        if !repository.is_empty() {
+            index_content.push_str("\n<document>\n<source>repository.txt</source>\n<document_content>\n");
+            index_content.push_str(&repository.join("\n"));
+            index_content.push_str("\n</document_content>\n</document>\n");
+        }
+        if !self.directory_tree.is_empty() {
+            index_content.push_str("\n<document>\n<source>directory_tree.txt</source>\n<document_content>\n");
+            index_content.push_str(&self.directory_tree);
+            index_content.push_str("</document_content>\n</document>\n");
+        }
+        if !self.hotspots.is_empty() {
+            index_content.push_str("\n<document>\n<source>hotspots.txt</source>\n<document_content>\n");
+            index_content.push_str(&self.hotspots.render());
+            index_content.push_str("\n</document_content>\n</document>\n");
+        }
+        if let Some(pull_request) = &self.pull_request {
+            index_content.push_str("\n<document>\n<source>pull_request.txt</source>\n<document_content>\n");
+            index_content.push_str(&pull_request.render());
+            index_content.push_str("\n</document_content>\n</document>\n");
+        }
         
         // Ajouter le JSON d'analyse
         index_content.push_str("\n<document>\n<source>analysis.json</source>\n<document_content>\n");
@@ -87,6 +357,24 @@ impl ProjectExporter {
             index_content.push_str(&analysis);
         }
         index_content.push_str("\n</document_content>\n</document>\n");
+
+        // Ajouter une copie unique des en-têtes retirés
+        if self.options.strip_headers {
+            for header in self.boilerplate_headers.iter().filter(|header| !self.policy.blocks(&header.source)) {
+                index_content.push_str(&format!(
+                    "\n<document>\n<source>boilerplate_header_{}.txt</source>\n<document_content>\n\
+                    The following header was stripped from {} file(s):\n\n{}\n</document_content>\n</document>\n",
+                    header.hash, header.affected_files, header.text
+                ));
+            }
+        }
+
+        // Ajouter le rapport Markdown (index des types)
+        if let Ok(report) = fs::read_to_string(self.project_dir.join("report.md")) {
+            index_content.push_str("\n<document>\n<source>report.md</source>\n<document_content>\n");
+            index_content.push_str(&report);
+            index_content.push_str("\n</document_content>\n</document>\n");
+        }
         
         // Ajouter tous les chunks dans l'ordre
         let chunks_dir = self.project_dir.join("chunks");
@@ -108,8 +396,13 @@ impl ProjectExporter {
             ## Files\n\
             - `complete_analysis.txt`: **Single file containing everything** - Use this for easy copy-paste into AI tools\n\
             - `analysis.json`: Complete analysis of the repository in JSON format\n\
+            - `changes.md`: What changed compared to the previous run (`analysis.previous.json`)\n\
+            - `report.md`: Markdown report with the alphabetical type index\n\
+            - `todos.md`: TODO, FIXME, HACK, XXX and BUG comments with their location\n\
+            - `manifest.json`: Exported files and topic bundles with token estimates\n\
+            - `bundle_<topic>.txt`: Topic-scoped bundles (only with `--bundle-by topic`)\n\
             - `chunks/`: Directory containing code files split into manageable chunks\n\
-                - Each chunk contains up to {} files\n\
+                - Each chunk contains up to {} files, all from the same workspace crate\n\
                 - Files are formatted with XML-style tags for easy parsing\n\n\
             ## Format\n\
             Files are wrapped in XML-style tags:\n\
@@ -131,7 +424,149 @@ impl ProjectExporter {
         
         let readme_path = self.project_dir.join("README.md");
         fs::write(readme_path, readme_content)?;
+
+        if self.options.bundle_by == BundleMode::Topic {
+            self.write_topic_bundles()?;
+        }
+
+        if !self.manifest.omitted.is_empty() {
+            println!("  ⊘ {} file(s) withheld by the never-export policy (see manifest.json)",
+                self.manifest.omitted.len());
+        }
+
+        let manifest = serde_json::to_string_pretty(&self.manifest)
+            .map_err(std::io::Error::other)?;
+        fs::write(self.project_dir.join("manifest.json"), manifest)?;
         
         Ok(())
     }
-}
\ No newline at end of file
+
+    /// Écrit un bundle `bundle_<thème>.txt` par thème détecté
+    fn write_topic_bundles(&mut self) -> std::io::Result<()> {
+        let files: Vec<(&str, &str)> = self.bundle_files
+            .iter()
+            .map(|(path, content)| (path.as_str(), &**content))
+            .collect();
+        let bundles = self.topic_classifier.classify(&files);
+        let contents: std::collections::HashMap<&str, &str> = files.into_iter().collect();
+
+        for (topic, files) in bundles {
+            let mut body = String::new();
+            for path in &files {
+                body.push_str(&DELIMITER.replace("{}", path));
+                body.push_str(contents.get(path.as_str()).copied().unwrap_or_default());
+            }
+
+            let overview = format!(
+                "# Topic bundle: {}\n\n\
+                This bundle contains the {} file(s) related to the `{}` area of the repository.\n\
+                Estimated tokens: {}\n\n\
+                ## Files\n{}",
+                topic,
+                files.len(),
+                topic,
+                estimate_tokens(&body),
+                files.iter().map(|f| format!("- {}\n", f)).collect::<String>()
+            );
+
+            let mut bundle = DELIMITER.replacen("{}", "bundle_overview.md", 1).replacen("{}", &overview, 1);
+            bundle.push_str(&body);
+
+            let bundle_file = sanitize_file_name(&format!("bundle_{}.txt", topic));
+            fs::write(self.project_dir.join(&bundle_file), &bundle)?;
+
+            self.manifest.bundles.push(BundleEntry {
+                topic,
+                bundle_file,
+                files,
+                estimated_tokens: estimate_tokens(&bundle),
+            });
+        }
+
+        Ok(())
+    }
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::options::AnalysisOptions;
+    use crate::analysis::repository::RepositoryAnalyzer;
+
+    const MARKER: &str = "ZETA_SECRET_7F1C";
+
+    /// Écrit `content` sous `root`, en créant les répertoires parents
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    /// Contenu de tous les fichiers sous `dir`
+    fn read_all(dir: &Path) -> Vec<(PathBuf, String)> {
+        let mut contents = Vec::new();
+        for entry in fs::read_dir(dir).unwrap() {
+            let path = entry.unwrap().path();
+            if path.is_dir() {
+                contents.extend(read_all(&path));
+            } else {
+                contents.push((path.clone(), String::from_utf8_lossy(&fs::read(&path).unwrap()).into_owned()));
+            }
+        }
+        contents
+    }
+
+    #[tokio::test]
+    async fn blocked_file_content_never_reaches_the_export() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let header = format!("// Copyright {}\n// Internal use only\n// Do not distribute\n", MARKER);
+        write(root, "Cargo.toml", "[package]\nname = \"fixture\"\nversion = \"0.1.0\"\n");
+        write(root, "src/lib.rs", "pub mod util;\n\npub fn token() -> Option<String> {\n    std::env::var(\"API_TOKEN\").ok()\n}\n");
+        write(root, "src/util.rs", "pub fn noop() {}\n");
+        write(
+            root,
+            "internal/vault.rs",
+            &format!(
+                "{header}\n/// Vault for {m}\npub struct Vault;\n\n/// Opens {m}\npub fn open() {{}}\n\n\
+                pub const KEY: &str = \"{m}\";\n\n// TODO: rotate {m}\n",
+                header = header,
+                m = MARKER
+            ),
+        );
+        write(root, "internal/seal.rs", &format!("{}\npub fn seal() {{}}\n", header));
+        write(root, "internal/.env.example", &format!("# Token for {m}\nAPI_TOKEN={m}\n", m = MARKER));
+
+        let options = AnalysisOptions { no_cache: true, ..Default::default() };
+        let (summary, _) = RepositoryAnalyzer::with_options(options).analyze_local(root).await.unwrap();
+        // L'analyse voit le contenu bloqué : seul l'export doit le retenir
+        assert!(serde_json::to_string(&summary).unwrap().contains(MARKER));
+        assert!(summary.boilerplate_headers.iter().any(|header| header.source.starts_with("internal/")));
+
+        let export_options = ExportOptions {
+            never_export: vec!["internal/".to_string()],
+            strip_headers: true,
+            ..Default::default()
+        };
+        let mut exporter = ProjectExporter::with_options(&summary.repo_url, export_options).unwrap();
+        let project_dir = exporter.project_dir().to_path_buf();
+        exporter.set_boilerplate_headers(&summary.boilerplate_headers);
+        for file_summary in &summary.file_summaries {
+            let content = fs::read_to_string(root.join(&*file_summary.path)).unwrap();
+            exporter.add_file(file_summary.path.to_string(), content).unwrap();
+        }
+        exporter.set_directory_tree(&summary.directory_tree);
+        exporter.set_hotspots(&summary.hotspots);
+        exporter.write_summary(&summary).unwrap();
+        exporter.write_report(&summary).unwrap();
+        exporter.write_api_surface(&summary).unwrap();
+        exporter.write_todos(&summary).unwrap();
+        exporter.finish().unwrap();
+
+        let exported = read_all(&project_dir);
+        fs::remove_dir_all(&project_dir).unwrap();
+        assert!(!exported.is_empty());
+        for (path, content) in exported {
+            assert!(!content.contains(MARKER), "{} leaks blocked content", path.display());
+        }
+    }
+}
@@ -0,0 +1,466 @@
+use std::collections::HashMap;
+
+use crate::types::analysis::{ApiItem, ApiItemKind, ModuleNode, ProjectSummary, StructField, TypeKind};
+use crate::types::Visibility;
+
+/// Variantes affichées par énumération dans la section `## Enums`
+const ENUM_VARIANTS: usize = 12;
+
+/// Doc-comments des types et fonctions : fichier, type propriétaire (méthodes) et nom
+type Docs<'a> = HashMap<(&'a str, Option<&'a str>, &'a str), &'a str>;
+
+/// Champs des structures : fichier et nom du type
+type Fields<'a> = HashMap<(&'a str, &'a str), &'a [StructField]>;
+
+/// Génère le rapport Markdown (`report.md`) d'une analyse
+pub fn render_report(summary: &ProjectSummary) -> String {
+    let mut report = format!("# Analysis report: {}\n\n", summary.repo_url);
+    report.push_str(&format!(
+        "- Branch analyzed: {}\n- Files analyzed: {}\n\n",
+        summary.repository_structure.branch_analyzed, summary.files_analyzed_label()
+    ));
+
+    report.push_str(&render_configuration(summary));
+    report.push_str(&render_workspace(summary));
+    report.push_str(&render_module_tree(summary));
+    report.push_str(&render_traits(summary));
+    report.push_str(&render_enums(summary));
+    report.push_str(&render_conversions(summary));
+    report.push_str(&render_error_types(summary));
+    report.push_str(&render_possibly_unused(summary));
+    report.push_str(&render_feature_flags(summary));
+    report.push_str(&render_patterns(summary));
+    report.push_str(&render_type_index(summary));
+    report
+}
+
+/// Section espace de travail : statistiques propres à chaque crate membre (rien hors
+/// espace de travail)
+pub fn render_workspace(summary: &ProjectSummary) -> String {
+    let members = &summary.project_overview.workspace_members;
+    if members.is_empty() {
+        return String::new();
+    }
+    let mut section = String::from("## Workspace\n\n");
+    section.push_str("| Crate | Path | Rust files | Code lines | Public types | Public fns | Dependencies |\n");
+    section.push_str("|---|---|---|---|---|---|---|\n");
+    for member in members {
+        section.push_str(&format!(
+            "| `{}` | `{}` | {} | {} | {} | {} | {} |\n",
+            member.name,
+            if member.path.is_empty() { "." } else { &member.path },
+            member.rust_files,
+            member.rust_code_lines,
+            member.public_types,
+            member.public_functions,
+            member.dependencies.len()
+        ));
+    }
+    section.push('\n');
+    section
+}
+
+/// Section configuration : matrice des clés (modèles et variables lues par le code)
+pub fn render_configuration(summary: &ProjectSummary) -> String {
+    let mut section = String::from("## Configuration\n\n");
+
+    if summary.config_matrix.is_empty() {
+        section.push_str("_No configuration keys found._\n\n");
+        return section;
+    }
+
+    section.push_str("| Key | Used by code | In template | Default | Notes |\n|---|---|---|---|---|\n");
+    for key in &summary.config_matrix {
+        section.push_str(&format!(
+            "| `{}` | {} | {} | {} | {} |\n",
+            key.key,
+            if key.required_by_code { "yes" } else { "no" },
+            if key.documented_in_template { "yes" } else { "no" },
+            key.default.as_deref().map(|value| format!("`{}`", value)).unwrap_or_default(),
+            key.comment.as_deref().unwrap_or("").replace('|', "\\|"),
+        ));
+    }
+    section.push('\n');
+
+    let missing: Vec<&str> = summary.config_matrix
+        .iter()
+        .filter(|key| key.required_by_code && !key.documented_in_template)
+        .map(|key| key.key.as_str())
+        .collect();
+    let unused: Vec<&str> = summary.config_matrix
+        .iter()
+        .filter(|key| key.documented_in_template && !key.required_by_code)
+        .map(|key| key.key.as_str())
+        .collect();
+    if !missing.is_empty() {
+        section.push_str(&format!("- Used in code but missing from templates: {}\n", missing.join(", ")));
+    }
+    if !unused.is_empty() {
+        section.push_str(&format!("- Documented in templates but not read by code: {}\n", unused.join(", ")));
+    }
+    if !missing.is_empty() || !unused.is_empty() {
+        section.push('\n');
+    }
+    section
+}
+
+/// Section modules : arbre des modules déclarés de chaque crate, indenté par niveau
+pub fn render_module_tree(summary: &ProjectSummary) -> String {
+    let mut section = String::from("## Modules\n\n");
+    if summary.project_overview.module_tree.is_empty() {
+        section.push_str("_No crate root (`src/lib.rs`, `src/main.rs`) analyzed._\n\n");
+        return section;
+    }
+    for root in &summary.project_overview.module_tree {
+        render_module(&mut section, root, 0);
+    }
+    section.push('\n');
+    section
+}
+
+/// Ligne d'un module puis de ses sous-modules, décalés de deux espaces par niveau
+fn render_module(section: &mut String, module: &ModuleNode, depth: usize) {
+    let visibility = if module.is_public { "" } else { " (private)" };
+    let file = module.file.as_deref().map_or("no analyzed file".to_string(), |file| format!("`{}`", file));
+    section.push_str(&format!("{}- `{}`{} — {}\n", "  ".repeat(depth), module.name, visibility, file));
+    for child in &module.children {
+        render_module(section, child, depth + 1);
+    }
+}
+
+/// Section traits : traits déclarés par le projet et types du projet qui les implémentent
+pub fn render_traits(summary: &ProjectSummary) -> String {
+    let mut section = String::from("## Traits\n\n");
+
+    let traits: Vec<_> = summary.project_overview.type_relations
+        .iter()
+        .filter(|relation| relation.kind == TypeKind::Trait)
+        .collect();
+    if traits.is_empty() {
+        section.push_str("_No traits declared._\n\n");
+        return section;
+    }
+
+    for relation in traits {
+        let implementors = if relation.implementors.is_empty() {
+            "no implementor in the project".to_string()
+        } else {
+            let names: Vec<String> = relation.implementors.iter().map(|name| format!("`{}`", name)).collect();
+            format!("implemented by {}", names.join(", "))
+        };
+        let location = if relation.module.is_empty() { &relation.defined_in } else { &relation.module };
+        section.push_str(&format!("- `{}` ({}) — {}\n", relation.type_name, location, implementors));
+    }
+    section.push('\n');
+    section
+}
+
+/// Section énumérations : variantes de chaque énumération du projet et forme de leur charge
+pub fn render_enums(summary: &ProjectSummary) -> String {
+    let mut section = String::from("## Enums\n\n");
+
+    let enums: Vec<_> = summary.project_overview.type_relations
+        .iter()
+        .filter(|relation| relation.kind == TypeKind::Enum)
+        .collect();
+    if enums.is_empty() {
+        section.push_str("_No enums declared._\n\n");
+        return section;
+    }
+
+    for relation in enums {
+        let location = if relation.module.is_empty() { &relation.defined_in } else { &relation.module };
+        section.push_str(&format!("- `{}` ({})\n", relation.enum_outline(ENUM_VARIANTS), location));
+    }
+    section.push('\n');
+    section
+}
+
+/// Section conversions : `From`, `TryFrom` et `Into` touchant un type du projet
+pub fn render_conversions(summary: &ProjectSummary) -> String {
+    let mut section = String::from("## Conversions\n\n");
+    let conversions = &summary.project_overview.conversions;
+    if conversions.is_empty() {
+        section.push_str("_No `From`, `TryFrom` or `Into` implementations involving project types._\n\n");
+        return section;
+    }
+    for conversion in conversions {
+        let external = if conversion.external { ", external" } else { "" };
+        section.push_str(&format!("- `{}` -> `{}` ({}{})\n", conversion.from, conversion.to, conversion.kind, external));
+    }
+    section.push('\n');
+    section
+}
+
+/// Section erreurs : types d'erreur du projet (variantes, traits, conversions entrantes)
+/// et fonctions renvoyant une erreur `anyhow` / `eyre`
+pub fn render_error_types(summary: &ProjectSummary) -> String {
+    let mut section = String::from("## Error types\n\n");
+    let errors = &summary.project_overview.error_types;
+    if errors.is_empty() {
+        section.push_str("_No type implementing `std::error::Error` and no `anyhow` / `eyre` return types._\n\n");
+        return section;
+    }
+    for error in &errors.types {
+        let derived = if error.derived { ", derived" } else { "" };
+        section.push_str(&format!(
+            "### `{}` ({}{})\n\n- Implements: {}\n",
+            error.name,
+            error.defined_in,
+            derived,
+            error.traits.join(", ")
+        ));
+        if !error.variants.is_empty() {
+            let variants: Vec<String> = error.variants.iter().map(|variant| format!("`{}`", variant.render())).collect();
+            section.push_str(&format!("- Variants ({}): {}\n", variants.len(), variants.join(", ")));
+        }
+        if !error.converts_from.is_empty() {
+            let sources: Vec<String> = error.converts_from.iter().map(|source| format!("`{}`", source)).collect();
+            section.push_str(&format!("- Converts from: {}\n", sources.join(", ")));
+        }
+        section.push('\n');
+    }
+    for boundary in &errors.boundaries {
+        section.push_str(&format!(
+            "- `{}` in the return type of {} functions ({} public)\n",
+            boundary.crate_name, boundary.functions, boundary.public_functions
+        ));
+    }
+    if !errors.boundaries.is_empty() {
+        section.push('\n');
+    }
+    section
+}
+
+/// Section éléments publics apparemment inutilisés, par fichier
+pub fn render_possibly_unused(summary: &ProjectSummary) -> String {
+    let mut section = String::from("## Possibly unused (heuristic)\n\n");
+    let unused = &summary.project_overview.possibly_unused;
+    if unused.is_empty() {
+        section.push_str("_Every public type and function is mentioned outside its own file._\n\n");
+        return section;
+    }
+    section.push_str(
+        "Public types and functions whose name never appears outside their own file. \
+         Glob re-exports, other crates and macro-generated code are not seen: check before deleting.\n\n",
+    );
+    for item in unused {
+        section.push_str(&format!("- `{} {}` — `{}`\n", item.kind.keyword(), item.name, item.defined_in));
+    }
+    section.push('\n');
+    section
+}
+
+/// Section fonctionnalités : éléments soumis à chaque fonctionnalité cargo, puis écarts
+/// entre les `cfg` du code et les fonctionnalités déclarées par les `Cargo.toml`
+pub fn render_feature_flags(summary: &ProjectSummary) -> String {
+    let overview = &summary.project_overview;
+    let mut section = String::from("## Feature flags\n\n");
+    if overview.feature_map.is_empty() && overview.declared_features.is_empty() {
+        section.push_str("_No cargo features declared or used._\n\n");
+        return section;
+    }
+    for (feature, items) in &overview.feature_map {
+        section.push_str(&format!("- `{}` ({} items)\n", feature, items.len()));
+        for item in items {
+            section.push_str(&format!("  - `{}`\n", item));
+        }
+    }
+    if !overview.feature_map.is_empty() {
+        section.push('\n');
+    }
+
+    let unused: Vec<&String> = overview
+        .declared_features
+        .iter()
+        .filter(|feature| *feature != "default" && !overview.feature_map.contains_key(*feature))
+        .collect();
+    if !unused.is_empty() {
+        section.push_str("Declared but never used in `cfg`:\n\n");
+        for feature in unused {
+            section.push_str(&format!("- `{}`\n", feature));
+        }
+        section.push('\n');
+    }
+    // Sans manifeste analysé, toute fonctionnalité paraîtrait non déclarée
+    if !overview.declared_features.is_empty() {
+        let undeclared: Vec<&String> =
+            overview.feature_map.keys().filter(|feature| !overview.declared_features.contains(*feature)).collect();
+        if !undeclared.is_empty() {
+            section.push_str("Used in `cfg` but not declared:\n\n");
+            for feature in undeclared {
+                section.push_str(&format!("- `{}`\n", feature));
+            }
+            section.push('\n');
+        }
+    }
+    section
+}
+
+/// Section idiomes : formes de conception reconnues, pour s'orienter dans l'architecture
+pub fn render_patterns(summary: &ProjectSummary) -> String {
+    let mut section = String::from("## Patterns\n\n");
+    if summary.important_patterns.is_empty() {
+        section.push_str("_No common Rust idioms detected._\n\n");
+        return section;
+    }
+    for pattern in &summary.important_patterns {
+        section.push_str(&format!("- **{}** — {}\n", pattern.name, pattern.description));
+    }
+    section.push('\n');
+    section
+}
+
+/// Annexe : index alphabétique des types et de leurs emplacements
+pub fn render_type_index(summary: &ProjectSummary) -> String {
+    let mut index = String::from("## Appendix: type index\n\n");
+
+    if summary.type_index.is_empty() {
+        index.push_str("_No types found._\n");
+        return index;
+    }
+
+    for (type_name, locations) in &summary.type_index {
+        for location in locations {
+            index.push_str(&format!(
+                "- `{}` ({}) — `{}` in {}:{}\n",
+                type_name, location.kind, location.module_path, location.file, location.line
+            ));
+        }
+    }
+    index
+}
+
+/// Travail en suspens (`todos.md`) : commentaires `TODO`, `FIXME`, `HACK`, `XXX` et
+/// `BUG`, regroupés par fichier
+pub fn render_todos(summary: &ProjectSummary) -> String {
+    let mut page = format!("# Outstanding work: {}\n\n", summary.repo_url);
+    if summary.todos.is_empty() {
+        page.push_str("_No TODO, FIXME, HACK, XXX or BUG comments found._\n");
+        return page;
+    }
+    page.push_str(&format!("{} markers found in Rust comments.\n", summary.todos.len()));
+    let mut current_file = None;
+    for todo in &summary.todos {
+        if current_file != Some(&todo.file) {
+            page.push_str(&format!("\n## `{}`\n\n", todo.file));
+            current_file = Some(&todo.file);
+        }
+        if todo.text.is_empty() {
+            page.push_str(&format!("- L{} **{}**\n", todo.line, todo.marker));
+        } else {
+            page.push_str(&format!("- L{} **{}** {}\n", todo.line, todo.marker, todo.text));
+        }
+    }
+    page
+}
+
+/// API publique du projet (`api_surface.md`) : signatures des éléments `pub` de chaque
+/// module précédées de leur doc-comment, méthodes regroupées par bloc `impl`
+pub fn render_api_surface(summary: &ProjectSummary) -> String {
+    let mut surface = format!("# Public API: {}\n\n", summary.repo_url);
+
+    if summary.api_surface.is_empty() {
+        surface.push_str("_No public items found._\n");
+        return surface;
+    }
+
+    let items: usize = summary.api_surface.iter().map(|module| module.items.len()).sum();
+    surface.push_str(&format!(
+        "{} public items in {} files (items under `#[cfg(test)]` excluded).\n",
+        items, summary.api_surface.len()
+    ));
+
+    let overview = &summary.project_overview;
+    let mut docs: Docs = HashMap::new();
+    let mut fields: Fields = HashMap::new();
+    for relation in &overview.type_relations {
+        if let Some(doc) = &relation.doc {
+            docs.insert((&relation.defined_in, None, &relation.type_name), doc);
+        }
+        if !relation.fields.is_empty() {
+            fields.insert((&relation.defined_in, &relation.type_name), &relation.fields);
+        }
+    }
+    for signature in &overview.method_signatures {
+        if let Some(doc) = &signature.doc {
+            docs.insert((&signature.defined_in, signature.owner.as_deref(), &signature.name), doc);
+        }
+    }
+
+    for module in &summary.api_surface {
+        surface.push_str(&format!("\n## `{}`\n\n_{}_\n\n```rust\n", module.module, module.path));
+        for item in module.items.iter().filter(|item| item.owner.is_none()) {
+            push_documented(&mut surface, "", item, &module.path, &docs, &fields);
+        }
+
+        // Méthodes par type, dans l'ordre de leur premier bloc `impl`
+        let mut owners: Vec<&str> = Vec::new();
+        for owner in module.items.iter().filter_map(|item| item.owner.as_deref()) {
+            if !owners.contains(&owner) {
+                owners.push(owner);
+            }
+        }
+        for owner in owners {
+            surface.push_str(&format!("\nimpl {} {{\n", owner));
+            for item in module.items.iter().filter(|item| item.owner.as_deref() == Some(owner)) {
+                push_documented(&mut surface, "    ", item, &module.path, &docs, &fields);
+            }
+            surface.push_str("}\n");
+        }
+        surface.push_str("```\n");
+    }
+    surface
+}
+
+/// Déclaration d'un élément, précédée de son doc-comment s'il en a un (types et
+/// fonctions) ; une structure liste ses champs publics
+fn push_documented(surface: &mut String, indent: &str, item: &ApiItem, path: &str, docs: &Docs, fields: &Fields) {
+    let documented = matches!(
+        item.kind,
+        ApiItemKind::Struct | ApiItemKind::Enum | ApiItemKind::Union | ApiItemKind::Trait | ApiItemKind::Type | ApiItemKind::Fn
+    );
+    if let Some(doc) = docs.get(&(path, item.owner.as_deref(), item.name.as_str())).filter(|_| documented) {
+        for line in doc.lines() {
+            surface.push_str(&format!("{}/// {}\n", indent, line).replace("/// \n", "///\n"));
+        }
+    }
+    let schema = fields
+        .get(&(path, item.name.as_str()))
+        .filter(|_| item.kind == ApiItemKind::Struct)
+        .and_then(|fields| struct_schema(item, fields));
+    let declaration = schema.unwrap_or_else(|| declaration(item));
+    surface.push_str(&format!("{}{}\n", indent, declaration));
+}
+
+/// Structure à champs nommés avec ses champs publics, un par ligne, et `..` si elle en
+/// a d'autres ; `None` si aucun champ n'est public
+fn struct_schema(item: &ApiItem, fields: &[StructField]) -> Option<String> {
+    let head = item.signature.strip_suffix("{ .. }")?;
+    let public: Vec<&StructField> = fields.iter().filter(|field| field.visibility == Visibility::Public).collect();
+    if public.is_empty() {
+        return None;
+    }
+    let mut schema = format!("{}{{\n", head);
+    for field in &public {
+        schema.push_str(&format!("    pub {}: {},\n", field.name, field.field_type));
+    }
+    if public.len() < fields.len() {
+        schema.push_str("    ..\n");
+    }
+    schema.push('}');
+    Some(schema)
+}
+
+/// Signature terminée comme une déclaration sans corps, suivie du module en ligne
+/// qui la contient éventuellement (`// mod inner`)
+fn declaration(item: &ApiItem) -> String {
+    let mut declaration = item.signature.clone();
+    if !declaration.ends_with('}') {
+        declaration.push(';');
+    }
+    if let Some((inline_module, _)) = item.name.rsplit_once("::") {
+        declaration.push_str(&format!(" // mod {}", inline_module));
+    }
+    declaration
+}
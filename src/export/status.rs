@@ -0,0 +1,320 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::panic;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+use crate::capabilities;
+use crate::error::GithubAnalyzerError;
+use crate::types::analysis::RunMetrics;
+
+/// Emplacement par défaut du rapport d'exécution
+pub const DEFAULT_STATUS_FILE: &str = "output/run_status.json";
+
+/// Issue de l'analyse d'un dépôt
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum RepoOutcome {
+    Running,
+    Success,
+    Partial,
+    Failed,
+    Crashed,
+}
+
+/// Statut d'un dépôt dans `run_status.json`
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RepoStatus {
+    pub repo: String,
+    pub status: RepoOutcome,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_kind: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_message: Option<String>,
+    pub duration_ms: u64,
+    pub api_requests: u64,
+    pub fetch_requests: u64,
+    pub rate_limit_waits: u64,
+    pub artifacts: Vec<String>,
+}
+
+/// Rapport d'exécution destiné aux orchestrateurs (`run_status.json`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RunStatus {
+    pub status: RepoOutcome,
+    pub started_at: u64,
+    pub duration_ms: u64,
+    pub repos: Vec<RepoStatus>,
+    /// Sous-systèmes optionnels compilés dans le binaire (nom → activé)
+    #[serde(default)]
+    pub capabilities: BTreeMap<String, bool>,
+}
+
+impl RunStatus {
+    /// Statut global : le pire des statuts par dépôt
+    fn overall(&self) -> RepoOutcome {
+        let statuses = || self.repos.iter().map(|repo| repo.status);
+        if statuses().any(|s| s == RepoOutcome::Crashed || s == RepoOutcome::Running) {
+            RepoOutcome::Crashed
+        } else if statuses().any(|s| s == RepoOutcome::Failed) {
+            RepoOutcome::Failed
+        } else if statuses().any(|s| s == RepoOutcome::Partial) {
+            RepoOutcome::Partial
+        } else {
+            RepoOutcome::Success
+        }
+    }
+}
+
+/// Collecte le statut de l'exécution et l'écrit sur disque.
+///
+/// Partagé avec le hook de panique, qui écrit le fichier avec le statut
+/// `crashed` si le processus s'interrompt en cours d'analyse.
+#[derive(Clone)]
+pub struct StatusRecorder {
+    path: PathBuf,
+    started: Instant,
+    state: Arc<Mutex<RunStatus>>,
+}
+
+impl StatusRecorder {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        let started_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or(0);
+
+        Self {
+            path: path.into(),
+            started: Instant::now(),
+            state: Arc::new(Mutex::new(RunStatus {
+                status: RepoOutcome::Running,
+                started_at,
+                duration_ms: 0,
+                repos: Vec::new(),
+                capabilities: capabilities::enabled(),
+            })),
+        }
+    }
+
+    /// Installe un hook de panique qui écrit le rapport avant le hook par défaut
+    pub fn install_panic_hook(&self) {
+        let recorder = self.clone();
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let message = info
+                .payload()
+                .downcast_ref::<&str>()
+                .map(|s| s.to_string())
+                .or_else(|| info.payload().downcast_ref::<String>().cloned())
+                .unwrap_or_else(|| "unknown panic".to_string());
+            recorder.crash(&message);
+            default_hook(info);
+        }));
+    }
+
+    /// Démarre le suivi d'un dépôt ; renvoie son index
+    pub fn start(&self, repo: &str) -> usize {
+        let mut state = self.lock();
+        state.repos.push(RepoStatus {
+            repo: repo.to_string(),
+            status: RepoOutcome::Running,
+            error_kind: None,
+            error_message: None,
+            duration_ms: 0,
+            api_requests: 0,
+            fetch_requests: 0,
+            rate_limit_waits: 0,
+            artifacts: Vec::new(),
+        });
+        state.repos.len() - 1
+    }
+
+    /// Termine le suivi d'un dépôt analysé (succès ou succès partiel)
+    pub fn complete(&self, index: usize, partial: bool, metrics: &RunMetrics, project_dir: &Path, elapsed_ms: u64) {
+        let mut state = self.lock();
+        let repo = &mut state.repos[index];
+        repo.status = if partial { RepoOutcome::Partial } else { RepoOutcome::Success };
+        repo.duration_ms = elapsed_ms;
+        repo.api_requests = metrics.api_requests;
+        repo.fetch_requests = metrics.fetch_requests;
+        repo.rate_limit_waits = metrics.rate_limit_waits;
+        repo.artifacts = list_artifacts(project_dir);
+    }
+
+    /// Termine le suivi d'un dépôt en échec
+    pub fn fail(&self, index: usize, error: &GithubAnalyzerError, elapsed_ms: u64) {
+        self.fail_with(index, error.kind(), &error.to_string(), elapsed_ms);
+    }
+
+    /// Termine le suivi d'un dépôt en échec hors erreur d'analyse (export, E/S)
+    pub fn fail_with(&self, index: usize, kind: &str, message: &str, elapsed_ms: u64) {
+        let mut state = self.lock();
+        let repo = &mut state.repos[index];
+        repo.status = RepoOutcome::Failed;
+        repo.error_kind = Some(kind.to_string());
+        repo.error_message = Some(message.to_string());
+        repo.duration_ms = elapsed_ms;
+    }
+
+    /// Écrit le rapport final
+    pub fn finish(&self) -> std::io::Result<()> {
+        let mut state = self.lock();
+        state.duration_ms = self.started.elapsed().as_millis() as u64;
+        state.status = state.overall();
+        write_status(&self.path, &state)
+    }
+
+    /// Rapport d'exécution en JSON, tel qu'écrit par `finish`
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(&*self.lock())
+    }
+
+    /// Marque les dépôts en cours comme interrompus et écrit le rapport (au mieux)
+    fn crash(&self, message: &str) {
+        // Le verrou peut être tenu par le thread qui panique : ne pas bloquer
+        let Ok(mut state) = self.state.try_lock() else {
+            return;
+        };
+        for repo in state.repos.iter_mut().filter(|repo| repo.status == RepoOutcome::Running) {
+            repo.status = RepoOutcome::Crashed;
+            repo.error_kind = Some("Panic".to_string());
+            repo.error_message = Some(message.to_string());
+        }
+        state.duration_ms = self.started.elapsed().as_millis() as u64;
+        state.status = RepoOutcome::Crashed;
+        let _ = write_status(&self.path, &state);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, RunStatus> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+fn write_status(path: &Path, status: &RunStatus) -> std::io::Result<()> {
+    if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(status)?;
+    fs::write(path, json)
+}
+
+/// Fichiers produits dans le répertoire d'export (chemins relatifs au répertoire courant)
+fn list_artifacts(project_dir: &Path) -> Vec<String> {
+    let mut artifacts: Vec<String> = fs::read_dir(project_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path().display().to_string())
+                .collect()
+        })
+        .unwrap_or_default();
+    artifacts.sort();
+    artifacts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+
+    #[test]
+    fn run_status_lists_compiled_capabilities() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run_status.json");
+        let recorder = StatusRecorder::new(&path);
+        recorder.finish().unwrap();
+
+        let written: Value = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let capabilities = written["capabilities"].as_object().unwrap();
+        assert_eq!(capabilities.len(), capabilities::CAPABILITIES.len());
+        for capability in &capabilities::CAPABILITIES {
+            assert_eq!(capabilities[capability.name], Value::Bool(capability.enabled), "{}", capability.name);
+        }
+        assert_eq!(serde_json::from_str::<Value>(&recorder.to_json().unwrap()).unwrap(), written);
+    }
+
+    fn read_status(path: &Path) -> RunStatus {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn successful_run_reports_metrics_and_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let project_dir = dir.path().join("owner_repo");
+        fs::create_dir(&project_dir).unwrap();
+        fs::write(project_dir.join("analysis.json"), "{}").unwrap();
+        fs::write(project_dir.join("summary.md"), "").unwrap();
+        let path = dir.path().join("status/run_status.json");
+
+        let recorder = StatusRecorder::new(&path);
+        let index = recorder.start("https://github.com/owner/repo");
+        let metrics = RunMetrics { api_requests: 12, fetch_requests: 40, rate_limit_waits: 1, ..Default::default() };
+        recorder.complete(index, false, &metrics, &project_dir, 250);
+        recorder.finish().unwrap();
+
+        let status = read_status(&path);
+        assert_eq!(status.status, RepoOutcome::Success);
+        let repo = &status.repos[0];
+        assert_eq!(repo.repo, "https://github.com/owner/repo");
+        assert_eq!(repo.status, RepoOutcome::Success);
+        assert_eq!((repo.api_requests, repo.fetch_requests, repo.rate_limit_waits), (12, 40, 1));
+        assert_eq!(repo.duration_ms, 250);
+        assert_eq!((&repo.error_kind, &repo.error_message), (&None, &None));
+        assert_eq!(
+            repo.artifacts,
+            vec![
+                project_dir.join("analysis.json").display().to_string(),
+                project_dir.join("summary.md").display().to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn typed_failure_is_the_overall_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run_status.json");
+
+        let recorder = StatusRecorder::new(&path);
+        let partial = recorder.start("owner/partial");
+        recorder.complete(partial, true, &RunMetrics::default(), dir.path(), 10);
+        let failed = recorder.start("owner/missing");
+        recorder.fail(failed, &GithubAnalyzerError::NotFound("owner/missing".to_string()), 5);
+        recorder.finish().unwrap();
+
+        let status = read_status(&path);
+        assert_eq!(status.status, RepoOutcome::Failed);
+        assert_eq!(status.repos[0].status, RepoOutcome::Partial);
+        let repo = &status.repos[1];
+        assert_eq!(repo.status, RepoOutcome::Failed);
+        assert_eq!(repo.error_kind.as_deref(), Some("NotFound"));
+        assert_eq!(repo.error_message.as_deref(), Some("Not found: owner/missing"));
+        assert!(repo.artifacts.is_empty());
+    }
+
+    #[test]
+    fn panic_hook_writes_a_crashed_report() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run_status.json");
+
+        let recorder = StatusRecorder::new(&path);
+        let done = recorder.start("owner/done");
+        recorder.complete(done, false, &RunMetrics::default(), dir.path(), 1);
+        recorder.start("owner/running");
+        recorder.install_panic_hook();
+        let result = panic::catch_unwind(|| panic!("simulated failure in owner/running"));
+        // Rétablit le hook par défaut pour les autres tests
+        let _ = panic::take_hook();
+        assert!(result.is_err());
+
+        let status = read_status(&path);
+        assert_eq!(status.status, RepoOutcome::Crashed);
+        assert_eq!(status.repos[0].status, RepoOutcome::Success);
+        let repo = &status.repos[1];
+        assert_eq!(repo.status, RepoOutcome::Crashed);
+        assert_eq!(repo.error_kind.as_deref(), Some("Panic"));
+        assert_eq!(repo.error_message.as_deref(), Some("simulated failure in owner/running"));
+    }
+}
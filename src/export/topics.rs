@@ -0,0 +1,219 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::types::analysis::{MethodSignature, TypeRelations};
+
+/// Bundle de repli pour les fichiers sans thème
+pub const MISC_TOPIC: &str = "misc";
+
+/// Thèmes reconnus : (nom, mots-clés de chemin, marqueurs de contenu)
+const TOPICS: [(&str, &[&str], &[&str]); 6] = [
+    (
+        "http_api",
+        &["api", "handler", "handlers", "routes", "router", "endpoint", "endpoints", "controller", "server", "web", "http"],
+        &["axum::", "actix_web", "warp::", "rocket::", "hyper::", "#[get(", "#[post(", "HttpResponse"],
+    ),
+    (
+        "persistence",
+        &["db", "database", "migrations", "migration", "models", "model", "schema", "repository", "repositories", "storage", "store"],
+        &["sqlx::", "diesel::", "rusqlite::", "sea_orm::", "mongodb::", "redis::", "CREATE TABLE"],
+    ),
+    (
+        "cli",
+        &["cli", "cmd", "commands", "bin", "main.rs"],
+        &["clap::", "structopt::", "std::env::args"],
+    ),
+    (
+        "configuration",
+        &["config", "settings", "Cargo.toml", "package.json", "go.mod"],
+        &["std::env::var", "dotenv"],
+    ),
+    (
+        "docs",
+        &["docs", "doc", "README", "CHANGELOG", "CONTRIBUTING", ".md"],
+        &[],
+    ),
+    (
+        "tests",
+        &["tests", "test", "spec", "benches", "fixtures"],
+        &["#[test]", "#[cfg(test)]", "#[tokio::test]"],
+    ),
+];
+
+/// Répartit des fichiers en bundles thématiques
+///
+/// Un fichier peut appartenir à plusieurs thèmes. Les fichiers déclarant des types
+/// utilisés par un thème y sont rattachés via le graphe de types du projet, de proche
+/// en proche.
+#[derive(Debug, Clone, Default)]
+pub struct TopicClassifier {
+    /// Fichier → fichiers déclarant les types qu'il utilise
+    dependencies: HashMap<String, BTreeSet<String>>,
+}
+
+impl TopicClassifier {
+    /// Classifieur sans graphe de types : seuls les thèmes directs sont retenus
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Classifieur propageant les thèmes par le graphe de types : dépendances des
+    /// relations fusionnées par `merge_type_relations`, et types cités par les
+    /// signatures de fonctions
+    pub fn from_type_graph(relations: &[TypeRelations], signatures: &[MethodSignature]) -> Self {
+        let defined_in: HashMap<&str, &str> = relations
+            .iter()
+            .map(|relation| (&*relation.type_name, &*relation.defined_in))
+            .collect();
+
+        let mut dependencies: HashMap<String, BTreeSet<String>> = HashMap::new();
+        let mut link = |user: &str, type_name: &str| {
+            if let Some(&owner) = defined_in.get(type_name) {
+                if !user.is_empty() && !owner.is_empty() && owner != user {
+                    dependencies.entry(user.to_string()).or_default().insert(owner.to_string());
+                }
+            }
+        };
+        for relation in relations {
+            for dependency in &relation.depends_on {
+                link(&relation.defined_in, dependency);
+            }
+        }
+        for signature in signatures {
+            let mentioned = signature.params.iter().map(|param| &**param).chain([&*signature.return_type]);
+            for name in mentioned.flat_map(|text| text.split(|c: char| !(c.is_alphanumeric() || c == '_'))) {
+                link(&signature.defined_in, name);
+            }
+        }
+        Self { dependencies }
+    }
+
+    /// Thèmes directement détectés pour un fichier (chemin et contenu)
+    fn direct_topics(&self, path: &str, content: &str) -> BTreeSet<String> {
+        let segments: Vec<String> = path
+            .split('/')
+            .map(|segment| segment.to_lowercase())
+            .collect();
+        let file_stem = segments
+            .last()
+            .map(|name| name.split('.').next().unwrap_or(name).to_string())
+            .unwrap_or_default();
+
+        TOPICS
+            .iter()
+            .filter(|(_, keywords, markers)| {
+                let path_match = keywords.iter().any(|keyword| {
+                    let keyword = keyword.to_lowercase();
+                    if keyword.contains('.') {
+                        path.to_lowercase().ends_with(&keyword)
+                    } else {
+                        segments.iter().any(|segment| segment == &keyword) || file_stem == keyword
+                    }
+                });
+                path_match || markers.iter().any(|marker| content.contains(marker))
+            })
+            .map(|(name, _, _)| name.to_string())
+            .collect()
+    }
+
+    /// Associe chaque thème à la liste ordonnée des fichiers (chemin, contenu) qui le composent
+    pub fn classify(&self, files: &[(&str, &str)]) -> BTreeMap<String, Vec<String>> {
+        let direct: Vec<BTreeSet<String>> = files
+            .iter()
+            .map(|(path, content)| self.direct_topics(path, content))
+            .collect();
+        let index_of: HashMap<&str, usize> = files.iter().enumerate().map(|(index, (path, _))| (*path, index)).collect();
+
+        // Propagation transitive : chaque fichier transmet ses thèmes directs à tous les
+        // fichiers qu'il atteint dans le graphe de types, hors fichiers de test
+        let mut topics = direct.clone();
+        for (index, (path, _)) in files.iter().enumerate() {
+            if direct[index].is_empty() || direct[index].contains("tests") {
+                continue;
+            }
+            let mut visited: BTreeSet<&str> = BTreeSet::from([*path]);
+            let mut pending = vec![*path];
+            while let Some(current) = pending.pop() {
+                for owner in self.dependencies.get(current).into_iter().flatten() {
+                    if visited.insert(owner) {
+                        pending.push(owner);
+                        if let Some(&owner_index) = index_of.get(owner.as_str()) {
+                            topics[owner_index].extend(direct[index].iter().cloned());
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut bundles: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for ((path, _), file_topics) in files.iter().zip(topics) {
+            if file_topics.is_empty() {
+                bundles.entry(MISC_TOPIC.to_string()).or_default().push(path.to_string());
+            }
+            for topic in file_topics {
+                bundles.entry(topic).or_default().push(path.to_string());
+            }
+        }
+        bundles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::file::{merge_type_relations, FileAnalyzer};
+
+    /// Classifieur construit comme à l'export : relations fusionnées et signatures
+    /// de fichiers analysés
+    fn classifier(files: &[(&str, &str)]) -> TopicClassifier {
+        let analyzer = FileAnalyzer::new();
+        let mut relations = Vec::new();
+        let mut signatures = Vec::new();
+        for (path, content) in files {
+            let (mut file_relations, mut file_signatures, _) = analyzer.analyze_with_patterns(content);
+            for relation in file_relations.iter_mut() {
+                relation.defined_in = (*path).into();
+            }
+            for signature in file_signatures.iter_mut() {
+                signature.defined_in = (*path).into();
+            }
+            relations.extend(file_relations);
+            signatures.extend(file_signatures);
+        }
+        TopicClassifier::from_type_graph(&merge_type_relations(relations), &signatures)
+    }
+
+    #[test]
+    fn topics_propagate_transitively_through_the_type_graph() {
+        let files = [
+            ("src/handlers.rs", "use axum::Json;\n\npub async fn show(order: Order) -> Json<Receipt> {\n    todo!()\n}\n"),
+            ("src/order.rs", "pub struct Order {\n    pub lines: Vec<Line>,\n}\n"),
+            ("src/line.rs", "pub struct Line {\n    pub price: Price,\n}\n"),
+            ("src/price.rs", "pub struct Price(pub u64);\n"),
+            ("src/receipt.rs", "pub struct Receipt {\n    pub total: u64,\n}\n"),
+            ("tests/order.rs", "#[test]\nfn builds() {\n    let _ = Unrelated::default();\n}\n"),
+            ("src/unrelated.rs", "#[derive(Default)]\npub struct Unrelated;\n"),
+        ];
+        let bundles = classifier(&files).classify(&files);
+
+        assert_eq!(bundles["http_api"], [
+            "src/handlers.rs", "src/order.rs", "src/line.rs", "src/price.rs", "src/receipt.rs",
+        ]);
+        assert_eq!(bundles["tests"], ["tests/order.rs"]);
+        // Un fichier de test ne transmet pas son thème aux types qu'il utilise
+        assert_eq!(bundles[MISC_TOPIC], ["src/unrelated.rs"]);
+    }
+
+    #[test]
+    fn files_matching_no_topic_fall_into_misc() {
+        let files = [
+            ("src/geometry.rs", "pub struct Point {\n    pub x: f64,\n}\n"),
+            ("src/lib.rs", "pub mod geometry;\n"),
+            ("src/db/mod.rs", "use sqlx::PgPool;\n"),
+        ];
+        let bundles = TopicClassifier::new().classify(&files);
+
+        assert_eq!(bundles[MISC_TOPIC], ["src/geometry.rs", "src/lib.rs"]);
+        assert_eq!(bundles["persistence"], ["src/db/mod.rs"]);
+        assert_eq!(bundles.len(), 2);
+    }
+}
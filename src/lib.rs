@@ -0,0 +1,8 @@
+pub mod error;
+pub mod types;
+pub mod analysis;
+pub mod api;
+pub mod capabilities;
+pub mod cli;
+pub mod export;
+pub mod schema;
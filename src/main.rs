@@ -1,73 +1,703 @@
-mod error;
-mod types;
-mod analysis;
-mod api;
-mod export;
-
+use std::collections::HashMap;
 use std::error::Error;
-use crate::analysis::repository::analyze_repository;
-use export::ProjectExporter;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use rust_repo_analyzer::{capabilities, cli, schema};
+use rust_repo_analyzer::error::GithubAnalyzerError;
+use rust_repo_analyzer::analysis::repository::analyze_repository;
+use rust_repo_analyzer::analysis::source::SourceTree;
+use rust_repo_analyzer::api::client::rate_limit_bucket;
+use rust_repo_analyzer::api::scheduler::SizeAwareScheduler;
+use rust_repo_analyzer::export::{
+    BranchComparison, ExportOptions, OrgSummary, ProjectExporter, StatusRecorder, SummaryRenderer, DEFAULT_RENDER_WIDTH, DEFAULT_STATUS_FILE,
+};
+use rust_repo_analyzer::types::analysis::ProjectSummary;
+use rust_repo_analyzer::types::locator::Provider;
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
     let args: Vec<String> = std::env::args().collect();
-    if args.len() < 2 {
-        println!("Usage: {} <repo_url1> [repo_url2] ...", args[0]);
+    let options = match cli::parse_args(&args[1..]) {
+        Ok(options) if options.show_capabilities => {
+            print!("{}", capabilities::render());
+            return Ok(());
+        }
+        Ok(options) if options.migrate.is_some() => {
+            if let Some(args) = &options.migrate {
+                migrate_analysis(args);
+            }
+            return Ok(());
+        }
+        Ok(options) if !options.repos.is_empty() || !options.orgs.is_empty() || options.from_archive.is_some() => options,
+        Ok(_) => {
+            println!("{}", cli::usage(&args[0]));
+            return Ok(());
+        }
+        Err(e) => {
+            println!("{}\n{}", e, cli::usage(&args[0]));
+            return Ok(());
+        }
+    };
+
+    let renderer = SummaryRenderer::new(DEFAULT_RENDER_WIDTH, !options.ascii);
+    let status = StatusRecorder::new(options.status_file.as_deref().unwrap_or(DEFAULT_STATUS_FILE));
+    status.install_panic_hook();
+
+    // Une capacité absente fait échouer toute l'exécution, rapport compris
+    if let Err(e) = options.require_capabilities() {
+        println!("{}", renderer.failure(&e));
+        fail_all(&status, &options, "MissingCapability", &e);
+        finish_run(&status, &options);
+        std::process::exit(1);
+    }
+
+    #[cfg(feature = "archive")]
+    if let Some(archive_path) = &options.from_archive {
+        println!("Analyzing archive: {}", archive_path);
+        let index = status.start(archive_path);
+        analyze_archive(Path::new(archive_path), &options, &renderer, &status, index, Instant::now()).await;
+    }
+
+    // Un token refusé interrompt l'exécution avant toute analyse ; un clone n'utilise pas l'API
+    // et seul le token GitHub peut être vérifié à l'avance
+    let first_remote = options.repos
+        .iter()
+        .find(|repo| options.local_path(repo).is_none() && options.archive_path(repo).is_none());
+    if let Some(repo_url) = first_remote
+        .filter(|_| !options.analysis.clone)
+        .filter(|repo| options.analysis.provider_for(repo) == Provider::Github)
+    {
+        if let Err(e) = verify_auth(repo_url, &options).await {
+            println!("{}", renderer.failure(&format!("Error authenticating: {}", e)));
+            if let Some(hint) = e.hint() {
+                println!("{}", renderer.hint(hint));
+            }
+            for repo_url in &options.repos {
+                let index = status.start(repo_url);
+                status.fail(index, &e, 0);
+            }
+            finish_run(&status, &options);
+            return Ok(());
+        }
+    }
+
+    for repo_url in &options.repos {
+        match &options.compare {
+            Some((base, head)) => compare_branches(repo_url, base, head, &options, &renderer, &status).await,
+            None => {
+                analyze_and_export(repo_url, &options, &renderer, &status).await;
+            }
+        }
+    }
+
+    for org in &options.orgs {
+        analyze_org(org, &options, &renderer, &status).await;
+    }
+
+    finish_run(&status, &options);
+    Ok(())
+}
+
+/// Marque en échec chaque cible de l'exécution (archive, dépôts, organisations)
+fn fail_all(status: &StatusRecorder, options: &cli::CliOptions, kind: &str, message: &str) {
+    let targets = options.from_archive.iter().chain(&options.repos).chain(&options.orgs);
+    for target in targets {
+        let index = status.start(target);
+        status.fail_with(index, kind, message, 0);
+    }
+}
+
+/// Écrit le rapport d'exécution et l'affiche en JSON avec `--summary-json`
+fn finish_run(status: &StatusRecorder, options: &cli::CliOptions) {
+    if let Err(e) = status.finish() {
+        println!("Warning: Failed to write run status: {}", e);
+    }
+    if options.summary_json {
+        match status.to_json() {
+            Ok(json) => println!("{}", json),
+            Err(e) => println!("Warning: Failed to render run status: {}", e),
+        }
+    }
+}
+
+/// Analyse et exporte deux branches d'un dépôt, puis écrit leur comparaison
+async fn compare_branches(
+    repo_url: &str,
+    base: &str,
+    head: &str,
+    options: &cli::CliOptions,
+    renderer: &SummaryRenderer,
+    status: &StatusRecorder,
+) {
+    let mut summaries = Vec::new();
+    for branch in [base, head] {
+        let mut branch_options = options.clone();
+        branch_options.analysis.git_ref = Some(branch.to_string());
+        branch_options.export.branch = Some(branch.to_string());
+        summaries.push(analyze_and_export(repo_url, &branch_options, renderer, status).await);
+    }
+
+    let [Some(base_summary), Some(head_summary)] = &summaries[..] else {
+        println!("{}", renderer.failure(&format!("Cannot compare {}..{}: an analysis failed", base, head)));
+        return;
+    };
+    let comparison = BranchComparison::compute(base_summary, head_summary);
+    match comparison.write(Path::new("output")) {
+        Ok(path) => println!(
+            "Branch comparison {}..{}: {} type(s) added, {} removed, {} changed; {} function(s) added, {} removed, {} changed ({})",
+            base, head,
+            comparison.types_added.len(), comparison.types_removed.len(), comparison.types_changed.len(),
+            comparison.functions_added.len(), comparison.functions_removed.len(), comparison.functions_changed.len(),
+            path.display()
+        ),
+        Err(e) => println!("{}", renderer.failure(&format!("Error writing comparison of {}: {}", repo_url, e))),
+    }
+}
+
+/// Analyse un dépôt puis l'exporte ; renvoie son résumé si les deux ont réussi
+async fn analyze_and_export(
+    repo_url: &str,
+    options: &cli::CliOptions,
+    renderer: &SummaryRenderer,
+    status: &StatusRecorder,
+) -> Option<ProjectSummary> {
+    let started = Instant::now();
+    if let Some(path) = options.local_path(repo_url) {
+        println!("Analyzing directory: {}", path.display());
+        let index = status.start(repo_url);
+        return analyze_local(&path, options, renderer, status, index, started).await;
+    }
+
+    #[cfg(feature = "archive")]
+    if let Some(path) = options.archive_path(repo_url) {
+        println!("Analyzing archive: {}", path.display());
+        let index = status.start(repo_url);
+        return analyze_archive(&path, options, renderer, status, index, started).await;
+    }
+
+    println!("Analyzing repository: {}", repo_url);
+    let index = status.start(repo_url);
+
+    #[cfg(feature = "git")]
+    if options.analysis.clone {
+        return analyze_clone(repo_url, options, renderer, status, index, started).await;
+    }
+
+    #[cfg(feature = "archive")]
+    if options.analysis.tarball {
+        return analyze_tarball(repo_url, options, renderer, status, index, started).await;
+    }
+
+    match analyze_repository(repo_url, &options.analysis).await {
+        // L'export suit l'URL canonique (dépôt renommé ou transféré)
+        Ok(mut summary) => match export_repository(&summary.repo_url.clone(), &mut summary, options, renderer).await {
+            Ok((complete, project_dir)) => {
+                status.complete(index, !complete, &summary.run_metrics, &project_dir, elapsed_ms(started));
+                Some(summary)
+            }
+            Err(e) => {
+                println!("{}", renderer.failure(&format!("Error exporting {}: {}", repo_url, e)));
+                status.fail_with(index, "IoError", &e.to_string(), elapsed_ms(started));
+                None
+            }
+        },
+        Err(e) => {
+            println!("{}", renderer.failure(&format!("Error analyzing {}: {}", repo_url, e)));
+            if let Some(hint) = e.hint() {
+                println!("{}", renderer.hint(hint));
+            }
+            status.fail(index, &e, elapsed_ms(started));
+            None
+        }
+    }
+}
+
+/// Analyse tous les dépôts d'une organisation et écrit leur résumé agrégé
+/// dans `output/<org>/org_summary.json`
+async fn analyze_org(
+    org: &str,
+    options: &cli::CliOptions,
+    renderer: &SummaryRenderer,
+    status: &StatusRecorder,
+) {
+    let client = options.analysis.github_client();
+    let repos = match client.get_org_repos(org).await {
+        Ok(repos) => repos,
+        Err(e) => {
+            println!("{}", renderer.failure(&format!("Error listing repositories of {}: {}", org, e)));
+            if let Some(hint) = e.hint() {
+                println!("{}", renderer.hint(hint));
+            }
+            return;
+        }
+    };
+
+    let listed = repos.len();
+    let selected: Vec<_> = repos.into_iter()
+        .filter(|repo| options.include_archived || !repo.archived)
+        .filter(|repo| options.include_forks || !repo.fork)
+        .collect();
+    println!(
+        "Organization {}: {} repositories to analyze ({} archived or forked skipped)",
+        org, selected.len(), listed - selected.len()
+    );
+
+    let mut org_summary = OrgSummary::new(org);
+    for repo in &selected {
+        match analyze_and_export(&repo.html_url, options, renderer, status).await {
+            Some(summary) => org_summary.record(&summary),
+            None => org_summary.record_failure(&repo.html_url),
+        }
+    }
+
+    match org_summary.write(Path::new("output")) {
+        Ok(path) => println!("{}", renderer.success(&format!(
+            "Organization {}: {} analyzed, {} failed, summary written to {}",
+            org, org_summary.totals.repositories_analyzed, org_summary.totals.repositories_failed, path.display()
+        ))),
+        Err(e) => println!("{}", renderer.failure(&format!("Error writing summary of {}: {}", org, e))),
+    }
+}
+
+/// Vérifie le token et affiche le quota qui s'applique ; seule une `AuthError`
+/// est bloquante, l'analyse signalera les autres problèmes
+async fn verify_auth(repo_url: &str, options: &cli::CliOptions) -> Result<(), GithubAnalyzerError> {
+    if options.analysis.offline {
         return Ok(());
     }
+    let client = options.analysis.github_client();
+    match client.verify_auth(repo_url).await {
+        Ok(quota) => {
+            println!(
+                "API access: {} ({} requests/hour, {} remaining)",
+                rate_limit_bucket(quota.limit), quota.limit, quota.remaining
+            );
+            Ok(())
+        }
+        Err(e @ GithubAnalyzerError::AuthError(_)) => Err(e),
+        Err(e) => {
+            println!("Warning: Could not verify authentication: {}", e);
+            Ok(())
+        }
+    }
+}
 
-    let repos = &args[1..];
-    
-    for repo_url in repos {
-        println!("Analyzing repository: {}", repo_url);
-        match analyze_repository(repo_url).await {
-            Ok(summary) => {
-                let mut exporter = ProjectExporter::new(repo_url)?;
-
-                if let Err(e) = exporter.write_summary(&summary) {
-                    println!("Warning: Failed to write analysis summary: {}", e);
-                } else {
-                    println!("✓ Analysis JSON exported");
+/// Télécharge les fichiers d'un dépôt analysé et les exporte.
+///
+/// Renvoie `false` si certains fichiers ou artefacts n'ont pas pu être produits.
+async fn export_repository(
+    repo_url: &str,
+    summary: &mut ProjectSummary,
+    options: &cli::CliOptions,
+    renderer: &SummaryRenderer,
+) -> std::io::Result<(bool, PathBuf)> {
+    let mut exporter = ProjectExporter::with_options(repo_url, export_options(summary, options))?;
+    exporter.set_boilerplate_headers(&summary.boilerplate_headers);
+    exporter.set_workspace_members(&summary.project_overview.workspace_members);
+    exporter.set_metadata(summary.metadata.as_ref());
+    exporter.set_pull_request(summary.pull_request.as_ref());
+
+    let client = options.analysis.repository_provider(repo_url);
+    let scheduler = SizeAwareScheduler::new(
+        options.analysis.fetch_byte_budget,
+        options.analysis.max_in_flight,
+    );
+    // Les filtres --include / --exclude s'appliquent aussi à l'export
+    let filter = options.analysis.glob_filter().unwrap_or_default();
+    let mut files: Vec<_> = summary.file_summaries
+        .iter()
+        .filter(|file_summary| filter.accepts(&summary.full_path(&file_summary.path)))
+        .map(|file_summary| {
+            let item = (file_summary.path.clone(), file_summary.url.clone());
+            (item, file_summary.size.max(0) as u64)
+        })
+        .collect();
+    let mut metrics = summary.run_metrics.clone();
+    let mut failures = 0;
+
+    // Contenus déjà téléchargés lors de l'analyse (cache par SHA)
+    if let Some(cache) = options.analysis.blob_cache() {
+        let shas: HashMap<_, _> = summary.file_summaries
+            .iter()
+            .map(|file_summary| (file_summary.path.clone(), file_summary.sha.as_str()))
+            .collect();
+        let mut pending = Vec::new();
+        for ((path, url), size) in files {
+            match cache.content(shas[&path]) {
+                Some(content) => {
+                    if let Err(e) = exporter.add_file(path.to_string(), content) {
+                        println!("Warning: Failed to export {}: {}", path, e);
+                        failures += 1;
+                    }
                 }
+                None => pending.push(((path, url), size)),
+            }
+        }
+        files = pending;
+    }
 
-                let client = api::client::GithubClient::new();
-                for file_summary in &summary.file_summaries {
-                    match client.get_file_content(&file_summary.url).await {
-                        Ok(content) => {
-                            if let Err(e) = exporter.add_file(file_summary.path.clone(), content) {
-                                println!("Warning: Failed to export {}: {}", file_summary.path, e);
+    // Avec un token GitHub, récupération par lots GraphQL ; le reste passe par l'API REST
+    if client.batch_fetch_available() {
+        let paths: Vec<String> = files.iter().map(|((path, _), _)| summary.full_path(path)).collect();
+        let branch = &summary.repository_structure.branch_analyzed;
+        match client.get_files_batch(repo_url, branch, &paths).await {
+            Ok(mut contents) => {
+                let mut pending = Vec::new();
+                for ((path, url), size) in files {
+                    match contents.remove(&summary.full_path(&path)) {
+                        Some(Some(content)) => {
+                            if let Err(e) = exporter.add_file(path.to_string(), content) {
+                                println!("Warning: Failed to export {}: {}", path, e);
+                                failures += 1;
                             }
                         }
-                        Err(e) => {
-                            println!("Warning: Failed to fetch {}: {}", file_summary.path, e);
-                            continue;
-                        }
+                        Some(None) => {} // Blob binaire, rien à exporter
+                        None => pending.push(((path, url), size)),
                     }
                 }
+                files = pending;
+            }
+            Err(e) => println!("Warning: GraphQL batch fetch failed, falling back to REST: {}", e),
+        }
+    }
 
-                if let Err(e) = exporter.finish() {
-                    println!("Warning: Failed to finalize export: {}", e);
-                } else {
-                    let repo_name = repo_url.split('/').last().unwrap_or("repo");
-                    println!("✓ Export completed in output/{}/", repo_name);
-                    println!("  → Copy output/{}/complete_analysis.txt to share the entire codebase", repo_name);
-                }
-                
-                println!("Quick stats:");
-                println!("  - Files analyzed: {}", summary.total_files);
-                println!("  - Primary language: {}", 
-                    summary.repository_structure.primary_language.as_deref().unwrap_or("Unknown"));
-                println!("  - Build systems: {}", 
-                    summary.repository_structure.build_systems.join(", "));
-                if summary.project_overview.total_rust_files > 0 {
-                    println!("  - Rust files: {}", summary.project_overview.total_rust_files);
-                    println!("  - Public types: {}", summary.project_overview.total_public_types);
-                    println!("  - Public functions: {}", summary.project_overview.total_public_functions);
+    scheduler.run(
+        files,
+        |(path, url)| {
+            let client = &client;
+            async move { (path, client.get_file_content(&url).await) }
+        },
+        |(path, result)| match result {
+            Ok(content) => {
+                if let Err(e) = exporter.add_file(path.to_string(), content) {
+                    println!("Warning: Failed to export {}: {}", path, e);
+                    failures += 1;
                 }
-            },
-            Err(e) => println!("✗ Error analyzing {}: {}", repo_url, e),
+            }
+            Err(e) => {
+                println!("Warning: Failed to fetch {}: {}", path, e);
+                failures += 1;
+            }
+        },
+        &mut metrics,
+    ).await;
+    metrics.api_requests += client.api_requests();
+    metrics.rate_limit_waits += client.rate_limit_waits();
+    if let Some(usage) = metrics.rate_limit.as_mut() {
+        if let Ok(Some(quota)) = client.get_rate_limit(repo_url).await {
+            usage.remaining_after = quota.remaining;
         }
     }
+    summary.run_metrics = metrics;
 
-    Ok(())
-}
\ No newline at end of file
+    let project_dir = exporter.project_dir().to_path_buf();
+    let complete = finish_export(exporter, summary, renderer);
+    Ok((complete && failures == 0, project_dir))
+}
+
+/// Analyse une archive locale et exporte son contenu sans accès réseau
+#[cfg(feature = "archive")]
+async fn analyze_archive(
+    archive_path: &Path,
+    options: &cli::CliOptions,
+    renderer: &SummaryRenderer,
+    status: &StatusRecorder,
+    index: usize,
+    started: Instant,
+) -> Option<ProjectSummary> {
+    use rust_repo_analyzer::analysis::repository::RepositoryAnalyzer;
+
+    let analyzer = RepositoryAnalyzer::with_options(options.analysis.clone());
+    match analyzer.analyze_archive(archive_path).await {
+        Ok((summary, archive)) => {
+            export_source(&summary, &archive, options, renderer, status, index, started);
+            Some(summary)
+        }
+        Err(e) => {
+            println!("{}", renderer.failure(&format!("Error analyzing {}: {}", archive_path.display(), e)));
+            if let Some(hint) = e.hint() {
+                println!("{}", renderer.hint(hint));
+            }
+            status.fail(index, &e, elapsed_ms(started));
+            None
+        }
+    }
+}
+
+/// Analyse un dépôt GitHub téléchargé en une seule archive tarball
+#[cfg(feature = "archive")]
+async fn analyze_tarball(
+    repo_url: &str,
+    options: &cli::CliOptions,
+    renderer: &SummaryRenderer,
+    status: &StatusRecorder,
+    index: usize,
+    started: Instant,
+) -> Option<ProjectSummary> {
+    use rust_repo_analyzer::analysis::repository::RepositoryAnalyzer;
+
+    let analyzer = RepositoryAnalyzer::for_repository(repo_url, options.analysis.clone());
+    match analyzer.analyze_tarball(repo_url).await {
+        Ok((summary, archive)) => {
+            export_source(&summary, &archive, options, renderer, status, index, started);
+            Some(summary)
+        }
+        Err(e) => {
+            println!("{}", renderer.failure(&format!("Error analyzing {}: {}", repo_url, e)));
+            if let Some(hint) = e.hint() {
+                println!("{}", renderer.hint(hint));
+            }
+            status.fail(index, &e, elapsed_ms(started));
+            None
+        }
+    }
+}
+
+/// Analyse un répertoire local et exporte son contenu sans accès réseau
+async fn analyze_local(
+    path: &Path,
+    options: &cli::CliOptions,
+    renderer: &SummaryRenderer,
+    status: &StatusRecorder,
+    index: usize,
+    started: Instant,
+) -> Option<ProjectSummary> {
+    use rust_repo_analyzer::analysis::repository::RepositoryAnalyzer;
+
+    let analyzer = RepositoryAnalyzer::with_options(options.analysis.clone());
+    match analyzer.analyze_local(path).await {
+        Ok((summary, tree)) => {
+            export_source(&summary, &tree, options, renderer, status, index, started);
+            Some(summary)
+        }
+        Err(e) => {
+            println!("{}", renderer.failure(&format!("Error analyzing {}: {}", path.display(), e)));
+            if let Some(hint) = e.hint() {
+                println!("{}", renderer.hint(hint));
+            }
+            status.fail(index, &e, elapsed_ms(started));
+            None
+        }
+    }
+}
+
+/// Analyse un clone git superficiel du dépôt puis l'exporte
+#[cfg(feature = "git")]
+async fn analyze_clone(
+    repo_url: &str,
+    options: &cli::CliOptions,
+    renderer: &SummaryRenderer,
+    status: &StatusRecorder,
+    index: usize,
+    started: Instant,
+) -> Option<ProjectSummary> {
+    use rust_repo_analyzer::analysis::repository::RepositoryAnalyzer;
+
+    let analyzer = RepositoryAnalyzer::for_repository(repo_url, options.analysis.clone());
+    match analyzer.analyze_clone(repo_url).await {
+        Ok((summary, tree, clone)) => {
+            export_source(&summary, &tree, options, renderer, status, index, started);
+            if clone.kept() {
+                println!("Clone kept in {}", clone.dir().display());
+            }
+            Some(summary)
+        }
+        Err(e) => {
+            println!("{}", renderer.failure(&format!("Error analyzing {}: {}", repo_url, e)));
+            if let Some(hint) = e.hint() {
+                println!("{}", renderer.hint(hint));
+            }
+            status.fail(index, &e, elapsed_ms(started));
+            None
+        }
+    }
+}
+
+/// Options d'export d'un résumé : son sous-répertoire éventuel distingue le dossier d'export
+fn export_options(summary: &ProjectSummary, options: &cli::CliOptions) -> ExportOptions {
+    ExportOptions { subpath: summary.subpath.clone(), ..options.export.clone() }
+}
+
+/// Exporte les fichiers analysés à partir d'une source locale (archive, répertoire)
+fn export_source(
+    summary: &ProjectSummary,
+    source: &dyn SourceTree,
+    options: &cli::CliOptions,
+    renderer: &SummaryRenderer,
+    status: &StatusRecorder,
+    index: usize,
+    started: Instant,
+) {
+    let mut exporter = match ProjectExporter::with_options(&summary.repo_url, export_options(summary, options)) {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            println!("{}", renderer.failure(&format!("Error exporting {}: {}", summary.repo_url, e)));
+            status.fail_with(index, "IoError", &e.to_string(), elapsed_ms(started));
+            return;
+        }
+    };
+    exporter.set_boilerplate_headers(&summary.boilerplate_headers);
+    exporter.set_workspace_members(&summary.project_overview.workspace_members);
+
+    let indices: HashMap<String, usize> = source.files()
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, file)| summary.relative_path(&file.path).map(|path| (path.to_string(), index)))
+        .collect();
+    let filter = options.analysis.glob_filter().unwrap_or_default();
+    let mut failures = 0;
+    for file_summary in &summary.file_summaries {
+        if !filter.accepts(&summary.full_path(&file_summary.path)) {
+            continue;
+        }
+        let content = indices
+            .get(&*file_summary.path)
+            .and_then(|index| source.read_text(*index));
+        if let Some(content) = content {
+            if let Err(e) = exporter.add_file(file_summary.path.to_string(), content) {
+                println!("Warning: Failed to export {}: {}", file_summary.path, e);
+                failures += 1;
+            }
+        }
+    }
+
+    let project_dir = exporter.project_dir().to_path_buf();
+    let complete = finish_export(exporter, summary, renderer) && failures == 0;
+    status.complete(index, !complete, &summary.run_metrics, &project_dir, elapsed_ms(started));
+}
+
+/// Message de fin d'export, avec le séparateur de chemin de la plateforme
+fn completion_message(project_dir: &Path) -> String {
+    format!("Export completed in {}{}", project_dir.display(), std::path::MAIN_SEPARATOR)
+}
+
+/// Écrit les artefacts d'analyse et affiche le bilan ; renvoie `false` si un artefact manque
+fn finish_export(mut exporter: ProjectExporter, summary: &ProjectSummary, renderer: &SummaryRenderer) -> bool {
+    exporter.set_directory_tree(&summary.directory_tree);
+    exporter.set_hotspots(&summary.hotspots);
+    exporter.set_description(summary.project_overview.description.as_deref());
+    exporter.set_build_script(summary.repository_structure.build_script.as_ref());
+    exporter.set_type_graph(&summary.project_overview.type_relations, &summary.project_overview.method_signatures);
+    let mut complete = true;
+    if let Err(e) = exporter.write_summary(summary) {
+        println!("Warning: Failed to write analysis summary: {}", e);
+        complete = false;
+    } else {
+        println!("{}", renderer.success("Analysis JSON exported"));
+        match exporter.write_changes() {
+            Ok(changes) => print!("{}", changes.render_console()),
+            Err(e) => println!("Warning: Failed to compare with the previous run: {}", e),
+        }
+    }
+
+    if let Err(e) = exporter.write_report(summary) {
+        println!("Warning: Failed to write report: {}", e);
+        complete = false;
+    }
+    if let Err(e) = exporter.write_api_surface(summary) {
+        println!("Warning: Failed to write API surface: {}", e);
+        complete = false;
+    }
+    if let Err(e) = exporter.write_todos(summary) {
+        println!("Warning: Failed to write TODO list: {}", e);
+        complete = false;
+    }
+
+    let project_dir = exporter.project_dir().to_path_buf();
+    if let Err(e) = exporter.finish() {
+        println!("Warning: Failed to finalize export: {}", e);
+        complete = false;
+    } else {
+        println!("{}", renderer.success(&completion_message(&project_dir)));
+        println!("{}", renderer.hint(&format!(
+            "Copy {} to share the entire codebase",
+            project_dir.join("complete_analysis.txt").display()
+        )));
+    }
+    
+    print!("{}", renderer.render_quick_stats(summary));
+    print!("{}", renderer.render_hotspots(summary));
+    print!("{}", renderer.render_dependency_cycles(summary));
+    print!("{}", renderer.render_skipped_files(summary));
+    print!("{}", renderer.render_binary_files(summary));
+    complete
+}
+
+/// Met à niveau un analysis.json vers la dernière version du schéma
+fn migrate_analysis(args: &cli::MigrateArgs) {
+    let input = std::path::Path::new(&args.input);
+    let output = args.output.as_deref().map(std::path::Path::new).unwrap_or(input);
+
+    let result = schema::load(input)
+        .and_then(|summary| serde_json::to_string_pretty(&summary)
+            .map_err(|e| GithubAnalyzerError::ParseError(e.to_string())))
+        .and_then(|json| std::fs::write(output, json)
+            .map_err(|e| GithubAnalyzerError::ParseError(format!("Cannot write {}: {}", output.display(), e))));
+
+    match result {
+        Ok(()) => println!("Migrated {} to schema {} in {}", input.display(), schema::SCHEMA_VERSION, output.display()),
+        Err(e) => println!("Error migrating {}: {}", input.display(), e),
+    }
+}
+
+fn elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_repo_analyzer::export::{RepoOutcome, RunStatus};
+
+    fn parse(args: &[&str]) -> cli::CliOptions {
+        cli::parse_args(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>()).unwrap()
+    }
+
+    #[test]
+    fn missing_capability_fails_every_target_in_the_report() {
+        let options = parse(&["--clone", "owner/repo", "--org", "acme"]);
+        let result = options.require_capabilities();
+        assert_eq!(result.is_ok(), cfg!(feature = "git"));
+        let Err(message) = result else {
+            return;
+        };
+        assert!(message.starts_with("--clone requires the `git` subsystem"), "{}", message);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("run_status.json");
+        let status = StatusRecorder::new(&path);
+        fail_all(&status, &options, "MissingCapability", &message);
+        finish_run(&status, &options);
+
+        let written: RunStatus = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(written.status, RepoOutcome::Failed);
+        let targets: Vec<&str> = written.repos.iter().map(|repo| repo.repo.as_str()).collect();
+        assert_eq!(targets, vec!["owner/repo", "acme"]);
+        for repo in &written.repos {
+            assert_eq!(repo.status, RepoOutcome::Failed);
+            assert_eq!(repo.error_kind.as_deref(), Some("MissingCapability"));
+            assert_eq!(repo.error_message.as_deref(), Some(message.as_str()));
+        }
+    }
+
+    #[cfg(windows)]
+    #[test]
+    fn completion_message_uses_backslashes_on_windows() {
+        let project_dir = Path::new("output").join("owner_repo");
+        assert_eq!(completion_message(&project_dir), "Export completed in output\\owner_repo\\");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn completion_message_uses_slashes_on_unix() {
+        let project_dir = Path::new("output").join("owner_repo");
+        assert_eq!(completion_message(&project_dir), "Export completed in output/owner_repo/");
+    }
+
+    #[test]
+    fn plain_analysis_needs_no_optional_capability() {
+        assert_eq!(parse(&["owner/repo"]).require_capabilities(), Ok(()));
+        let archive = parse(&["--from-archive", "repo.tar.gz"]).require_capabilities();
+        assert_eq!(archive.is_ok(), cfg!(feature = "archive"));
+    }
+}
@@ -0,0 +1,265 @@
+//! Versions du schéma de `analysis.json` et migrations entre versions.
+//!
+//! Historique (version majeure) :
+//! - 1 : format initial, sans `schema_version` ; catégories sérialisées
+//!   `{"Source": "rs"}` / `"Configuration"`, visibilités `"Public"`, `"PublicCrate"`
+//! - 2 : catégories étiquetées `{"kind": "source", "value": "rs"}`, visibilités
+//!   en snake_case, ajout de `release_readiness` et `type_index`
+//! - 3 : constantes sous forme d'objets `{"name", "type", "value"}` au lieu de triplets
+//! - 4 : `dependencies`, `key_types` et `important_patterns` sous forme d'objets
+//!   (`{"name", "version_req", "kind", ..}`, `{"name", "score", ..}`,
+//!   `{"name", "description", "types"}`) au lieu de listes de noms
+
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::path::Path;
+
+use crate::error::GithubAnalyzerError;
+use crate::types::analysis::{ProjectSummary, ReleaseReadiness};
+
+/// Version du schéma de `analysis.json`.
+///
+/// À incrémenter à chaque changement de représentation sérialisée, avec une
+/// étape de migration dans `migrate`.
+pub const SCHEMA_VERSION: &str = "4.0.0";
+
+/// Version majeure courante du schéma
+pub const SCHEMA_MAJOR: u32 = 4;
+
+/// Dernière version du résumé, produite par `migrate`
+pub type ProjectSummaryLatest = ProjectSummary;
+
+/// Version majeure d'un document `analysis.json` (1 si `schema_version` est absent)
+pub fn detect_version(value: &Value) -> u32 {
+    value["schema_version"]
+        .as_str()
+        .and_then(|version| version.split('.').next())
+        .and_then(|major| major.parse().ok())
+        .unwrap_or(1)
+}
+
+/// Met à niveau un document `analysis.json` de la version majeure `from` vers la dernière
+pub fn migrate(mut value: Value, from: u32) -> Result<ProjectSummaryLatest, GithubAnalyzerError> {
+    if from > SCHEMA_MAJOR {
+        return Err(GithubAnalyzerError::ParseError(format!(
+            "analysis.json schema version {} is newer than supported version {}", from, SCHEMA_MAJOR
+        )));
+    }
+    if !value.is_object() {
+        return Err(GithubAnalyzerError::ParseError("analysis.json is not a JSON object".to_string()));
+    }
+
+    if from < 2 {
+        migrate_v1_to_v2(&mut value);
+    }
+    if from < 3 {
+        migrate_v2_to_v3(&mut value);
+    }
+    if from < 4 {
+        migrate_v3_to_v4(&mut value);
+    }
+    value["schema_version"] = json!(SCHEMA_VERSION);
+
+    serde_json::from_value(value).map_err(|e| GithubAnalyzerError::ParseError(format!(
+        "Migrated analysis.json does not match schema {}: {}", SCHEMA_VERSION, e
+    )))
+}
+
+/// Charge un `analysis.json` de n'importe quelle version, migré vers la dernière
+pub fn load(path: &Path) -> Result<ProjectSummaryLatest, GithubAnalyzerError> {
+    let json = fs::read_to_string(path)
+        .map_err(|e| GithubAnalyzerError::ParseError(format!("Cannot read {}: {}", path.display(), e)))?;
+    let value: Value = serde_json::from_str(&json)
+        .map_err(|e| GithubAnalyzerError::ParseError(e.to_string()))?;
+    let from = detect_version(&value);
+    migrate(value, from)
+}
+
+/// 1 → 2 : étiquetage des catégories, visibilités en snake_case, nouvelles sections
+fn migrate_v1_to_v2(value: &mut Value) {
+    if let Some(files) = value["file_summaries"].as_array_mut() {
+        for file in files {
+            let category = match file["category"].take() {
+                Value::String(kind) => json!({ "kind": snake_case(&kind) }),
+                Value::Object(tagged) => match tagged.into_iter().next() {
+                    Some((kind, content)) => json!({ "kind": snake_case(&kind), "value": content }),
+                    None => json!({ "kind": "unknown" }),
+                },
+                _ => json!({ "kind": "unknown" }),
+            };
+            file["category"] = category;
+        }
+    }
+
+    if let Some(signatures) = value["project_overview"]["method_signatures"].as_array_mut() {
+        for signature in signatures {
+            if let Some(visibility) = signature["visibility"].as_str() {
+                signature["visibility"] = json!(snake_case(visibility));
+            }
+        }
+    }
+
+    let summary = value.as_object_mut().expect("checked by migrate");
+    summary
+        .entry("release_readiness")
+        .or_insert_with(|| serde_json::to_value(ReleaseReadiness::default()).unwrap_or_default());
+    summary.entry("type_index").or_insert_with(|| Value::Object(Map::new()));
+    if let Some(overview) = summary.get_mut("project_overview").and_then(Value::as_object_mut) {
+        overview.entry("total_heuristic_files").or_insert(json!(0));
+        overview.entry("total_heuristic_definitions").or_insert(json!(0));
+    }
+}
+
+/// 2 → 3 : triplets de constantes `[nom, type, valeur]` en objets
+fn migrate_v2_to_v3(value: &mut Value) {
+    if let Some(constants) = value["project_overview"]["configuration"]["constants"].as_array_mut() {
+        for constant in constants {
+            if let Some([name, const_type, constant_value]) = constant.as_array().map(Vec::as_slice) {
+                *constant = json!({ "name": name, "type": const_type, "value": constant_value });
+            }
+        }
+    }
+}
+
+/// 3 → 4 : noms de dépendances, de types clés et d'idiomes en objets ; les champs
+/// que le format 3 ne portait pas (version, score, description) restent vides
+fn migrate_v3_to_v4(value: &mut Value) {
+    wrap_names(&mut value["project_overview"]["dependencies"], |name| json!({ "name": name, "kind": "normal" }));
+    wrap_names(&mut value["project_overview"]["key_types"], |name| {
+        json!({ "name": name, "score": 0, "used_by": 0, "referencing_files": 0 })
+    });
+    wrap_names(&mut value["important_patterns"], |name| json!({ "name": name, "description": "", "types": [] }));
+}
+
+/// Remplace chaque nom d'une liste par l'objet construit par `wrap` ; les entrées
+/// déjà sous forme d'objet sont laissées telles quelles
+fn wrap_names(list: &mut Value, wrap: impl Fn(&str) -> Value) {
+    if let Some(entries) = list.as_array_mut() {
+        for entry in entries {
+            if let Some(name) = entry.as_str() {
+                *entry = wrap(name);
+            }
+        }
+    }
+}
+
+/// `PublicCrate` → `public_crate`
+fn snake_case(name: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            result.push('_');
+        }
+        result.extend(c.to_lowercase());
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `analysis.json` figé au format 4, produit sur une petite crate
+    const FROZEN_V4: &str = include_str!("../tests/fixtures/analysis_v4.json");
+    /// Le même document au format 3 (dépendances, types clés et idiomes en noms)
+    const FROZEN_V3: &str = include_str!("../tests/fixtures/analysis_v3.json");
+    /// Le même document au format 1 (sans `schema_version`)
+    const FROZEN_V1: &str = include_str!("../tests/fixtures/analysis_v1.json");
+
+    fn parse(json: &str) -> Value {
+        serde_json::from_str(json).unwrap()
+    }
+
+    /// Document migré, resérialisé tel que l'écrirait l'export
+    fn migrated(json: &str) -> Value {
+        let value = parse(json);
+        let from = detect_version(&value);
+        serde_json::to_value(migrate(value, from).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn versions_are_detected_from_the_major_component() {
+        assert_eq!(detect_version(&parse(FROZEN_V4)), 4);
+        assert_eq!(detect_version(&parse(FROZEN_V3)), 3);
+        assert_eq!(detect_version(&parse(FROZEN_V1)), 1);
+        assert_eq!(detect_version(&json!({ "schema_version": "12.1.0" })), 12);
+        assert_eq!(detect_version(&json!({ "schema_version": "draft" })), 1);
+    }
+
+    #[test]
+    fn current_document_migrates_to_a_valid_latest_document() {
+        let latest = migrated(FROZEN_V4);
+        assert_eq!(latest["schema_version"], SCHEMA_VERSION);
+        assert_eq!(latest["repo_url"], "file:///work/tinycrate");
+        assert_eq!(latest["files_analyzed"], json!(["Cargo.toml", "src/lib.rs", "src/shapes.rs"]));
+        // Le document migré se relit sans perte
+        let reread: ProjectSummaryLatest = serde_json::from_value(latest.clone()).unwrap();
+        assert_eq!(serde_json::to_value(reread).unwrap(), latest);
+    }
+
+    #[test]
+    fn first_format_migrates_to_the_same_document() {
+        let from_v1 = migrated(FROZEN_V1);
+        let from_v3 = migrated(FROZEN_V3);
+
+        assert_eq!(from_v1["file_summaries"][0]["category"], json!({ "kind": "configuration" }));
+        assert_eq!(from_v1["file_summaries"][1]["category"], json!({ "kind": "source", "value": "rs" }));
+        assert_eq!(from_v1["project_overview"]["method_signatures"][0]["visibility"], "public");
+        assert_eq!(
+            from_v1["project_overview"]["configuration"]["constants"][0],
+            json!({ "name": "MAX_SHAPES", "type": "usize", "value": "16" })
+        );
+        // Sections absentes du format 1 : ajoutées vides
+        assert_eq!(from_v1["type_index"], json!({}));
+        assert_eq!(from_v1["release_readiness"]["tags_checked"], false);
+        for section in ["file_summaries", "important_patterns", "repository_structure"] {
+            assert_eq!(from_v1[section], from_v3[section], "{}", section);
+        }
+        assert_eq!(from_v1["project_overview"]["method_signatures"], from_v3["project_overview"]["method_signatures"]);
+    }
+
+    #[test]
+    fn name_lists_of_the_third_format_become_objects() {
+        let from_v3 = migrated(FROZEN_V3);
+        let from_v4 = migrated(FROZEN_V4);
+
+        assert_eq!(from_v3["project_overview"]["dependencies"], json!([{ "name": "serde", "kind": "normal" }]));
+        assert_eq!(
+            from_v3["project_overview"]["key_types"],
+            json!([{ "name": "Shape", "score": 0, "used_by": 0, "referencing_files": 0 }])
+        );
+        assert_eq!(from_v3["important_patterns"], json!([{ "name": "Newtype", "description": "", "types": [] }]));
+        // Le reste du document est inchangé
+        for section in ["file_summaries", "repository_structure", "type_index", "release_readiness"] {
+            assert_eq!(from_v3[section], from_v4[section], "{}", section);
+        }
+
+        // Les entrées déjà structurées ne sont pas touchées
+        let mut mixed = parse(FROZEN_V4);
+        mixed["project_overview"]["dependencies"].as_array_mut().unwrap().push(json!("tokio"));
+        let mixed = serde_json::to_value(migrate(mixed, 3).unwrap()).unwrap();
+        assert_eq!(mixed["project_overview"]["dependencies"][0], from_v4["project_overview"]["dependencies"][0]);
+        assert_eq!(mixed["project_overview"]["dependencies"][1], json!({ "name": "tokio", "kind": "normal" }));
+    }
+
+    #[test]
+    fn newer_or_malformed_documents_are_rejected() {
+        let newer = migrate(parse(FROZEN_V4), SCHEMA_MAJOR + 1).unwrap_err();
+        assert!(newer.to_string().contains("is newer than supported version"), "{}", newer);
+        assert!(matches!(migrate(json!([1, 2]), 3), Err(GithubAnalyzerError::ParseError(_))));
+        let mismatch = migrate(json!({ "repo_url": 3 }), SCHEMA_MAJOR).unwrap_err();
+        assert!(mismatch.to_string().contains("does not match schema"), "{}", mismatch);
+    }
+
+    #[test]
+    fn load_migrates_files_on_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("analysis.json");
+        fs::write(&path, FROZEN_V1).unwrap();
+        let summary = load(&path).unwrap();
+        assert_eq!(summary.schema_version, SCHEMA_VERSION);
+        assert_eq!(summary.total_files, 3);
+
+        assert!(load(&dir.path().join("missing.json")).is_err());
+    }
+}
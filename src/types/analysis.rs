@@ -1,18 +1,333 @@
-use serde::Serialize;
-use super::{FileCategory, Visibility};
+use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use super::{FileCategory, SharedStr, Visibility};
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProjectSummary {
+    pub schema_version: String,
     pub repo_url: String,
-    pub files_analyzed: Vec<String>,
+    pub files_analyzed: Vec<SharedStr>,
     pub total_files: i32,
     pub file_summaries: Vec<FileSummary>,
-    pub important_patterns: Vec<String>,
+    /// Idiomes reconnus dans le code (builder, newtype, typestate…)
+    pub important_patterns: Vec<ImportantPattern>,
     pub project_overview: ProjectOverview,
     pub repository_structure: RepositoryStructure,
+    pub release_readiness: ReleaseReadiness,
+    /// Index alphabétique des types : nom court → emplacements de définition
+    pub type_index: BTreeMap<String, Vec<TypeLocation>>,
+    #[serde(default)]
+    pub run_metrics: RunMetrics,
+    #[serde(default)]
+    pub boilerplate_headers: Vec<BoilerplateHeader>,
+    #[serde(default)]
+    pub provenance: Provenance,
+    /// Clés de configuration : modèles (`.env.example`, ...) et variables lues par le code
+    #[serde(default)]
+    pub config_matrix: Vec<ConfigKey>,
+    /// Description, popularité et licence du dépôt (absentes pour une archive locale)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<RepositoryMetadata>,
+    /// Sous-répertoire analysé d'un monorepo ; les chemins des fichiers lui sont relatifs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub subpath: Option<String>,
+    /// Pull request analysée : seuls ses fichiers modifiés figurent dans le résumé
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pull_request: Option<PullRequestSummary>,
+    /// Fichiers écartés par les filtres `--include` / `--exclude`
+    #[serde(default)]
+    pub filtered_files: i32,
+    /// Fichiers laissés de côté par l'analyse, avec leur taille et la raison
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_files: Vec<SkippedFile>,
+    /// Fichiers binaires (par extension ou octet NUL en tête), ni analysés ni exportés
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub binary_files: Vec<BinaryFile>,
+    /// Arborescence des fichiers analysés (`├──` / `└──`), avec le nombre de fichiers
+    /// de chaque répertoire
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub directory_tree: String,
+    /// Fichiers qui pèsent le plus dans l'export et dans l'API du projet
+    #[serde(default, skip_serializing_if = "Hotspots::is_empty")]
+    pub hotspots: Hotspots,
+    /// API publique (`pub` hors `#[cfg(test)]`) des fichiers Rust, par module
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub api_surface: Vec<ApiModule>,
+    /// Commentaires `TODO`, `FIXME`, `HACK`, `XXX` et `BUG` des fichiers Rust, par
+    /// fichier puis par ligne
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub todos: Vec<TodoItem>,
 }
 
-#[derive(Debug, Serialize, Clone)]
+/// Travail en suspens signalé par un commentaire (`// TODO: gérer les erreurs`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TodoItem {
+    pub file: String,
+    /// Ligne du marqueur, à partir de 1
+    pub line: usize,
+    /// `TODO`, `FIXME`, `HACK`, `XXX` ou `BUG`
+    pub marker: String,
+    /// Texte qui suit le marqueur sur sa ligne
+    pub text: String,
+}
+
+/// Fichier classé par une mesure (`Hotspots`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Hotspot {
+    pub path: String,
+    pub value: u64,
+}
+
+/// Fichiers les plus volumineux et les plus chargés, par mesure décroissante
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct Hotspots {
+    /// Taille en octets
+    pub largest_files: Vec<Hotspot>,
+    /// Nombre de lignes
+    pub longest_files: Vec<Hotspot>,
+    pub most_public_functions: Vec<Hotspot>,
+    /// Somme des dépendances des types définis dans le fichier
+    pub most_type_dependencies: Vec<Hotspot>,
+}
+
+impl Hotspots {
+    pub fn is_empty(&self) -> bool {
+        self.largest_files.is_empty()
+            && self.longest_files.is_empty()
+            && self.most_public_functions.is_empty()
+            && self.most_type_dependencies.is_empty()
+    }
+
+    /// Tableau texte, en tête de l'export complet
+    pub fn render(&self) -> String {
+        let sections = [
+            ("Largest files", "bytes", &self.largest_files),
+            ("Longest files", "lines", &self.longest_files),
+            ("Most public functions", "functions", &self.most_public_functions),
+            ("Most type dependencies", "dependencies", &self.most_type_dependencies),
+        ];
+        let mut lines = Vec::new();
+        for (title, unit, hotspots) in sections {
+            if hotspots.is_empty() {
+                continue;
+            }
+            if !lines.is_empty() {
+                lines.push(String::new());
+            }
+            lines.push(format!("{} ({}):", title, unit));
+            for hotspot in hotspots {
+                lines.push(format!("  {:>10}  {}", hotspot.value, hotspot.path));
+            }
+        }
+        lines.join("\n")
+    }
+}
+
+/// Fichier du dépôt non analysé ni exporté
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct SkippedFile {
+    pub path: String,
+    /// Taille déclarée (octets)
+    pub size: u64,
+    pub reason: SkipReason,
+}
+
+/// Éléments publics d'un fichier Rust
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ApiModule {
+    /// Chemin du module déduit de celui du fichier (`crate::analysis::file`)
+    pub module: String,
+    pub path: String,
+    pub items: Vec<ApiItem>,
+}
+
+/// Élément `pub` d'un module
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ApiItem {
+    pub kind: ApiItemKind,
+    pub name: String,
+    /// Type dont l'élément est une méthode ou une constante associée
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    /// Déclaration sur une ligne, sans corps (`pub fn new(path: &str) -> Self`)
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiItemKind {
+    Struct,
+    Enum,
+    Union,
+    Trait,
+    Fn,
+    Const,
+    Static,
+    Type,
+}
+
+impl ApiItemKind {
+    /// Mot-clé de la déclaration (`struct`, `fn`)
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            ApiItemKind::Struct => "struct",
+            ApiItemKind::Enum => "enum",
+            ApiItemKind::Union => "union",
+            ApiItemKind::Trait => "trait",
+            ApiItemKind::Fn => "fn",
+            ApiItemKind::Const => "const",
+            ApiItemKind::Static => "static",
+            ApiItemKind::Type => "type",
+        }
+    }
+}
+
+/// Fichier binaire du dépôt
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BinaryFile {
+    pub path: String,
+    /// Taille déclarée (octets)
+    pub size: u64,
+}
+
+/// Raison pour laquelle un fichier a été laissé de côté
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// Taille supérieure à `--max-file-size`
+    TooLarge,
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::TooLarge => write!(f, "larger than --max-file-size"),
+        }
+    }
+}
+
+impl ProjectSummary {
+    /// Nombre de fichiers analysés, rapporté au total quand des filtres s'appliquent
+    /// (`120 of 480 (filters applied)`)
+    pub fn files_analyzed_label(&self) -> String {
+        if self.filtered_files == 0 {
+            return self.total_files.to_string();
+        }
+        format!("{} of {} (filters applied)", self.total_files, self.total_files + self.filtered_files)
+    }
+
+    /// Chemin relatif au sous-répertoire analysé ; `None` s'il est en dehors
+    pub fn relative_path<'a>(&self, path: &'a str) -> Option<&'a str> {
+        match &self.subpath {
+            Some(subpath) => path.strip_prefix(subpath.as_str())?.strip_prefix('/'),
+            None => Some(path),
+        }
+    }
+
+    /// Chemin dans le dépôt d'un fichier du résumé
+    pub fn full_path(&self, path: &str) -> String {
+        match &self.subpath {
+            Some(subpath) => format!("{}/{}", subpath, path),
+            None => path.to_string(),
+        }
+    }
+}
+
+/// Métadonnées du dépôt lues via `GET /repos/{owner}/{repo}`
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RepositoryMetadata {
+    pub full_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub stargazers_count: u64,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub topics: Vec<String>,
+    /// Identifiant SPDX de la licence détectée par GitHub
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    pub default_branch: String,
+    /// Date du dernier push (ISO 8601)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pushed_at: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+impl RepositoryMetadata {
+    /// Présentation texte, en tête de l'export complet
+    pub fn render(&self) -> String {
+        let mut lines = vec![format!("Repository: {}", self.full_name)];
+        if let Some(description) = &self.description {
+            lines.push(format!("Description: {}", description));
+        }
+        lines.push(format!("Stars: {}", self.stargazers_count));
+        if !self.topics.is_empty() {
+            lines.push(format!("Topics: {}", self.topics.join(", ")));
+        }
+        lines.push(format!("License: {}", self.license.as_deref().unwrap_or("none detected")));
+        lines.push(format!("Default branch: {}", self.default_branch));
+        if let Some(pushed_at) = &self.pushed_at {
+            lines.push(format!("Last push: {}", pushed_at));
+        }
+        if self.archived {
+            lines.push("Archived: yes (read-only, no longer maintained)".to_string());
+        }
+        lines.join("\n")
+    }
+}
+
+/// Pull request analysée en mode PR
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PullRequestSummary {
+    pub number: u64,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub base_ref: String,
+    pub head_ref: String,
+    /// Commit de tête auquel les fichiers ont été lus
+    pub head_sha: String,
+    /// Tous les fichiers modifiés, supprimés compris (sans contenu exporté)
+    pub files: Vec<PullRequestFile>,
+}
+
+/// Fichier modifié par une pull request
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct PullRequestFile {
+    pub path: String,
+    pub status: String,
+    pub additions: u64,
+    pub deletions: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub previous_path: Option<String>,
+}
+
+impl PullRequestSummary {
+    /// Présentation texte, en tête de l'export complet
+    pub fn render(&self) -> String {
+        let mut lines = vec![
+            format!("Pull request #{}: {}", self.number, self.title),
+            format!("Branches: {} <- {} ({})", self.base_ref, self.head_ref, self.head_sha),
+        ];
+        if let Some(body) = self.body.as_deref().filter(|body| !body.trim().is_empty()) {
+            lines.push(String::new());
+            lines.push(body.trim().to_string());
+        }
+        lines.push(String::new());
+        lines.push(format!("Changed files ({}):", self.files.len()));
+        for file in &self.files {
+            let path = match &file.previous_path {
+                Some(previous) => format!("{} -> {}", previous, file.path),
+                None => file.path.clone(),
+            };
+            lines.push(format!("  {} {} (+{} -{})", file.status, path, file.additions, file.deletions));
+        }
+        lines.join("\n")
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct RepositoryStructure {
     pub has_src_directory: bool,
     pub has_tests: bool,
@@ -20,50 +335,1303 @@ pub struct RepositoryStructure {
     pub primary_language: Option<String>,
     pub build_systems: Vec<String>,
     pub branch_analyzed: String,
+    /// Répartition des langages en octets selon GitHub, du plus au moins représenté
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub languages: Vec<(String, u64)>,
+    /// Répertoires exclus du parcours (dépendances vendorisées, `.gitignore`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_directories: Vec<String>,
+    /// Part de chaque langage dans le code analysé, hors documentation et configuration,
+    /// du plus au moins représenté
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub language_breakdown: Vec<LanguageShare>,
+    /// Pipelines d'intégration continue détectés
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ci_summary: Option<CiSummary>,
+    /// Licence (expression SPDX) du manifeste racine, sinon du premier fichier de
+    /// licence reconnu
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<String>,
+    /// Sous-modules Git, analysés ou non
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub submodules: Vec<Submodule>,
+    /// Liens symboliques rencontrés et leur cible
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symlinks: Vec<Symlink>,
+    /// Un `build.rs` s'exécute à la compilation (génération de code, liaison native)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub has_build_script: bool,
+    /// Ce que fait le script de build
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub build_script: Option<BuildScript>,
+}
+
+/// Sous-module Git du dépôt
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Submodule {
+    pub path: String,
+    /// URL déclarée dans `.gitmodules`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Commit référencé par le dépôt parent
+    pub commit: String,
+    /// Fichiers du sous-module inclus dans l'analyse (`--recurse-submodules`)
+    #[serde(default)]
+    pub analyzed: bool,
+}
+
+/// Lien symbolique du dépôt
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Symlink {
+    pub path: String,
+    pub target: String,
+    /// Fichier du dépôt analysé à la place du lien ; absent si la cible sort du
+    /// dépôt ou n'est pas un fichier analysé
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resolved: Option<String>,
+}
+
+/// Script de build (`build.rs`) exécuté par cargo avant la compilation du crate
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct BuildScript {
+    pub path: String,
+    /// Fichiers et variables surveillés (`cargo:rerun-if-changed`, `rerun-if-env-changed`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rerun_if_changed: Vec<String>,
+    /// Bibliothèques natives liées (`cargo:rustc-link-lib`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_libs: Vec<String>,
+    /// `cfg` activés pour le crate (`cargo:rustc-cfg`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cfgs: Vec<String>,
+    /// Autres directives, sous la forme `clé=valeur` (`rustc-env=VERSION={}`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub other_directives: Vec<String>,
+    /// Variables d'environnement lues (`env::var`, `env!`, `option_env!`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub env_vars: Vec<String>,
+    /// Générateurs de code et outils de compilation invoqués (`bindgen`, `prost-build`, `cc`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generators: Vec<String>,
+}
+
+impl BuildScript {
+    /// Description courte, par exemple `generates code with bindgen, links ssl, crypto`
+    pub fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if !self.generators.is_empty() {
+            parts.push(format!("uses {}", self.generators.join(", ")));
+        }
+        if !self.link_libs.is_empty() {
+            parts.push(format!("links {}", self.link_libs.join(", ")));
+        }
+        if !self.cfgs.is_empty() {
+            parts.push(format!("sets cfg {}", self.cfgs.join(", ")));
+        }
+        if !self.env_vars.is_empty() {
+            parts.push(format!("reads {}", self.env_vars.join(", ")));
+        }
+        if parts.is_empty() {
+            return self.path.clone();
+        }
+        format!("{} ({})", self.path, parts.join("; "))
+    }
+
+    /// Lignes ajoutées au résumé texte du fichier
+    pub fn summarize(&self) -> String {
+        let mut summary = String::from("Build script: runs at compile time, part of the code may be generated\n");
+        let sections = [
+            ("Code generators", &self.generators),
+            ("Linked libraries", &self.link_libs),
+            ("Cfg flags", &self.cfgs),
+            ("Rerun if changed", &self.rerun_if_changed),
+            ("Environment variables", &self.env_vars),
+            ("Other directives", &self.other_directives),
+        ];
+        for (title, values) in sections {
+            if !values.is_empty() {
+                summary.push_str(&format!("{}: {}\n", title, values.join(", ")));
+            }
+        }
+        summary
+    }
+}
+
+/// Fichier de configuration d'intégration continue
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct CiPipeline {
+    pub path: String,
+    /// `GitHub Actions`, `GitLab CI`, `Jenkins`, ...
+    pub provider: String,
+    /// Nom du workflow (sinon celui du fichier ou du fournisseur)
+    pub name: String,
+    /// Événements déclencheurs (`push`, `pull_request`, `schedule`, ...)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<String>,
+    /// Jobs, ou étapes pour Jenkins
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub jobs: Vec<String>,
+}
+
+impl CiPipeline {
+    /// Lignes ajoutées au résumé texte du fichier
+    pub fn summarize(&self) -> String {
+        let mut summary = format!("CI pipeline: {} ({})\n", self.name, self.provider);
+        if !self.triggers.is_empty() {
+            summary.push_str(&format!("Triggers: {}\n", self.triggers.join(", ")));
+        }
+        if !self.jobs.is_empty() {
+            summary.push_str(&format!("Jobs: {}\n", self.jobs.join(", ")));
+        }
+        summary
+    }
 }
 
-#[derive(Debug, Serialize, Clone)]
+/// Intégration continue du dépôt
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct CiSummary {
+    pub pipelines: Vec<CiPipeline>,
+}
+
+impl CiSummary {
+    /// Description courte par fournisseur, par exemple
+    /// `3 GitHub Actions workflows (test, release, docs)`
+    pub fn describe(&self) -> String {
+        let mut providers: Vec<&str> = Vec::new();
+        for pipeline in &self.pipelines {
+            if !providers.contains(&pipeline.provider.as_str()) {
+                providers.push(&pipeline.provider);
+            }
+        }
+        providers
+            .into_iter()
+            .map(|provider| {
+                let pipelines: Vec<&CiPipeline> = self.pipelines
+                    .iter()
+                    .filter(|pipeline| pipeline.provider == provider)
+                    .collect();
+                let noun = if provider == "GitHub Actions" { "workflow" } else { "pipeline" };
+                // Un seul pipeline sans nom propre : ses jobs sont plus parlants
+                let names: Vec<&str> = match pipelines.as_slice() {
+                    [pipeline] if pipeline.name == provider => pipeline.jobs.iter().map(String::as_str).collect(),
+                    _ => pipelines.iter().map(|pipeline| pipeline.name.as_str()).collect(),
+                };
+                let mut description = format!(
+                    "{} {} {}{}",
+                    pipelines.len(), provider, noun, if pipelines.len() == 1 { "" } else { "s" }
+                );
+                if !names.is_empty() {
+                    description.push_str(&format!(" ({})", names.join(", ")));
+                }
+                description
+            })
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Part d'un langage dans les fichiers source analysés
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct LanguageShare {
+    pub language: String,
+    pub files: usize,
+    pub bytes: u64,
+    pub lines: usize,
+    /// Pourcentage des octets de code, arrondi au dixième
+    pub percent: f64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ProjectOverview {
     pub total_rust_files: i32,
     pub total_public_types: i32,
     pub total_public_functions: i32,
+    /// Fonctions de test : tests unitaires et tests d'intégration
     pub total_tests: i32,
+    /// Fonctions de test dans les sources du crate (`#[test]`, modules `#[cfg(test)]`)
+    #[serde(default)]
+    pub unit_tests: i32,
+    /// Fonctions de test des fichiers sous `tests/`
+    #[serde(default)]
+    pub integration_tests: i32,
+    /// Fichiers de test d'intégration sous `tests/`
+    #[serde(default)]
+    pub integration_test_files: i32,
     pub main_modules: Vec<String>,
-    pub key_types: Vec<String>,
-    pub dependencies: Vec<String>,
+    /// Arbre des modules de chaque crate, d'après les déclarations `mod x;`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub module_tree: Vec<ModuleNode>,
+    /// Types les plus centraux du graphe, du plus au moins utilisé
+    pub key_types: Vec<KeyType>,
+    /// Dépendances déclarées dans les `Cargo.toml`
+    pub dependencies: Vec<Dependency>,
+    /// Crates tierces importées par les fichiers Rust, triées
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub external_crates_used: Vec<String>,
     pub type_relations: Vec<TypeRelations>,
     pub method_signatures: Vec<MethodSignature>,
     pub configuration: Configuration,
+    pub total_heuristic_files: i32,
+    pub total_heuristic_definitions: i32,
+    /// Fonctions et méthodes `async` parmi `method_signatures`
+    #[serde(default)]
+    pub async_functions: i32,
+    /// Répertoires de premier niveau et sous-modules de `src/`, du plus volumineux au
+    /// plus petit
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub directory_stats: Vec<DirectoryStats>,
+    /// Présentation du projet tirée du README : premier titre et premier paragraphe
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Fonctionnalités listées dans le README
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub readme_highlights: Vec<String>,
+    /// Cycles de dépendances : groupes de types, puis de modules (`crate::a::b`), qui
+    /// dépendent mutuellement les uns des autres
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependency_cycles: Vec<Vec<String>>,
+    /// Lignes des fichiers source, par langage
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub lines_by_language: BTreeMap<String, LineCounts>,
+    /// Code `unsafe` des fichiers Rust
+    #[serde(default, skip_serializing_if = "UnsafeUsage::is_empty")]
+    pub unsafe_usage: UnsafeUsage,
+    /// Points de panique des fichiers Rust du crate (exemples, bancs d'essai et tests
+    /// exclus)
+    #[serde(default, skip_serializing_if = "PanicUsage::is_empty")]
+    pub panic_points: PanicUsage,
+    /// Macros définies par le projet (`macro_rules!`, macros procédurales)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub macros: Vec<MacroDefinition>,
+    /// Paquets déclarés `proc-macro = true` dans leur `Cargo.toml`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub proc_macro_crates: Vec<String>,
+    /// Fonctions les plus complexes, par complexité cyclomatique décroissante
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub complex_functions: Vec<ComplexFunction>,
+    /// Conversions (`From`, `TryFrom`, `Into`) vers ou depuis un type du projet
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub conversions: Vec<Conversion>,
+    /// Types et fonctions `pub` que rien ne semble utiliser hors de leur fichier
+    /// (heuristique, voir `UsageIndex`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub possibly_unused: Vec<PossiblyUnused>,
+    /// Éléments soumis à chaque fonctionnalité par `#[cfg(feature = "..")]`
+    /// (`fn crate::a::Type::name`, `mod crate::archive`, `impl Display for Foo (crate::a)`)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub feature_map: BTreeMap<String, Vec<String>>,
+    /// Fonctionnalités déclarées par les `Cargo.toml` (`[features]`, dépendances optionnelles)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub declared_features: Vec<String>,
+    /// Crates membres de l'espace de travail cargo, chacune avec ses statistiques (les
+    /// totaux ci-dessus couvrent tout le dépôt) ; vide hors espace de travail
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub workspace_members: Vec<CrateOverview>,
+    /// Paquet du `Cargo.toml` racine ; pour un espace de travail virtuel, absent (voir
+    /// `workspace_members`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crate_metadata: Option<CrateMetadata>,
+    /// Exécutables, exemples et bancs d'essai cargo (hors statistiques du code Rust
+    /// pour les deux derniers)
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub binary_targets: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub example_targets: usize,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub bench_targets: usize,
+    /// Types d'erreur du projet, leurs conversions, et recours à `anyhow` / `eyre` dans
+    /// les signatures
+    #[serde(default, skip_serializing_if = "ErrorTaxonomy::is_empty")]
+    pub error_types: ErrorTaxonomy,
 }
 
-#[derive(Debug, Serialize, Clone)]
-pub struct FileSummary {
+/// Élément public jamais mentionné hors de son fichier : candidat à la suppression,
+/// à vérifier (réexportations globales, usages par d'autres crates)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct PossiblyUnused {
+    /// Nom, qualifié par le type pour une méthode (`Client::reset`)
+    pub name: String,
+    pub kind: ApiItemKind,
+    pub defined_in: String,
+}
+
+/// Conversion entre deux types déclarée par un bloc `impl From`, `TryFrom` ou `Into`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Conversion {
+    /// Type converti, tel qu'écrit (`io::Error`)
+    pub from: String,
+    /// Type obtenu
+    pub to: String,
+    pub kind: ConversionKind,
+    /// L'un des deux types n'est pas déclaré par le projet (bibliothèque standard, crate tierce)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub external: bool,
+}
+
+/// Trait déclarant une conversion
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ConversionKind {
+    From,
+    TryFrom,
+    Into,
+}
+
+impl std::fmt::Display for ConversionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConversionKind::From => write!(f, "From"),
+            ConversionKind::TryFrom => write!(f, "TryFrom"),
+            ConversionKind::Into => write!(f, "Into"),
+        }
+    }
+}
+
+/// Gestion des erreurs du projet
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct ErrorTaxonomy {
+    /// Types implémentant `std::error::Error`, par nom
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<ErrorType>,
+    /// Crates d'erreurs dynamiques (`anyhow`, `eyre`) présentes dans les types de retour
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub boundaries: Vec<ErrorBoundary>,
+}
+
+impl ErrorTaxonomy {
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.boundaries.is_empty()
+    }
+}
+
+/// Type d'erreur déclaré par le projet (`impl Error for X`, `#[derive(thiserror::Error)]`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ErrorType {
+    pub name: String,
+    pub kind: TypeKind,
+    pub defined_in: String,
+    /// Variantes d'une énumération, dans l'ordre de déclaration
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<EnumVariant>,
+    /// Traits usuels implémentés ou dérivés (`Debug`, `Display`, `Error`, ...)
+    pub traits: Vec<String>,
+    /// `Display` et `Error` dérivés (`thiserror`) plutôt qu'écrits à la main
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub derived: bool,
+    /// Types convertis en cette erreur (`From`, `TryFrom`, `Into`), tels qu'écrits
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub converts_from: Vec<String>,
+}
+
+/// Fonctions dont le type de retour passe par une crate d'erreurs dynamiques
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ErrorBoundary {
+    /// `anyhow` ou `eyre`
+    pub crate_name: String,
+    pub functions: usize,
+    /// Parmi elles, fonctions `pub`
+    pub public_functions: usize,
+}
+
+/// Fonction classée par sa complexité cyclomatique (`complex_functions`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ComplexFunction {
+    /// Nom qualifié par le type propriétaire (`GithubClient::new`)
+    pub name: String,
+    pub defined_in: String,
+    pub complexity: u32,
+    pub body_lines: usize,
+}
+
+/// Macro définie par le projet
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct MacroDefinition {
+    /// Nom d'invocation (`vec`, `Serialize` pour une dérivation)
+    pub name: String,
+    pub kind: MacroKind,
+    /// Utilisable hors de la crate : `#[macro_export]`, toujours vrai pour une macro
+    /// procédurale
+    pub exported: bool,
+    pub defined_in: String,
+    /// Ligne de la définition (à partir de 1)
+    pub line: usize,
+    /// Invocations dans les fichiers Rust du projet (`name!`, `#[derive(Name)]`, `#[name]`)
+    pub invocations: usize,
+}
+
+/// Forme d'une macro
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum MacroKind {
+    /// `macro_rules! name`
+    Declarative,
+    /// `#[proc_macro]`
+    FunctionLike,
+    /// `#[proc_macro_derive(Name)]`
+    Derive,
+    /// `#[proc_macro_attribute]`
+    Attribute,
+}
+
+/// Module d'une crate et ses sous-modules déclarés
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ModuleNode {
+    pub name: String,
+    /// Chemin du module (`crate::analysis::file`)
     pub path: String,
+    /// Fichier du module ; `None` si aucun fichier analysé ne correspond à la déclaration
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Déclaré `pub mod` (la racine de la crate est publique)
+    pub is_public: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<ModuleNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct FileSummary {
+    pub path: SharedStr,
     pub size: i32,
     pub summary: String,
     pub category: FileCategory,
     pub url: String,
+    /// SHA du blob Git, clé du cache de contenus
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub sha: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heuristic: Option<HeuristicAnalysis>,
+    /// Nombre total de correspondances par motif du résumé (avant troncature)
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub pattern_counts: BTreeMap<String, usize>,
+    /// Contenu récupéré par l'API Blobs (fichier de plus de 1 Mo)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fetched_via_blob: bool,
+    /// Nombre de lignes du contenu analysé
+    #[serde(default)]
+    pub lines: usize,
+    /// Somme des dépendances des types définis dans le fichier
+    #[serde(default)]
+    pub type_dependencies: usize,
+    /// Lignes de code, de commentaires et vides
+    #[serde(default, skip_serializing_if = "LineCounts::is_empty")]
+    pub loc: LineCounts,
+    /// Fonctions, blocs, `impl` et traits `unsafe` (fichiers Rust)
+    #[serde(default, skip_serializing_if = "UnsafeCounts::is_empty")]
+    pub unsafe_code: UnsafeCounts,
+    /// `.unwrap()`, `.expect(..)`, `panic!`, ... hors code de test (fichiers Rust)
+    #[serde(default, skip_serializing_if = "PanicCounts::is_empty")]
+    pub panic_points: PanicCounts,
+    /// Instructions `use` et `extern crate` (fichiers Rust), une entrée par chemin importé
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imports: Vec<Import>,
+    /// Racine d'un exécutable (`src/main.rs`, `src/bin/`, `[[bin]]`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bin_target: bool,
+}
+
+/// Chemin importé par un `use` (`serde::Serialize`, `crate::types::*`)
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Import {
+    /// Chemin importé, sans le `*` d'un import global
+    pub path: String,
+    /// Nom donné par `as`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// Import global (`use module::*`)
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub glob: bool,
+    pub kind: ImportKind,
+}
+
+impl Import {
+    /// Crate désignée par le premier segment du chemin
+    pub fn crate_name(&self) -> &str {
+        self.path.split("::").next().unwrap_or(&self.path)
+    }
+}
+
+/// Origine d'un import
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportKind {
+    /// Module du crate (`crate::`, `self::`, `super::`, module déclaré dans le fichier)
+    Internal,
+    /// Bibliothèque standard (`std`, `core`, `alloc`)
+    Std,
+    /// Crate tierce
+    External,
+}
+
+/// Décompte des lignes d'un fichier ou d'un langage
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineCounts {
+    pub total: usize,
+    pub code: usize,
+    pub comments: usize,
+    pub blank: usize,
+}
+
+impl LineCounts {
+    pub fn is_empty(&self) -> bool {
+        self.total == 0
+    }
+
+    pub fn add(&mut self, other: &LineCounts) {
+        self.total += other.total;
+        self.code += other.code;
+        self.comments += other.comments;
+        self.blank += other.blank;
+    }
 }
 
-#[derive(Debug, Serialize, Clone)]
+/// Code `unsafe` d'un fichier ou du projet, par forme
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UnsafeCounts {
+    pub blocks: usize,
+    pub functions: usize,
+    pub impls: usize,
+    pub traits: usize,
+}
+
+impl UnsafeCounts {
+    pub fn total(&self) -> usize {
+        self.blocks + self.functions + self.impls + self.traits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    pub fn record(&mut self, kind: UnsafeKind) {
+        match kind {
+            UnsafeKind::Block => self.blocks += 1,
+            UnsafeKind::Function => self.functions += 1,
+            UnsafeKind::Impl => self.impls += 1,
+            UnsafeKind::Trait => self.traits += 1,
+        }
+    }
+
+    pub fn add(&mut self, other: &UnsafeCounts) {
+        self.blocks += other.blocks;
+        self.functions += other.functions;
+        self.impls += other.impls;
+        self.traits += other.traits;
+    }
+}
+
+/// Forme d'un usage de `unsafe`
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum UnsafeKind {
+    /// `unsafe { .. }`
+    Block,
+    /// `unsafe fn`
+    Function,
+    /// `unsafe impl`
+    Impl,
+    /// `unsafe trait`
+    Trait,
+}
+
+/// Usage de `unsafe` à une ligne d'un fichier
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct UnsafeLocation {
+    pub path: String,
+    pub line: usize,
+    pub kind: UnsafeKind,
+}
+
+/// Points de panique d'un fichier ou du projet, hors code de test
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct PanicCounts {
+    /// `.unwrap()`
+    pub unwrap: usize,
+    /// `.expect(..)`
+    pub expect: usize,
+    /// `panic!`
+    pub panic: usize,
+    /// `unreachable!`
+    pub unreachable: usize,
+    /// `todo!`
+    pub todo: usize,
+}
+
+impl PanicCounts {
+    pub fn total(&self) -> usize {
+        self.unwrap + self.expect + self.panic + self.unreachable + self.todo
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total() == 0
+    }
+
+    /// Compte un appel d'après son nom (`unwrap`, `expect`, `panic`, ...)
+    pub fn record(&mut self, name: &str) {
+        match name {
+            "unwrap" => self.unwrap += 1,
+            "expect" => self.expect += 1,
+            "panic" => self.panic += 1,
+            "unreachable" => self.unreachable += 1,
+            "todo" => self.todo += 1,
+            _ => {},
+        }
+    }
+
+    pub fn add(&mut self, other: &PanicCounts) {
+        self.unwrap += other.unwrap;
+        self.expect += other.expect;
+        self.panic += other.panic;
+        self.unreachable += other.unreachable;
+        self.todo += other.todo;
+    }
+
+    /// Décompte par forme, de la plus fréquente à la moins fréquente
+    /// (`.unwrap ×31, .expect ×12, panic! ×4`)
+    pub fn describe(&self) -> String {
+        let mut parts = [
+            (self.unwrap, ".unwrap"),
+            (self.expect, ".expect"),
+            (self.panic, "panic!"),
+            (self.unreachable, "unreachable!"),
+            (self.todo, "todo!"),
+        ];
+        parts.sort_by_key(|(count, _)| std::cmp::Reverse(*count));
+        parts
+            .iter()
+            .filter(|(count, _)| *count > 0)
+            .map(|(count, name)| format!("{} ×{}", name, count))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+/// Points de panique du projet : décompte et fichiers qui en concentrent le plus
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct PanicUsage {
+    pub counts: PanicCounts,
+    /// Fichiers par nombre décroissant de points de panique
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub top_files: Vec<Hotspot>,
+}
+
+impl PanicUsage {
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty()
+    }
+}
+
+/// Code `unsafe` du projet : décompte, emplacements et interdictions
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct UnsafeUsage {
+    pub counts: UnsafeCounts,
+    /// Emplacements, triés par fichier puis par ligne
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub locations: Vec<UnsafeLocation>,
+    /// Fichiers portant `#![forbid(unsafe_code)]` ou `#![deny(unsafe_code)]`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub forbidden_in: Vec<String>,
+}
+
+impl UnsafeUsage {
+    pub fn is_empty(&self) -> bool {
+        self.counts.is_empty() && self.forbidden_in.is_empty()
+    }
+}
+
+/// Idiome Rust reconnu dans le projet et types qui le mettent en œuvre
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct ImportantPattern {
+    /// Nom de l'idiome (`Builder`, `Newtype`, …)
+    pub name: String,
+    /// Explication suivie des occurrences (`builders whose ..: `FooBuilder` → `Foo``)
+    pub description: String,
+    pub types: Vec<String>,
+}
+
+/// Type central du projet, classé par `score`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct KeyType {
+    pub name: String,
+    /// `used_by` + `referencing_files`
+    pub score: usize,
+    /// Types qui l'utilisent directement
+    pub used_by: usize,
+    /// Fichiers, hors celui qui le déclare, dont un type ou une signature le mentionne
+    pub referencing_files: usize,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub defined_in: String,
+}
+
+/// Volume de code d'un répertoire de premier niveau ou d'un sous-module de `src/`
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct DirectoryStats {
+    pub path: String,
+    pub file_count: usize,
+    /// Somme des tailles déclarées (octets)
+    pub total_size: u64,
+    pub rust_files: usize,
+    /// Lignes des fichiers analysés
+    pub loc: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TypeRelations {
-    pub type_name: String,
-    pub implemented_traits: Vec<String>,
-    pub used_by: Vec<String>,
-    pub depends_on: Vec<String>,
+    pub type_name: SharedStr,
+    #[serde(default)]
+    pub kind: TypeKind,
+    pub implemented_traits: Vec<SharedStr>,
+    pub used_by: Vec<SharedStr>,
+    pub depends_on: Vec<SharedStr>,
+    /// Fichier déclarant le type
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub defined_in: SharedStr,
+    /// Module déclarant le type (`crate::analysis::file`)
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub module: SharedStr,
+    /// Types référencés mais non déclarés dans le même fichier, rattachés au graphe
+    /// du projet par `merge_type_relations` (vide ensuite)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub references: Vec<SharedStr>,
+    /// Types du projet qui implémentent le trait (traits uniquement)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub implementors: Vec<SharedStr>,
+    /// Paramètres génériques de la déclaration (`T: Clone`, `'a`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generics: Vec<String>,
+    /// Doc-comment de la déclaration, tronqué à `--doc-length` caractères
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// Macros du projet appliquées à la déclaration (`#[derive(Name)]`, attribut)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub macros: Vec<String>,
+    /// Variantes d'une énumération, dans l'ordre de déclaration
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub variants: Vec<EnumVariant>,
+    /// Champs d'une structure, dans l'ordre de déclaration
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<StructField>,
+    /// Type désigné par un alias (`std::result::Result<T, GithubAnalyzerError>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias_of: Option<String>,
 }
 
-#[derive(Debug, Serialize, Clone)]
-pub struct MethodSignature {
+/// Champ d'une structure
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct StructField {
+    /// Nom du champ, position (`0`, `1`) dans une structure tuple
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: String,
+    pub visibility: Visibility,
+}
+
+impl TypeRelations {
+    /// Énumération et ses variantes sur une ligne (`FileCategory { Source(String), Configuration }`),
+    /// les variantes au-delà de `limit` remplacées par `…`
+    pub fn enum_outline(&self, limit: usize) -> String {
+        let mut variants: Vec<String> = self.variants.iter().take(limit).map(EnumVariant::render).collect();
+        if self.variants.len() > limit {
+            variants.push("…".to_string());
+        }
+        if variants.is_empty() {
+            format!("{} {{}}", self.type_name)
+        } else {
+            format!("{} {{ {} }}", self.type_name, variants.join(", "))
+        }
+    }
+}
+
+/// Variante d'une énumération
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct EnumVariant {
     pub name: String,
-    pub params: Vec<String>,
-    pub return_type: String,
+    #[serde(default)]
+    pub shape: VariantShape,
+    /// Types d'une variante tuple, champs `nom: Type` d'une variante structure
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub fields: Vec<String>,
+}
+
+impl EnumVariant {
+    /// Variante telle qu'écrite, sans attributs ni discriminant (`Source(String)`,
+    /// `Io { path: PathBuf }`, `Configuration`)
+    pub fn render(&self) -> String {
+        match self.shape {
+            VariantShape::Unit => self.name.clone(),
+            VariantShape::Tuple => format!("{}({})", self.name, self.fields.join(", ")),
+            VariantShape::Struct => format!("{} {{ {} }}", self.name, self.fields.join(", ")),
+        }
+    }
+}
+
+/// Forme de la charge d'une variante
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum VariantShape {
+    #[default]
+    Unit,
+    Tuple,
+    Struct,
+}
+
+/// Nature d'une déclaration de type
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TypeKind {
+    #[default]
+    Struct,
+    Enum,
+    Trait,
+    /// `type Nom = ...;`
+    Alias,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct MethodSignature {
+    pub name: SharedStr,
+    pub params: Vec<SharedStr>,
+    pub return_type: SharedStr,
     pub visibility: Visibility,
+    /// Fichier déclarant la fonction
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub defined_in: SharedStr,
+    /// Module déclarant la fonction (`crate::analysis::file`)
+    #[serde(default, skip_serializing_if = "str::is_empty")]
+    pub module: SharedStr,
+    /// Type du bloc `impl` contenant la méthode, ou trait qui la déclare ; `None`
+    /// pour une fonction libre
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner: Option<SharedStr>,
+    /// Trait implémenté par le bloc `impl Trait for Type` contenant la méthode
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trait_impl: Option<SharedStr>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_async: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_unsafe: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_const: bool,
+    /// Paramètres génériques tels qu'écrits (`T`, `T: Clone`, `'a`, `const N: usize`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub generics: Vec<String>,
+    /// Prédicats de la clause `where` (`T: DeserializeOwned`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub where_bounds: Vec<String>,
+    /// Doc-comment de la fonction, tronqué à `--doc-length` caractères
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub doc: Option<String>,
+    /// Complexité cyclomatique approchée du corps ; 0 pour une déclaration sans corps
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub complexity: u32,
+    /// Lignes du corps, accolades comprises
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub body_lines: usize,
+}
+
+/// Mesure nulle, omise à la sérialisation
+fn is_zero<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+impl MethodSignature {
+    /// Nom qualifié par le type propriétaire (`GithubClient::new`)
+    pub fn qualified_name(&self) -> String {
+        match &self.owner {
+            Some(owner) => format!("{}::{}", owner, self.name),
+            None => self.name.to_string(),
+        }
+    }
+
+    /// Paramètres génériques entre chevrons (`<T: Clone>`), vide s'il n'y en a pas
+    pub fn generic_list(&self) -> String {
+        if self.generics.is_empty() {
+            String::new()
+        } else {
+            format!("<{}>", self.generics.join(", "))
+        }
+    }
+
+    /// Qualificatifs dans l'ordre de Rust, suivis d'une espace (`const async unsafe `)
+    pub fn qualifiers(&self) -> String {
+        [(self.is_const, "const "), (self.is_async, "async "), (self.is_unsafe, "unsafe ")]
+            .iter()
+            .filter(|(present, _)| *present)
+            .map(|(_, keyword)| *keyword)
+            .collect()
+    }
 }
 
-#[derive(Debug, Serialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Configuration {
-    pub constants: Vec<(String, String, String)>,
+    pub constants: Vec<ConstantDefinition>,
     pub feature_flags: Vec<String>,
     pub custom_attributes: Vec<String>,
-}
\ No newline at end of file
+}
+
+/// Dépendance déclarée dans un `Cargo.toml`
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct Dependency {
+    pub name: String,
+    /// Contrainte de version (`"1.0"`, `"^0.4"`) ; `None` pour une dépendance `git`,
+    /// `path` ou héritée de l'espace de travail sans version
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version_req: Option<String>,
+    pub kind: DependencyKind,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub optional: bool,
+    /// Fonctionnalités activées (`features = [..]`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub features: Vec<String>,
+    /// Manifeste déclarant la dépendance
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub defined_in: String,
+    /// Ni importée ni désignée par un chemin dans les fichiers Rust analysés
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unused: bool,
+}
+
+/// Métadonnées d'un paquet (`[package]` de son `Cargo.toml`), valeurs héritées de
+/// l'espace de travail résolues
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct CrateMetadata {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Édition Rust (`2021`) ; cargo retient `2015` en son absence
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub edition: Option<String>,
+    /// Version minimale de Rust prise en charge (`rust-version`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rust_version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Bibliothèque : section `[lib]` ou `src/lib.rs`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub lib: bool,
+    /// Exécutable : section `[[bin]]`, `src/main.rs` ou `src/bin/`
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub bin: bool,
+}
+
+impl CrateMetadata {
+    /// Cibles du paquet : `lib`, `bin` ou `lib + bin`
+    pub fn targets(&self) -> &'static str {
+        match (self.lib, self.bin) {
+            (true, true) => "lib + bin",
+            (true, false) => "lib",
+            (false, true) => "bin",
+            (false, false) => "no target",
+        }
+    }
+}
+
+/// Crate membre d'un espace de travail cargo, avec ses propres statistiques
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub struct CrateOverview {
+    /// Nom du paquet (`[package] name`)
+    pub name: String,
+    /// Répertoire de la crate (vide pour le paquet racine)
+    pub path: String,
+    /// Dépendances déclarées par son `Cargo.toml`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<Dependency>,
+    /// Édition, MSRV, version et cibles du paquet
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<CrateMetadata>,
+    pub files: usize,
+    pub rust_files: usize,
+    /// Lignes de code des fichiers Rust
+    pub rust_code_lines: usize,
+    /// Types `pub` de l'API (structures, énumérations, unions, traits, alias)
+    pub public_types: usize,
+    /// Fonctions et méthodes `pub` de l'API
+    pub public_functions: usize,
+}
+
+/// Section d'un `Cargo.toml` déclarant une dépendance
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyKind {
+    /// `[dependencies]`
+    Normal,
+    /// `[dev-dependencies]`
+    Dev,
+    /// `[build-dependencies]`
+    Build,
+}
+
+/// Constante déclarée (`const NAME: Type = value;`)
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConstantDefinition {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub const_type: String,
+    pub value: String,
+    /// Fichier déclarant la constante
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub defined_in: String,
+}
+
+/// Indicateurs de maturité des publications : versions, changelog, stabilité de l'API
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct ReleaseReadiness {
+    /// Dernière version : tag semver le plus récent, sinon première entrée du changelog
+    pub latest_version: Option<String>,
+    /// Date de la dernière version, lue dans son entrée de changelog
+    pub latest_release_date: Option<String>,
+    /// Un CHANGELOG contient au moins une entrée versionnée
+    pub changelog_maintained: bool,
+    /// Dernières entrées versionnées du changelog (hors `[Unreleased]`)
+    pub changelog_entries: Vec<ChangelogEntry>,
+    /// Tags semver du plus récent au plus ancien
+    pub semver_tags: Vec<String>,
+    /// Les tags du dépôt ont pu être récupérés
+    pub tags_checked: bool,
+    /// Types publics marqués `#[non_exhaustive]`
+    pub non_exhaustive_public_types: i32,
+    /// Attributs `#[stable]` / `#[unstable]` distincts rencontrés
+    pub stability_attributes: Vec<String>,
+    /// Version minimale de Rust déclarée (`rust-version` du Cargo.toml)
+    pub rust_version: Option<String>,
+}
+
+/// Entrée versionnée d'un changelog
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ChangelogEntry {
+    /// Version sans préfixe `v` (`1.2.0`, `1.0.0-rc.1`)
+    pub version: String,
+    /// Date `AAAA-MM-JJ` figurant dans le titre de l'entrée
+    pub date: Option<String>,
+}
+
+/// Résultat de l'analyse heuristique d'un langage sans analyseur dédié
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HeuristicAnalysis {
+    pub heuristic: bool,
+    pub language: String,
+    pub definitions: Vec<HeuristicDefinition>,
+    pub imports: Vec<String>,
+    pub code_lines: i32,
+    pub comment_lines: i32,
+    pub blank_lines: i32,
+    pub max_nesting: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct HeuristicDefinition {
+    pub name: String,
+    pub kind: String,
+    pub line: i32,
+}
+
+/// Emplacement de la définition d'un type
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct TypeLocation {
+    pub file: String,
+    pub line: i32,
+    pub module_path: String,
+    pub kind: String,
+}
+
+/// Métriques d'exécution (requêtes, concurrence)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct RunMetrics {
+    pub fetch_requests: u64,
+    pub peak_in_flight_requests: u64,
+    pub peak_in_flight_bytes: u64,
+    #[serde(default)]
+    pub api_requests: u64,
+    #[serde(default)]
+    pub rate_limit_waits: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitUsage>,
+}
+
+/// Quota de l'API REST au début et à la fin de l'exécution
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimitUsage {
+    pub limit: u64,
+    pub remaining_before: u64,
+    pub remaining_after: u64,
+    /// Timestamp Unix de réinitialisation du quota
+    pub reset: u64,
+}
+
+impl RateLimitUsage {
+    /// Requêtes décomptées du quota pendant l'exécution (0 si le quota a été réinitialisé)
+    pub fn consumed(&self) -> u64 {
+        self.remaining_before.saturating_sub(self.remaining_after)
+    }
+}
+
+/// En-tête de commentaires (licence, copyright) répété dans de nombreux fichiers
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BoilerplateHeader {
+    pub hash: String,
+    pub line_count: i32,
+    pub affected_files: i32,
+    pub text: String,
+    /// Fichier dont le texte de l'en-tête a été relevé
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub source: String,
+}
+
+/// Origine des fichiers analysés
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    #[default]
+    GithubApi,
+    Archive,
+    GithubTarball,
+    /// Répertoire local
+    Local,
+    /// Clone git superficiel
+    GitClone,
+    /// Fichiers modifiés par une pull request
+    PullRequest,
+}
+
+/// Provenance de l'analyse (source et empreinte de l'archive le cas échéant)
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub struct Provenance {
+    pub source_kind: SourceKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_path: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub archive_sha256: Option<String>,
+    /// Commit analysé, lu sur le HEAD d'un clone
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub commit_sha: Option<String>,
+}
+
+/// Ligne de la matrice de configuration
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ConfigKey {
+    pub key: String,
+    /// Lue par le code (variable d'environnement)
+    pub required_by_code: bool,
+    /// Présente dans un modèle de configuration
+    pub documented_in_template: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(default)]
+    pub used_in: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde::de::DeserializeOwned;
+    use serde_json::{json, Value};
+    use std::fmt::Debug;
+
+    use crate::analysis::options::AnalysisOptions;
+    use crate::analysis::repository::RepositoryAnalyzer;
+
+    /// Chaque valeur se sérialise sous la forme attendue et se relit à l'identique
+    fn assert_round_trip<T>(cases: &[(T, &str)])
+    where
+        T: Serialize + DeserializeOwned + PartialEq + Debug,
+    {
+        for (value, expected) in cases {
+            let serialized = serde_json::to_value(value).unwrap();
+            assert_eq!(serialized, json!(expected), "{:?}", value);
+            assert_eq!(&serde_json::from_value::<T>(serialized).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn item_kinds_round_trip() {
+        assert_round_trip(&[
+            (ApiItemKind::Struct, "struct"),
+            (ApiItemKind::Enum, "enum"),
+            (ApiItemKind::Union, "union"),
+            (ApiItemKind::Trait, "trait"),
+            (ApiItemKind::Fn, "fn"),
+            (ApiItemKind::Const, "const"),
+            (ApiItemKind::Static, "static"),
+            (ApiItemKind::Type, "type"),
+        ]);
+        assert_round_trip(&[
+            (TypeKind::Struct, "struct"),
+            (TypeKind::Enum, "enum"),
+            (TypeKind::Trait, "trait"),
+            (TypeKind::Alias, "alias"),
+        ]);
+        assert_round_trip(&[
+            (VariantShape::Unit, "unit"),
+            (VariantShape::Tuple, "tuple"),
+            (VariantShape::Struct, "struct"),
+        ]);
+    }
+
+    #[test]
+    fn analysis_kinds_round_trip() {
+        assert_round_trip(&[(SkipReason::TooLarge, "too_large")]);
+        assert_round_trip(&[
+            (ConversionKind::From, "From"),
+            (ConversionKind::TryFrom, "TryFrom"),
+            (ConversionKind::Into, "Into"),
+        ]);
+        assert_round_trip(&[
+            (MacroKind::Declarative, "declarative"),
+            (MacroKind::FunctionLike, "function_like"),
+            (MacroKind::Derive, "derive"),
+            (MacroKind::Attribute, "attribute"),
+        ]);
+        assert_round_trip(&[
+            (ImportKind::Internal, "internal"),
+            (ImportKind::Std, "std"),
+            (ImportKind::External, "external"),
+        ]);
+        assert_round_trip(&[
+            (UnsafeKind::Block, "block"),
+            (UnsafeKind::Function, "function"),
+            (UnsafeKind::Impl, "impl"),
+            (UnsafeKind::Trait, "trait"),
+        ]);
+        assert_round_trip(&[
+            (DependencyKind::Normal, "normal"),
+            (DependencyKind::Dev, "dev"),
+            (DependencyKind::Build, "build"),
+        ]);
+        assert_round_trip(&[
+            (SourceKind::GithubApi, "github_api"),
+            (SourceKind::Archive, "archive"),
+            (SourceKind::GithubTarball, "github_tarball"),
+            (SourceKind::Local, "local"),
+            (SourceKind::GitClone, "git_clone"),
+            (SourceKind::PullRequest, "pull_request"),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn analysis_json_reads_back_with_the_crate_types() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"fixture\"\nversion = \"0.1.0\"\n").unwrap();
+        std::fs::write(
+            dir.path().join("src/lib.rs"),
+            "/// Point\n#[derive(Debug, Clone)]\npub struct Point { pub x: i32 }\n\n\
+            pub(crate) enum Shape { Dot(Point), Empty }\n\npub const LIMIT: usize = 3;\n\n\
+            pub fn area(shape: &Shape) -> i32 { 0 }\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("README.md"), "# Fixture\n\nA fixture crate.\n").unwrap();
+
+        let options = AnalysisOptions { no_cache: true, ..Default::default() };
+        let (summary, _) = RepositoryAnalyzer::with_options(options).analyze_local(dir.path()).await.unwrap();
+
+        let json = serde_json::to_value(&summary).unwrap();
+        let categories: Vec<&Value> = json["file_summaries"].as_array().unwrap().iter().map(|file| &file["category"]).collect();
+        assert!(categories.contains(&&json!({"kind": "source", "value": "rs"})));
+        assert!(categories.contains(&&json!({"kind": "configuration"})));
+
+        let read_back: ProjectSummary = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&read_back).unwrap(), json);
+    }
+}
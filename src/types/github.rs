@@ -1,5 +1,7 @@
 use serde::Deserialize;
 
+use super::analysis::RepositoryMetadata;
+
 #[derive(Debug, Deserialize)]
 pub struct GithubContent {
     pub name: String,
@@ -9,6 +11,144 @@ pub struct GithubContent {
     pub url: String,
     pub content: Option<String>,
     pub encoding: Option<String>,
+    /// URL du contenu brut, seule source pour les fichiers de plus de 1 Mo
+    #[serde(default)]
+    pub download_url: Option<String>,
     #[serde(rename = "type")]
     pub content_type: String,
-}
\ No newline at end of file
+}
+
+/// Métadonnées d'un dépôt (`GET /repos/{owner}/{repo}`)
+#[derive(Debug, Deserialize)]
+pub struct GithubRepoInfo {
+    pub full_name: String,
+    pub default_branch: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub stargazers_count: u64,
+    #[serde(default)]
+    pub topics: Vec<String>,
+    #[serde(default)]
+    pub license: Option<GithubLicense>,
+    #[serde(default)]
+    pub pushed_at: Option<String>,
+    #[serde(default)]
+    pub archived: bool,
+}
+
+/// Licence détectée par GitHub pour un dépôt
+#[derive(Debug, Deserialize)]
+pub struct GithubLicense {
+    pub spdx_id: Option<String>,
+}
+
+impl GithubRepoInfo {
+    /// Métadonnées conservées dans le résumé du projet
+    pub fn metadata(&self) -> RepositoryMetadata {
+        RepositoryMetadata {
+            full_name: self.full_name.clone(),
+            description: self.description.clone().filter(|description| !description.is_empty()),
+            stargazers_count: self.stargazers_count,
+            topics: self.topics.clone(),
+            license: self.license.as_ref().and_then(|license| license.spdx_id.clone()),
+            default_branch: self.default_branch.clone(),
+            pushed_at: self.pushed_at.clone(),
+            archived: self.archived,
+        }
+    }
+}
+
+/// Quota de l'API REST (`GET /rate_limit`, champ `rate`)
+#[derive(Debug, Deserialize, Clone, Copy)]
+pub struct GithubRateLimitStatus {
+    pub limit: u64,
+    pub remaining: u64,
+    /// Timestamp Unix de réinitialisation
+    pub reset: u64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GithubRateLimit {
+    pub rate: GithubRateLimitStatus,
+}
+
+/// Blob renvoyé par l'API Blobs (`git/blobs/{sha}`)
+#[derive(Debug, Deserialize)]
+pub struct GithubBlob {
+    pub sha: String,
+    pub size: u64,
+    pub content: String,
+    /// `base64` ou `utf-8`
+    pub encoding: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GithubTag {
+    pub name: String,
+}
+
+/// Pull request renvoyée par `GET /repos/{owner}/{repo}/pulls/{number}`
+#[derive(Debug, Deserialize)]
+pub struct GithubPullRequest {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub head: GithubPullRef,
+    pub base: GithubPullRef,
+}
+
+/// Branche source ou cible d'une pull request
+#[derive(Debug, Deserialize)]
+pub struct GithubPullRef {
+    #[serde(rename = "ref")]
+    pub git_ref: String,
+    pub sha: String,
+}
+
+/// Fichier modifié par une pull request (`GET .../pulls/{number}/files`)
+#[derive(Debug, Deserialize)]
+pub struct GithubPullFile {
+    pub filename: String,
+    /// `added`, `modified`, `removed`, `renamed`, ...
+    pub status: String,
+    #[serde(default)]
+    pub additions: u64,
+    #[serde(default)]
+    pub deletions: u64,
+    /// Ancien chemin d'un fichier renommé
+    #[serde(default)]
+    pub previous_filename: Option<String>,
+}
+
+/// Dépôt listé par `GET /orgs/{org}/repos`
+#[derive(Debug, Deserialize, Clone)]
+pub struct GithubOrgRepo {
+    pub full_name: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub archived: bool,
+    #[serde(default)]
+    pub fork: bool,
+}
+
+/// Arborescence récursive renvoyée par la Git Trees API
+#[derive(Debug, Deserialize)]
+pub struct GithubTree {
+    pub sha: String,
+    pub tree: Vec<GithubTreeEntry>,
+    #[serde(default)]
+    pub truncated: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct GithubTreeEntry {
+    pub path: String,
+    pub mode: String,
+    /// `blob`, `tree` ou `commit` (sous-module)
+    #[serde(rename = "type")]
+    pub entry_type: String,
+    pub sha: String,
+    pub size: Option<i32>,
+}
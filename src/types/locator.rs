@@ -0,0 +1,396 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::GithubAnalyzerError;
+
+/// Hôte GitHub public, utilisé par défaut
+pub const GITHUB_HOST: &str = "github.com";
+
+/// Forge hébergeant un dépôt
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Provider {
+    #[default]
+    Github,
+    Gitlab,
+    /// Gitea et Forgejo (Codeberg)
+    Gitea,
+}
+
+impl Provider {
+    /// Forge déduite de l'hôte : GitLab si son nom contient `gitlab`, Gitea pour
+    /// codeberg.org ou un hôte nommé `gitea`/`forgejo`, GitHub sinon
+    pub fn detect(host: &str) -> Self {
+        let host = host.to_ascii_lowercase();
+        if host.contains("gitlab") {
+            Provider::Gitlab
+        } else if ["codeberg", "gitea", "forgejo"].iter().any(|name| host.contains(name)) {
+            Provider::Gitea
+        } else {
+            Provider::Github
+        }
+    }
+}
+
+impl FromStr for Provider {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "github" => Ok(Provider::Github),
+            "gitlab" => Ok(Provider::Gitlab),
+            "gitea" | "forgejo" => Ok(Provider::Gitea),
+            other => Err(format!("Invalid provider: {} (expected github, gitlab or gitea)", other)),
+        }
+    }
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Provider::Github => write!(f, "GitHub"),
+            Provider::Gitlab => write!(f, "GitLab"),
+            Provider::Gitea => write!(f, "Gitea"),
+        }
+    }
+}
+
+/// Emplacement d'un dépôt GitHub extrait d'une URL.
+///
+/// Formes acceptées :
+/// - `https://github.com/owner/repo`, avec ou sans `/` final ni `.git`
+/// - `github.com/owner/repo` ou `owner/repo`
+/// - `git@github.com:owner/repo.git` et `ssh://git@github.com/owner/repo.git`
+/// - `.../tree/<ref>/<sous-chemin>` et `.../blob/<ref>/<sous-chemin>`
+/// - `.../pull/<numéro>` pour une pull request
+/// - les mêmes formes sur un hôte GitHub Enterprise (`https://ghe.example.com/org/repo`)
+///
+/// Le premier segment après `tree/` ou `blob/` est la référence : une branche
+/// contenant `/` doit être passée avec `--ref`.
+///
+/// Sur GitLab, `owner` est l'espace de noms complet (`group/subgroup`) et la
+/// référence suit `/-/tree/` ou `/-/blob/`. Sur Gitea, elle suit
+/// `/src/branch/`, `/src/tag/` ou `/src/commit/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoLocator {
+    pub provider: Provider,
+    pub host: String,
+    pub owner: String,
+    pub repo: String,
+    pub git_ref: Option<String>,
+    pub subpath: Option<String>,
+    /// Numéro de pull request (`.../pull/<numéro>`)
+    pub pull_request: Option<u64>,
+}
+
+impl RepoLocator {
+    /// URL de base de l'API de l'hôte : `https://api.github.com` pour github.com,
+    /// `https://<hôte>/api/v3` pour GitHub Enterprise Server, `https://<hôte>/api/v4`
+    /// pour GitLab, `https://<hôte>/api/v1` pour Gitea
+    pub fn default_api_base(&self) -> String {
+        match self.provider {
+            Provider::Gitlab => format!("https://{}/api/v4", self.host),
+            Provider::Gitea => format!("https://{}/api/v1", self.host),
+            Provider::Github if self.host == GITHUB_HOST => "https://api.github.com".to_string(),
+            Provider::Github => format!("https://{}/api/v3", self.host),
+        }
+    }
+
+    /// URL de l'API du dépôt à partir d'une base (`<base>/repos/owner/repo`)
+    pub fn api_url_with_base(&self, api_base: &str) -> String {
+        format!("{}/repos/{}/{}", api_base.trim_end_matches('/'), self.owner, self.repo)
+    }
+
+    /// URL de l'API du dépôt sur son propre hôte
+    pub fn api_url(&self) -> String {
+        self.api_url_with_base(&self.default_api_base())
+    }
+
+    /// Chemin complet du projet (`owner/repo`, `group/subgroup/project` sur GitLab)
+    pub fn project_path(&self) -> String {
+        format!("{}/{}", self.owner, self.repo)
+    }
+
+    /// Identifiant du projet dans l'API GitLab : son chemin complet encodé
+    /// (`group%2Fsubgroup%2Fproject`)
+    pub fn project_id(&self) -> String {
+        encode_component(&self.project_path())
+    }
+
+    /// Base des liens vers les fichiers à une référence donnée
+    /// (`https://github.com/owner/repo/blob/<ref>`, `/-/blob/<ref>` sur GitLab,
+    /// `/src/commit/<ref>` sur Gitea)
+    pub fn blob_url(&self, git_ref: &str) -> String {
+        match self.provider {
+            Provider::Github => format!("https://{}/{}/blob/{}", self.host, self.project_path(), git_ref),
+            Provider::Gitlab => format!("https://{}/{}/-/blob/{}", self.host, self.project_path(), git_ref),
+            Provider::Gitea => format!("https://{}/{}/src/commit/{}", self.host, self.project_path(), git_ref),
+        }
+    }
+
+    /// Nom du répertoire d'export (`owner_repo`, `group_subgroup_project`), unique par
+    /// dépôt ; `owner_repo_pr<numéro>` pour une pull request
+    pub fn output_dir_name(&self) -> String {
+        let name = format!("{}_{}", self.owner.replace('/', "_"), self.repo);
+        match self.pull_request {
+            Some(number) => format!("{}_pr{}", name, number),
+            None => name,
+        }
+    }
+
+    /// Emplacement canonique si l'API désigne le dépôt sous un autre nom
+    /// (`full_name` d'un dépôt renommé ou transféré), référence et sous-chemin conservés
+    pub fn moved_to(&self, full_name: &str) -> Option<RepoLocator> {
+        let (owner, repo) = full_name.rsplit_once('/')?;
+        let unchanged = owner.eq_ignore_ascii_case(&self.owner) && repo.eq_ignore_ascii_case(&self.repo);
+        (!unchanged).then(|| RepoLocator {
+            owner: owner.to_string(),
+            repo: repo.to_string(),
+            ..self.clone()
+        })
+    }
+}
+
+impl fmt::Display for RepoLocator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "https://{}/{}/{}", self.host, self.owner, self.repo)?;
+        if let Some(git_ref) = &self.git_ref {
+            let prefix = match self.provider {
+                Provider::Github => "/tree",
+                Provider::Gitlab => "/-/tree",
+                Provider::Gitea => "/src/branch",
+            };
+            write!(f, "{}/{}", prefix, git_ref)?;
+            if let Some(subpath) = &self.subpath {
+                write!(f, "/{}", subpath)?;
+            }
+        }
+        if let Some(number) = self.pull_request {
+            write!(f, "/pull/{}", number)?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for RepoLocator {
+    type Err = GithubAnalyzerError;
+
+    /// Forge déduite de l'hôte (voir `Provider::detect`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_as(s, None)
+    }
+}
+
+impl RepoLocator {
+    /// Analyse une URL de dépôt pour une forge donnée, ou déduite de l'hôte
+    pub fn parse_as(s: &str, provider: Option<Provider>) -> Result<Self, GithubAnalyzerError> {
+        let invalid = || GithubAnalyzerError::ParseError(format!("Invalid repository URL: {}", s));
+        let input = s.trim();
+
+        // Sépare l'hôte du chemin `owner/repo/...` ; sans hôte explicite, github.com
+        let (host, path) = if let Some(rest) = input.strip_prefix("git@") {
+            rest.split_once(':').ok_or_else(invalid)?
+        } else {
+            let scheme = ["https://", "http://", "ssh://", "git://"]
+                .iter()
+                .find_map(|scheme| input.strip_prefix(scheme));
+            let rest = scheme.unwrap_or(input);
+            let rest = rest.strip_prefix("git@").unwrap_or(rest);
+            let (first, remainder) = rest.split_once('/').unwrap_or((rest, ""));
+            if scheme.is_some() || first.contains('.') {
+                // Le port d'une URL SSH ne vaut pas pour l'API HTTPS
+                let first = if input.starts_with("ssh://") || input.starts_with("git://") {
+                    first.split(':').next().unwrap_or(first)
+                } else {
+                    first
+                };
+                (first, remainder)
+            } else {
+                (GITHUB_HOST, rest)
+            }
+        };
+        let host = host.strip_prefix("www.").unwrap_or(host);
+        if host.is_empty() || host.contains("://") {
+            return Err(invalid());
+        }
+
+        let path = path.split(['?', '#']).next().unwrap_or("");
+        let provider = provider.unwrap_or_else(|| Provider::detect(host));
+        let mut segments: Vec<&str> = path.split('/').filter(|segment| !segment.is_empty()).collect();
+
+        // Sur GitLab, tous les segments avant `-` forment le chemin du projet
+        let project_len = match provider {
+            Provider::Github | Provider::Gitea => segments.len().min(2),
+            Provider::Gitlab => segments.iter().position(|segment| *segment == "-").unwrap_or(segments.len()),
+        };
+        let mut rest = segments.split_off(project_len).into_iter();
+        if provider == Provider::Gitlab && rest.as_slice().first() == Some(&"-") {
+            rest.next();
+        }
+        let repo = segments.pop().ok_or_else(invalid)?;
+        let repo = repo.strip_suffix(".git").unwrap_or(repo);
+        if segments.is_empty() || repo.is_empty() {
+            return Err(invalid());
+        }
+        let owner = segments.join("/");
+
+        let mut pull_request = None;
+        let (git_ref, subpath) = match rest.next() {
+            None => (None, None),
+            // Pull request : `pull/<numéro>`, éventuellement suivi d'un onglet (`/files`)
+            Some("pull") if provider == Provider::Github => {
+                let number = rest.next().and_then(|number| number.parse().ok()).ok_or_else(invalid)?;
+                pull_request = Some(number);
+                (None, None)
+            }
+            Some("tree" | "blob") => {
+                let git_ref = rest.next().ok_or_else(invalid)?;
+                let subpath = rest.collect::<Vec<_>>().join("/");
+                (Some(git_ref.to_string()), Some(subpath).filter(|p| !p.is_empty()))
+            }
+            // Gitea : `src/branch/<ref>`, `src/tag/<ref>` ou `src/commit/<sha>`
+            Some("src") if provider == Provider::Gitea => {
+                if !matches!(rest.next(), Some("branch" | "tag" | "commit")) {
+                    return Err(invalid());
+                }
+                let git_ref = rest.next().ok_or_else(invalid)?;
+                let subpath = rest.collect::<Vec<_>>().join("/");
+                (Some(git_ref.to_string()), Some(subpath).filter(|p| !p.is_empty()))
+            }
+            Some(_) => return Err(invalid()),
+        };
+
+        Ok(Self {
+            provider,
+            host: host.to_string(),
+            owner,
+            repo: repo.to_string(),
+            git_ref,
+            subpath,
+            pull_request,
+        })
+    }
+}
+
+/// Encode un segment d'URL : seuls les caractères non réservés (RFC 3986) restent
+/// tels quels, `/` compris devient `%2F`
+pub fn encode_component(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(byte as char),
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locate(url: &str) -> RepoLocator {
+        url.parse().unwrap_or_else(|error| panic!("{}: {:?}", url, error))
+    }
+
+    #[test]
+    fn every_plain_url_shape_names_the_same_repository() {
+        for url in [
+            "https://github.com/owner/repo",
+            "https://github.com/owner/repo/",
+            "https://github.com/owner/repo.git",
+            "https://www.github.com/owner/repo",
+            "http://github.com/owner/repo",
+            "github.com/owner/repo",
+            "owner/repo",
+            "git@github.com:owner/repo.git",
+            "ssh://git@github.com/owner/repo.git",
+            "https://github.com/owner/repo?tab=readme#usage",
+        ] {
+            let locator = locate(url);
+            assert_eq!(locator.host, GITHUB_HOST, "{}", url);
+            assert_eq!((locator.owner.as_str(), locator.repo.as_str()), ("owner", "repo"), "{}", url);
+            assert_eq!((&locator.git_ref, &locator.subpath), (&None, &None), "{}", url);
+            assert_eq!(locator.api_url(), "https://api.github.com/repos/owner/repo", "{}", url);
+        }
+    }
+
+    #[test]
+    fn tree_and_blob_urls_carry_a_ref_and_subpath() {
+        let locator = locate("https://github.com/owner/repo/tree/feature/foo");
+        assert_eq!(locator.git_ref.as_deref(), Some("feature"));
+        assert_eq!(locator.subpath.as_deref(), Some("foo"));
+
+        let locator = locate("https://github.com/owner/repo/tree/main");
+        assert_eq!(locator.git_ref.as_deref(), Some("main"));
+        assert_eq!(locator.subpath, None);
+
+        let locator = locate("https://github.com/owner/repo/blob/v1.2.0/src/lib.rs");
+        assert_eq!(locator.git_ref.as_deref(), Some("v1.2.0"));
+        assert_eq!(locator.subpath.as_deref(), Some("src/lib.rs"));
+        assert_eq!(locator.api_url(), "https://api.github.com/repos/owner/repo");
+    }
+
+    #[test]
+    fn pull_request_urls_are_numbered() {
+        let locator = locate("https://github.com/owner/repo/pull/42/files");
+        assert_eq!(locator.pull_request, Some(42));
+        assert_eq!(locator.output_dir_name(), "owner_repo_pr42");
+        assert!("https://github.com/owner/repo/pull/latest".parse::<RepoLocator>().is_err());
+    }
+
+    #[test]
+    fn enterprise_and_ssh_ports_use_the_https_api_of_the_host() {
+        let locator = locate("ssh://git@ghe.example.com:2222/org/tool.git");
+        assert_eq!(locator.host, "ghe.example.com");
+        assert_eq!(locator.api_url(), "https://ghe.example.com/api/v3/repos/org/tool");
+        assert_eq!(locator.blob_url("main"), "https://ghe.example.com/org/tool/blob/main");
+    }
+
+    #[test]
+    fn output_directories_do_not_collide_across_owners() {
+        let first = locate("https://github.com/alice/utils").output_dir_name();
+        let second = locate("git@github.com:bob/utils.git").output_dir_name();
+        assert_eq!(first, "alice_utils");
+        assert_eq!(second, "bob_utils");
+    }
+
+    #[test]
+    fn incomplete_or_unknown_urls_are_rejected() {
+        for url in [
+            "",
+            "https://github.com/",
+            "https://github.com/owner",
+            "https://github.com/owner/.git",
+            "git@github.com",
+            "https://github.com/owner/repo/issues/1",
+            "https://github.com/owner/repo/tree",
+        ] {
+            assert!(
+                matches!(url.parse::<RepoLocator>(), Err(GithubAnalyzerError::ParseError(_))),
+                "{}",
+                url
+            );
+        }
+    }
+
+    #[test]
+    fn display_round_trips_through_parsing() {
+        for url in [
+            "https://github.com/owner/repo",
+            "https://github.com/owner/repo/tree/dev/src/api",
+            "https://github.com/owner/repo/pull/7",
+        ] {
+            assert_eq!(locate(url).to_string(), url);
+            assert_eq!(locate(&locate(url).to_string()), locate(url));
+        }
+    }
+
+    #[test]
+    fn renamed_repository_keeps_ref_and_subpath() {
+        let locator = locate("https://github.com/old/name/tree/dev/src");
+        assert_eq!(locator.moved_to("Old/Name"), None);
+        let moved = locator.moved_to("new-org/new-name").unwrap();
+        assert_eq!((moved.owner.as_str(), moved.repo.as_str()), ("new-org", "new-name"));
+        assert_eq!((moved.git_ref.as_deref(), moved.subpath.as_deref()), (Some("dev"), Some("src")));
+    }
+}
@@ -1,20 +1,188 @@
-use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+
+use crate::error::GithubAnalyzerError;
 
 pub mod github;
 pub mod analysis;
+pub mod locator;
+
+pub use crate::schema::SCHEMA_VERSION;
+
+/// Chaîne partagée utilisée pour les noms de types et chemins répétés.
+///
+/// Sérialisée exactement comme une `String`.
+pub type SharedStr = Arc<str>;
 
-#[derive(Debug, Serialize, Clone)]
+/// Catégorie d'un fichier du dépôt.
+///
+/// Représentation serde stable (adjacently tagged) :
+/// `{"kind": "source", "value": "rs"}` pour les sources,
+/// `{"kind": "configuration"}` pour les autres variantes.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
 pub enum FileCategory {
     Source(String),
     Configuration,
     Documentation,
     Test,
+    /// Exemple cargo (fichier Rust sous `examples/`)
+    Example,
+    /// Banc d'essai cargo (fichier Rust sous `benches/`)
+    Benchmark,
+    /// Configuration d'intégration continue (GitHub Actions, GitLab CI, Jenkins, ...)
+    CiConfig,
+    /// Image, police, bibliothèque compilée, archive... : ni analysé ni exporté
+    Binary,
     Unknown,
 }
 
-#[derive(Debug, Serialize, Clone)]
+impl fmt::Display for FileCategory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FileCategory::Source(ext) => write!(f, "source:{}", ext),
+            FileCategory::Configuration => write!(f, "configuration"),
+            FileCategory::Documentation => write!(f, "documentation"),
+            FileCategory::Test => write!(f, "test"),
+            FileCategory::Example => write!(f, "example"),
+            FileCategory::Benchmark => write!(f, "benchmark"),
+            FileCategory::CiConfig => write!(f, "ci_config"),
+            FileCategory::Binary => write!(f, "binary"),
+            FileCategory::Unknown => write!(f, "unknown"),
+        }
+    }
+}
+
+impl FromStr for FileCategory {
+    type Err = GithubAnalyzerError;
+
+    /// Accepte la forme produite par `Display` (`source:rs`, `test`, ...)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim().to_lowercase();
+        match s.split_once(':') {
+            Some(("source", ext)) if !ext.is_empty() => Ok(FileCategory::Source(ext.to_string())),
+            Some(_) => Err(GithubAnalyzerError::ParseError(format!("Invalid file category: {}", s))),
+            None => match s.as_str() {
+                "configuration" => Ok(FileCategory::Configuration),
+                "documentation" => Ok(FileCategory::Documentation),
+                "test" => Ok(FileCategory::Test),
+                "example" => Ok(FileCategory::Example),
+                "benchmark" => Ok(FileCategory::Benchmark),
+                "ci_config" => Ok(FileCategory::CiConfig),
+                "binary" => Ok(FileCategory::Binary),
+                "unknown" => Ok(FileCategory::Unknown),
+                _ => Err(GithubAnalyzerError::ParseError(format!("Invalid file category: {}", s))),
+            },
+        }
+    }
+}
+
+/// Visibilité d'un élément Rust, sérialisée en `snake_case`
+/// (`"public"`, `"private"`, `"public_crate"`).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub enum Visibility {
     Public,
     Private,
     PublicCrate,
-}
\ No newline at end of file
+}
+
+impl fmt::Display for Visibility {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Visibility::Public => write!(f, "public"),
+            Visibility::Private => write!(f, "private"),
+            Visibility::PublicCrate => write!(f, "public_crate"),
+        }
+    }
+}
+
+impl FromStr for Visibility {
+    type Err = GithubAnalyzerError;
+
+    /// Accepte la forme `Display` ainsi que la syntaxe Rust (`pub`, `pub(crate)`)
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "public" | "pub" => Ok(Visibility::Public),
+            "private" | "priv" => Ok(Visibility::Private),
+            "public_crate" | "pub(crate)" => Ok(Visibility::PublicCrate),
+            other => Err(GithubAnalyzerError::ParseError(format!("Invalid visibility: {}", other))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn categories() -> Vec<(FileCategory, serde_json::Value)> {
+        vec![
+            (FileCategory::Source("rs".to_string()), json!({"kind": "source", "value": "rs"})),
+            (FileCategory::Configuration, json!({"kind": "configuration"})),
+            (FileCategory::Documentation, json!({"kind": "documentation"})),
+            (FileCategory::Test, json!({"kind": "test"})),
+            (FileCategory::Example, json!({"kind": "example"})),
+            (FileCategory::Benchmark, json!({"kind": "benchmark"})),
+            (FileCategory::CiConfig, json!({"kind": "ci_config"})),
+            (FileCategory::Binary, json!({"kind": "binary"})),
+            (FileCategory::Unknown, json!({"kind": "unknown"})),
+        ]
+    }
+
+    #[test]
+    fn file_category_serializes_adjacently_tagged() {
+        for (category, expected) in categories() {
+            assert_eq!(serde_json::to_value(&category).unwrap(), expected, "{:?}", category);
+        }
+    }
+
+    #[test]
+    fn file_category_round_trips_through_serde() {
+        for (category, _) in categories() {
+            let json = serde_json::to_string(&category).unwrap();
+            assert_eq!(serde_json::from_str::<FileCategory>(&json).unwrap(), category);
+        }
+    }
+
+    #[test]
+    fn file_category_round_trips_through_display() {
+        for (category, _) in categories() {
+            assert_eq!(category.to_string().parse::<FileCategory>().unwrap(), category);
+        }
+        assert_eq!(" Source:RS ".parse::<FileCategory>().unwrap(), FileCategory::Source("rs".to_string()));
+    }
+
+    #[test]
+    fn file_category_rejects_unknown_forms() {
+        for input in ["", "source:", "sources", "config:toml", "{\"Source\":\"rs\"}"] {
+            assert!(input.parse::<FileCategory>().is_err(), "{}", input);
+        }
+        // Ancienne forme externe (`{"Source": "rs"}`), remplacée par la forme étiquetée
+        assert!(serde_json::from_value::<FileCategory>(json!({"Source": "rs"})).is_err());
+    }
+
+    #[test]
+    fn visibility_serializes_in_snake_case() {
+        for (visibility, expected) in [
+            (Visibility::Public, "public"),
+            (Visibility::Private, "private"),
+            (Visibility::PublicCrate, "public_crate"),
+        ] {
+            assert_eq!(serde_json::to_value(visibility).unwrap(), json!(expected));
+            assert_eq!(serde_json::from_value::<Visibility>(json!(expected)).unwrap(), visibility);
+            assert_eq!(visibility.to_string(), expected);
+            assert_eq!(expected.parse::<Visibility>().unwrap(), visibility);
+        }
+    }
+
+    #[test]
+    fn visibility_parses_rust_syntax() {
+        assert_eq!("pub".parse::<Visibility>().unwrap(), Visibility::Public);
+        assert_eq!("pub(crate)".parse::<Visibility>().unwrap(), Visibility::PublicCrate);
+        assert!("pub(super)".parse::<Visibility>().is_err());
+        assert!("protected".parse::<Visibility>().is_err());
+    }
+}